@@ -0,0 +1,74 @@
+//! Compares serializing a tiny fixed-shape document as a `MicroSpeak` against building & serializing the equivalent
+//! `Speak` tree. Run with `cargo bench --bench micro`.
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	hint::black_box,
+	sync::atomic::{AtomicUsize, Ordering},
+	time::Instant
+};
+
+use ssml::{
+	Flavor, Serialize, SerializeOptions,
+	micro::{MicroPart, MicroSpeak}
+};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		unsafe { System.alloc(layout) }
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		unsafe { System.dealloc(ptr, layout) }
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const ITERATIONS: usize = 200_000;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+	f();
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+	let start = Instant::now();
+	for _ in 0..ITERATIONS {
+		f();
+	}
+	let elapsed = start.elapsed();
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+	println!(
+		"{name:>10}: {:>8.1} ns/iter, {:>5.1} allocations/iter",
+		elapsed.as_nanos() as f64 / ITERATIONS as f64,
+		allocations as f64 / ITERATIONS as f64
+	);
+}
+
+fn main() {
+	let text = "Your verification code is 1 2 3 4.";
+	let flavor = Flavor::MicrosoftAzureCognitiveSpeechServices;
+	let options = SerializeOptions::default().flavor(flavor);
+	let mut out = String::with_capacity(512);
+
+	bench("micro", || {
+		out.clear();
+		let doc = MicroSpeak::new(
+			Some("en-US"),
+			Some("en-US-JennyNeural"),
+			[MicroPart::Text(black_box(text)), MicroPart::Break(ssml::TimeDesignation::from_millis(500.))]
+		);
+		doc.serialize(&mut out, flavor).unwrap();
+		black_box(&out);
+	});
+	bench("tree", || {
+		out.clear();
+		let doc = ssml::speak(Some("en-US"), [ssml::voice("en-US-JennyNeural", [ssml::Element::from(black_box(text)), ssml::breaks("500ms").into()])]);
+		doc.serialize(&mut out, &options).unwrap();
+		black_box(&out);
+	});
+}
@@ -0,0 +1,351 @@
+//! Arbitrary user data attached to elements.
+//!
+//! Annotations are preserved through clones, visitors & transformations, but are never serialized to SSML, making them
+//! useful for carrying application data (e.g. CMS IDs or review states) alongside the nodes of a document.
+//!
+//! ```
+//! # use ssml::Serialize;
+//! use ssml::annotations::Annotated;
+//!
+//! # fn main() -> ssml::Result<()> {
+//! let voice = ssml::voice("en-US-JennyNeural", ["Hello, world!"]).with_annotation("cms-id", "1234");
+//! assert_eq!(voice.annotation("cms-id"), Some("1234"));
+//!
+//! let doc = ssml::speak(None, [voice]);
+//! assert_eq!(
+//! 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+//! 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-JennyNeural">Hello, world!</voice></speak>"#
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Text`](crate::Text) and [`Break`](crate::Break) elements can't hold annotations themselves; wrap them in a
+//! [`Group`] to annotate them.
+//!
+//! Alongside user data, annotations also hold the [`Span`] an element was parsed from, if any, and optionally its raw
+//! markup; see [`parse_str_preserving`](crate::parse::parse_str_preserving).
+//!
+//! Elements can also be marked as [protected](Annotated::protected), so that hand-tuned sections are left untouched by
+//! the passes in [`transform`](crate::transform).
+
+use alloc::{borrow::Cow, collections::BTreeMap, string::ToString};
+
+use crate::{
+	Audio, CustomElement, Element, Emphasis, Group, Lang, Lexicon, Mark, Meta, Paragraph, Phoneme, Prosody, SayAs, Sentence, Speak, Sub, Variant, Voice, Word,
+	mstts::{AudioDuration, BackgroundAudio, Express, Math, Silence, TtsEmbedding, Viseme},
+	polly::{Domain, Effect},
+	raw::RawSource,
+	span::Span,
+	watson::{ExpressAs, VoiceTransformation}
+};
+
+/// The annotation key which marks an element as protected from transformation passes. See [`Annotated::protected`].
+pub const PROTECTED: &str = "ssml:protected";
+
+/// A set of key-value annotations attached to an element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Annotations<'s> {
+	map: BTreeMap<Cow<'s, str>, Cow<'s, str>>,
+	#[cfg_attr(feature = "serde", serde(skip))]
+	span: Option<Span>,
+	#[cfg_attr(feature = "serde", serde(skip))]
+	raw: Option<RawSource<'s>>
+}
+
+impl<'s> Annotations<'s> {
+	/// Returns the value of the annotation with the given key.
+	pub fn get(&self, key: &str) -> Option<&str> {
+		self.map.get(key).map(|v| &**v)
+	}
+
+	/// Sets the value of an annotation, returning its previous value.
+	pub fn insert(&mut self, key: impl Into<Cow<'s, str>>, value: impl Into<Cow<'s, str>>) -> Option<Cow<'s, str>> {
+		self.map.insert(key.into(), value.into())
+	}
+
+	/// Removes an annotation, returning its value.
+	pub fn remove(&mut self, key: &str) -> Option<Cow<'s, str>> {
+		self.map.remove(key)
+	}
+
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.map.contains_key(key)
+	}
+
+	/// Returns an iterator over all annotations, ordered by key.
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+		self.map.iter().map(|(k, v)| (&**k, &**v))
+	}
+
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	pub fn clear(&mut self) {
+		self.map.clear();
+	}
+
+	/// Returns whether the element is protected from transformation passes. See [`Annotated::protected`].
+	pub fn is_protected(&self) -> bool {
+		self.contains_key(PROTECTED)
+	}
+
+	pub fn set_protected(&mut self, protected: bool) {
+		if protected {
+			self.insert(PROTECTED, "true");
+		} else {
+			self.remove(PROTECTED);
+		}
+	}
+
+	/// Returns the location of the element in the document it was parsed from, if any. See [`Span`].
+	pub fn span(&self) -> Option<&Span> {
+		self.span.as_ref()
+	}
+
+	pub fn set_span(&mut self, span: Span) {
+		self.span = Some(span);
+	}
+
+	pub fn take_span(&mut self) -> Option<Span> {
+		self.span.take()
+	}
+
+	/// Returns the markup the element was parsed from, if it was parsed with
+	/// [`parse_str_preserving`](crate::parse::parse_str_preserving).
+	pub fn raw_source(&self) -> Option<&str> {
+		self.raw.as_ref().map(|raw| &*raw.source)
+	}
+
+	/// Discards the markup the element was parsed from, so it is always re-serialized.
+	pub fn clear_raw_source(&mut self) {
+		self.raw = None;
+	}
+
+	pub(crate) fn raw(&self) -> Option<&RawSource<'s>> {
+		self.raw.as_ref()
+	}
+
+	pub(crate) fn set_raw(&mut self, raw: RawSource<'s>) {
+		self.raw = Some(raw);
+	}
+
+	pub fn to_owned(&self) -> Annotations<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Annotations<'static> {
+		Annotations {
+			map: self
+				.map
+				.into_iter()
+				.map(|(k, v)| {
+					(
+						match k {
+							Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+							Cow::Owned(b) => Cow::Owned(b)
+						},
+						match v {
+							Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+							Cow::Owned(b) => Cow::Owned(b)
+						}
+					)
+				})
+				.collect(),
+			span: self.span,
+			raw: self.raw.map(RawSource::into_owned)
+		}
+	}
+}
+
+/// Trait for elements which can hold [`Annotations`].
+pub trait Annotated<'s> {
+	fn annotations(&self) -> &Annotations<'s>;
+
+	fn annotations_mut(&mut self) -> &mut Annotations<'s>;
+
+	/// Returns the value of the annotation with the given key.
+	fn annotation<'a>(&'a self, key: &str) -> Option<&'a str>
+	where
+		's: 'a
+	{
+		self.annotations().get(key)
+	}
+
+	/// Returns the location of this element in the document it was parsed from, if any.
+	fn span<'a>(&'a self) -> Option<&'a Span>
+	where
+		's: 'a
+	{
+		self.annotations().span()
+	}
+
+	/// Sets the value of an annotation.
+	fn with_annotation(mut self, key: impl Into<Cow<'s, str>>, value: impl Into<Cow<'s, str>>) -> Self
+	where
+		Self: Sized
+	{
+		self.annotations_mut().insert(key, value);
+		self
+	}
+
+	/// Marks this element as protected, so that it & its children are skipped by transformation passes, like
+	/// [`insert_breaks`](crate::transform::insert_breaks) or [`retarget_voices`](crate::retarget_voices). Use this for
+	/// hand-tuned sections, e.g. carefully crafted pronunciations, which should survive automated pipelines.
+	///
+	/// Protection is stored as the [`PROTECTED`] annotation; to protect [`Text`](crate::Text), wrap it in a [`Group`].
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// use ssml::{Flavor, annotations::Annotated, transform::{BreakRules, insert_breaks}};
+	///
+	/// let mut doc = ssml::speak(None, [
+	/// 	ssml::Element::from(ssml::group(["Hello. Hi."]).protected()),
+	/// 	ssml::Element::from("Hello. Hi.")
+	/// ]);
+	/// insert_breaks(&mut doc, &BreakRules::new().with_after_sentence("200ms").into(), Flavor::Generic);
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Hello. Hi.Hello.<break time="+200ms"/> Hi.</speak>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	fn protected(mut self) -> Self
+	where
+		Self: Sized
+	{
+		self.annotations_mut().set_protected(true);
+		self
+	}
+
+	/// Returns whether this element is [protected](Annotated::protected).
+	fn is_protected(&self) -> bool {
+		self.annotations().is_protected()
+	}
+}
+
+macro_rules! impl_annotated {
+	($($t:ident),*) => {
+		$(impl<'s> Annotated<'s> for $t<'s> {
+			fn annotations(&self) -> &Annotations<'s> {
+				&self.annotations
+			}
+
+			fn annotations_mut(&mut self) -> &mut Annotations<'s> {
+				&mut self.annotations
+			}
+		})*
+	};
+}
+
+impl_annotated!(Speak, Audio, Voice, Meta, Emphasis, Mark, Lexicon, SayAs, Phoneme, Sub, Paragraph, Sentence, Word, Group, Lang, Prosody);
+impl_annotated!(
+	CustomElement,
+	Variant,
+	Express,
+	Silence,
+	BackgroundAudio,
+	Viseme,
+	TtsEmbedding,
+	AudioDuration,
+	Math,
+	Domain,
+	Effect,
+	ExpressAs,
+	VoiceTransformation
+);
+
+impl<'s> Element<'s> {
+	/// Returns the annotations of this element, or `None` if this element can't hold annotations (i.e. [`Text`] or
+	/// [`Break`]).
+	///
+	/// [`Text`]: crate::Text
+	/// [`Break`]: crate::Break
+	pub fn annotations(&self) -> Option<&Annotations<'s>> {
+		match self {
+			Element::Audio(el) => Some(el.annotations()),
+			Element::Voice(el) => Some(el.annotations()),
+			Element::Meta(el) => Some(el.annotations()),
+			Element::Emphasis(el) => Some(el.annotations()),
+			Element::Mark(el) => Some(el.annotations()),
+			Element::Lexicon(el) => Some(el.annotations()),
+			Element::SayAs(el) => Some(el.annotations()),
+			Element::Phoneme(el) => Some(el.annotations()),
+			Element::Sub(el) => Some(el.annotations()),
+			Element::Paragraph(el) => Some(el.annotations()),
+			Element::Sentence(el) => Some(el.annotations()),
+			Element::Word(el) => Some(el.annotations()),
+			Element::Group(el) => Some(el.annotations()),
+			Element::Lang(el) => Some(el.annotations()),
+			Element::Prosody(el) => Some(el.annotations()),
+			Element::FlavorMSTTS(crate::mstts::Element::Express(el)) => Some(el.annotations()),
+			Element::FlavorMSTTS(crate::mstts::Element::Silence(el)) => Some(el.annotations()),
+			Element::FlavorMSTTS(crate::mstts::Element::BackgroundAudio(el)) => Some(el.annotations()),
+			Element::FlavorMSTTS(crate::mstts::Element::Viseme(el)) => Some(el.annotations()),
+			Element::FlavorMSTTS(crate::mstts::Element::TtsEmbedding(el)) => Some(el.annotations()),
+			Element::FlavorMSTTS(crate::mstts::Element::AudioDuration(el)) => Some(el.annotations()),
+			Element::FlavorMSTTS(crate::mstts::Element::Math(el)) => Some(el.annotations()),
+			Element::FlavorPolly(crate::polly::Element::Domain(el)) => Some(el.annotations()),
+			Element::FlavorPolly(crate::polly::Element::Effect(el)) => Some(el.annotations()),
+			Element::FlavorWatson(crate::watson::Element::ExpressAs(el)) => Some(el.annotations()),
+			Element::FlavorWatson(crate::watson::Element::VoiceTransformation(el)) => Some(el.annotations()),
+			Element::Custom(el) => Some(el.annotations()),
+			Element::Variant(el) => Some(el.annotations()),
+			Element::Text(_) | Element::Break(_) => None
+		}
+	}
+
+	/// Returns the location of this element in the document it was parsed from, if any.
+	pub fn span(&self) -> Option<&Span> {
+		self.annotations().and_then(Annotations::span)
+	}
+
+	/// Returns whether this element is [protected](Annotated::protected) from transformation passes.
+	pub fn is_protected(&self) -> bool {
+		self.annotations().is_some_and(Annotations::is_protected)
+	}
+
+	/// Returns a mutable reference to the annotations of this element. See [`Element::annotations`].
+	pub fn annotations_mut(&mut self) -> Option<&mut Annotations<'s>> {
+		match self {
+			Element::Audio(el) => Some(el.annotations_mut()),
+			Element::Voice(el) => Some(el.annotations_mut()),
+			Element::Meta(el) => Some(el.annotations_mut()),
+			Element::Emphasis(el) => Some(el.annotations_mut()),
+			Element::Mark(el) => Some(el.annotations_mut()),
+			Element::Lexicon(el) => Some(el.annotations_mut()),
+			Element::SayAs(el) => Some(el.annotations_mut()),
+			Element::Phoneme(el) => Some(el.annotations_mut()),
+			Element::Sub(el) => Some(el.annotations_mut()),
+			Element::Paragraph(el) => Some(el.annotations_mut()),
+			Element::Sentence(el) => Some(el.annotations_mut()),
+			Element::Word(el) => Some(el.annotations_mut()),
+			Element::Group(el) => Some(el.annotations_mut()),
+			Element::Lang(el) => Some(el.annotations_mut()),
+			Element::Prosody(el) => Some(el.annotations_mut()),
+			Element::FlavorMSTTS(crate::mstts::Element::Express(el)) => Some(el.annotations_mut()),
+			Element::FlavorMSTTS(crate::mstts::Element::Silence(el)) => Some(el.annotations_mut()),
+			Element::FlavorMSTTS(crate::mstts::Element::BackgroundAudio(el)) => Some(el.annotations_mut()),
+			Element::FlavorMSTTS(crate::mstts::Element::Viseme(el)) => Some(el.annotations_mut()),
+			Element::FlavorMSTTS(crate::mstts::Element::TtsEmbedding(el)) => Some(el.annotations_mut()),
+			Element::FlavorMSTTS(crate::mstts::Element::AudioDuration(el)) => Some(el.annotations_mut()),
+			Element::FlavorMSTTS(crate::mstts::Element::Math(el)) => Some(el.annotations_mut()),
+			Element::FlavorPolly(crate::polly::Element::Domain(el)) => Some(el.annotations_mut()),
+			Element::FlavorPolly(crate::polly::Element::Effect(el)) => Some(el.annotations_mut()),
+			Element::FlavorWatson(crate::watson::Element::ExpressAs(el)) => Some(el.annotations_mut()),
+			Element::FlavorWatson(crate::watson::Element::VoiceTransformation(el)) => Some(el.annotations_mut()),
+			Element::Custom(el) => Some(el.annotations_mut()),
+			Element::Variant(el) => Some(el.annotations_mut()),
+			Element::Text(_) | Element::Break(_) => None
+		}
+	}
+}
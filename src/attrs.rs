@@ -0,0 +1,137 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+	Audio, Emphasis, Lang, Lexicon, Mark, Paragraph, Phoneme, Prosody, SayAs, Sentence, Speak, Sub, Voice, Word, XmlWriter,
+	mstts::{AudioDuration, BackgroundAudio, Express, Math, Silence, TtsEmbedding, Viseme}
+};
+
+/// A list of attribute names & values.
+pub(crate) type Attrs<'s> = Vec<(Cow<'s, str>, Cow<'s, str>)>;
+
+/// Trait for elements which can carry attributes that aren't modeled by this crate, e.g. vendor extensions.
+///
+/// Extra attributes are written after the element's own attributes, regardless of the [`Flavor`](crate::Flavor).
+/// When parsing, unrecognized attributes of known elements are stored as extra attributes, so they survive a round
+/// trip through this crate instead of being dropped.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::ExtraAttrs;
+///
+/// let doc = ssml::parse::parse_str(r#"<speak><emphasis level="strong" vendor:pace="2">Hi!</emphasis></speak>"#)?;
+/// let ssml::Element::Emphasis(emphasis) = &doc.children()[0] else { unreachable!() };
+/// assert_eq!(emphasis.extra_attr("vendor:pace"), Some("2"));
+///
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><emphasis level="strong" vendor:pace="2">Hi!</emphasis></speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub trait ExtraAttrs<'s> {
+	/// Returns the extra attributes of this element, in the order they will be written.
+	fn extra_attrs(&self) -> &[(Cow<'s, str>, Cow<'s, str>)];
+
+	fn extra_attrs_mut(&mut self) -> &mut Vec<(Cow<'s, str>, Cow<'s, str>)>;
+
+	/// Returns the value of the extra attribute with the given name.
+	fn extra_attr<'a>(&'a self, name: &str) -> Option<&'a str>
+	where
+		's: 'a
+	{
+		self.extra_attrs().iter().find(|(n, _)| n == name).map(|(_, value)| &**value)
+	}
+
+	/// Sets the value of an extra attribute, replacing its previous value if it was already set.
+	fn set_extra_attr(&mut self, name: impl Into<Cow<'s, str>>, value: impl Into<Cow<'s, str>>) {
+		let (name, value) = (name.into(), value.into());
+		let attrs = self.extra_attrs_mut();
+		match attrs.iter_mut().find(|(n, _)| *n == name) {
+			Some((_, v)) => *v = value,
+			None => attrs.push((name, value))
+		}
+	}
+
+	/// Sets the value of an extra attribute, replacing its previous value if it was already set.
+	fn with_extra_attr(mut self, name: impl Into<Cow<'s, str>>, value: impl Into<Cow<'s, str>>) -> Self
+	where
+		Self: Sized
+	{
+		self.set_extra_attr(name, value);
+		self
+	}
+
+	/// Removes an extra attribute, returning its value.
+	fn remove_extra_attr(&mut self, name: &str) -> Option<Cow<'s, str>> {
+		let attrs = self.extra_attrs_mut();
+		let index = attrs.iter().position(|(n, _)| n == name)?;
+		Some(attrs.remove(index).1)
+	}
+}
+
+macro_rules! impl_extra_attrs {
+	($($t:ident.$field:ident),*) => {
+		$(impl<'s> ExtraAttrs<'s> for $t<'s> {
+			fn extra_attrs(&self) -> &[(Cow<'s, str>, Cow<'s, str>)] {
+				&self.$field
+			}
+
+			fn extra_attrs_mut(&mut self) -> &mut Vec<(Cow<'s, str>, Cow<'s, str>)> {
+				&mut self.$field
+			}
+		})*
+	};
+}
+
+impl_extra_attrs!(
+	Speak.extra_attrs,
+	Audio.extra_attrs,
+	Voice.attrs,
+	Emphasis.extra_attrs,
+	Mark.extra_attrs,
+	Lexicon.extra_attrs,
+	SayAs.extra_attrs,
+	Phoneme.extra_attrs,
+	Sub.extra_attrs,
+	Paragraph.extra_attrs,
+	Sentence.extra_attrs,
+	Word.extra_attrs,
+	Lang.extra_attrs,
+	Prosody.extra_attrs,
+	Express.extra_attrs,
+	Silence.extra_attrs,
+	BackgroundAudio.extra_attrs,
+	Viseme.extra_attrs,
+	TtsEmbedding.extra_attrs,
+	AudioDuration.extra_attrs,
+	Math.extra_attrs
+);
+
+/// Writes a list of attributes to the element currently being written.
+pub(crate) fn write<W: Write>(writer: &mut XmlWriter<W>, attrs: &Attrs) -> crate::Result<()> {
+	for (name, value) in attrs {
+		writer.attr(&**name, &**value)?;
+	}
+	Ok(())
+}
+
+pub(crate) fn into_owned(attrs: Attrs) -> Attrs<'static> {
+	attrs
+		.into_iter()
+		.map(|(name, value)| {
+			(
+				match name {
+					Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+					Cow::Owned(b) => Cow::Owned(b)
+				},
+				match value {
+					Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+					Cow::Owned(b) => Cow::Owned(b)
+				}
+			)
+		})
+		.collect()
+}
@@ -1,10 +1,13 @@
-use alloc::{borrow::Cow, string::ToString, vec::Vec};
-use core::fmt::Write;
+use alloc::{borrow::Cow, string::ToString, vec, vec::Vec};
+use core::{fmt::Write, mem};
 
 use crate::{
-	Element, Serialize, SerializeOptions, XmlWriter,
+	Desc, Element, Serialize, SerializeOptions, Speak, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
 	unit::{Decibels, SpeedFormatter, TimeDesignation},
-	util
+	util,
+	visit::{self, Visit}
 };
 
 /// Specify repeating an [`Audio`] element's playback for a certain number of times, or for a determined duration.
@@ -20,18 +23,51 @@ pub enum AudioRepeat {
 	Duration(TimeDesignation)
 }
 
+/// When an [`Audio`] element's source is fetched, as a hint to the speech synthesizer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FetchHint {
+	/// The audio may be fetched as soon as the document is loaded, before it's needed. (`fetchhint="prefetch"`)
+	Prefetch,
+	/// The audio is only fetched when it's played. (`fetchhint="safe"`)
+	Safe
+}
+
+impl FetchHint {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			FetchHint::Prefetch => "prefetch",
+			FetchHint::Safe => "safe"
+		}
+	}
+}
+
 /// [`Audio`] supports the insertion of recorded audio files and the insertion of other audio formats in conjunction
 /// with synthesized speech output.
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Audio<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
 	src: Cow<'s, str>,
-	desc: Option<Cow<'s, str>>,
+	#[cfg_attr(feature = "serde", serde(borrow, default, skip_serializing_if = "Vec::is_empty"))]
+	desc: Vec<Desc<'s>>,
+	#[cfg_attr(feature = "serde", serde(borrow))]
 	alternate: Vec<Element<'s>>,
 	clip: (Option<TimeDesignation>, Option<TimeDesignation>),
 	repeat: Option<AudioRepeat>,
 	sound_level: Option<Decibels>,
-	speed: Option<f32>
+	speed: Option<f32>,
+	fetch_timeout: Option<TimeDesignation>,
+	fetch_hint: Option<FetchHint>,
+	max_age: Option<u32>,
+	max_stale: Option<u32>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> Audio<'s> {
@@ -65,26 +101,39 @@ impl<'s> Audio<'s> {
 		self
 	}
 
-	/// Sets an accessible description for this audio element.
+	/// Adds an accessible description for this audio element. Descriptions in multiple languages can be provided by
+	/// calling this multiple times with a [`Desc`] for each language.
 	///
 	/// ```
 	/// ssml::audio("cat_purr.ogg").with_desc("a purring cat");
 	/// ```
-	pub fn with_desc(mut self, desc: impl Into<Cow<'s, str>>) -> Self {
-		self.desc = Some(desc.into());
+	pub fn with_desc(mut self, desc: impl Into<Desc<'s>>) -> Self {
+		self.desc.push(desc.into());
 		self
 	}
 
+	/// Returns the text of this audio element's first description, if any.
 	pub fn desc(&self) -> Option<&str> {
-		self.desc.as_deref()
+		self.desc.first().map(Desc::text)
 	}
 
-	pub fn set_desc(&mut self, desc: impl Into<Cow<'s, str>>) {
-		self.desc = Some(desc.into());
+	/// Returns all of this audio element's descriptions.
+	pub fn descs(&self) -> &[Desc<'s>] {
+		&self.desc
 	}
 
-	pub fn take_desc(&mut self) -> Option<Cow<'s, str>> {
-		self.desc.take()
+	pub fn descs_mut(&mut self) -> &mut Vec<Desc<'s>> {
+		&mut self.desc
+	}
+
+	/// Replaces all of this audio element's descriptions with `desc`.
+	pub fn set_desc(&mut self, desc: impl Into<Desc<'s>>) {
+		self.desc = vec![desc.into()];
+	}
+
+	/// Removes all of this audio element's descriptions, returning them.
+	pub fn take_desc(&mut self) -> Vec<Desc<'s>> {
+		mem::take(&mut self.desc)
 	}
 
 	/// Specify an offset from the beginning and to the end of which to clip this audio's duration to.
@@ -209,13 +258,113 @@ impl<'s> Audio<'s> {
 	}
 
 	pub fn set_speed(&mut self, speed: f32) {
-		self.speed = Some(speed.into());
+		self.speed = Some(speed);
 	}
 
 	pub fn take_speed(&mut self) -> Option<f32> {
 		self.speed.take()
 	}
 
+	/// Specify how long to wait while fetching the audio before giving up & using the alternate content instead.
+	///
+	/// ```
+	/// ssml::audio("https://example.com/hold_music.mp3")
+	/// 	.with_fetch_timeout("5s")
+	/// 	.with_alternate(["Please hold."]);
+	/// ```
+	pub fn with_fetch_timeout(mut self, timeout: impl Into<TimeDesignation>) -> Self {
+		self.fetch_timeout = Some(timeout.into());
+		self
+	}
+
+	pub fn fetch_timeout(&self) -> Option<&TimeDesignation> {
+		self.fetch_timeout.as_ref()
+	}
+
+	pub fn set_fetch_timeout(&mut self, timeout: impl Into<TimeDesignation>) {
+		self.fetch_timeout = Some(timeout.into());
+	}
+
+	pub fn take_fetch_timeout(&mut self) -> Option<TimeDesignation> {
+		self.fetch_timeout.take()
+	}
+
+	/// Specify whether the audio may be fetched ahead of time, or only when it's played. See [`FetchHint`].
+	///
+	/// ```
+	/// # use ssml::{FetchHint, Serialize, SerializeOptions};
+	/// # fn main() -> ssml::Result<()> {
+	/// let audio = ssml::audio("https://example.com/menu.wav")
+	/// 	.with_fetch_hint(FetchHint::Prefetch)
+	/// 	.with_fetch_timeout("3s")
+	/// 	.with_max_age(86400);
+	/// assert_eq!(
+	/// 	audio.serialize_to_string(&SerializeOptions::default())?,
+	/// 	r#"<audio src="https://example.com/menu.wav" fetchtimeout="+3000ms" fetchhint="prefetch" maxage="86400"/>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_fetch_hint(mut self, hint: FetchHint) -> Self {
+		self.fetch_hint = Some(hint);
+		self
+	}
+
+	pub fn fetch_hint(&self) -> Option<FetchHint> {
+		self.fetch_hint
+	}
+
+	pub fn set_fetch_hint(&mut self, hint: FetchHint) {
+		self.fetch_hint = Some(hint);
+	}
+
+	pub fn take_fetch_hint(&mut self) -> Option<FetchHint> {
+		self.fetch_hint.take()
+	}
+
+	/// Specify the maximum age of a cached copy of the audio which may be used, in seconds, like the `max-age` HTTP
+	/// cache control directive.
+	///
+	/// ```
+	/// // re-fetch the daily announcement at least once an hour, but tolerate a copy up to 5 minutes out of date
+	/// ssml::audio("https://example.com/announcement.wav").with_max_age(3600).with_max_stale(300);
+	/// ```
+	pub fn with_max_age(mut self, seconds: u32) -> Self {
+		self.max_age = Some(seconds);
+		self
+	}
+
+	pub fn max_age(&self) -> Option<u32> {
+		self.max_age
+	}
+
+	pub fn set_max_age(&mut self, seconds: u32) {
+		self.max_age = Some(seconds);
+	}
+
+	pub fn take_max_age(&mut self) -> Option<u32> {
+		self.max_age.take()
+	}
+
+	/// Specify how long past its expiration a cached copy of the audio may still be used, in seconds, like the
+	/// `max-stale` HTTP cache control directive.
+	pub fn with_max_stale(mut self, seconds: u32) -> Self {
+		self.max_stale = Some(seconds);
+		self
+	}
+
+	pub fn max_stale(&self) -> Option<u32> {
+		self.max_stale
+	}
+
+	pub fn set_max_stale(&mut self, seconds: u32) {
+		self.max_stale = Some(seconds);
+	}
+
+	pub fn take_max_stale(&mut self) -> Option<u32> {
+		self.max_stale.take()
+	}
+
 	/// Returns a reference to the elements contained in this `audio` element's alternate/fallback section.
 	pub fn alternate(&self) -> &[Element<'s>] {
 		&self.alternate
@@ -236,22 +385,42 @@ impl<'s> Audio<'s> {
 				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
 				Cow::Owned(b) => Cow::Owned(b)
 			},
-			desc: match self.desc {
-				Some(Cow::Borrowed(b)) => Some(Cow::Owned(b.to_string())),
-				Some(Cow::Owned(b)) => Some(Cow::Owned(b)),
-				None => None
-			},
+			desc: self.desc.into_iter().map(Desc::into_owned).collect(),
 			alternate: self.alternate.into_iter().map(Element::into_owned).collect(),
 			clip: self.clip,
 			repeat: self.repeat,
 			sound_level: self.sound_level,
-			speed: self.speed
+			speed: self.speed,
+			fetch_timeout: self.fetch_timeout,
+			fetch_hint: self.fetch_hint,
+			max_age: self.max_age,
+			max_stale: self.max_stale,
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
 
 impl<'s> Serialize for Audio<'s> {
 	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		if self.clip.0.is_some() {
+			util::require_v1_1(options, "clipBegin")?;
+		}
+		if self.clip.1.is_some() {
+			util::require_v1_1(options, "clipEnd")?;
+		}
+		match &self.repeat {
+			Some(AudioRepeat::Duration(_)) => util::require_v1_1(options, "repeatDur")?,
+			Some(AudioRepeat::Times(_)) => util::require_v1_1(options, "times")?,
+			None => {}
+		}
+		if self.sound_level.is_some() {
+			util::require_v1_1(options, "soundLevel")?;
+		}
+		if self.speed.is_some() {
+			util::require_v1_1(options, "speed")?;
+		}
+
 		writer.element("audio", |writer| {
 			writer.attr("src", &*self.src)?;
 
@@ -265,11 +434,16 @@ impl<'s> Serialize for Audio<'s> {
 				}
 			}
 
-			writer.attr_opt("soundLevel", self.sound_level.as_ref().map(|t| t))?;
-			writer.attr_opt("speed", self.speed.map(|s| SpeedFormatter(s)))?;
+			writer.attr_opt("soundLevel", self.sound_level.as_ref())?;
+			writer.attr_opt("speed", self.speed.map(SpeedFormatter))?;
+			writer.attr_opt("fetchtimeout", self.fetch_timeout.as_ref())?;
+			writer.attr_opt("fetchhint", self.fetch_hint.map(|hint| hint.as_str()))?;
+			writer.attr_opt("maxage", self.max_age)?;
+			writer.attr_opt("maxstale", self.max_stale)?;
+			attrs::write(writer, &self.extra_attrs)?;
 
-			if let Some(desc) = &self.desc {
-				writer.element("desc", |writer| writer.text(desc))?;
+			for desc in &self.desc {
+				desc.serialize_xml(writer, options)?;
 			}
 
 			util::serialize_elements(writer, &self.alternate, options)?;
@@ -288,3 +462,43 @@ impl<'s> Serialize for Audio<'s> {
 pub fn audio<'s>(src: impl Into<Cow<'s, str>>) -> Audio<'s> {
 	Audio::new(src)
 }
+
+/// An audio file referenced by a document, as returned by [`Speak::audio_sources`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct AudioSource<'a> {
+	/// The URI of the audio file.
+	pub src: &'a str,
+	/// The offset into the audio file at which playback begins, if clipped.
+	pub clip_begin: Option<&'a TimeDesignation>,
+	/// The offset into the audio file at which playback ends, if clipped.
+	pub clip_end: Option<&'a TimeDesignation>,
+	/// How the audio file is repeated, if at all.
+	pub repeat: Option<&'a AudioRepeat>
+}
+
+#[derive(Default)]
+struct SourceCollector<'a> {
+	sources: Vec<AudioSource<'a>>
+}
+
+impl<'a> Visit<'a> for SourceCollector<'a> {
+	fn visit_audio(&mut self, node: &'a Audio) {
+		let source = AudioSource {
+			src: node.src(),
+			clip_begin: node.clip_begin(),
+			clip_end: node.clip_end(),
+			repeat: node.repeat()
+		};
+		if !self.sources.contains(&source) {
+			self.sources.push(source);
+		}
+		visit::visit_audio(self, node);
+	}
+}
+
+pub(crate) fn sources<'a>(doc: &'a Speak) -> Vec<AudioSource<'a>> {
+	let mut collector = SourceCollector::default();
+	collector.visit_speak(doc);
+	collector.sources
+}
@@ -0,0 +1,475 @@
+//! Helpers for composing long-form content, like audiobooks.
+//!
+//! A [`Book`] is made up of [`Chapter`]s. Each chapter starts with a [`Mark`] labelling it, so its position in the
+//! synthesized audio can be found, followed by its title and its text, split into paragraphs at blank lines.
+//!
+//! Long documents usually exceed the size limit of a single synthesis request; [`split`] splits a document into
+//! multiple documents which each fit within a limit, with continuity marks at the boundaries between them.
+//!
+//! ```
+//! # use ssml::Serialize;
+//! # fn main() -> ssml::Result<()> {
+//! use ssml::audiobook::{Book, Chapter};
+//!
+//! let book = Book::new()
+//! 	.with_chapter(
+//! 		Chapter::new("Chapter One").with_text("It was a dark and stormy night.\n\nThe rain fell in torrents.")
+//! 	)
+//! 	.with_chapter(
+//! 		Chapter::new("Chapter Two")
+//! 			.with_text("The next morning was bright.")
+//! 			.with_voice("en-US-GuyNeural")
+//! 	);
+//! assert_eq!(
+//! 	book.build().serialize_to_string(&ssml::SerializeOptions::default().pretty())?,
+//! 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">
+//! 	<mark name="chapter-1" />
+//! 	<p>
+//! 		Chapter One
+//! 	</p>
+//! 	<break time="+1000ms" />
+//! 	<p>
+//! 		It was a dark and stormy night.
+//! 	</p>
+//! 	<p>
+//! 		The rain fell in torrents.
+//! 	</p>
+//! 	<break time="+2000ms" />
+//! 	<voice name="en-US-GuyNeural">
+//! 		<mark name="chapter-2" />
+//! 		<p>
+//! 			Chapter Two
+//! 		</p>
+//! 		<break time="+1000ms" />
+//! 		<p>
+//! 			The next morning was bright.
+//! 		</p>
+//! 	</voice>
+//! </speak>"#
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
+use core::mem;
+
+use crate::{
+	Break, Element, Mark, Paragraph, ProsodyControl, Serialize, SerializeOptions, Speak, TimeDesignation, VoiceConfig, group, ids::IdGenerator, prosody, voice
+};
+
+/// A chapter of a [`Book`]: a labelled section with a title and paragraphs of text, optionally read with its own voice
+/// and/or prosody.
+#[derive(Debug, Clone)]
+pub struct Chapter<'s> {
+	title: Cow<'s, str>,
+	label: Option<Cow<'s, str>>,
+	content: Vec<Element<'s>>,
+	voice: Option<VoiceConfig<'s>>,
+	prosody: Option<ProsodyControl>,
+	pause: Break
+}
+
+impl<'s> Chapter<'s> {
+	pub fn new(title: impl Into<Cow<'s, str>>) -> Self {
+		Chapter {
+			title: title.into(),
+			label: None,
+			content: Vec::new(),
+			voice: None,
+			prosody: None,
+			pause: Break::new_with_time(TimeDesignation::from_millis(1000.))
+		}
+	}
+
+	/// Sets the name of the [`Mark`] at the start of this chapter. When the chapter is part of a [`Book`], this
+	/// defaults to `chapter-{n}`, where `n` is the (1-based) number of the chapter; otherwise, no mark is added.
+	pub fn with_label(mut self, label: impl Into<Cow<'s, str>>) -> Self {
+		self.label = Some(label.into());
+		self
+	}
+
+	/// Adds text to this chapter. The text is split into [`Paragraph`]s at blank lines.
+	pub fn with_text(mut self, text: impl Into<Cow<'s, str>>) -> Self {
+		match text.into() {
+			Cow::Borrowed(text) => self.content.extend(blocks(text).into_iter().map(|block| Paragraph::new([block]).into())),
+			Cow::Owned(text) => self
+				.content
+				.extend(blocks(&text).into_iter().map(|block| Paragraph::new([String::from(block)]).into()))
+		}
+		self
+	}
+
+	/// Adds a paragraph made of the given elements to this chapter.
+	pub fn with_paragraph<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(mut self, elements: I) -> Self {
+		self.content.push(Paragraph::new(elements).into());
+		self
+	}
+
+	/// Reads this chapter with the given voice instead of the voice of the surrounding document.
+	pub fn with_voice(mut self, config: impl Into<VoiceConfig<'s>>) -> Self {
+		self.voice = Some(config.into());
+		self
+	}
+
+	/// Reads this chapter with the given prosody.
+	pub fn with_prosody(mut self, control: impl Into<ProsodyControl>) -> Self {
+		self.prosody = Some(control.into());
+		self
+	}
+
+	/// Sets the pause after the title of this chapter. Defaults to 1 second.
+	pub fn with_pause(mut self, pause: impl Into<Break>) -> Self {
+		self.pause = pause.into();
+		self
+	}
+
+	/// Expands this chapter into a [`Voice`](crate::Voice) or [`Prosody`](crate::Prosody) if it has overrides, or a
+	/// [`Group`](crate::Group) otherwise.
+	pub fn build(self) -> Element<'s> {
+		let mut children = Vec::with_capacity(self.content.len() + 3);
+		if let Some(label) = self.label {
+			children.push(Mark::new(label).into());
+		}
+		children.push(Paragraph::new([self.title]).into());
+		children.push(self.pause.into());
+		children.extend(self.content);
+		match (self.voice, self.prosody) {
+			(Some(config), Some(control)) => voice(config, [prosody(control, children)]).into(),
+			(Some(config), None) => voice(config, children).into(),
+			(None, Some(control)) => prosody(control, children).into(),
+			(None, None) => group(children).into()
+		}
+	}
+}
+
+impl<'s> From<Chapter<'s>> for Element<'s> {
+	fn from(value: Chapter<'s>) -> Self {
+		value.build()
+	}
+}
+
+/// Splits `text` into blocks separated by blank lines, trimming each block.
+fn blocks(text: &str) -> Vec<&str> {
+	let mut blocks = Vec::new();
+	let (mut start, mut end, mut offset) = (None, 0, 0);
+	for line in text.split_inclusive('\n') {
+		if line.trim().is_empty() {
+			if let Some(start) = start.take() {
+				blocks.push(text[start..end].trim());
+			}
+		} else {
+			start.get_or_insert(offset);
+			end = offset + line.len();
+		}
+		offset += line.len();
+	}
+	if let Some(start) = start {
+		blocks.push(text[start..end].trim());
+	}
+	blocks
+}
+
+/// A book made up of [`Chapter`]s, separated by pauses.
+#[derive(Debug, Clone)]
+pub struct Book<'s> {
+	lang: Option<Cow<'s, str>>,
+	chapters: Vec<Chapter<'s>>,
+	pause: Break
+}
+
+impl Default for Book<'_> {
+	fn default() -> Self {
+		Book::new()
+	}
+}
+
+impl<'s> Book<'s> {
+	pub fn new() -> Self {
+		Book {
+			lang: None,
+			chapters: Vec::new(),
+			pause: Break::new_with_time(TimeDesignation::from_millis(2000.))
+		}
+	}
+
+	/// Sets the language of the book.
+	pub fn with_lang(mut self, lang: impl Into<Cow<'s, str>>) -> Self {
+		self.lang = Some(lang.into());
+		self
+	}
+
+	pub fn with_chapter(mut self, chapter: Chapter<'s>) -> Self {
+		self.chapters.push(chapter);
+		self
+	}
+
+	/// Sets the pause between chapters. Defaults to 2 seconds.
+	pub fn with_pause(mut self, pause: impl Into<Break>) -> Self {
+		self.pause = pause.into();
+		self
+	}
+
+	pub fn chapters(&self) -> &[Chapter<'s>] {
+		&self.chapters
+	}
+
+	/// Expands this book into an SSML document.
+	///
+	/// The pause between chapters is placed at the end of the previous chapter, so that it's read with the previous
+	/// chapter's voice.
+	pub fn build(self) -> Speak<'s> {
+		let mut doc = Speak::new(None, [] as [Element; 0]);
+		if let Some(lang) = self.lang {
+			doc.set_lang(lang);
+		}
+		let count = self.chapters.len();
+		for (i, mut chapter) in self.chapters.into_iter().enumerate() {
+			if chapter.label.is_none() {
+				chapter.label = Some(Cow::Owned(format!("chapter-{}", i + 1)));
+			}
+			if i + 1 < count {
+				chapter.content.push(self.pause.clone().into());
+			}
+			doc.push(chapter);
+		}
+		doc
+	}
+}
+
+impl<'s> From<Book<'s>> for Speak<'s> {
+	fn from(value: Book<'s>) -> Self {
+		value.build()
+	}
+}
+
+/// Configures how [`split`] splits a document.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SplitOptions {
+	/// The maximum length of each serialized document, in bytes. Since there are at least as many bytes as there are
+	/// characters, this also works for providers which limit the number of characters in a request.
+	pub max_len: usize,
+	/// The prefix used for the names of continuity marks, followed by an ID generated by [`ids`](SplitOptions::ids).
+	/// Defaults to `chunk-`.
+	pub mark_prefix: Cow<'static, str>,
+	/// Generates the ID following the prefix of each continuity mark's name. Defaults to [`IdGenerator::sequential`],
+	/// which (starting from `1`) uses the index of the document starting at the mark.
+	pub ids: IdGenerator,
+	/// The options used to serialize (and thus measure) the documents.
+	pub serialize: SerializeOptions
+}
+
+impl SplitOptions {
+	pub fn new(max_len: usize) -> Self {
+		SplitOptions {
+			max_len,
+			mark_prefix: Cow::Borrowed("chunk-"),
+			ids: IdGenerator::sequential(),
+			serialize: SerializeOptions::default()
+		}
+	}
+
+	pub fn with_mark_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+		self.mark_prefix = prefix.into();
+		self
+	}
+
+	pub fn with_ids(mut self, ids: IdGenerator) -> Self {
+		self.ids = ids;
+		self
+	}
+
+	pub fn with_serialize_options(mut self, options: SerializeOptions) -> Self {
+		self.serialize = options;
+		self
+	}
+}
+
+/// Splits `doc` into multiple documents which each serialize to at most [`SplitOptions::max_len`] bytes, so that each
+/// can be sent as a separate synthesis request.
+///
+/// Documents are split between the direct children of `doc` where possible. Elements which are too long by themselves
+/// are split between their children, with the element repeated around each part, so that e.g. the voice & prosody of
+/// a [`Chapter`] carry over into the next document; text is split between words. Elements which can't be split
+/// further, like a single word longer than the limit, are placed in a document of their own, which may exceed the
+/// limit.
+///
+/// Every document other than the first starts with a continuity mark named `{mark_prefix}{id}`, and every
+/// document other than the last ends with a mark of the same name as the start of the next document, so the
+/// synthesized audio can be aligned and stitched back together. Each document keeps the language, defaults &
+/// metadata of `doc`.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::audiobook::SplitOptions;
+///
+/// let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", ["The quick brown fox jumps over the lazy dog."])]);
+/// let parts = ssml::audiobook::split(doc, &SplitOptions::new(220))?;
+/// assert_eq!(parts.len(), 2);
+/// assert_eq!(
+/// 	parts[0].serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-JennyNeural">The quick brown fox <mark name="chunk-1"/></voice></speak>"#
+/// );
+/// assert_eq!(
+/// 	parts[1].serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-JennyNeural"><mark name="chunk-1"/>jumps over the lazy dog.</voice></speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn split<'s>(mut doc: Speak<'s>, options: &SplitOptions) -> crate::Result<Vec<Speak<'s>>> {
+	let children = mem::take(doc.children_mut());
+	let mark_len = measure(&Mark::new(format!("{}{}", options.mark_prefix, usize::MAX)), &options.serialize)?;
+	let mut shell = doc.clone();
+	shell.push("x");
+	let overhead = measure(&shell, &options.serialize)? - 1 + 2 * mark_len;
+
+	let mut packer = Packer::new(&options.serialize, options.max_len.saturating_sub(overhead));
+	for child in children {
+		packer.add(child)?;
+	}
+	let chunks = packer.finish();
+	if chunks.is_empty() {
+		return Ok(alloc::vec![doc]);
+	}
+
+	let count = chunks.len();
+	// an ID is generated for each document, though the first document doesn't start with a mark
+	let mut ids = options.ids.clone();
+	let names: Vec<String> = (0..count).map(|_| format!("{}{}", options.mark_prefix, ids.next_id())).collect();
+	Ok(chunks
+		.into_iter()
+		.enumerate()
+		.map(|(i, mut chunk)| {
+			if i > 0 {
+				insert_mark(&mut chunk, true, Mark::new(names[i].clone()));
+			}
+			if i + 1 < count {
+				insert_mark(&mut chunk, false, Mark::new(names[i + 1].clone()));
+			}
+			let mut part = doc.clone();
+			*part.children_mut() = chunk;
+			part
+		})
+		.collect())
+}
+
+fn measure<T: Serialize>(el: &T, options: &SerializeOptions) -> crate::Result<usize> {
+	Ok(el.serialize_to_string(options)?.len())
+}
+
+/// Inserts a continuity mark at the start or end of `chunk`. The mark is placed inside the first or last element of the
+/// chunk if it's a [`Voice`](crate::Voice), so the mark doesn't end up outside of a voice.
+fn insert_mark<'s>(chunk: &mut Vec<Element<'s>>, at_start: bool, mark: Mark<'s>) {
+	let index = if at_start { 0 } else { chunk.len().saturating_sub(1) };
+	let target = match chunk.get_mut(index) {
+		Some(Element::Voice(voice)) => voice.children_mut(),
+		_ => chunk
+	};
+	if at_start { target.insert(0, mark.into()) } else { target.push(mark.into()) }
+}
+
+/// Greedily packs elements into chunks which each serialize to at most `budget` bytes.
+struct Packer<'s, 'o> {
+	options: &'o SerializeOptions,
+	budget: usize,
+	chunks: Vec<Vec<Element<'s>>>,
+	current: Vec<Element<'s>>,
+	current_len: usize
+}
+
+impl<'s, 'o> Packer<'s, 'o> {
+	fn new(options: &'o SerializeOptions, budget: usize) -> Self {
+		Packer {
+			options,
+			budget,
+			chunks: Vec::new(),
+			current: Vec::new(),
+			current_len: 0
+		}
+	}
+
+	fn add(&mut self, el: Element<'s>) -> crate::Result<()> {
+		let len = measure(&el, self.options)?;
+		if len > self.budget {
+			return self.split(el, len);
+		}
+		self.place(el, len);
+		Ok(())
+	}
+
+	/// Adds `el` to the current chunk, or to a new chunk if it doesn't fit in the current one.
+	fn place(&mut self, el: Element<'s>, len: usize) {
+		if self.current_len + len > self.budget {
+			self.flush();
+		}
+		self.current.push(el);
+		self.current_len += len;
+	}
+
+	/// Splits an element which is too long to fit in a chunk by itself into parts which do fit.
+	fn split(&mut self, mut el: Element<'s>, len: usize) -> crate::Result<()> {
+		if let Element::Text(text) = &el {
+			let mut part = String::new();
+			let mut part_len = 0;
+			for word in text.split_inclusive(char::is_whitespace) {
+				let word_len = measure(&Element::from(word), self.options)?;
+				if part_len + word_len > self.budget && !part.is_empty() {
+					self.place(Element::from(mem::take(&mut part)), part_len);
+					part_len = 0;
+				}
+				part.push_str(word);
+				part_len += word_len;
+			}
+			if !part.is_empty() {
+				self.place(Element::from(part), part_len);
+			}
+			return Ok(());
+		}
+
+		let children = match &mut el {
+			// audio alternate content can't be split; the audio is either played or it isn't
+			Element::Audio(_) => None,
+			// splitting a `say-as` would change how its text is interpreted
+			Element::SayAs(_) => None,
+			el => el.children_mut().map(mem::take)
+		};
+		let Some(children) = children else {
+			self.place(el, len);
+			return Ok(());
+		};
+
+		let mut shell = el.clone();
+		if let Some(shell_children) = shell.children_mut() {
+			shell_children.push(Element::from("x"));
+		}
+		let overhead = measure(&shell, self.options)? - 1;
+		let mut inner = Packer::new(self.options, self.budget.saturating_sub(overhead));
+		for child in children {
+			inner.add(child)?;
+		}
+		for chunk in inner.finish() {
+			let mut part = el.clone();
+			if let Some(part_children) = part.children_mut() {
+				*part_children = chunk;
+			}
+			let len = measure(&part, self.options)?;
+			self.place(part, len);
+		}
+		Ok(())
+	}
+
+	fn flush(&mut self) {
+		if !self.current.is_empty() {
+			self.chunks.push(mem::take(&mut self.current));
+		}
+		self.current_len = 0;
+	}
+
+	fn finish(mut self) -> Vec<Vec<Element<'s>>> {
+		self.flush();
+		self.chunks
+	}
+}
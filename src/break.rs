@@ -14,20 +14,95 @@ pub enum BreakStrength {
 	ExtraStrong
 }
 
-#[derive(Debug, Clone)]
+impl BreakStrength {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			BreakStrength::None => "none",
+			BreakStrength::ExtraWeak => "x-weak",
+			BreakStrength::Weak => "weak",
+			BreakStrength::Medium => "medium",
+			BreakStrength::Strong => "strong",
+			BreakStrength::ExtraStrong => "x-strong"
+		}
+	}
+}
+
+/// A pause in speech, with a [strength](BreakStrength) describing its prosodic boundary and/or an exact duration.
+///
+/// Either or both may be specified; when both are, speech synthesizers use the duration, and the strength only affects
+/// the prosody around the break. A break with neither is a [`Medium`](BreakStrength::Medium) break.
+///
+/// ```
+/// # use ssml::{Break, BreakStrength, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// let pause = ssml::breaks(BreakStrength::Strong).with_time("2s".parse::<ssml::TimeDesignation>()?);
+/// assert_eq!(
+/// 	pause.serialize_to_string(&SerializeOptions::default())?,
+/// 	r#"<break strength="strong" time="+2000ms"/>"#
+/// );
+///
+/// // a strength or time alone converts into a break
+/// assert_eq!(ssml::breaks("500ms").time().map(|t| t.to_millis()), Some(500.));
+/// assert_eq!(Break::from(BreakStrength::Weak).strength(), Some(BreakStrength::Weak));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum Break {
-	Strength(BreakStrength),
-	Time(TimeDesignation)
+pub struct Break {
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+	strength: Option<BreakStrength>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+	time: Option<TimeDesignation>
 }
 
 impl Break {
+	/// Creates a break with neither a strength nor a time, which speech synthesizers treat as a
+	/// [`Medium`](BreakStrength::Medium) break.
+	pub fn new() -> Self {
+		Break::default()
+	}
+
 	pub fn new_with_strength(strength: BreakStrength) -> Self {
-		Break::Strength(strength)
+		Break::new().with_strength(strength)
 	}
 
 	pub fn new_with_time(time: impl Into<TimeDesignation>) -> Self {
-		Break::Time(time.into())
+		Break::new().with_time(time)
+	}
+
+	pub fn strength(&self) -> Option<BreakStrength> {
+		self.strength
+	}
+
+	pub fn with_strength(mut self, strength: BreakStrength) -> Self {
+		self.strength = Some(strength);
+		self
+	}
+
+	pub fn set_strength(&mut self, strength: BreakStrength) {
+		self.strength = Some(strength);
+	}
+
+	pub fn take_strength(&mut self) -> Option<BreakStrength> {
+		self.strength.take()
+	}
+
+	pub fn time(&self) -> Option<&TimeDesignation> {
+		self.time.as_ref()
+	}
+
+	pub fn with_time(mut self, time: impl Into<TimeDesignation>) -> Self {
+		self.time = Some(time.into());
+		self
+	}
+
+	pub fn set_time(&mut self, time: impl Into<TimeDesignation>) {
+		self.time = Some(time.into());
+	}
+
+	pub fn take_time(&mut self) -> Option<TimeDesignation> {
+		self.time.take()
 	}
 }
 
@@ -48,16 +123,9 @@ where
 
 impl Serialize for Break {
 	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
-		writer.element("break", |writer| match self {
-			Break::Strength(strength) => writer.attr("strength", match strength {
-				BreakStrength::None => "none",
-				BreakStrength::ExtraWeak => "x-weak",
-				BreakStrength::Weak => "weak",
-				BreakStrength::Medium => "medium",
-				BreakStrength::Strong => "strong",
-				BreakStrength::ExtraStrong => "x-strong"
-			}),
-			Break::Time(time) => writer.attr("time", time)
+		writer.element("break", |writer| {
+			writer.attr_opt("strength", self.strength.map(|strength| strength.as_str()))?;
+			writer.attr_opt("time", self.time.as_ref())
 		})
 	}
 }
@@ -0,0 +1,557 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::Debug;
+
+use crate::{Element, Flavor};
+
+/// A kind of SSML element, used to query whether a [`Flavor`] supports it with [`Flavor::supports`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ElementKind {
+	/// [`Audio`](crate::Audio) - `<audio>`
+	Audio,
+	/// [`Break`](crate::Break) - `<break>`
+	Break,
+	/// [`Emphasis`](crate::Emphasis) - `<emphasis>`
+	Emphasis,
+	/// [`Lang`](crate::Lang) - `<lang>`
+	Lang,
+	/// [`Lexicon`](crate::Lexicon) - `<lexicon>`
+	Lexicon,
+	/// [`Mark`](crate::Mark) - `<mark>`
+	Mark,
+	/// [`Paragraph`](crate::Paragraph) - `<p>`
+	Paragraph,
+	/// [`Phoneme`](crate::Phoneme) - `<phoneme>`
+	Phoneme,
+	/// [`Prosody`](crate::Prosody) - `<prosody>`
+	Prosody,
+	/// [`SayAs`](crate::SayAs) - `<say-as>`
+	SayAs,
+	/// [`Sentence`](crate::Sentence) - `<s>`
+	Sentence,
+	/// [`Sub`](crate::Sub) - `<sub>`
+	Sub,
+	/// [`Voice`](crate::Voice) - `<voice>`
+	Voice,
+	/// [`Word`](crate::Word) - `<token>` or `<w>`
+	Word,
+	/// [`Express`](crate::mstts::Express) - `<mstts:express-as>`
+	MsttsExpressAs,
+	/// [`Silence`](crate::mstts::Silence) - `<mstts:silence>`
+	MsttsSilence,
+	/// [`BackgroundAudio`](crate::mstts::BackgroundAudio) - `<mstts:backgroundaudio>`
+	MsttsBackgroundAudio,
+	/// [`Viseme`](crate::mstts::Viseme) - `<mstts:viseme>`
+	MsttsViseme,
+	/// [`TtsEmbedding`](crate::mstts::TtsEmbedding) - `<mstts:ttsembedding>`
+	MsttsTtsEmbedding,
+	/// [`AudioDuration`](crate::mstts::AudioDuration) - `<mstts:audioduration>`
+	MsttsAudioDuration,
+	/// [`Math`](crate::mstts::Math) - `<math>`
+	MsttsMath,
+	/// [`Domain`](crate::polly::Domain) - `<amazon:domain>`
+	AmazonDomain,
+	/// [`Effect`](crate::polly::Effect) - `<amazon:effect>`
+	AmazonEffect,
+	/// [`ExpressAs`](crate::watson::ExpressAs) - `<express-as>`
+	WatsonExpressAs,
+	/// [`VoiceTransformation`](crate::watson::VoiceTransformation) - `<voice-transformation>`
+	WatsonVoiceTransformation
+}
+
+impl ElementKind {
+	/// All element kinds, in the order they are declared.
+	pub const ALL: &'static [ElementKind] = &[
+		ElementKind::Audio,
+		ElementKind::Break,
+		ElementKind::Emphasis,
+		ElementKind::Lang,
+		ElementKind::Lexicon,
+		ElementKind::Mark,
+		ElementKind::Paragraph,
+		ElementKind::Phoneme,
+		ElementKind::Prosody,
+		ElementKind::SayAs,
+		ElementKind::Sentence,
+		ElementKind::Sub,
+		ElementKind::Voice,
+		ElementKind::Word,
+		ElementKind::MsttsExpressAs,
+		ElementKind::MsttsSilence,
+		ElementKind::MsttsBackgroundAudio,
+		ElementKind::MsttsViseme,
+		ElementKind::MsttsTtsEmbedding,
+		ElementKind::MsttsAudioDuration,
+		ElementKind::MsttsMath,
+		ElementKind::AmazonDomain,
+		ElementKind::AmazonEffect,
+		ElementKind::WatsonExpressAs,
+		ElementKind::WatsonVoiceTransformation
+	];
+
+	/// Returns the kind of `el`, or `None` for elements which aren't written as a standard tag of their own: text,
+	/// [`Group`](crate::Group)s, [`Variant`](crate::Variant)s & raw or custom elements.
+	pub fn of(el: &Element) -> Option<ElementKind> {
+		Some(match el {
+			Element::Audio(_) => ElementKind::Audio,
+			Element::Break(_) => ElementKind::Break,
+			Element::Emphasis(_) => ElementKind::Emphasis,
+			Element::Lang(_) => ElementKind::Lang,
+			Element::Mark(_) => ElementKind::Mark,
+			Element::Lexicon(_) => ElementKind::Lexicon,
+			Element::Paragraph(_) => ElementKind::Paragraph,
+			Element::Phoneme(_) => ElementKind::Phoneme,
+			Element::Prosody(_) => ElementKind::Prosody,
+			Element::SayAs(_) => ElementKind::SayAs,
+			Element::Sentence(_) => ElementKind::Sentence,
+			Element::Sub(_) => ElementKind::Sub,
+			Element::Voice(_) => ElementKind::Voice,
+			Element::Word(_) => ElementKind::Word,
+			Element::FlavorMSTTS(crate::mstts::Element::Express(_)) => ElementKind::MsttsExpressAs,
+			Element::FlavorMSTTS(crate::mstts::Element::Silence(_)) => ElementKind::MsttsSilence,
+			Element::FlavorMSTTS(crate::mstts::Element::BackgroundAudio(_)) => ElementKind::MsttsBackgroundAudio,
+			Element::FlavorMSTTS(crate::mstts::Element::Viseme(_)) => ElementKind::MsttsViseme,
+			Element::FlavorMSTTS(crate::mstts::Element::TtsEmbedding(_)) => ElementKind::MsttsTtsEmbedding,
+			Element::FlavorMSTTS(crate::mstts::Element::AudioDuration(_)) => ElementKind::MsttsAudioDuration,
+			Element::FlavorMSTTS(crate::mstts::Element::Math(_)) => ElementKind::MsttsMath,
+			Element::FlavorPolly(crate::polly::Element::Domain(_)) => ElementKind::AmazonDomain,
+			Element::FlavorPolly(crate::polly::Element::Effect(_)) => ElementKind::AmazonEffect,
+			Element::FlavorWatson(crate::watson::Element::ExpressAs(_)) => ElementKind::WatsonExpressAs,
+			Element::FlavorWatson(crate::watson::Element::VoiceTransformation(_)) => ElementKind::WatsonVoiceTransformation,
+			_ => return None
+		})
+	}
+
+	/// Returns whether this kind of element is exclusive to
+	/// [`Flavor::MicrosoftAzureCognitiveSpeechServices`](crate::Flavor::MicrosoftAzureCognitiveSpeechServices), i.e.
+	/// is one of the elements of the [`mstts`](crate::mstts) module.
+	pub const fn is_mstts(&self) -> bool {
+		matches!(
+			self,
+			ElementKind::MsttsExpressAs
+				| ElementKind::MsttsSilence
+				| ElementKind::MsttsBackgroundAudio
+				| ElementKind::MsttsViseme
+				| ElementKind::MsttsTtsEmbedding
+				| ElementKind::MsttsAudioDuration
+				| ElementKind::MsttsMath
+		)
+	}
+
+	/// Returns the generic tag name of this kind of element.
+	pub const fn tag(&self) -> &'static str {
+		match self {
+			ElementKind::Audio => "audio",
+			ElementKind::Break => "break",
+			ElementKind::Emphasis => "emphasis",
+			ElementKind::Lang => "lang",
+			ElementKind::Lexicon => "lexicon",
+			ElementKind::Mark => "mark",
+			ElementKind::Paragraph => "p",
+			ElementKind::Phoneme => "phoneme",
+			ElementKind::Prosody => "prosody",
+			ElementKind::SayAs => "say-as",
+			ElementKind::Sentence => "s",
+			ElementKind::Sub => "sub",
+			ElementKind::Voice => "voice",
+			ElementKind::Word => "token",
+			ElementKind::MsttsExpressAs => "mstts:express-as",
+			ElementKind::MsttsSilence => "mstts:silence",
+			ElementKind::MsttsBackgroundAudio => "mstts:backgroundaudio",
+			ElementKind::MsttsViseme => "mstts:viseme",
+			ElementKind::MsttsTtsEmbedding => "mstts:ttsembedding",
+			ElementKind::MsttsAudioDuration => "mstts:audioduration",
+			ElementKind::MsttsMath => "math",
+			ElementKind::AmazonDomain => "amazon:domain",
+			ElementKind::AmazonEffect => "amazon:effect",
+			ElementKind::WatsonExpressAs => "express-as",
+			ElementKind::WatsonVoiceTransformation => "voice-transformation"
+		}
+	}
+}
+
+/// How well a [`Flavor`] supports a kind of element. See [`Flavor::supports`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Support {
+	/// The element is supported by all voices.
+	Full,
+	/// The element is only supported by some voices (e.g. not by Amazon Polly's Neural voices), or only some of its
+	/// attributes are supported. Including it is safe, but it may not have an effect.
+	Partial,
+	/// The element isn't supported at all; [validation](crate::validate) rejects it.
+	None
+}
+
+/// How well each [`Flavor`] supports a kind of element; a row of the matrix returned by [`Flavor::capabilities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlavorSupport([Support; Flavor::ALL.len()]);
+
+impl FlavorSupport {
+	/// Returns how well `flavor` supports the element.
+	pub const fn get(&self, flavor: Flavor) -> Support {
+		self.0[flavor as usize]
+	}
+
+	/// Returns an iterator over the support of each flavor, in the order of [`Flavor::ALL`].
+	pub fn iter(&self) -> impl Iterator<Item = (Flavor, Support)> + '_ {
+		Flavor::ALL.iter().copied().zip(self.0.iter().copied())
+	}
+}
+
+const CAPABILITIES: [(ElementKind, FlavorSupport); ElementKind::ALL.len()] = {
+	let mut matrix = [(ElementKind::Audio, FlavorSupport([Support::Full; Flavor::ALL.len()])); ElementKind::ALL.len()];
+	let mut i = 0;
+	while i < ElementKind::ALL.len() {
+		let kind = ElementKind::ALL[i];
+		matrix[i].0 = kind;
+		let mut j = 0;
+		while j < Flavor::ALL.len() {
+			// `FlavorSupport::get` indexes by discriminant
+			assert!(Flavor::ALL[j] as usize == j);
+			matrix[i].1.0[j] = Flavor::ALL[j].supports(kind);
+			j += 1;
+		}
+		i += 1;
+	}
+	matrix
+};
+
+impl Flavor {
+	/// All flavors, in the order they are declared.
+	pub const ALL: &'static [Flavor] = &[
+		Flavor::Generic,
+		Flavor::MicrosoftAzureCognitiveSpeechServices,
+		Flavor::GoogleCloudTextToSpeech,
+		Flavor::AmazonPolly,
+		Flavor::PykeSongbird,
+		Flavor::IbmWatson,
+		Flavor::EspeakNg,
+		Flavor::ElevenLabs
+	];
+
+	/// Returns the full support matrix of every [kind of element](ElementKind::ALL) by every [flavor](Flavor::ALL), as
+	/// reported by [`Flavor::supports`]. The matrix is computed at compile time, so tools can render an up-to-date
+	/// support table from it.
+	///
+	/// ```
+	/// use ssml::{ElementKind, Flavor, Support};
+	///
+	/// let matrix = Flavor::capabilities();
+	/// assert_eq!(matrix.len(), ElementKind::ALL.len());
+	///
+	/// let (kind, support) = matrix.iter().find(|(kind, _)| *kind == ElementKind::Voice).unwrap();
+	/// assert_eq!(support.get(Flavor::AmazonPolly), Support::None);
+	/// for (flavor, support) in support.iter() {
+	/// 	assert_eq!(support, flavor.supports(*kind));
+	/// }
+	/// ```
+	pub const fn capabilities() -> &'static [(ElementKind, FlavorSupport)] {
+		&CAPABILITIES
+	}
+
+	/// Returns how well this flavor supports the given kind of element, so applications can decide whether to include
+	/// it before building a document.
+	///
+	/// [`Flavor::Generic`] assumes all elements are fully supported.
+	///
+	/// ```
+	/// use ssml::{ElementKind, Flavor, Support};
+	///
+	/// assert_eq!(Flavor::AmazonPolly.supports(ElementKind::Voice), Support::None);
+	/// assert_eq!(Flavor::AmazonPolly.supports(ElementKind::Emphasis), Support::Partial);
+	/// assert_eq!(Flavor::GoogleCloudTextToSpeech.supports(ElementKind::Emphasis), Support::Full);
+	/// ```
+	pub const fn supports(&self, kind: ElementKind) -> Support {
+		match (self, kind) {
+			(Flavor::ElevenLabs, ElementKind::Break) => Support::Full,
+			// only some models support phonemes
+			(Flavor::ElevenLabs, ElementKind::Phoneme) => Support::Partial,
+			(Flavor::ElevenLabs, _) => Support::None,
+			(Flavor::AmazonPolly, ElementKind::Audio | ElementKind::Voice | ElementKind::MsttsExpressAs) => Support::None,
+			// lexicons are configured through the API instead
+			(Flavor::AmazonPolly | Flavor::GoogleCloudTextToSpeech, ElementKind::Lexicon) => Support::None,
+			// not supported by Neural voices; Neural voices also only support some prosody attributes
+			(Flavor::AmazonPolly, ElementKind::Emphasis | ElementKind::Prosody) => Support::Partial,
+			(Flavor::GoogleCloudTextToSpeech, ElementKind::Word | ElementKind::MsttsExpressAs) => Support::None,
+			// only some voices support emphasis, and express-as styles are specific to each voice
+			(Flavor::MicrosoftAzureCognitiveSpeechServices, ElementKind::Emphasis | ElementKind::MsttsExpressAs) => Support::Partial,
+			// domains are only supported by some Neural voices, and effects only by Standard voices
+			(Flavor::AmazonPolly, ElementKind::AmazonDomain | ElementKind::AmazonEffect) => Support::Partial,
+			(Flavor::IbmWatson, ElementKind::Audio | ElementKind::Lexicon | ElementKind::Voice) => Support::None,
+			// not supported by neural voices; expressions & transformations are only supported by some voices
+			(Flavor::IbmWatson, ElementKind::Emphasis | ElementKind::WatsonExpressAs | ElementKind::WatsonVoiceTransformation) => Support::Partial,
+			// eSpeak NG implements SSML 1.0, & plays audio through a callback rather than from the document
+			(Flavor::EspeakNg, ElementKind::Audio | ElementKind::Lexicon | ElementKind::Word) => Support::None,
+			// only some `interpret-as` values are supported
+			(Flavor::EspeakNg, ElementKind::SayAs) => Support::Partial,
+			(Flavor::Generic | Flavor::PykeSongbird, _) => Support::Full,
+			(Flavor::MicrosoftAzureCognitiveSpeechServices, kind) if kind.is_mstts() => Support::Full,
+			(_, ElementKind::AmazonDomain | ElementKind::AmazonEffect) => Support::None,
+			(_, ElementKind::WatsonExpressAs | ElementKind::WatsonVoiceTransformation) => Support::None,
+			(_, kind) if kind.is_mstts() => Support::None,
+			_ => Support::Full
+		}
+	}
+
+	fn builtin_namespaces(self) -> Vec<(&'static str, &'static str)> {
+		match self {
+			Flavor::MicrosoftAzureCognitiveSpeechServices => vec![("mstts", "http://www.w3.org/2001/mstts")],
+			_ => Vec::new()
+		}
+	}
+}
+
+/// How a [`FlavorSpec`] rewrites an attribute; see [`FlavorSpec::rewrite_attribute`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeRewrite {
+	/// Write the attribute as-is.
+	Keep,
+	/// Don't write the attribute.
+	Remove,
+	/// Write the attribute with a different name, e.g. a vendor-prefixed name.
+	Rename(String),
+	/// Write the attribute with a different value.
+	Set(String)
+}
+
+/// Describes a speech synthesis provider's dialect of SSML, allowing downstream crates to support providers which
+/// aren't included in [`Flavor`].
+///
+/// Custom specs are used with [`SerializeOptions::with_flavor_spec`](crate::SerializeOptions::with_flavor_spec).
+/// Output which isn't covered by this trait, like tag names & `interpret-as` values, follows the spec's
+/// [`base`](FlavorSpec::base) flavor. [`Flavor`] itself implements this trait.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::{AttributeRewrite, ElementKind, Flavor, FlavorSpec, ProsodyRate, Support};
+///
+/// #[derive(Debug)]
+/// struct Acme;
+///
+/// impl FlavorSpec for Acme {
+/// 	fn base(&self) -> Flavor {
+/// 		Flavor::Generic
+/// 	}
+///
+/// 	fn supports(&self, kind: ElementKind) -> Support {
+/// 		match kind {
+/// 			ElementKind::Audio => Support::None,
+/// 			kind => self.base().supports(kind)
+/// 		}
+/// 	}
+///
+/// 	fn namespaces(&self) -> Vec<(&str, &str)> {
+/// 		vec![("acme", "https://acme.example/ssml")]
+/// 	}
+///
+/// 	fn rewrite_attribute(&self, element: &str, name: &str, _value: &str) -> AttributeRewrite {
+/// 		match (element, name) {
+/// 			("prosody", "rate") => AttributeRewrite::Rename("acme:speed".into()),
+/// 			_ => AttributeRewrite::Keep
+/// 		}
+/// 	}
+/// }
+///
+/// let options = ssml::SerializeOptions::default().with_flavor_spec(Acme);
+/// let doc = ssml::speak(None, [ssml::prosody(ProsodyRate::Fast, ["Hello!"])]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&options)?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:acme="https://acme.example/ssml"><prosody acme:speed="fast">Hello!</prosody></speak>"#
+/// );
+///
+/// let doc = ssml::speak(None, [ssml::audio("https://example.com/chime.mp3")]);
+/// assert!(matches!(doc.serialize_to_string(&options), Err(ssml::Error::UnsupportedElement("audio", _))));
+/// # Ok(())
+/// # }
+/// ```
+pub trait FlavorSpec: Debug + Send + Sync {
+	/// The built-in flavor this spec builds upon.
+	fn base(&self) -> Flavor;
+
+	/// Returns how well this flavor supports the given kind of element. Unsupported elements fail serialization when
+	/// [`perform_checks`](crate::SerializeOptions::perform_checks) is enabled.
+	fn supports(&self, kind: ElementKind) -> Support {
+		self.base().supports(kind)
+	}
+
+	/// Returns the `(prefix, uri)` pairs of the XML namespaces to declare on the root `<speak>` element. Defaults to
+	/// the namespaces of the [`base`](FlavorSpec::base) flavor.
+	fn namespaces(&self) -> Vec<(&str, &str)> {
+		self.base().builtin_namespaces()
+	}
+
+	/// Rewrites the attribute `name` of an `element` (identified by its tag name) before it's written.
+	fn rewrite_attribute(&self, element: &str, name: &str, value: &str) -> AttributeRewrite {
+		let _ = (element, name, value);
+		AttributeRewrite::Keep
+	}
+}
+
+impl FlavorSpec for Flavor {
+	fn base(&self) -> Flavor {
+		*self
+	}
+
+	fn supports(&self, kind: ElementKind) -> Support {
+		Flavor::supports(self, kind)
+	}
+}
+
+/// A set of [`ElementKind`]s supported by a speech synthesis voice, for providers where each voice advertises which
+/// SSML features it supports, like the [Spiel](https://project-spiel.org) speech API.
+///
+/// As a [`FlavorSpec`], elements outside of the set aren't supported; elements in the set are supported as well as
+/// they are by the [`base`](Capabilities::base) flavor ([`Flavor::Generic`] by default). Use it with
+/// [`SerializeOptions::with_flavor_spec`](crate::SerializeOptions::with_flavor_spec) to reject unsupported elements,
+/// with [`serialize_best_effort`](crate::diagnostics::serialize_best_effort) to strip them from the output, or with
+/// [`LintConfig::flavor_spec`](crate::validate::LintConfig::flavor_spec) &
+/// [`apply_fixes`](crate::transform::apply_fixes) to strip them from the document itself.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::{Capabilities, ElementKind, SerializeOptions, diagnostics::serialize_best_effort};
+///
+/// // the feature flags advertised by a voice, & which elements each flag enables
+/// const FEATURE_SSML_BREAK: u64 = 1 << 4;
+/// const FEATURE_SSML_EMPHASIS: u64 = 1 << 5;
+/// const FEATURE_SSML_SAY_AS: u64 = 1 << 6;
+/// const FEATURES: &[(u64, ElementKind)] = &[
+/// 	(FEATURE_SSML_BREAK, ElementKind::Break),
+/// 	(FEATURE_SSML_EMPHASIS, ElementKind::Emphasis),
+/// 	(FEATURE_SSML_SAY_AS, ElementKind::SayAs)
+/// ];
+///
+/// let voice_features = FEATURE_SSML_BREAK | FEATURE_SSML_SAY_AS;
+/// let capabilities = Capabilities::none().with(ElementKind::Paragraph).with_flags(voice_features, FEATURES);
+/// assert!(capabilities.contains(ElementKind::Break));
+/// assert!(!capabilities.contains(ElementKind::Emphasis));
+///
+/// let doc = ssml::speak(
+/// 	None,
+/// 	[
+/// 		ssml::emphasis(ssml::EmphasisLevel::Strong, ["Wait!"]).into(),
+/// 		ssml::Element::from(ssml::breaks(ssml::BreakStrength::Medium))
+/// 	]
+/// );
+/// let options = SerializeOptions::default().with_flavor_spec(capabilities);
+/// assert!(matches!(doc.serialize_to_string(&options), Err(ssml::Error::UnsupportedElement("emphasis", _))));
+///
+/// let (ssml, diagnostics) = serialize_best_effort(&doc, &options)?;
+/// assert_eq!(
+/// 	ssml,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Wait!<break strength="medium"/></speak>"#
+/// );
+/// assert_eq!(diagnostics.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+	base: Flavor,
+	kinds: u64
+}
+
+// each kind of element is stored as a bit of `Capabilities::kinds`
+const _: () = assert!(ElementKind::ALL.len() <= u64::BITS as usize);
+
+impl Capabilities {
+	/// Creates a set which doesn't support any elements.
+	pub const fn none() -> Self {
+		Capabilities { base: Flavor::Generic, kinds: 0 }
+	}
+
+	/// Creates a set which supports all elements.
+	pub const fn all() -> Self {
+		Capabilities {
+			base: Flavor::Generic,
+			kinds: u64::MAX
+		}
+	}
+
+	const fn bit(kind: ElementKind) -> u64 {
+		1 << kind as u32
+	}
+
+	/// Returns whether the set contains the given kind of element.
+	pub const fn contains(&self, kind: ElementKind) -> bool {
+		self.kinds & Self::bit(kind) != 0
+	}
+
+	/// Adds a kind of element to the set.
+	pub const fn with(mut self, kind: ElementKind) -> Self {
+		self.kinds |= Self::bit(kind);
+		self
+	}
+
+	/// Removes a kind of element from the set.
+	pub const fn without(mut self, kind: ElementKind) -> Self {
+		self.kinds &= !Self::bit(kind);
+		self
+	}
+
+	/// Adds a kind of element to the set.
+	pub fn insert(&mut self, kind: ElementKind) {
+		self.kinds |= Self::bit(kind);
+	}
+
+	/// Removes a kind of element from the set.
+	pub fn remove(&mut self, kind: ElementKind) {
+		self.kinds &= !Self::bit(kind);
+	}
+
+	/// Adds the kinds of elements enabled by a provider's feature `flags` to the set. `mapping` pairs each flag with
+	/// the kind of element it enables; a kind is added if all bits of its flag are set in `flags`.
+	pub fn with_flags(mut self, flags: u64, mapping: &[(u64, ElementKind)]) -> Self {
+		for &(flag, kind) in mapping {
+			if flags & flag == flag {
+				self.insert(kind);
+			}
+		}
+		self
+	}
+
+	/// The flavor which elements in the set are written in & checked against.
+	pub const fn base(&self) -> Flavor {
+		self.base
+	}
+
+	/// Sets the flavor which elements in the set are written in & checked against.
+	pub const fn with_base(mut self, base: Flavor) -> Self {
+		self.base = base;
+		self
+	}
+
+	/// Returns an iterator over the kinds of elements in the set.
+	pub fn iter(&self) -> impl Iterator<Item = ElementKind> + '_ {
+		ElementKind::ALL.iter().copied().filter(|kind| self.contains(*kind))
+	}
+}
+
+impl Default for Capabilities {
+	fn default() -> Self {
+		Capabilities::all()
+	}
+}
+
+impl FromIterator<ElementKind> for Capabilities {
+	fn from_iter<T: IntoIterator<Item = ElementKind>>(iter: T) -> Self {
+		let mut capabilities = Capabilities::none();
+		iter.into_iter().for_each(|kind| capabilities.insert(kind));
+		capabilities
+	}
+}
+
+impl FlavorSpec for Capabilities {
+	fn base(&self) -> Flavor {
+		self.base
+	}
+
+	fn supports(&self, kind: ElementKind) -> Support {
+		if self.contains(kind) { self.base.supports(kind) } else { Support::None }
+	}
+}
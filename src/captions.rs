@@ -0,0 +1,389 @@
+//! Generate SRT & WebVTT captions for SSML documents.
+//!
+//! Captions are split into sentence-aligned cues, with timing estimated by a [`DurationModel`]. Estimated timings are
+//! only approximate; for accurate captions, use [`Captions::with_marks`] to insert a [`Mark`] at the start of each cue,
+//! then [`Captions::refine`] the cues using the timepoints (bookmark events) returned by your speech synthesis
+//! provider.
+//!
+//! ```
+//! use ssml::{captions::Captions, duration::DurationModel};
+//!
+//! let doc = ssml::speak(None, ["Hello there. How are you?"]);
+//! let captions = Captions::estimate(&doc, &DurationModel::default());
+//! assert_eq!(
+//! 	captions.to_srt(),
+//! 	"1\n00:00:00,000 --> 00:00:01,100\nHello there.\n\n2\n00:00:01,100 --> 00:00:02,600\nHow are you?\n"
+//! );
+//! ```
+
+use alloc::{
+	borrow::Cow,
+	format,
+	string::{String, ToString},
+	vec::Vec
+};
+use core::{fmt::Write, mem};
+
+use crate::{
+	Element, Mark, Speak, Text, TimeDesignation,
+	duration::{DurationModel, EstimateSink, Estimator},
+	ids::IdGenerator,
+	visit::Visit
+};
+
+/// A single caption cue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+	/// The identifier of this cue. For cues created by [`Captions::with_marks`], this is the name of the [`Mark`]
+	/// preceding the cue.
+	pub id: String,
+	pub start: TimeDesignation,
+	pub end: TimeDesignation,
+	pub text: String
+}
+
+/// Configuration for [`Captions::with_marks`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CaptionOptions {
+	/// The prefix used for the names of inserted marks, followed by an ID generated by
+	/// [`ids`](CaptionOptions::ids). Defaults to `caption-`.
+	pub mark_prefix: Cow<'static, str>,
+	/// Generates the ID following the prefix of each mark's name. Defaults to [`IdGenerator::sequential`], which uses
+	/// the index of the cue.
+	pub ids: IdGenerator
+}
+
+impl Default for CaptionOptions {
+	fn default() -> Self {
+		CaptionOptions {
+			mark_prefix: Cow::Borrowed("caption-"),
+			ids: IdGenerator::sequential()
+		}
+	}
+}
+
+impl CaptionOptions {
+	pub fn with_mark_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+		self.mark_prefix = prefix.into();
+		self
+	}
+
+	pub fn with_ids(mut self, ids: IdGenerator) -> Self {
+		self.ids = ids;
+		self
+	}
+}
+
+/// A list of caption [`Cue`]s.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Captions {
+	cues: Vec<Cue>
+}
+
+impl Captions {
+	/// Generates sentence-aligned captions for a document, with timing estimated by the given [`DurationModel`].
+	pub fn estimate(doc: &Speak, model: &DurationModel) -> Captions {
+		let mut estimator = Estimator::new(model, CueBuilder::new(model, None));
+		estimator.visit_speak(doc);
+		estimator.sink.finish()
+	}
+
+	/// Inserts a [`Mark`] at the start of each sentence in the document, and generates captions with one cue per mark.
+	///
+	/// The returned cues are initially estimated; once the document has been synthesized, use [`Captions::refine`]
+	/// with the timepoints reported for each mark to get accurate timings.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// use ssml::{
+	/// 	TimeDesignation,
+	/// 	captions::{CaptionOptions, Captions},
+	/// 	duration::DurationModel
+	/// };
+	///
+	/// # fn main() -> ssml::Result<()> {
+	/// let mut doc = ssml::speak(None, ["Hello there. How are you?"]);
+	/// let mut captions = Captions::with_marks(&mut doc, &DurationModel::default(), &CaptionOptions::default());
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><mark name="caption-0"/>Hello there. <mark name="caption-1"/>How are you?</speak>"#
+	/// );
+	///
+	/// // ...synthesize the document, receiving timepoints for each mark...
+	/// captions.refine([("caption-0", TimeDesignation::from_millis(50.)), ("caption-1", TimeDesignation::from_millis(1400.))]);
+	/// assert_eq!(captions.cues()[1].start, TimeDesignation::from_millis(1400.));
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_marks(doc: &mut Speak, model: &DurationModel, options: &CaptionOptions) -> Captions {
+		let mut inserter = MarkInserter {
+			prefix: &options.mark_prefix,
+			ids: options.ids.clone(),
+			at_sentence_start: true
+		};
+		inserter.process(doc.children_mut());
+
+		let mut estimator = Estimator::new(model, CueBuilder::new(model, Some(&options.mark_prefix)));
+		estimator.visit_speak(doc);
+		estimator.sink.finish()
+	}
+
+	/// Returns the cues of these captions in order.
+	pub fn cues(&self) -> &[Cue] {
+		&self.cues
+	}
+
+	/// Returns a mutable reference to the cues of these captions.
+	pub fn cues_mut(&mut self) -> &mut Vec<Cue> {
+		&mut self.cues
+	}
+
+	/// Refines cue timings using timepoints returned by a speech synthesis provider, given as pairs of cue IDs (mark
+	/// names) and their offset in the synthesized audio.
+	///
+	/// Refined cues keep their estimated length, but are shortened where necessary so they don't overlap the next cue.
+	pub fn refine<'a>(&mut self, timepoints: impl IntoIterator<Item = (&'a str, TimeDesignation)>) {
+		for (id, at) in timepoints {
+			if let Some(cue) = self.cues.iter_mut().find(|c| c.id == id) {
+				let length = cue.end.to_millis() - cue.start.to_millis();
+				cue.end = TimeDesignation::from_millis(at.to_millis() + length);
+				cue.start = at;
+			}
+		}
+		for i in 1..self.cues.len() {
+			let next_start = self.cues[i].start.to_millis();
+			let cue = &mut self.cues[i - 1];
+			if cue.end.to_millis() > next_start {
+				cue.end = TimeDesignation::from_millis(next_start.max(cue.start.to_millis()));
+			}
+		}
+	}
+
+	/// Formats these captions as a SubRip (`.srt`) subtitle file.
+	pub fn to_srt(&self) -> String {
+		let mut out = String::new();
+		for (i, cue) in self.cues.iter().enumerate() {
+			if i != 0 {
+				out.push('\n');
+			}
+			let _ = writeln!(out, "{}", i + 1);
+			write_timestamp(&mut out, &cue.start, ',');
+			out.push_str(" --> ");
+			write_timestamp(&mut out, &cue.end, ',');
+			out.push('\n');
+			out.push_str(&cue.text);
+			out.push('\n');
+		}
+		out
+	}
+
+	/// Formats these captions as a WebVTT (`.vtt`) file.
+	///
+	/// ```
+	/// use ssml::{captions::Captions, duration::DurationModel};
+	///
+	/// let doc = ssml::speak(None, ["Hello there."]);
+	/// let captions = Captions::estimate(&doc, &DurationModel::default());
+	/// assert_eq!(captions.to_webvtt(), "WEBVTT\n\n1\n00:00:00.000 --> 00:00:01.100\nHello there.\n");
+	/// ```
+	pub fn to_webvtt(&self) -> String {
+		let mut out = String::from("WEBVTT\n");
+		for cue in &self.cues {
+			out.push('\n');
+			out.push_str(&cue.id);
+			out.push('\n');
+			write_timestamp(&mut out, &cue.start, '.');
+			out.push_str(" --> ");
+			write_timestamp(&mut out, &cue.end, '.');
+			out.push('\n');
+			// a blank line would terminate the cue early
+			for line in cue.text.lines().filter(|l| !l.trim().is_empty()) {
+				out.push_str(line);
+				out.push('\n');
+			}
+		}
+		out
+	}
+}
+
+fn write_timestamp(out: &mut String, time: &TimeDesignation, separator: char) {
+	let millis = time.to_millis().max(0.) as u64;
+	let _ = write!(out, "{:02}:{:02}:{:02}{separator}{:03}", millis / 3_600_000, (millis / 60_000) % 60, (millis / 1000) % 60, millis % 1000);
+}
+
+fn ends_sentence(text: &str) -> bool {
+	text.trim_end().ends_with(['.', '!', '?'])
+}
+
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+	text.split_whitespace()
+		.map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+struct CueBuilder<'p> {
+	model: &'p DurationModel,
+	/// When `Some`, cues are delimited by marks with this prefix instead of by sentences.
+	mark_prefix: Option<&'p str>,
+	cues: Vec<Cue>,
+	current: Option<Cue>
+}
+
+impl<'p> CueBuilder<'p> {
+	fn new(model: &'p DurationModel, mark_prefix: Option<&'p str>) -> Self {
+		CueBuilder {
+			model,
+			mark_prefix,
+			cues: Vec::new(),
+			current: None
+		}
+	}
+
+	fn close(&mut self) {
+		if let Some(cue) = self.current.take() {
+			if !cue.text.is_empty() {
+				self.cues.push(cue);
+			}
+		}
+	}
+
+	fn push_word(&mut self, word: &str, start: f32, end: f32) {
+		let index = self.cues.len();
+		let cue = self.current.get_or_insert_with(|| Cue {
+			id: (index + 1).to_string(),
+			start: TimeDesignation::from_millis(start),
+			end: TimeDesignation::from_millis(start),
+			text: String::new()
+		});
+		if !cue.text.is_empty() {
+			cue.text.push(' ');
+		}
+		cue.text.push_str(word);
+		cue.end = TimeDesignation::from_millis(end);
+
+		if self.mark_prefix.is_none() && ends_sentence(word) {
+			self.close();
+		}
+	}
+
+	fn finish(mut self) -> Captions {
+		self.close();
+		Captions { cues: self.cues }
+	}
+}
+
+impl EstimateSink for CueBuilder<'_> {
+	fn text(&mut self, text: &str, start: f32, end: f32) {
+		// distribute the run's duration over its words according to the model, so sentence pauses are accounted for
+		let natural = self.model.text_millis(text);
+		let scale = if natural > 0. { (end - start) / natural } else { 0. };
+		let mut word_start = start;
+		for (_, word) in words(text) {
+			let word_end = word_start + self.model.text_millis(word) * scale;
+			self.push_word(word, word_start, word_end);
+			word_start = word_end;
+		}
+	}
+
+	fn audio(&mut self, desc: &str, start: f32, end: f32) {
+		self.push_word(&format!("[{}]", desc.trim()), start, end);
+	}
+
+	fn mark(&mut self, name: &str, at: f32) {
+		let Some(prefix) = self.mark_prefix else {
+			return;
+		};
+		if name.starts_with(prefix) {
+			self.close();
+			self.current = Some(Cue {
+				id: name.to_string(),
+				start: TimeDesignation::from_millis(at),
+				end: TimeDesignation::from_millis(at),
+				text: String::new()
+			});
+		}
+	}
+}
+
+struct MarkInserter<'o> {
+	prefix: &'o str,
+	ids: IdGenerator,
+	at_sentence_start: bool
+}
+
+impl MarkInserter<'_> {
+	fn next_mark<'s>(&mut self) -> Element<'s> {
+		let mark = Mark::new(format!("{}{}", self.prefix, self.ids.next_id()));
+		self.at_sentence_start = false;
+		mark.into()
+	}
+
+	fn process<'s>(&mut self, children: &mut Vec<Element<'s>>) {
+		for mut el in mem::take(children) {
+			match &mut el {
+				Element::Text(text) => {
+					self.split_text(text, children);
+					continue;
+				}
+				Element::SayAs(say_as) => {
+					if self.at_sentence_start {
+						children.push(self.next_mark());
+					}
+					self.at_sentence_start = ends_sentence(&say_as.text());
+				}
+				Element::Phoneme(phoneme) => {
+					if self.at_sentence_start {
+						children.push(self.next_mark());
+					}
+					self.at_sentence_start = ends_sentence(phoneme.text());
+				}
+				Element::Sub(sub) => {
+					if self.at_sentence_start {
+						children.push(self.next_mark());
+					}
+					self.at_sentence_start = ends_sentence(sub.text());
+				}
+				Element::Paragraph(_) | Element::Sentence(_) => {
+					// sentences are delimited by the element itself, even without punctuation
+					self.at_sentence_start = true;
+					if let Some(el_children) = el.children_mut() {
+						self.process(el_children);
+					}
+					self.at_sentence_start = true;
+				}
+				Element::Audio(audio) => {
+					if audio.desc().is_some() {
+						if self.at_sentence_start {
+							children.push(self.next_mark());
+						}
+						self.at_sentence_start = true;
+					}
+				}
+				el => {
+					if let Some(el_children) = el.children_mut() {
+						self.process(el_children);
+					}
+				}
+			}
+			children.push(el);
+		}
+	}
+
+	fn split_text<'s>(&mut self, text: &Text<'s>, out: &mut Vec<Element<'s>>) {
+		let text = text.as_str();
+		let mut segment_start = 0;
+		for (pos, word) in words(text) {
+			if self.at_sentence_start {
+				if !text[segment_start..pos].trim().is_empty() {
+					out.push(Text::from(text[segment_start..pos].to_string()).into());
+					segment_start = pos;
+				}
+				out.push(self.next_mark());
+			}
+			self.at_sentence_start = ends_sentence(word);
+		}
+		if segment_start < text.len() {
+			out.push(Text::from(text[segment_start..].to_string()).into());
+		}
+	}
+}
@@ -0,0 +1,121 @@
+use alloc::{
+	borrow::Cow,
+	string::{String, ToString}
+};
+use core::fmt::Write;
+
+use crate::{Serialize, SerializeOptions, XmlWriter, util};
+
+/// [`Desc`] is a textual description of the content of an [`Audio`](crate::Audio) element, e.g. for screen readers or
+/// captions. Descriptions are never spoken.
+///
+/// An audio element may carry several descriptions in different languages; see
+/// [`Audio::with_desc`](crate::Audio::with_desc).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Desc<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	text: Cow<'s, str>,
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option"))]
+	lang: Option<Cow<'s, str>>
+}
+
+impl<'s> Desc<'s> {
+	/// Creates a new description.
+	pub fn new(text: impl Into<Cow<'s, str>>) -> Self {
+		Desc { text: text.into(), lang: None }
+	}
+
+	pub fn text(&self) -> &str {
+		&self.text
+	}
+
+	pub fn set_text(&mut self, text: impl Into<Cow<'s, str>>) {
+		self.text = text.into();
+	}
+
+	/// Sets the language of this description, e.g. `de-DE`, written as its `xml:lang` attribute.
+	pub fn with_lang(mut self, lang: impl Into<Cow<'s, str>>) -> Self {
+		self.lang = Some(lang.into());
+		self
+	}
+
+	pub fn lang(&self) -> Option<&str> {
+		self.lang.as_deref()
+	}
+
+	pub fn set_lang(&mut self, lang: impl Into<Cow<'s, str>>) {
+		self.lang = Some(lang.into());
+	}
+
+	pub fn take_lang(&mut self) -> Option<Cow<'s, str>> {
+		self.lang.take()
+	}
+
+	pub fn to_owned(&self) -> Desc<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Desc<'static> {
+		Desc {
+			text: match self.text {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			},
+			lang: match self.lang {
+				Some(Cow::Borrowed(b)) => Some(Cow::Owned(b.to_string())),
+				Some(Cow::Owned(b)) => Some(Cow::Owned(b)),
+				None => None
+			}
+		}
+	}
+}
+
+impl<'s> From<&'s str> for Desc<'s> {
+	fn from(value: &'s str) -> Self {
+		Desc::new(value)
+	}
+}
+
+impl From<String> for Desc<'_> {
+	fn from(value: String) -> Self {
+		Desc::new(value)
+	}
+}
+
+impl<'s> From<Cow<'s, str>> for Desc<'s> {
+	fn from(value: Cow<'s, str>) -> Self {
+		Desc::new(value)
+	}
+}
+
+impl<'s> Serialize for Desc<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.without_plain_text(|writer| {
+			writer.element("desc", |writer| {
+				writer.attr_opt("xml:lang", self.lang.as_deref())?;
+				util::write_text(writer, &self.text, options)
+			})
+		})
+	}
+}
+
+/// Creates a new [`Desc`] description for an [`Audio`](crate::Audio) element.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let audio = ssml::audio("applause.ogg")
+/// 	.with_desc(ssml::desc("applause").with_lang("en-US"))
+/// 	.with_desc(ssml::desc("Applaus").with_lang("de-DE"));
+///
+/// assert_eq!(
+/// 	audio.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<audio src="applause.ogg"><desc xml:lang="en-US">applause</desc><desc xml:lang="de-DE">Applaus</desc></audio>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn desc<'s>(text: impl Into<Cow<'s, str>>) -> Desc<'s> {
+	Desc::new(text)
+}
@@ -0,0 +1,114 @@
+//! Best-effort serialization which recovers from errors instead of aborting.
+//!
+//! For user-generated documents, partial output is often preferable to none. [`serialize_best_effort`] skips (or
+//! downgrades) elements which would fail to serialize, returning the output alongside a [`Diagnostic`] for each
+//! element it had to alter.
+//!
+//! Alternatively, [`serialize_with_warnings`] writes offending elements unchanged, reporting each as a warning, for
+//! when a provider is known to tolerate them.
+//!
+//! ```
+//! # use ssml::Serialize;
+//! use ssml::{CustomElement, UnknownElementPolicy, diagnostics::serialize_best_effort};
+//!
+//! # fn main() -> ssml::Result<()> {
+//! let doc = ssml::speak(None, [CustomElement::new("amazon:breath").into(), ssml::Element::from("Hello!")]);
+//! let options = ssml::SerializeOptions::default().unknown_elements(UnknownElementPolicy::Deny);
+//! assert!(doc.serialize_to_string(&options).is_err());
+//!
+//! let (ssml, diagnostics) = serialize_best_effort(&doc, &options)?;
+//! assert_eq!(ssml, r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Hello!</speak>"#);
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].path, [0]);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display};
+
+use crate::{Error, Serialize, SerializeOptions, Speak, XmlWriter};
+
+/// How an offending element was handled during best-effort serialization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Recovery {
+	/// The element & its children were omitted from the output.
+	Skipped,
+	/// The element itself was omitted, but its children were still serialized.
+	Unwrapped,
+	/// The element was written unchanged; see [`serialize_with_warnings`].
+	Emitted
+}
+
+/// An error which was recovered from during best-effort serialization.
+#[derive(Debug)]
+pub struct Diagnostic {
+	/// The path of the offending element; see [`Speak::element_at`].
+	pub path: Vec<usize>,
+	/// The error which would have been returned by regular serialization.
+	pub error: Error,
+	/// How the offending element was handled.
+	pub recovery: Recovery
+}
+
+impl Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let recovery = match self.recovery {
+			Recovery::Skipped => "skipped",
+			Recovery::Unwrapped => "unwrapped",
+			Recovery::Emitted => "emitted anyway"
+		};
+		write!(f, "{} (element at {:?} was {recovery})", self.error, self.path)
+	}
+}
+
+/// Serializes a document, skipping or downgrading elements which fail to serialize instead of returning an error.
+///
+/// Errors which can't be attributed to a single element (e.g. errors from the underlying writer) are still returned.
+pub fn serialize_best_effort(doc: &Speak, options: &SerializeOptions) -> crate::Result<(String, Vec<Diagnostic>)> {
+	let mut out = String::new();
+	let mut writer = XmlWriter::for_options(&mut out, options);
+	writer.diagnostics = Some(Vec::new());
+	doc.serialize_xml(&mut writer, options)?;
+
+	let diagnostics = writer.diagnostics.take().unwrap_or_default();
+	Ok((out, diagnostics))
+}
+
+/// Serializes a document, writing elements which would fail to serialize unchanged instead of returning an error, and
+/// returning a [`Diagnostic`] (with [`Recovery::Emitted`]) for each of them.
+///
+/// This is useful to log incompatibilities without failing production synthesis. Errors which can't be attributed to a
+/// single element are still returned.
+///
+/// ```
+/// # use ssml::Serialize;
+/// use ssml::diagnostics::{Recovery, serialize_with_warnings};
+///
+/// # fn main() -> ssml::Result<()> {
+/// let doc = ssml::speak(None, [ssml::mstts::express(ssml::mstts::express::Cheerful, ["Hello!"])]);
+/// let options = ssml::SerializeOptions::default().flavor(ssml::Flavor::AmazonPolly);
+/// assert!(doc.serialize_to_string(&options).is_err());
+///
+/// let (ssml, warnings) = serialize_with_warnings(&doc, &options)?;
+/// assert_eq!(ssml, r#"<speak><mstts:express-as style="cheerful" styledegree="1">Hello!</mstts:express-as></speak>"#);
+/// assert_eq!(warnings.len(), 1);
+/// assert_eq!(warnings[0].recovery, Recovery::Emitted);
+/// assert_eq!(
+/// 	warnings[0].to_string(),
+/// 	"`mstts:express-as` is not supported by AmazonPolly (element at [0] was emitted anyway)"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn serialize_with_warnings(doc: &Speak, options: &SerializeOptions) -> crate::Result<(String, Vec<Diagnostic>)> {
+	let mut out = String::new();
+	let mut writer = XmlWriter::for_options(&mut out, options);
+	writer.diagnostics = Some(Vec::new());
+	writer.emit_anyway = true;
+	doc.serialize_xml(&mut writer, options)?;
+
+	let diagnostics = writer.diagnostics.take().unwrap_or_default();
+	Ok((out, diagnostics))
+}
@@ -0,0 +1,288 @@
+//! Estimate how long a document will take to speak.
+//!
+//! Speech synthesis services don't report the duration of synthesized audio until it has been synthesized. The
+//! [`DurationModel`] provides a rough, configurable estimate based on word counts, breaks and prosody, which is useful
+//! for planning (e.g. caption timing) before any audio exists.
+//!
+//! ```
+//! use ssml::duration::DurationModel;
+//!
+//! let doc = ssml::speak(None, ["Hello, world!"]) + ssml::breaks("1s");
+//! let duration = DurationModel::default().estimate(&doc);
+//! assert_eq!(duration.to_millis(), 800. + 300. + 1000.);
+//! ```
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+	Audio, AudioRepeat, Break, BreakStrength, Element, Mark, Phoneme, Prosody, ProsodyRate, SayAs, Speak, SpeechFormat, Sub, Text, TimeDesignation, Variant,
+	VariantSelection,
+	visit::{self, Visit}
+};
+
+/// A simple model used to estimate the spoken duration of SSML elements.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DurationModel {
+	/// The average speaking rate at normal speed, in words per minute. Defaults to `150`.
+	pub words_per_minute: f32,
+	/// The time taken to speak a single character of spelled-out text (e.g. [`SpeechFormat::SpellOut`]), in
+	/// milliseconds. Defaults to `300`.
+	pub spelled_character_millis: f32,
+	/// The additional pause after sentence-ending punctuation, in milliseconds. Defaults to `300`.
+	pub sentence_pause_millis: f32,
+	/// The duration of [`Break`]s specified by strength, in milliseconds, indexed from [`BreakStrength::None`] to
+	/// [`BreakStrength::ExtraStrong`].
+	pub break_strength_millis: [f32; 6],
+	/// The assumed duration of [`Audio`] elements whose duration can't be determined from their attributes, in
+	/// milliseconds. Defaults to `0`.
+	pub audio_millis: f32
+}
+
+impl Default for DurationModel {
+	fn default() -> Self {
+		DurationModel {
+			words_per_minute: 150.,
+			spelled_character_millis: 300.,
+			sentence_pause_millis: 300.,
+			break_strength_millis: [0., 100., 250., 500., 750., 1000.],
+			audio_millis: 0.
+		}
+	}
+}
+
+impl DurationModel {
+	pub fn with_words_per_minute(mut self, wpm: f32) -> Self {
+		self.words_per_minute = wpm;
+		self
+	}
+
+	pub fn with_spelled_character_millis(mut self, millis: f32) -> Self {
+		self.spelled_character_millis = millis;
+		self
+	}
+
+	pub fn with_sentence_pause_millis(mut self, millis: f32) -> Self {
+		self.sentence_pause_millis = millis;
+		self
+	}
+
+	pub fn with_audio_millis(mut self, millis: f32) -> Self {
+		self.audio_millis = millis;
+		self
+	}
+
+	/// Estimates the spoken duration of an entire document.
+	pub fn estimate(&self, doc: &Speak) -> TimeDesignation {
+		let mut estimator = Estimator::new(self, ());
+		estimator.visit_speak(doc);
+		TimeDesignation::from_millis(estimator.elapsed)
+	}
+
+	/// Estimates the spoken duration of a single element.
+	pub fn estimate_element(&self, element: &Element) -> TimeDesignation {
+		let mut estimator = Estimator::new(self, ());
+		estimator.visit_element(element);
+		TimeDesignation::from_millis(estimator.elapsed)
+	}
+
+	/// Estimates the time taken to speak a run of text at normal speed, in milliseconds.
+	pub fn text_millis(&self, text: &str) -> f32 {
+		let mut millis = 0.;
+		for word in text.split_whitespace() {
+			millis += 60_000. / self.words_per_minute;
+			if word.ends_with(['.', '!', '?']) {
+				millis += self.sentence_pause_millis;
+			}
+		}
+		millis
+	}
+
+	/// Estimates the duration of a [`Break`], in milliseconds.
+	pub fn break_millis(&self, el: &Break) -> f32 {
+		// an explicit time takes precedence over the strength
+		if let Some(time) = el.time() {
+			return time.to_millis();
+		}
+		self.break_strength_millis[match el.strength().unwrap_or_default() {
+			BreakStrength::None => 0,
+			BreakStrength::ExtraWeak => 1,
+			BreakStrength::Weak => 2,
+			BreakStrength::Medium => 3,
+			BreakStrength::Strong => 4,
+			BreakStrength::ExtraStrong => 5
+		}]
+	}
+
+	/// Estimates the duration of an [`Audio`] element from its clip, repeat & speed attributes, in milliseconds.
+	pub fn audio_millis(&self, el: &Audio) -> f32 {
+		let clip = match (el.clip_begin(), el.clip_end()) {
+			(Some(begin), Some(end)) => Some((end.to_millis() - begin.to_millis()).max(0.)),
+			(None, Some(end)) => Some(end.to_millis()),
+			_ => None
+		};
+		let millis = match el.repeat() {
+			Some(AudioRepeat::Duration(duration)) => return duration.to_millis(),
+			Some(AudioRepeat::Times(times)) => clip.unwrap_or(self.audio_millis) * times.max(0.),
+			None => clip.unwrap_or(self.audio_millis)
+		};
+		match el.speed() {
+			Some(speed) if speed > 0. => millis / speed,
+			_ => millis
+		}
+	}
+
+	fn say_as_millis(&self, el: &SayAs) -> f32 {
+		match el.format() {
+			SpeechFormat::SpellOut | SpeechFormat::Digits | SpeechFormat::Telephone => {
+				el.text().chars().filter(|c| c.is_alphanumeric()).count() as f32 * self.spelled_character_millis
+			}
+			_ => self.text_millis(&el.text())
+		}
+	}
+}
+
+/// Returns the speed multiplier of a [`ProsodyRate`], where `1.0` is normal speed.
+pub(crate) fn rate_multiplier(rate: &ProsodyRate) -> f32 {
+	match rate {
+		ProsodyRate::Slower => 0.5,
+		ProsodyRate::Slow => 0.64,
+		ProsodyRate::Medium | ProsodyRate::Default => 1.,
+		ProsodyRate::Fast => 1.55,
+		ProsodyRate::Faster => 2.,
+		ProsodyRate::Rate(rate) => rate.max(0.01)
+	}
+}
+
+/// Receives spoken content from an [`Estimator`] along with its estimated timing.
+pub(crate) trait EstimateSink {
+	/// Called for a run of spoken text, spoken from `start` to `end` (in milliseconds).
+	fn text(&mut self, _text: &str, _start: f32, _end: f32) {}
+
+	/// Called for an audio element with an accessible description.
+	fn audio(&mut self, _desc: &str, _start: f32, _end: f32) {}
+
+	/// Called for a [`Mark`] reached at `at` milliseconds.
+	fn mark(&mut self, _name: &str, _at: f32) {}
+
+	/// Called before an element is visited, with the path of indices leading to the element from the document root.
+	fn enter(&mut self, _path: &[usize], _el: &Element, _at: f32) {}
+
+	/// Called after an element (and all of its children) has been visited.
+	fn exit(&mut self, _path: &[usize], _el: &Element, _at: f32) {}
+}
+
+impl EstimateSink for () {}
+
+/// A [`Visit`]or which walks through a document, tracking the estimated elapsed time.
+pub(crate) struct Estimator<'m, S> {
+	model: &'m DurationModel,
+	rate: f32,
+	pub(crate) elapsed: f32,
+	pub(crate) sink: S,
+	path: Vec<usize>,
+	/// The index of the next child for each level of `path`.
+	next_index: Vec<usize>
+}
+
+impl<'m, S: EstimateSink> Estimator<'m, S> {
+	pub(crate) fn new(model: &'m DurationModel, sink: S) -> Self {
+		Estimator {
+			model,
+			rate: 1.,
+			elapsed: 0.,
+			sink,
+			path: Vec::new(),
+			next_index: vec![0]
+		}
+	}
+
+	fn advance(&mut self, millis: f32) -> (f32, f32) {
+		let start = self.elapsed;
+		self.elapsed += millis / self.rate;
+		(start, self.elapsed)
+	}
+}
+
+impl<'s, S: EstimateSink> Visit<'s> for Estimator<'_, S> {
+	fn visit_element(&mut self, node: &'s Element) {
+		let index = self.next_index.last_mut().map_or(0, |i| {
+			*i += 1;
+			*i - 1
+		});
+		self.path.push(index);
+		self.next_index.push(0);
+		self.sink.enter(&self.path, node, self.elapsed);
+		// disabled groups aren't serialized, so they take no time
+		if !matches!(node, Element::Group(group) if !group.is_enabled()) {
+			visit::visit_element(self, node);
+		}
+		self.sink.exit(&self.path, node, self.elapsed);
+		self.next_index.pop();
+		self.path.pop();
+	}
+
+	fn visit_variant(&mut self, node: &'s Variant) {
+		// only the alternative serialized by default is spoken
+		if let Some(i) = node.select(&VariantSelection::First) {
+			if let Some(next) = self.next_index.last_mut() {
+				*next = i;
+			}
+			self.visit_element(&node.children()[i]);
+		}
+	}
+
+	fn visit_text(&mut self, node: &'s Text) {
+		let (start, end) = self.advance(self.model.text_millis(node));
+		self.sink.text(node, start, end);
+	}
+
+	fn visit_say_as(&mut self, node: &'s SayAs) {
+		let (start, end) = self.advance(self.model.say_as_millis(node));
+		self.sink.text(&node.text(), start, end);
+	}
+
+	fn visit_phoneme(&mut self, node: &'s Phoneme) {
+		let (start, end) = self.advance(self.model.text_millis(node.text()));
+		self.sink.text(node.text(), start, end);
+	}
+
+	fn visit_sub(&mut self, node: &'s Sub) {
+		// the alias is what's actually spoken, but the written text is what's displayed
+		let (start, end) = self.advance(self.model.text_millis(node.alias()));
+		self.sink.text(node.text(), start, end);
+	}
+
+	fn visit_break(&mut self, node: &'s Break) {
+		// break durations are absolute and aren't affected by the speaking rate
+		self.elapsed += self.model.break_millis(node);
+	}
+
+	fn visit_mark(&mut self, node: &'s Mark) {
+		self.sink.mark(node.name(), self.elapsed);
+	}
+
+	fn visit_audio(&mut self, node: &'s Audio) {
+		let start = self.elapsed;
+		self.elapsed += self.model.audio_millis(node);
+		if let Some(desc) = node.desc() {
+			self.sink.audio(desc, start, self.elapsed);
+		}
+	}
+
+	fn visit_prosody(&mut self, node: &'s Prosody) {
+		let outer_rate = self.rate;
+		self.rate *= node.control().rate.as_ref().map(rate_multiplier).unwrap_or(1.);
+		if let Some(duration) = &node.control().duration {
+			// `duration` takes precedence over `rate`; scale the rate so that the children take exactly `duration`.
+			let mut natural = Estimator::new(self.model, ());
+			natural.rate = self.rate;
+			visit::visit_prosody(&mut natural, node);
+			if duration.to_millis() > 0. && natural.elapsed > 0. {
+				self.rate *= natural.elapsed / duration.to_millis();
+			}
+		}
+		visit::visit_prosody(self, node);
+		self.rate = outer_rate;
+	}
+}
@@ -1,7 +1,10 @@
 use alloc::{borrow::Cow, string::ToString, vec::Vec};
 use core::fmt::{Debug, Write};
 
-use crate::{Audio, Break, Emphasis, Lang, Mark, Meta, Prosody, SayAs, Serialize, SerializeOptions, Text, Voice, XmlWriter, group::Group, util};
+use crate::{
+	Audio, Break, Emphasis, Lang, Lexicon, Mark, Meta, Paragraph, Phoneme, Prosody, SayAs, Sentence, Serialize, SerializeOptions, Sub, Text, Variant, Voice,
+	Word, XmlWriter, annotations::Annotations, diagnostics::Recovery, group::Group, util
+};
 
 macro_rules! el {
 	(
@@ -29,6 +32,9 @@ macro_rules! el {
 
 		impl<'s> $crate::Serialize for $name<'s> {
 			fn serialize_xml<W: ::core::fmt::Write>(&self, writer: &mut $crate::XmlWriter<W>, options: &$crate::SerializeOptions) -> $crate::Result<()> {
+				if $crate::raw::write_preserved(self, $crate::raw::RawSourced::raw_annotations(self), writer, options)? {
+					return Ok(());
+				}
 				match self {
 					$($name::$variant(inner) => inner.serialize_xml(writer, options),)*
 				}
@@ -44,26 +50,49 @@ el! {
 	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[non_exhaustive]
 	pub enum Element<'s> {
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Text(Text<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Audio(Audio<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Voice(Voice<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Meta(Meta<'s>),
 		Break(Break),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Emphasis(Emphasis<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Mark(Mark<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Lexicon(Lexicon<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		SayAs(SayAs<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Phoneme(Phoneme<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Sub(Sub<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Paragraph(Paragraph<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Sentence(Sentence<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Word(Word<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Group(Group<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Lang(Lang<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Prosody(Prosody<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Variant(Variant<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		FlavorMSTTS(crate::mstts::Element<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		FlavorPolly(crate::polly::Element<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		FlavorWatson(crate::watson::Element<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
 		Custom(CustomElement<'s>)
-		// Lang(LangElement),
-		// Paragraph(ParagraphElement),
-		// Phoneme(PhonemeElement),
-		// Prosody(ProsodyElement),
-		// Sub(SubElement),
-		// Sentence(SentenceElement),
-		// Word(WordElement)
 	}
 }
 
@@ -81,8 +110,69 @@ impl<'s> Element<'s> {
 			Self::Break(el) => Element::Break(el),
 			Self::Emphasis(el) => Element::Emphasis(el.into_owned()),
 			Self::Mark(el) => Element::Mark(el.into_owned()),
+			Self::Lexicon(el) => Element::Lexicon(el.into_owned()),
 			Self::Custom(el) => Element::Custom(el.into_owned()),
-			_ => panic!()
+			Self::Variant(el) => Element::Variant(el.into_owned()),
+			Self::Phoneme(el) => Element::Phoneme(el.into_owned()),
+			Self::Sub(el) => Element::Sub(el.into_owned()),
+			Self::Paragraph(el) => Element::Paragraph(el.into_owned()),
+			Self::Sentence(el) => Element::Sentence(el.into_owned()),
+			Self::Word(el) => Element::Word(el.into_owned()),
+			Self::SayAs(el) => Element::SayAs(el.into_owned()),
+			Self::Group(el) => Element::Group(el.into_owned()),
+			Self::Lang(el) => Element::Lang(el.into_owned()),
+			Self::Prosody(el) => Element::Prosody(el.into_owned()),
+			Self::FlavorMSTTS(el) => Element::FlavorMSTTS(el.into_owned()),
+			Self::FlavorPolly(el) => Element::FlavorPolly(el.into_owned()),
+			Self::FlavorWatson(el) => Element::FlavorWatson(el.into_owned())
+		}
+	}
+
+	/// Returns a reference to the child elements of this element, if it is a container.
+	///
+	/// For [`Audio`], this returns the alternate (fallback) content.
+	pub fn children(&self) -> Option<&[Element<'s>]> {
+		match self {
+			Self::Audio(el) => Some(el.alternate()),
+			Self::Voice(el) => Some(el.children()),
+			Self::Emphasis(el) => Some(el.children()),
+			Self::SayAs(el) => Some(el.children()),
+			Self::Paragraph(el) => Some(el.children()),
+			Self::Sentence(el) => Some(el.children()),
+			Self::Word(el) => Some(el.children()),
+			Self::Group(el) => Some(el.children()),
+			Self::Variant(el) => Some(el.children()),
+			Self::Lang(el) => Some(el.children()),
+			Self::Prosody(el) => Some(el.children()),
+			Self::FlavorMSTTS(el) => el.children(),
+			Self::FlavorPolly(el) => el.children(),
+			Self::FlavorWatson(el) => el.children(),
+			Self::Custom(el) => Some(el.children()),
+			Self::Text(_) | Self::Meta(_) | Self::Break(_) | Self::Mark(_) | Self::Lexicon(_) | Self::Phoneme(_) | Self::Sub(_) => None
+		}
+	}
+
+	/// Returns a mutable reference to the child elements of this element, if it is a container.
+	///
+	/// For [`Audio`], this returns the alternate (fallback) content.
+	pub fn children_mut(&mut self) -> Option<&mut Vec<Element<'s>>> {
+		match self {
+			Self::Audio(el) => Some(el.alternate_mut()),
+			Self::Voice(el) => Some(el.children_mut()),
+			Self::Emphasis(el) => Some(el.children_mut()),
+			Self::SayAs(el) => Some(el.children_mut()),
+			Self::Paragraph(el) => Some(el.children_mut()),
+			Self::Sentence(el) => Some(el.children_mut()),
+			Self::Word(el) => Some(el.children_mut()),
+			Self::Group(el) => Some(el.children_mut()),
+			Self::Variant(el) => Some(el.children_mut()),
+			Self::Lang(el) => Some(el.children_mut()),
+			Self::Prosody(el) => Some(el.children_mut()),
+			Self::FlavorMSTTS(el) => el.children_mut(),
+			Self::FlavorPolly(el) => el.children_mut(),
+			Self::FlavorWatson(el) => el.children_mut(),
+			Self::Custom(el) => Some(el.children_mut()),
+			Self::Text(_) | Self::Meta(_) | Self::Break(_) | Self::Mark(_) | Self::Lexicon(_) | Self::Phoneme(_) | Self::Sub(_) => None
 		}
 	}
 }
@@ -106,9 +196,14 @@ impl<'s, T: Into<Element<'s>>> IntoElement<'s> for T {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CustomElement<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
 	tag: Cow<'s, str>,
+	#[cfg_attr(feature = "serde", serde(borrow, deserialize_with = "crate::serde_borrow::pairs"))]
 	attrs: Vec<(Cow<'s, str>, Cow<'s, str>)>,
-	children: Vec<Element<'s>>
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	children: Vec<Element<'s>>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> CustomElement<'s> {
@@ -116,7 +211,8 @@ impl<'s> CustomElement<'s> {
 		Self {
 			tag: tag.into(),
 			attrs: Vec::new(),
-			children: Vec::new()
+			children: Vec::new(),
+			annotations: Annotations::default()
 		}
 	}
 
@@ -135,6 +231,26 @@ impl<'s> CustomElement<'s> {
 		self
 	}
 
+	/// Returns the tag name of this element.
+	pub fn tag(&self) -> &str {
+		&self.tag
+	}
+
+	/// Returns a reference to the attributes of this element.
+	pub fn attrs(&self) -> &[(Cow<'s, str>, Cow<'s, str>)] {
+		&self.attrs
+	}
+
+	/// Returns a reference to the elements contained within this element.
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	/// Returns a mutable reference to the elements contained within this element.
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
 	pub fn to_owned(&self) -> CustomElement<'static> {
 		self.clone().into_owned()
 	}
@@ -161,13 +277,17 @@ impl<'s> CustomElement<'s> {
 					)
 				})
 				.collect(),
-			children: self.children.into_iter().map(Element::into_owned).collect()
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
 
 impl<'s> Serialize for CustomElement<'s> {
 	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		if !options.unknown_elements.permits_tag(&self.tag) && !writer.recover(crate::Error::UnknownElement(self.tag.to_string()), Recovery::Unwrapped)? {
+			return util::serialize_elements(writer, &self.children, options);
+		}
 		writer.element(&self.tag, |writer| {
 			for (name, value) in &self.attrs {
 				writer.attr(name, value.as_ref())?;
@@ -176,3 +296,67 @@ impl<'s> Serialize for CustomElement<'s> {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use alloc::{
+		string::{String, ToString},
+		vec::Vec
+	};
+
+	use super::Element;
+	use crate::{Serialize, SerializeOptions};
+
+	fn assert_static(_: &[Element<'static>]) {}
+
+	fn serialize_all(elements: &[Element], options: &SerializeOptions) -> crate::Result<Vec<String>> {
+		elements.iter().map(|el| el.serialize_to_string(options)).collect()
+	}
+
+	/// Converts elements borrowing from `source` into owned elements, checking that they outlive `source` and are
+	/// written identically.
+	fn erase_lifetimes(source: String, build: impl for<'s> FnOnce(&'s str) -> Vec<Element<'s>>) -> crate::Result<()> {
+		let options = SerializeOptions::default().flavor(crate::Flavor::MicrosoftAzureCognitiveSpeechServices);
+		let (expected, owned) = {
+			let elements = build(&source);
+			let expected = serialize_all(&elements, &options)?;
+			(expected, elements.into_iter().map(Element::into_owned).collect::<Vec<_>>())
+		};
+		drop(source);
+
+		assert_static(&owned);
+		assert_eq!(serialize_all(&owned, &options)?, expected);
+		Ok(())
+	}
+
+	#[test]
+	fn into_owned_parsed() -> crate::Result<()> {
+		erase_lifetimes(
+			concat!(
+				r#"<voice name="en-US-JennyNeural"><mstts:express-as style="cheerful">Hi,</mstts:express-as>"#,
+				r#"<lang xml:lang="fr-FR">bonjour</lang><prosody rate="fast" pitch="+2st">!</prosody>"#,
+				r#"<say-as interpret-as="characters">SSML</say-as><break time="1s"/><emphasis>really</emphasis>"#,
+				r#"<p><s>One <w role="amazon:VB">read</w></s></p><sub alias="World Wide Web">WWW</sub><mark name="m"/>"#,
+				r#"<phoneme alphabet="ipa" ph="təˈmeɪtoʊ">tomato</phoneme><audio src="a.ogg"><desc>desc</desc>fallback</audio>"#,
+				r#"<custom attr="1">custom</custom></voice>"#
+			)
+			.to_string(),
+			|source| crate::parse::parse_fragment(source).unwrap()
+		)
+	}
+
+	#[test]
+	fn into_owned_built() -> crate::Result<()> {
+		erase_lifetimes("Hello".to_string(), |source| {
+			alloc::vec![
+				crate::group([source]).into(),
+				crate::lang(source, [source]).into(),
+				crate::prosody(crate::ProsodyControl::default(), [source]).into(),
+				crate::say_as(crate::SpeechFormat::SpellOut, source).into(),
+				crate::mstts::express(crate::mstts::express::Cheerful, [source]).into(),
+				crate::Meta::new(source).into(),
+				crate::variant().into()
+			]
+		})
+	}
+}
@@ -4,7 +4,12 @@ use core::{
 	ops::{Add, AddAssign}
 };
 
-use crate::{Element, Serialize, SerializeOptions, XmlWriter, util};
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -16,18 +21,39 @@ pub enum EmphasisLevel {
 	Strong
 }
 
+impl EmphasisLevel {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			EmphasisLevel::Reduced => "reduced",
+			EmphasisLevel::None => "none",
+			EmphasisLevel::Moderate => "moderate",
+			EmphasisLevel::Strong => "strong"
+		}
+	}
+}
+
 #[derive(Clone, Default, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Emphasis<'s> {
 	level: EmphasisLevel,
-	pub(crate) children: Vec<Element<'s>>
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> Emphasis<'s> {
 	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(level: EmphasisLevel, elements: I) -> Self {
 		Self {
 			level,
-			children: elements.into_iter().map(|f| f.into()).collect()
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
 		}
 	}
 
@@ -62,7 +88,9 @@ impl<'s> Emphasis<'s> {
 	pub fn into_owned(self) -> Emphasis<'static> {
 		Emphasis {
 			level: self.level,
-			children: self.children.into_iter().map(Element::into_owned).collect()
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
@@ -70,12 +98,8 @@ impl<'s> Emphasis<'s> {
 impl<'s> Serialize for Emphasis<'s> {
 	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
 		writer.element("emphasis", |writer| {
-			writer.attr("level", match self.level {
-				EmphasisLevel::Reduced => "reduced",
-				EmphasisLevel::None => "none",
-				EmphasisLevel::Moderate => "moderate",
-				EmphasisLevel::Strong => "strong"
-			})?;
+			writer.attr("level", self.level.as_str())?;
+			attrs::write(writer, &self.extra_attrs)?;
 			util::serialize_elements(writer, &self.children, options)
 		})
 	}
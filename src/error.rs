@@ -1,9 +1,10 @@
+use alloc::string::String;
 use core::{
 	fmt::{self, Display},
 	str::Utf8Error
 };
 
-use crate::{DecibelsError, TimeDesignationError};
+use crate::{DecibelsError, Flavor, SsmlVersion, TimeDesignationError, VoiceConfigError, parse::ParseError, transform::BudgetExceeded};
 
 #[derive(Debug)]
 #[non_exhaustive]
@@ -11,8 +12,32 @@ pub enum Error {
 	FmtError(fmt::Error),
 	TimeDesignationError(TimeDesignationError),
 	DecibelsError(DecibelsError),
+	VoiceConfigError(VoiceConfigError),
 	AttributesInChildContext,
-	Utf8Error(Utf8Error)
+	Utf8Error(Utf8Error),
+	/// An element failed validation; see [`Validate`](crate::validate::Validate).
+	InvalidElement(&'static str),
+	/// An element not permitted by the configured [`UnknownElementPolicy`](crate::UnknownElementPolicy) was
+	/// encountered.
+	UnknownElement(String),
+	/// An SSML document could not be parsed; see [`parse`](crate::parse).
+	ParseError(ParseError),
+	/// A construct (element or attribute) was serialized which requires a newer version of SSML than the one targeted
+	/// by [`SerializeOptions::version`](crate::SerializeOptions::version).
+	UnsupportedVersion(&'static str, SsmlVersion),
+	/// An element was serialized which isn't supported by the targeted [`Flavor`]; see
+	/// [`SerializeOptions::perform_checks`](crate::SerializeOptions::perform_checks).
+	UnsupportedElement(&'static str, Flavor),
+	/// An element was serialized which is only partially supported by the targeted [`Flavor`]; see
+	/// [`SerializeOptions::partial_support`](crate::SerializeOptions::partial_support).
+	PartiallySupportedElement(&'static str, Flavor),
+	/// A value was written which appears to already be escaped, or whose [`EscapedDisplay`](crate::EscapedDisplay)
+	/// implementation left markup unescaped; see
+	/// [`SerializeOptions::check_escaping`](crate::SerializeOptions::check_escaping). Holds a description of the
+	/// problem & the offending value.
+	EscapingError(&'static str, String),
+	/// A transformation pass exceeded its [`PassBudget`](crate::transform::PassBudget).
+	BudgetExceeded(BudgetExceeded)
 }
 
 unsafe impl Send for Error {}
@@ -28,7 +53,8 @@ macro_rules! impl_from {
 }
 
 impl_from! {
-	FmtError => fmt::Error, Utf8Error => Utf8Error, TimeDesignationError => TimeDesignationError, DecibelsError => DecibelsError
+	FmtError => fmt::Error, Utf8Error => Utf8Error, TimeDesignationError => TimeDesignationError, DecibelsError => DecibelsError, VoiceConfigError => VoiceConfigError, ParseError => ParseError,
+	BudgetExceeded => BudgetExceeded
 }
 
 impl Display for Error {
@@ -38,7 +64,16 @@ impl Display for Error {
 			Error::Utf8Error(e) => e.fmt(f),
 			Error::TimeDesignationError(e) => e.fmt(f),
 			Error::DecibelsError(e) => e.fmt(f),
-			Error::AttributesInChildContext => f.write_str("invalid ordering: attempted to write attributes after writing children")
+			Error::VoiceConfigError(e) => e.fmt(f),
+			Error::AttributesInChildContext => f.write_str("invalid ordering: attempted to write attributes after writing children"),
+			Error::InvalidElement(reason) => write!(f, "invalid element: {reason}"),
+			Error::UnknownElement(tag) => write!(f, "element `{tag}` is not permitted by the unknown element policy"),
+			Error::ParseError(e) => e.fmt(f),
+			Error::UnsupportedVersion(construct, required) => write!(f, "`{construct}` requires SSML {required}"),
+			Error::UnsupportedElement(tag, flavor) => write!(f, "`{tag}` is not supported by {flavor:?}"),
+			Error::PartiallySupportedElement(tag, flavor) => write!(f, "`{tag}` is only partially supported by {flavor:?}"),
+			Error::EscapingError(reason, value) => write!(f, "{reason}: {value:?}"),
+			Error::BudgetExceeded(e) => e.fmt(f)
 		}
 	}
 }
@@ -0,0 +1,130 @@
+//! Conversion of documents to & from a generic stream of XML events.
+//!
+//! Streaming XML libraries like `quick-xml` & `xml-rs` represent documents as a sequence of start tags, end tags &
+//! text. [`to_events`] produces the same sequence from a typed [`Speak`] document, and [`from_events`] reads one back,
+//! so SSML can be passed to & from code built on those libraries - or inspected generically, without matching on every
+//! [`Element`](crate::Element) variant - without this crate depending on them. Each [`XmlEvent`] maps directly onto an
+//! event of those libraries, e.g. [`XmlEvent::Start`] to `quick_xml::events::Event::Start`.
+//!
+//! ```
+//! # fn main() -> ssml::Result<()> {
+//! use std::borrow::Cow;
+//!
+//! use ssml::{
+//! 	Flavor, SerializeOptions,
+//! 	events::{self, XmlEvent}
+//! };
+//!
+//! let doc =
+//! 	ssml::speak(None, [ssml::voice("en-US-Neural2-F", ["Hello!"]), ssml::voice("en-US-Neural2-D", ["Goodbye!"])]);
+//! let options = SerializeOptions::default().flavor(Flavor::GoogleCloudTextToSpeech);
+//! let mut events = events::to_events(&doc, &options)?;
+//!
+//! // the equivalent of the XPath query `//voice/@name`
+//! let names: Vec<_> = events
+//! 	.iter()
+//! 	.filter_map(|event| match event {
+//! 		XmlEvent::Start { name, attrs } if name == "voice" => {
+//! 			attrs.iter().find(|(attr, _)| attr == "name").map(|(_, value)| value.clone())
+//! 		}
+//! 		_ => None
+//! 	})
+//! 	.collect();
+//! assert_eq!(names, ["en-US-Neural2-F", "en-US-Neural2-D"]);
+//!
+//! // rewrite the events & read them back into a document
+//! for event in &mut events {
+//! 	if let XmlEvent::Text(text) = event {
+//! 		*text = Cow::Owned(text.to_uppercase());
+//! 	}
+//! }
+//! let doc = events::from_events(events)?;
+//! assert_eq!(
+//! 	doc.to_min_string(Flavor::GoogleCloudTextToSpeech)?,
+//! 	r#"<speak><voice name="en-US-Neural2-F">HELLO!</voice><voice name="en-US-Neural2-D">GOODBYE!</voice></speak>"#
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{Serialize, SerializeOptions, Speak, util};
+
+/// A single event in a stream of XML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum XmlEvent<'s> {
+	/// The start of an element, with its attributes' unescaped values. Every `Start` is matched by an [`End`] -
+	/// elements without content produce a `Start` immediately followed by an `End`.
+	///
+	/// [`End`]: XmlEvent::End
+	Start {
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		name: Cow<'s, str>,
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		attrs: Vec<(Cow<'s, str>, Cow<'s, str>)>
+	},
+	/// The end of the element most recently started.
+	End {
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		name: Cow<'s, str>
+	},
+	/// Unescaped text content.
+	Text(#[cfg_attr(feature = "serde", serde(borrow))] Cow<'s, str>)
+}
+
+impl XmlEvent<'_> {
+	pub fn to_owned(&self) -> XmlEvent<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> XmlEvent<'static> {
+		match self {
+			XmlEvent::Start { name, attrs } => XmlEvent::Start {
+				name: Cow::Owned(name.into_owned()),
+				attrs: attrs
+					.into_iter()
+					.map(|(name, value)| (Cow::Owned(name.into_owned()), Cow::Owned(value.into_owned())))
+					.collect()
+			},
+			XmlEvent::End { name } => XmlEvent::End { name: Cow::Owned(name.into_owned()) },
+			XmlEvent::Text(text) => XmlEvent::Text(Cow::Owned(text.into_owned()))
+		}
+	}
+}
+
+/// Converts a document into the stream of XML events it serializes to with `options`.
+///
+/// The events describe the document exactly as [`Serialize::serialize_to_string`] would write it - flavor-specific
+/// namespaces, attributes & checks included - except that whitespace from [`SerializeOptions::pretty`] is never
+/// produced.
+pub fn to_events(doc: &Speak, options: &SerializeOptions) -> crate::Result<Vec<XmlEvent<'static>>> {
+	let xml = doc.serialize_to_string(&options.clone().min())?;
+	Ok(crate::parse::events(&xml)?.into_iter().map(XmlEvent::into_owned).collect())
+}
+
+/// Reads a document from a stream of XML events, which must describe a single `<speak>` element.
+///
+/// Events are interpreted just like [parsed](crate::parse::parse_str) SSML: unknown elements become
+/// [`CustomElement`](crate::CustomElement)s, and whitespace which only serves to lay out the document is dropped.
+pub fn from_events<'s>(events: impl IntoIterator<Item = XmlEvent<'s>>) -> crate::Result<Speak<'static>> {
+	let mut xml = String::new();
+	for event in events {
+		match event {
+			XmlEvent::Start { name, attrs } => {
+				write!(xml, "<{name}")?;
+				for (name, value) in attrs {
+					write!(xml, " {name}=\"")?;
+					util::escape(&mut xml, value)?;
+					xml.push('"');
+				}
+				xml.push('>');
+			}
+			XmlEvent::End { name } => write!(xml, "</{name}>")?,
+			XmlEvent::Text(text) => util::escape(&mut xml, text)?
+		}
+	}
+	Ok(crate::parse::parse_str(&xml)?.into_owned())
+}
@@ -0,0 +1,279 @@
+//! Find which sections of a document changed between two versions, so only their audio needs to be re-synthesized.
+//!
+//! Each direct child of a [`Speak`] document is a [section](Section), identified by a [`Fingerprint`] of its serialized
+//! SSML. Since a section's ID is derived from its content rather than its position, inserting or removing a section
+//! doesn't change the IDs of the sections around it, so synthesized audio can be cached by section ID.
+//! [`SectionDiff`] matches up the sections of two documents & reports which were added, removed, or changed.
+//!
+//! ```
+//! # fn main() -> ssml::Result<()> {
+//! use ssml::fingerprint::{SectionChange, SectionDiff};
+//!
+//! let options = ssml::SerializeOptions::default();
+//! let inbox = |count: &str| {
+//! 	ssml::speak(
+//! 		Some("en-US"),
+//! 		[
+//! 			ssml::sentence(["Welcome back."]),
+//! 			ssml::sentence([format!("You have {count} new messages.")]),
+//! 			ssml::sentence(["Goodbye!"])
+//! 		]
+//! 	)
+//! };
+//! let (old, new) = (inbox("3"), inbox("4"));
+//!
+//! let diff = SectionDiff::new(&old, &new, &options)?;
+//! assert_eq!(
+//! 	diff.changes(),
+//! 	[
+//! 		SectionChange::Unchanged { old: 0, new: 0 },
+//! 		SectionChange::Changed { old: 1, new: 1 },
+//! 		SectionChange::Unchanged { old: 2, new: 2 }
+//! 	]
+//! );
+//!
+//! // only the second sentence needs to be synthesized again
+//! let stale: Vec<_> = diff.resynthesize().map(|section| section.index()).collect();
+//! assert_eq!(stale, [1]);
+//! assert_eq!(diff.old_sections()[0].id(), diff.new_sections()[0].id());
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt::{self, Display, Write};
+
+use crate::{Serialize, SerializeOptions, Speak};
+
+/// A 64-bit hash of serialized SSML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+	/// Computes the fingerprint of `value` as serialized with `options`.
+	pub fn of(value: &impl Serialize, options: &SerializeOptions) -> crate::Result<Self> {
+		let mut hasher = Hasher::new();
+		value.serialize(&mut hasher, options)?;
+		Ok(Fingerprint(hasher.0))
+	}
+
+	/// Returns this fingerprint combined with `other`, e.g. to include the settings of the enclosing document.
+	pub fn combine(self, other: Fingerprint) -> Self {
+		let mut hasher = Hasher(self.0);
+		hasher.write_bytes(&other.0.to_le_bytes());
+		Fingerprint(hasher.0)
+	}
+
+	pub fn get(&self) -> u64 {
+		self.0
+	}
+}
+
+impl Display for Fingerprint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{:016x}", self.0)
+	}
+}
+
+/// FNV-1a, which is stable across platforms & releases, unlike [`core::hash::Hasher`]s.
+struct Hasher(u64);
+
+impl Hasher {
+	fn new() -> Self {
+		Hasher(0xcbf29ce484222325)
+	}
+
+	fn write_bytes(&mut self, bytes: &[u8]) {
+		for b in bytes {
+			self.0 = (self.0 ^ *b as u64).wrapping_mul(0x100000001b3);
+		}
+	}
+}
+
+impl Write for Hasher {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.write_bytes(s.as_bytes());
+		Ok(())
+	}
+}
+
+/// A direct child of a [`Speak`] document, synthesized as a unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Section {
+	id: String,
+	index: usize,
+	fingerprint: Fingerprint
+}
+
+impl Section {
+	/// Returns the stable ID of this section: its [fingerprint](Section::fingerprint) in hexadecimal, followed by `-n`
+	/// for the `n`th repeat of identical content (e.g. the same sentence spoken twice).
+	pub fn id(&self) -> &str {
+		&self.id
+	}
+
+	/// Returns the index of this section within the document's children.
+	pub fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Returns the fingerprint of this section's content. The document's own settings, like its language, are included,
+	/// so changing them changes every section.
+	pub fn fingerprint(&self) -> Fingerprint {
+		self.fingerprint
+	}
+}
+
+/// Splits `doc` into its [`Section`]s, fingerprinting them as serialized with `options`.
+pub fn sections(doc: &Speak, options: &SerializeOptions) -> crate::Result<Vec<Section>> {
+	let root = Fingerprint::of(&doc.without_children(), options)?;
+	let mut sections: Vec<Section> = Vec::with_capacity(doc.children().len());
+	for (index, el) in doc.children().iter().enumerate() {
+		let fingerprint = root.combine(Fingerprint::of(el, options)?);
+		let repeats = sections.iter().filter(|section| section.fingerprint == fingerprint).count();
+		let id = match repeats {
+			0 => format!("{fingerprint}"),
+			n => format!("{fingerprint}-{n}")
+		};
+		sections.push(Section { id, index, fingerprint });
+	}
+	Ok(sections)
+}
+
+/// How a section differs between two versions of a document. Indices are those of the section in the old & new
+/// documents' [sections](SectionDiff::old_sections).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SectionChange {
+	/// The section is identical in both documents, so its audio can be reused.
+	Unchanged { old: usize, new: usize },
+	/// The section's content was modified in place.
+	Changed { old: usize, new: usize },
+	/// The section was inserted into the new document.
+	Added { new: usize },
+	/// The section was removed from the old document.
+	Removed { old: usize }
+}
+
+/// The differences between the [`Section`]s of two versions of a document. See the [module-level documentation](self).
+///
+/// Sections are matched by the longest common subsequence of their fingerprints. Between matched sections, removed
+/// sections are paired up with added sections in order as [`Changed`](SectionChange::Changed); any left over are
+/// reported as [`Removed`](SectionChange::Removed) or [`Added`](SectionChange::Added).
+///
+/// ```
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::fingerprint::{SectionChange, SectionDiff};
+///
+/// let options = ssml::SerializeOptions::default();
+/// let old = ssml::speak(None, ["One.", "Three."]);
+/// let new = ssml::speak(None, ["One.", "Two.", "Three."]);
+///
+/// let diff = SectionDiff::new(&old, &new, &options)?;
+/// assert_eq!(diff.changes()[1], SectionChange::Added { new: 1 });
+/// // sections keep their IDs when others are inserted before them
+/// assert_eq!(diff.old_sections()[1].id(), diff.new_sections()[2].id());
+/// assert_eq!(diff.stale().count(), 0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SectionDiff {
+	old: Vec<Section>,
+	new: Vec<Section>,
+	changes: Vec<SectionChange>
+}
+
+impl SectionDiff {
+	/// Compares the sections of `old` & `new`, as serialized with `options`.
+	pub fn new(old: &Speak, new: &Speak, options: &SerializeOptions) -> crate::Result<Self> {
+		Ok(SectionDiff::from_sections(sections(old, options)?, sections(new, options)?))
+	}
+
+	/// Compares previously computed sections, e.g. those of the old document stored alongside its audio.
+	pub fn from_sections(old: Vec<Section>, new: Vec<Section>) -> Self {
+		let changes = diff(&old, &new);
+		SectionDiff { old, new, changes }
+	}
+
+	pub fn old_sections(&self) -> &[Section] {
+		&self.old
+	}
+
+	pub fn new_sections(&self) -> &[Section] {
+		&self.new
+	}
+
+	/// Returns the changes between the two documents, in document order.
+	pub fn changes(&self) -> &[SectionChange] {
+		&self.changes
+	}
+
+	/// Returns whether the two documents have identical sections.
+	pub fn is_unchanged(&self) -> bool {
+		self.changes.iter().all(|change| matches!(change, SectionChange::Unchanged { .. }))
+	}
+
+	/// Returns the sections of the new document whose audio must be synthesized, because they were added or changed.
+	pub fn resynthesize(&self) -> impl Iterator<Item = &Section> + '_ {
+		self.changes.iter().filter_map(|change| match change {
+			SectionChange::Changed { new, .. } | SectionChange::Added { new } => Some(&self.new[*new]),
+			SectionChange::Unchanged { .. } | SectionChange::Removed { .. } => None
+		})
+	}
+
+	/// Returns the sections of the old document whose audio is no longer used.
+	pub fn stale(&self) -> impl Iterator<Item = &Section> + '_ {
+		self.changes.iter().filter_map(|change| match change {
+			SectionChange::Changed { old, .. } | SectionChange::Removed { old } => Some(&self.old[*old]),
+			SectionChange::Unchanged { .. } | SectionChange::Added { .. } => None
+		})
+	}
+}
+
+fn diff(old: &[Section], new: &[Section]) -> Vec<SectionChange> {
+	// lengths[i][j] is the length of the longest common subsequence of old[i..] & new[j..]
+	let width = new.len() + 1;
+	let mut lengths = vec![0u32; (old.len() + 1) * width];
+	for i in (0..old.len()).rev() {
+		for j in (0..new.len()).rev() {
+			lengths[i * width + j] = if old[i].fingerprint == new[j].fingerprint {
+				lengths[(i + 1) * width + j + 1] + 1
+			} else {
+				lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+			};
+		}
+	}
+
+	let mut changes = Vec::with_capacity(old.len().max(new.len()));
+	let (mut removed, mut added) = (Vec::new(), Vec::new());
+	let (mut i, mut j) = (0, 0);
+	while i < old.len() || j < new.len() {
+		if i < old.len() && j < new.len() && old[i].fingerprint == new[j].fingerprint {
+			flush(&mut changes, &mut removed, &mut added);
+			changes.push(SectionChange::Unchanged { old: i, new: j });
+			i += 1;
+			j += 1;
+		} else if j < new.len() && (i == old.len() || lengths[i * width + j + 1] >= lengths[(i + 1) * width + j]) {
+			added.push(j);
+			j += 1;
+		} else {
+			removed.push(i);
+			i += 1;
+		}
+	}
+	flush(&mut changes, &mut removed, &mut added);
+	changes
+}
+
+/// Pairs up a run of removed & added sections as changes.
+fn flush(changes: &mut Vec<SectionChange>, removed: &mut Vec<usize>, added: &mut Vec<usize>) {
+	let paired = removed.len().min(added.len());
+	changes.extend(removed.iter().zip(added.iter()).map(|(&old, &new)| SectionChange::Changed { old, new }));
+	changes.extend(removed[paired..].iter().map(|&old| SectionChange::Removed { old }));
+	changes.extend(added[paired..].iter().map(|&new| SectionChange::Added { new }));
+	removed.clear();
+	added.clear();
+}
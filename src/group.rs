@@ -1,24 +1,95 @@
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
 use core::{
 	fmt::Write,
 	ops::{Add, AddAssign}
 };
 
-use crate::{Element, Serialize, SerializeOptions, XmlWriter};
+use crate::{Element, Serialize, SerializeOptions, Speak, XmlWriter, annotations::Annotations, util};
 
-#[derive(Clone, Default, Debug)]
+/// A transparent container of elements. Groups are not serialized themselves; only their children are.
+///
+/// A group can be given a label & be disabled, so templates can toggle whole sections of a document on or off without
+/// rebuilding it. Disabled groups (and their children) are skipped during serialization.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let mut doc =
+/// 	ssml::speak(None, [ssml::group(["Hello!"]), ssml::group([" Today's special offer..."]).with_label("promo")]);
+/// doc.set_groups_enabled("promo", false);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Hello!</speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Group<'s> {
-	pub(crate) children: Vec<Element<'s>>
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) children: Vec<Element<'s>>,
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option"))]
+	pub(crate) label: Option<Cow<'s, str>>,
+	#[cfg_attr(feature = "serde", serde(default = "default_enabled"))]
+	pub(crate) enabled: bool,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+#[cfg(feature = "serde")]
+fn default_enabled() -> bool {
+	true
+}
+
+impl Default for Group<'_> {
+	fn default() -> Self {
+		Group::new(Vec::<Element>::new())
+	}
 }
 
 impl<'s> Group<'s> {
 	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Self {
 		Self {
-			children: elements.into_iter().map(|f| f.into()).collect()
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			label: None,
+			enabled: true,
+			annotations: Annotations::default()
 		}
 	}
 
+	/// Sets the label of this group, which can be used to toggle it with [`Speak::set_groups_enabled`].
+	pub fn with_label(mut self, label: impl Into<Cow<'s, str>>) -> Self {
+		self.label = Some(label.into());
+		self
+	}
+
+	pub fn label(&self) -> Option<&str> {
+		self.label.as_deref()
+	}
+
+	pub fn set_label(&mut self, label: impl Into<Cow<'s, str>>) {
+		self.label = Some(label.into());
+	}
+
+	pub fn take_label(&mut self) -> Option<Cow<'s, str>> {
+		self.label.take()
+	}
+
+	/// Sets whether this group is enabled. Disabled groups are skipped during serialization.
+	pub fn with_enabled(mut self, enabled: bool) -> Self {
+		self.enabled = enabled;
+		self
+	}
+
+	pub fn is_enabled(&self) -> bool {
+		self.enabled
+	}
+
+	pub fn set_enabled(&mut self, enabled: bool) {
+		self.enabled = enabled;
+	}
+
 	pub fn children(&self) -> &[Element<'s>] {
 		&self.children
 	}
@@ -41,15 +112,25 @@ impl<'s> Group<'s> {
 
 	pub fn into_owned(self) -> Group<'static> {
 		Group {
-			children: self.children.into_iter().map(Element::into_owned).collect()
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			label: match self.label {
+				Some(Cow::Borrowed(b)) => Some(Cow::Owned(b.to_string())),
+				Some(Cow::Owned(b)) => Some(Cow::Owned(b)),
+				None => None
+			},
+			enabled: self.enabled,
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
 
 impl<'s> Serialize for Group<'s> {
 	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
-		for child in &self.children {
-			child.serialize_xml(writer, options)?;
+		if !self.enabled {
+			return Ok(());
+		}
+		for (i, child) in self.children.iter().enumerate() {
+			util::serialize_child(writer, child, i, options)?;
 		}
 		Ok(())
 	}
@@ -70,6 +151,30 @@ impl<'s, 's2: 's, T: Into<Element<'s2>>> AddAssign<T> for Group<'s> {
 	}
 }
 
+fn set_groups_enabled(elements: &mut [Element], label: &str, enabled: bool) -> usize {
+	let mut count = 0;
+	for element in elements {
+		if let Element::Group(group) = element {
+			if group.label() == Some(label) {
+				group.enabled = enabled;
+				count += 1;
+			}
+		}
+		if let Some(children) = element.children_mut() {
+			count += set_groups_enabled(children, label, enabled);
+		}
+	}
+	count
+}
+
+impl Speak<'_> {
+	/// Enables or disables all [`Group`]s in this document with the given label, returning the number of groups
+	/// affected.
+	pub fn set_groups_enabled(&mut self, label: &str, enabled: bool) -> usize {
+		set_groups_enabled(self.children_mut(), label, enabled)
+	}
+}
+
 pub fn group<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Group<'s> {
 	Group::new(elements)
 }
@@ -0,0 +1,307 @@
+//! Render SSML documents as annotated HTML for previewing.
+//!
+//! The generated HTML is a fragment intended for review interfaces: voices are color-coded, elements which alter
+//! speech (e.g. [`Prosody`] or [`SayAs`]) carry tooltips describing their settings, and audio elements are rendered as
+//! links to their source. Every element is given a class of the form `ssml-<element>`, so the preview can be styled
+//! further.
+//!
+//! ```
+//! use ssml::html::to_html;
+//!
+//! let doc = ssml::speak(None, [ssml::say_as(ssml::SpeechFormat::SpellOut, "SSML")]) + ssml::breaks("1s");
+//! assert_eq!(
+//! 	to_html(&doc),
+//! 	r#"<div class="ssml-speak"><span class="ssml-say-as" title="say-as: spell-out">SSML</span><span class="ssml-break" title="break: +1000ms"></span></div>"#
+//! );
+//! ```
+
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec
+};
+use core::fmt::Write;
+
+use crate::{
+	Audio, Break, CustomElement, Element, Emphasis, Flavor, Lang, Lexicon, Mark, Meta, Paragraph, Phoneme, Prosody, SayAs, Sentence, Speak, Sub, Text, Variant,
+	Voice, Word, mstts, polly, util,
+	visit::{self, Visit},
+	watson
+};
+
+/// Renders a document as an HTML fragment.
+pub fn to_html(doc: &Speak) -> String {
+	let mut renderer = HtmlRenderer { out: String::new() };
+	renderer.visit_speak(doc);
+	renderer.out
+}
+
+struct HtmlRenderer {
+	out: String
+}
+
+impl HtmlRenderer {
+	fn escaped(&mut self, text: &str) {
+		let _ = util::escape(&mut self.out, text);
+	}
+
+	fn open(&mut self, tag: &str, class: &str, attrs: &[(&str, &str)]) {
+		let _ = write!(self.out, "<{tag} class=\"ssml-{class}\"");
+		for (name, value) in attrs {
+			let _ = write!(self.out, " {name}=\"");
+			self.escaped(value);
+			self.out.push('"');
+		}
+		self.out.push('>');
+	}
+
+	fn close(&mut self, tag: &str) {
+		let _ = write!(self.out, "</{tag}>");
+	}
+
+	/// Opens a `<span>` with a tooltip, visits `children`, then closes the span.
+	fn span(&mut self, class: &str, title: &str, extra: &[(&str, &str)], children: &[Element]) {
+		let mut attrs = Vec::with_capacity(extra.len() + 1);
+		attrs.push(("title", title));
+		attrs.extend_from_slice(extra);
+		self.open("span", class, &attrs);
+		for child in children {
+			self.visit_element(child);
+		}
+		self.close("span");
+	}
+}
+
+/// Picks a stable, readable color for a voice name.
+fn voice_color(name: &str) -> String {
+	// FNV-1a
+	let hash = name.bytes().fold(0x811c9dc5u32, |hash, b| (hash ^ b as u32).wrapping_mul(0x01000193));
+	format!("color: hsl({}, 70%, 35%)", hash % 360)
+}
+
+impl<'s> Visit<'s> for HtmlRenderer {
+	fn visit_speak(&mut self, node: &'s Speak) {
+		match node.lang() {
+			Some(lang) => self.open("div", "speak", &[("lang", lang)]),
+			None => self.open("div", "speak", &[])
+		}
+		visit::visit_speak(self, node);
+		self.close("div");
+	}
+
+	fn visit_text(&mut self, node: &'s Text) {
+		self.escaped(node);
+	}
+
+	fn visit_audio(&mut self, node: &'s Audio) {
+		let title = match node.desc() {
+			Some(desc) => format!("audio: {desc}"),
+			None => "audio".to_string()
+		};
+		self.open("a", "audio", &[("href", node.src()), ("title", &title)]);
+		self.escaped(node.desc().unwrap_or(node.src()));
+		self.close("a");
+		if !node.alternate().is_empty() {
+			self.span("audio-alternate", "audio fallback content", &[], node.alternate());
+		}
+	}
+
+	fn visit_voice(&mut self, node: &'s Voice) {
+		let config = node.config();
+		let mut settings = Vec::new();
+		if let Some(names) = &config.names {
+			settings.push(names.join(", "));
+		}
+		if let Some(gender) = &config.gender {
+			settings.push(format!("gender: {gender}"));
+		}
+		if let Some(age) = config.age {
+			settings.push(format!("age: {age}"));
+		}
+		if let Some(variant) = &config.variant {
+			settings.push(format!("variant: {variant}"));
+		}
+		if let Some(languages) = &config.languages {
+			settings.push(format!("languages: {}", languages.join(", ")));
+		}
+		let title = format!("voice: {}", settings.join("; "));
+		let name = config.names.as_ref().and_then(|n| n.first()).map_or("", |n| &**n);
+		let style = voice_color(name);
+		self.span("voice", &title, &[("data-voice", name), ("style", &style)], node.children());
+	}
+
+	fn visit_meta(&mut self, node: &'s Meta) {
+		self.open("code", "meta", &[("title", "raw SSML")]);
+		self.escaped(node.raw());
+		self.close("code");
+	}
+
+	fn visit_break(&mut self, node: &'s Break) {
+		let title = match (node.strength(), node.time()) {
+			(Some(strength), Some(time)) => format!("break: {}, {time}", strength.as_str()),
+			(Some(strength), None) => format!("break: {}", strength.as_str()),
+			(None, Some(time)) => format!("break: {time}"),
+			(None, None) => "break".to_string()
+		};
+		self.span("break", &title, &[], &[]);
+	}
+
+	fn visit_emphasis(&mut self, node: &'s Emphasis) {
+		self.open("em", "emphasis", &[("title", &format!("emphasis: {}", node.level().as_str()))]);
+		visit::visit_emphasis(self, node);
+		self.close("em");
+	}
+
+	fn visit_mark(&mut self, node: &'s Mark) {
+		self.span("mark", &format!("mark: {}", node.name()), &[("data-mark", node.name())], &[]);
+	}
+
+	fn visit_lexicon(&mut self, node: &'s Lexicon) {
+		self.span("lexicon", &format!("lexicon: {}", node.uri()), &[], &[]);
+	}
+
+	fn visit_say_as(&mut self, node: &'s SayAs) {
+		let format = node.format();
+		let mut title = format!("say-as: {}", format.interpret_as(Flavor::Generic));
+		if let Some(f) = format.format() {
+			let _ = write!(title, "; format: {f}");
+		}
+		if let Some(detail) = format.detail() {
+			let _ = write!(title, "; detail: {detail}");
+		}
+		self.open("span", "say-as", &[("title", &title)]);
+		visit::visit_say_as(self, node);
+		self.close("span");
+	}
+
+	fn visit_phoneme(&mut self, node: &'s Phoneme) {
+		self.open("span", "phoneme", &[("title", &format!("phoneme ({}): {}", node.alphabet().as_str(), node.ph()))]);
+		self.escaped(node.text());
+		self.close("span");
+	}
+
+	fn visit_paragraph(&mut self, node: &'s Paragraph) {
+		self.open("p", "paragraph", &[]);
+		visit::visit_paragraph(self, node);
+		self.close("p");
+	}
+
+	fn visit_sentence(&mut self, node: &'s Sentence) {
+		self.span("sentence", "sentence", &[], node.children());
+	}
+
+	fn visit_word(&mut self, node: &'s Word) {
+		match node.role() {
+			Some(role) => self.span("word", &format!("word: {role}"), &[], node.children()),
+			None => self.span("word", "word", &[], node.children())
+		}
+	}
+
+	fn visit_sub(&mut self, node: &'s Sub) {
+		self.open("span", "sub", &[("title", &format!("sub: {}", node.alias()))]);
+		self.escaped(node.text());
+		self.close("span");
+	}
+
+	fn visit_lang(&mut self, node: &'s Lang) {
+		self.span("lang", &format!("lang: {}", node.language()), &[("lang", node.language())], node.children());
+	}
+
+	fn visit_prosody(&mut self, node: &'s Prosody) {
+		let control = node.control();
+		let mut settings = Vec::new();
+		if let Some(pitch) = &control.pitch {
+			settings.push(format!("pitch: {pitch}"));
+		}
+		if let Some(contour) = &control.contour {
+			settings.push(format!("contour: {contour}"));
+		}
+		if let Some(range) = &control.range {
+			settings.push(format!("range: {range}"));
+		}
+		if let Some(rate) = &control.rate {
+			settings.push(format!("rate: {rate}"));
+		}
+		if let Some(duration) = &control.duration {
+			settings.push(format!("duration: {duration}"));
+		}
+		if let Some(volume) = &control.volume {
+			settings.push(format!("volume: {volume}"));
+		}
+		self.span("prosody", &format!("prosody: {}", settings.join("; ")), &[], node.children());
+	}
+
+	fn visit_custom(&mut self, node: &'s CustomElement) {
+		let mut title = format!("<{}", node.tag());
+		for (name, value) in node.attrs() {
+			let _ = write!(title, " {name}=\"{value}\"");
+		}
+		title.push('>');
+		self.span("custom", &title, &[], node.children());
+	}
+
+	fn visit_variant(&mut self, node: &'s Variant) {
+		self.open("span", "variant", &[("title", "variant")]);
+		for (key, weight, el) in node.alternatives() {
+			self.span("variant-alternative", &format!("alternative: {key} (weight {weight})"), &[("data-key", key)], core::slice::from_ref(el));
+		}
+		self.close("span");
+	}
+
+	fn visit_mstts_express(&mut self, node: &'s mstts::Express) {
+		let expression = node.expression();
+		let title = format!("mstts:express-as: {} ({})", expression.name(), expression.degree());
+		self.span("mstts-express", &title, &[], node.children());
+	}
+
+	fn visit_mstts_silence(&mut self, node: &'s mstts::Silence) {
+		self.span("mstts-silence", &format!("mstts:silence: {} {}", node.kind(), node.value()), &[], &[]);
+	}
+
+	fn visit_mstts_background_audio(&mut self, node: &'s mstts::BackgroundAudio) {
+		self.span("mstts-backgroundaudio", &format!("mstts:backgroundaudio: {}", node.src()), &[], &[]);
+	}
+
+	fn visit_mstts_viseme(&mut self, node: &'s mstts::Viseme) {
+		self.span("mstts-viseme", &format!("mstts:viseme: {}", node.kind()), &[], &[]);
+	}
+
+	fn visit_mstts_tts_embedding(&mut self, node: &'s mstts::TtsEmbedding) {
+		self.span("mstts-ttsembedding", &format!("mstts:ttsembedding: {}", node.speaker_profile_id()), &[], node.children());
+	}
+
+	fn visit_mstts_audio_duration(&mut self, node: &'s mstts::AudioDuration) {
+		self.span("mstts-audioduration", &format!("mstts:audioduration: {}", node.value()), &[], &[]);
+	}
+
+	fn visit_mstts_math(&mut self, node: &'s mstts::Math) {
+		self.span("mstts-math", &format!("math: {}", node.content()), &[], &[]);
+	}
+
+	fn visit_polly_domain(&mut self, node: &'s polly::Domain) {
+		self.span("amazon-domain", &format!("amazon:domain: {}", node.name()), &[], node.children());
+	}
+
+	fn visit_polly_effect(&mut self, node: &'s polly::Effect) {
+		self.span("amazon-effect", &format!("amazon:effect: {:?}", node.kind()), &[], node.children());
+	}
+
+	fn visit_watson_express_as(&mut self, node: &'s watson::ExpressAs) {
+		self.span("watson-express-as", &format!("express-as: {}", node.expression()), &[], node.children());
+	}
+
+	fn visit_watson_voice_transformation(&mut self, node: &'s watson::VoiceTransformation) {
+		self.span("watson-voice-transformation", &format!("voice-transformation: {:?}", node.kind()), &[], node.children());
+	}
+
+	fn visit_element(&mut self, node: &'s Element) {
+		match node {
+			Element::Group(group) if !group.is_enabled() => {}
+			Element::Group(group) => match group.label() {
+				Some(label) => self.span("group", &format!("group: {label}"), &[], group.children()),
+				None => self.span("group", "group", &[], group.children())
+			},
+			node => visit::visit_element(self, node)
+		}
+	}
+}
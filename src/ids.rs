@@ -0,0 +1,54 @@
+//! Deterministic generation of names for elements inserted by passes, like the marks inserted by
+//! [`Captions::with_marks`](crate::captions::Captions::with_marks) & [`audiobook::split`](crate::audiobook::split).
+//!
+//! Passes take an [`IdGenerator`] through their options, and start from a fresh copy of it on every run, so running a
+//! pass over the same document with the same options always produces identical names, keeping caches & diffs stable.
+//!
+//! ```
+//! use ssml::ids::IdGenerator;
+//!
+//! let mut ids = IdGenerator::sequential();
+//! assert_eq!([ids.next_id(), ids.next_id()], ["0", "1"]);
+//!
+//! let mut a = IdGenerator::seeded(42);
+//! let mut b = IdGenerator::seeded(42);
+//! assert_eq!(a.next_id(), b.next_id());
+//! assert_eq!(a.next_id().len(), 16);
+//! assert_ne!(a.next_id(), IdGenerator::seeded(43).next_id());
+//! ```
+
+use alloc::{format, string::String};
+
+use crate::variant::splitmix64;
+
+/// Generates a sequence of names. See the [module-level documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdGenerator {
+	seed: Option<u64>,
+	count: u64
+}
+
+impl IdGenerator {
+	/// Creates a generator which names elements by their index, starting from `0`. This is the default.
+	pub fn sequential() -> Self {
+		IdGenerator { seed: None, count: 0 }
+	}
+
+	/// Creates a generator which gives elements pseudo-random names of 16 hexadecimal digits, determined by `seed`.
+	///
+	/// Unlike sequential names, these are unlikely to collide with the names of another document's elements (when using
+	/// a different seed), e.g. when documents are concatenated.
+	pub fn seeded(seed: u64) -> Self {
+		IdGenerator { seed: Some(seed), count: 0 }
+	}
+
+	/// Returns the next name in the sequence.
+	pub fn next_id(&mut self) -> String {
+		let index = self.count;
+		self.count += 1;
+		match self.seed {
+			Some(seed) => format!("{:016x}", splitmix64(seed.wrapping_add(index.wrapping_mul(0x9e3779b97f4a7c15)))),
+			None => format!("{index}")
+		}
+	}
+}
@@ -0,0 +1,304 @@
+//! Conversion of utterance options from other text-to-speech crates into SSML.
+//!
+//! Many TTS wrapper crates describe how an utterance should be spoken with a plain struct of options - a rate, pitch,
+//! volume & voice. [`UtteranceOptions::from_serialize`] reads such a struct through its [`serde::Serialize`]
+//! implementation, so any options struct deriving `Serialize` can be converted without this crate depending on it:
+//!
+//! ```
+//! # use ssml::Serialize;
+//! # fn main() -> ssml::Result<()> {
+//! use ssml::interop::UtteranceOptions;
+//!
+//! #[derive(serde::Serialize)]
+//! struct TtsOptions {
+//! 	rate: f32,
+//! 	pitch: f32,
+//! 	volume: f32,
+//! 	voice_id: Option<String>
+//! }
+//!
+//! let options = TtsOptions { rate: 1.5, pitch: 2., volume: 1., voice_id: Some("en-US-JennyNeural".into()) };
+//! let options = UtteranceOptions::from_serialize(&options).unwrap();
+//! assert_eq!(options.rate, Some(1.5));
+//! assert_eq!(options.voice.as_deref(), Some("en-US-JennyNeural"));
+//!
+//! let doc = ssml::speak(None, [options.wrap(["Hello, world!"])]);
+//! assert_eq!(
+//! 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+//! 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-JennyNeural"><prosody pitch="+12st" rate="150%">Hello, world!</prosody></voice></speak>"#
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{
+	borrow::Cow,
+	string::{String, ToString},
+	vec,
+	vec::Vec
+};
+use core::fmt::{self, Display};
+
+use serde::ser::{self, Impossible, SerializeStruct, Serializer};
+
+use crate::{Element, ProsodyControl, ProsodyPitch, ProsodyRate, ProsodyVolume, VoiceConfig};
+
+/// Utterance options in the style of TTS wrapper crates.
+///
+/// `rate`, `pitch` & `volume` are multipliers of the voice's normal value, i.e. `1.0` is normal, `2.0` is twice as
+/// fast, high or loud, and `0.5` is half. Options from backends which use a different range should be normalized to
+/// this range first.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct UtteranceOptions<'s> {
+	/// The speaking rate, as a multiplier of the normal rate.
+	pub rate: Option<f32>,
+	/// The pitch, as a multiplier of the normal pitch.
+	pub pitch: Option<f32>,
+	/// The volume, as a multiplier of the normal volume. `0.0` is silent.
+	pub volume: Option<f32>,
+	/// The name (or ID) of the voice to use.
+	pub voice: Option<Cow<'s, str>>,
+	/// The language to speak in, e.g. `en-US`.
+	pub language: Option<Cow<'s, str>>
+}
+
+impl<'s> UtteranceOptions<'s> {
+	/// Reads utterance options from any struct implementing [`serde::Serialize`].
+	///
+	/// Fields are matched by name, ignoring case & underscores:
+	/// - `rate`, `speed` or `speaking_rate` for the rate;
+	/// - `pitch` for the pitch;
+	/// - `volume` for the volume;
+	/// - `voice`, `voice_name`, `voice_id`, or the `name` or `id` of a nested `voice` struct for the voice;
+	/// - `language`, `lang`, `locale`, `language_code`, or the `language` of a nested `voice` struct for the language.
+	///
+	/// Other fields, and fields with values of an unexpected type, are ignored. Returns `None` if `options` isn't a
+	/// struct.
+	pub fn from_serialize<T: ser::Serialize + ?Sized>(options: &T) -> Option<UtteranceOptions<'static>> {
+		let mut fields = Vec::new();
+		options
+			.serialize(FieldSerializer {
+				fields: &mut fields,
+				key: String::new(),
+				depth: 0
+			})
+			.ok()?;
+
+		let mut out = UtteranceOptions::default();
+		for (key, value) in fields {
+			match (&*key, value) {
+				("rate" | "speed" | "speakingrate", Value::Number(n)) => out.rate = Some(n as f32),
+				("pitch", Value::Number(n)) => out.pitch = Some(n as f32),
+				("volume", Value::Number(n)) => out.volume = Some(n as f32),
+				("voice" | "voicename" | "voiceid" | "voice.name" | "voice.id", Value::Text(s)) => out.voice = Some(Cow::Owned(s)),
+				("language" | "lang" | "locale" | "languagecode" | "voice.language" | "voice.lang", Value::Text(s)) => out.language = Some(Cow::Owned(s)),
+				_ => {}
+			}
+		}
+		Some(out)
+	}
+
+	/// Returns the prosody settings described by these options. Options which are normal (`1.0`) are omitted.
+	pub fn prosody(&self) -> ProsodyControl {
+		let normal = |value: &f32| *value != 1.;
+		ProsodyControl {
+			rate: self.rate.filter(normal).map(ProsodyRate::new),
+			pitch: self
+				.pitch
+				.filter(normal)
+				.filter(|pitch| *pitch > 0.)
+				.map(|pitch| ProsodyPitch::st(12. * pitch.log2())),
+			volume: self.volume.filter(normal).map(|volume| match volume {
+				v if v <= 0. => ProsodyVolume::Silent,
+				v => ProsodyVolume::db(20. * v.log10())
+			}),
+			..ProsodyControl::default()
+		}
+	}
+
+	/// Returns the voice settings described by these options, or `None` if they don't select a voice or language.
+	pub fn voice_config(&self) -> Option<VoiceConfig<'s>> {
+		if self.voice.is_none() && self.language.is_none() {
+			return None;
+		}
+		Some(VoiceConfig {
+			names: self.voice.clone().map(|voice| vec![voice]),
+			languages: self.language.clone().map(|language| vec![language]),
+			..VoiceConfig::default()
+		})
+	}
+
+	/// Wraps `elements` in the [`Voice`](crate::Voice) and/or [`Prosody`](crate::Prosody) elements described by these
+	/// options. If the options are all normal, the elements are wrapped in a transparent [`Group`](crate::Group).
+	pub fn wrap<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&self, elements: I) -> Element<'s> {
+		let prosody = self.prosody();
+		let has_prosody = prosody.rate.is_some() || prosody.pitch.is_some() || prosody.volume.is_some();
+		match (self.voice_config(), has_prosody) {
+			(Some(config), true) => crate::voice(config, [crate::prosody(prosody, elements)]).into(),
+			(Some(config), false) => crate::voice(config, elements).into(),
+			(None, true) => crate::prosody(prosody, elements).into(),
+			(None, false) => crate::group(elements).into()
+		}
+	}
+}
+
+enum Value {
+	Number(f64),
+	Text(String)
+}
+
+/// The value of a field had a type which isn't understood; the field is skipped.
+#[derive(Debug)]
+struct Unsupported;
+
+impl Display for Unsupported {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("unsupported value")
+	}
+}
+
+impl ser::StdError for Unsupported {}
+
+impl ser::Error for Unsupported {
+	fn custom<T: Display>(_: T) -> Self {
+		Unsupported
+	}
+}
+
+/// Flattens the fields of a struct (and of structs nested one level deep) into normalized `key`/`parent.key` pairs.
+struct FieldSerializer<'a> {
+	fields: &'a mut Vec<(String, Value)>,
+	key: String,
+	depth: u8
+}
+
+impl FieldSerializer<'_> {
+	fn push(self, value: Value) -> Result<(), Unsupported> {
+		if self.depth == 0 {
+			return Err(Unsupported);
+		}
+		self.fields.push((self.key, value));
+		Ok(())
+	}
+}
+
+macro_rules! serialize_number {
+	($($method:ident: $t:ty),*) => {
+		$(fn $method(self, v: $t) -> Result<(), Unsupported> {
+			self.push(Value::Number(v as f64))
+		})*
+	};
+}
+
+impl<'a> Serializer for FieldSerializer<'a> {
+	type Ok = ();
+	type Error = Unsupported;
+	type SerializeSeq = Impossible<(), Unsupported>;
+	type SerializeTuple = Impossible<(), Unsupported>;
+	type SerializeTupleStruct = Impossible<(), Unsupported>;
+	type SerializeTupleVariant = Impossible<(), Unsupported>;
+	type SerializeMap = Impossible<(), Unsupported>;
+	type SerializeStruct = Self;
+	type SerializeStructVariant = Impossible<(), Unsupported>;
+
+	serialize_number!(serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64);
+	serialize_number!(serialize_u8: u8, serialize_u16: u16, serialize_u32: u32, serialize_u64: u64);
+	serialize_number!(serialize_f32: f32, serialize_f64: f64);
+
+	fn serialize_bool(self, _: bool) -> Result<(), Unsupported> {
+		Err(Unsupported)
+	}
+
+	fn serialize_char(self, v: char) -> Result<(), Unsupported> {
+		self.push(Value::Text(v.to_string()))
+	}
+
+	fn serialize_str(self, v: &str) -> Result<(), Unsupported> {
+		self.push(Value::Text(v.to_string()))
+	}
+
+	fn serialize_bytes(self, _: &[u8]) -> Result<(), Unsupported> {
+		Err(Unsupported)
+	}
+
+	fn serialize_none(self) -> Result<(), Unsupported> {
+		Ok(())
+	}
+
+	fn serialize_some<T: ser::Serialize + ?Sized>(self, value: &T) -> Result<(), Unsupported> {
+		value.serialize(self)
+	}
+
+	fn serialize_unit(self) -> Result<(), Unsupported> {
+		Ok(())
+	}
+
+	fn serialize_unit_struct(self, _: &'static str) -> Result<(), Unsupported> {
+		Ok(())
+	}
+
+	fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<(), Unsupported> {
+		self.push(Value::Text(variant.to_string()))
+	}
+
+	fn serialize_newtype_struct<T: ser::Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), Unsupported> {
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<T: ser::Serialize + ?Sized>(self, _: &'static str, _: u32, _: &'static str, _: &T) -> Result<(), Unsupported> {
+		Err(Unsupported)
+	}
+
+	fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Unsupported> {
+		Err(Unsupported)
+	}
+
+	fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Unsupported> {
+		Err(Unsupported)
+	}
+
+	fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Unsupported> {
+		Err(Unsupported)
+	}
+
+	fn serialize_tuple_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self::SerializeTupleVariant, Unsupported> {
+		Err(Unsupported)
+	}
+
+	fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Unsupported> {
+		Err(Unsupported)
+	}
+
+	fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Unsupported> {
+		if self.depth > 1 { Err(Unsupported) } else { Ok(self) }
+	}
+
+	fn serialize_struct_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self::SerializeStructVariant, Unsupported> {
+		Err(Unsupported)
+	}
+}
+
+impl SerializeStruct for FieldSerializer<'_> {
+	type Ok = ();
+	type Error = Unsupported;
+
+	fn serialize_field<T: ser::Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Unsupported> {
+		let mut path = self.key.clone();
+		if !path.is_empty() {
+			path.push('.');
+		}
+		path.extend(key.chars().filter(|c| *c != '_').map(|c| c.to_ascii_lowercase()));
+		// values of unsupported types are skipped rather than failing the whole struct
+		let _ = value.serialize(FieldSerializer {
+			fields: self.fields,
+			key: path,
+			depth: self.depth + 1
+		});
+		Ok(())
+	}
+
+	fn end(self) -> Result<(), Unsupported> {
+		Ok(())
+	}
+}
@@ -0,0 +1,165 @@
+//! Helpers for common telephony IVR (interactive voice response) prompts.
+//!
+//! These expand to standard elements, so the resulting prompts can be further customized or embedded like any other
+//! content.
+//!
+//! ```
+//! # use ssml::Serialize;
+//! # fn main() -> ssml::Result<()> {
+//! use ssml::ivr::Menu;
+//!
+//! let menu = Menu::new()
+//! 	.with_intro("Thanks for calling.")
+//! 	.with_option("1", "billing")
+//! 	.with_option("2", "support");
+//! assert_eq!(
+//! 	ssml::speak(None, [menu]).serialize_to_string(&ssml::SerializeOptions::default())?,
+//! 	concat!(
+//! 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><p><s>Thanks for calling.</s>"#,
+//! 		r#"<s>For billing, press <say-as interpret-as="spell-out">1</say-as>.</s><break time="+500ms"/>"#,
+//! 		r#"<s>For support, press <say-as interpret-as="spell-out">2</say-as>.</s><break time="+500ms"/></p></speak>"#
+//! 	)
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{borrow::Cow, format, vec::Vec};
+
+use crate::{Break, Element, Group, Paragraph, Sentence, SpeechFormat, TimeDesignation, breaks, say_as};
+
+/// A menu of options, each selected by pressing a key, e.g. "For billing, press 1."
+///
+/// Each option is read as its own sentence, followed by a pause (500ms by default) to give the caller time to react.
+#[derive(Debug, Clone)]
+pub struct Menu<'s> {
+	intro: Vec<Element<'s>>,
+	options: Vec<(Cow<'s, str>, Cow<'s, str>)>,
+	lead: Cow<'s, str>,
+	action: Cow<'s, str>,
+	pause: Break
+}
+
+impl Default for Menu<'_> {
+	fn default() -> Self {
+		Menu::new()
+	}
+}
+
+impl<'s> Menu<'s> {
+	pub fn new() -> Self {
+		Menu {
+			intro: Vec::new(),
+			options: Vec::new(),
+			lead: Cow::Borrowed("For"),
+			action: Cow::Borrowed("press"),
+			pause: Break::new_with_time(TimeDesignation::from_millis(500.))
+		}
+	}
+
+	/// Adds a sentence which is read before the options.
+	pub fn with_intro(mut self, sentence: impl Into<Element<'s>>) -> Self {
+		self.intro.push(sentence.into());
+		self
+	}
+
+	/// Adds an option selected by pressing `key`, e.g. `1`, `*` or `#`.
+	pub fn with_option(mut self, key: impl Into<Cow<'s, str>>, label: impl Into<Cow<'s, str>>) -> Self {
+		self.options.push((key.into(), label.into()));
+		self
+	}
+
+	/// Sets the wording of each option, which is read as `{lead} {label}, {action} {key}.`. Defaults to `For` &
+	/// `press`.
+	pub fn with_wording(mut self, lead: impl Into<Cow<'s, str>>, action: impl Into<Cow<'s, str>>) -> Self {
+		self.lead = lead.into();
+		self.action = action.into();
+		self
+	}
+
+	/// Sets the pause after each option.
+	pub fn with_pause(mut self, pause: impl Into<Break>) -> Self {
+		self.pause = pause.into();
+		self
+	}
+
+	/// Expands this menu into a [`Paragraph`].
+	pub fn build(self) -> Paragraph<'s> {
+		let mut paragraph = Paragraph::new(self.intro.into_iter().map(|el| match el {
+			Element::Text(text) => Sentence::new([text]).into(),
+			el => el
+		}));
+		for (key, label) in self.options {
+			paragraph.push(option(format!("{} {label}, {} ", self.lead, self.action), key));
+			paragraph.push(self.pause.clone());
+		}
+		paragraph
+	}
+}
+
+impl<'s> From<Menu<'s>> for Element<'s> {
+	fn from(value: Menu<'s>) -> Self {
+		value.build().into()
+	}
+}
+
+fn option<'s>(instruction: impl Into<Cow<'s, str>>, key: Cow<'s, str>) -> Sentence<'s> {
+	Sentence::new([Element::from(instruction.into()), say_as(SpeechFormat::SpellOut, key).into(), Element::from(".")])
+}
+
+/// Reads back a value entered by the caller, then asks them to confirm it with `yes` or reject it with `no`.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let prompt = ssml::ivr::confirmation([ssml::ivr::digits("4811", 0, "0ms")], "1", "2");
+/// assert_eq!(
+/// 	prompt.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	concat!(
+/// 		r#"<p><s>You entered <say-as interpret-as="digits">4811</say-as>.</s>"#,
+/// 		r#"<s>If this is correct, press <say-as interpret-as="spell-out">1</say-as>.</s>"#,
+/// 		r#"<s>Otherwise, press <say-as interpret-as="spell-out">2</say-as>.</s></p>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn confirmation<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(value: I, yes: impl Into<Cow<'s, str>>, no: impl Into<Cow<'s, str>>) -> Paragraph<'s> {
+	let mut entered = Sentence::new(["You entered "]);
+	entered.extend(value);
+	entered.push(".");
+	Paragraph::new([entered, option("If this is correct, press ", yes.into()), option("Otherwise, press ", no.into())])
+}
+
+/// Reads a string of digits (e.g. an account number) digit by digit, in groups of `group` digits separated by `pause`,
+/// so the caller can follow along or type them on their keypad. A `group` of 0 reads all digits as one group.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// assert_eq!(
+/// 	ssml::ivr::digits("5551234", 3, "300ms").serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	concat!(
+/// 		r#"<say-as interpret-as="digits">555</say-as><break time="+300ms"/><say-as interpret-as="digits">123</say-as>"#,
+/// 		r#"<break time="+300ms"/><say-as interpret-as="digits">4</say-as>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn digits<'s>(digits: &'s str, group: usize, pause: impl Into<Break>) -> Group<'s> {
+	let pause = pause.into();
+	let group = if group == 0 { digits.len().max(1) } else { group };
+	let mut out = Group::default();
+	let mut rest = digits;
+	while !rest.is_empty() {
+		let end = rest.char_indices().nth(group).map_or(rest.len(), |(i, _)| i);
+		let (chunk, tail) = rest.split_at(end);
+		if !out.children().is_empty() {
+			out.push(breaks(pause.clone()));
+		}
+		out.push(say_as(SpeechFormat::Digits, chunk));
+		rest = tail;
+	}
+	out
+}
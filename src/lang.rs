@@ -4,7 +4,12 @@ use core::{
 	ops::{Add, AddAssign}
 };
 
-use crate::{Element, Serialize, SerializeOptions, XmlWriter, util};
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -29,9 +34,18 @@ impl LangFailure {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Lang<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
 	language: Cow<'s, str>,
 	failure_behavior: Option<LangFailure>,
-	pub(crate) children: Vec<Element<'s>>
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> Lang<'s> {
@@ -39,7 +53,9 @@ impl<'s> Lang<'s> {
 		Self {
 			language: language.into(),
 			failure_behavior: None,
-			children: elements.into_iter().map(|f| f.into()).collect()
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
 		}
 	}
 
@@ -60,6 +76,14 @@ impl<'s> Lang<'s> {
 		self.failure_behavior.take()
 	}
 
+	pub fn language(&self) -> &str {
+		&self.language
+	}
+
+	pub fn set_language(&mut self, language: impl Into<Cow<'s, str>>) {
+		self.language = language.into();
+	}
+
 	pub fn children(&self) -> &[Element<'s>] {
 		&self.children
 	}
@@ -87,16 +111,20 @@ impl<'s> Lang<'s> {
 				Cow::Owned(b) => Cow::Owned(b)
 			},
 			failure_behavior: self.failure_behavior,
-			children: self.children.into_iter().map(Element::into_owned).collect()
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
 
 impl<'s> Serialize for Lang<'s> {
 	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		util::require_v1_1(options, "lang")?;
 		writer.element("lang", |writer| {
 			writer.attr("xml:lang", &*self.language)?;
 			writer.attr_opt("onlangfailure", self.failure_behavior.as_ref().map(LangFailure::as_str))?;
+			attrs::write(writer, &self.extra_attrs)?;
 			util::serialize_elements(writer, &self.children, options)
 		})
 	}
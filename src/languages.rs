@@ -0,0 +1,210 @@
+//! Report every language referenced by a document, e.g. to verify that localization coverage matches expectations.
+//!
+//! Languages are collected from the root `xml:lang`, [`Lang`](crate::Lang) elements, voices'
+//! [`languages`](crate::VoiceConfig::languages), and the locales implied by voice names like `en-US-JennyNeural` (see
+//! [`voice_locale`]). A voice whose name implies a different language than the one it's declared or used with is
+//! reported as a [`LanguageConflict`].
+//!
+//! ```
+//! use ssml::languages::{LanguageReport, LanguageSource};
+//!
+//! let doc = ssml::speak(
+//! 	Some("en-US"),
+//! 	[
+//! 		ssml::voice("en-US-JennyNeural", ["Hello!"]),
+//! 		ssml::voice("fr-FR-DeniseNeural", [ssml::lang("de-DE", ["Guten Tag!"])])
+//! 	]
+//! );
+//! let report = LanguageReport::new(&doc);
+//!
+//! assert_eq!(report.languages().collect::<Vec<_>>(), ["de-DE", "en-US", "fr-FR"]);
+//! assert!(
+//! 	report
+//! 		.references()
+//! 		.iter()
+//! 		.any(|r| r.lang == "fr-FR" && r.source == LanguageSource::VoiceName)
+//! );
+//!
+//! // `fr-FR-DeniseNeural` is used in an `en-US` document.
+//! let conflict = &report.conflicts()[0];
+//! assert_eq!(conflict.path, [1]);
+//! assert_eq!((conflict.implied.as_str(), conflict.expected.as_str()), ("fr-FR", "en-US"));
+//! ```
+
+use alloc::{
+	collections::BTreeSet,
+	string::{String, ToString},
+	vec::Vec
+};
+
+use crate::{Element, Speak, VoiceConfig, transform::voice_locale};
+
+/// Where a [`LanguageRef`] was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LanguageSource {
+	/// The root `xml:lang` attribute of the document.
+	Root,
+	/// The document's [default language](crate::DocumentDefaults::lang).
+	Default,
+	/// A [`Lang`](crate::Lang) element.
+	Lang,
+	/// The `languages` of a voice, including the document's [default voice](crate::DocumentDefaults::voice).
+	Voice,
+	/// The locale implied by a voice's name; see [`voice_locale`].
+	VoiceName
+}
+
+/// A reference to a language in a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageRef {
+	/// The language tag, as written in the document.
+	pub lang: String,
+	pub source: LanguageSource,
+	/// The path of the element referencing the language (see [`Speak::element_at`]), or an empty path if the language
+	/// is referenced by the document itself.
+	pub path: Vec<usize>
+}
+
+/// A voice whose name implies a different language than the one it's expected to speak.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageConflict {
+	/// The path of the voice element (see [`Speak::element_at`]), or an empty path for the document's default voice.
+	pub path: Vec<usize>,
+	/// The name of the voice.
+	pub voice: String,
+	/// The locale implied by the voice's name.
+	pub implied: String,
+	/// The language the voice is expected to speak: its own `languages` if set, otherwise the language in effect where
+	/// it's used.
+	pub expected: String,
+	/// Where the expected language was found.
+	pub source: LanguageSource
+}
+
+/// All languages referenced by a document, along with any conflicts between them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LanguageReport {
+	references: Vec<LanguageRef>,
+	conflicts: Vec<LanguageConflict>
+}
+
+impl LanguageReport {
+	/// Collects the languages referenced by the given document.
+	pub fn new(doc: &Speak) -> LanguageReport {
+		let mut report = LanguageReport::default();
+		let mut context = None;
+		if let Some(lang) = doc.lang() {
+			report.reference(lang, LanguageSource::Root, &[]);
+			context = Some((lang.to_string(), LanguageSource::Root));
+		}
+		let defaults = doc.defaults();
+		if let Some(lang) = defaults.lang.as_deref() {
+			report.reference(lang, LanguageSource::Default, &[]);
+			context = context.or_else(|| Some((lang.to_string(), LanguageSource::Default)));
+		}
+		if let Some(voice) = &defaults.voice {
+			context = report.voice(voice, &[], context);
+		}
+
+		let mut path = Vec::new();
+		for (i, child) in doc.children().iter().enumerate() {
+			path.push(i);
+			report.element(child, &mut path, &context);
+			path.pop();
+		}
+		report
+	}
+
+	fn reference(&mut self, lang: &str, source: LanguageSource, path: &[usize]) {
+		self.references.push(LanguageRef {
+			lang: lang.to_string(),
+			source,
+			path: path.to_vec()
+		});
+	}
+
+	/// Records the languages of a voice & checks them against the language in effect, returning the language in effect
+	/// within the voice.
+	fn voice(&mut self, config: &VoiceConfig, path: &[usize], context: Option<(String, LanguageSource)>) -> Option<(String, LanguageSource)> {
+		let declared = config.languages.as_deref().unwrap_or_default();
+		for lang in declared {
+			self.reference(lang, LanguageSource::Voice, path);
+		}
+
+		let expected = match declared.first() {
+			Some(lang) => Some((lang.to_string(), LanguageSource::Voice)),
+			None => context
+		};
+		let mut implied = None;
+		for name in config.names.as_deref().unwrap_or_default() {
+			let Some(locale) = voice_locale(name) else {
+				continue;
+			};
+			self.reference(locale, LanguageSource::VoiceName, path);
+			implied = implied.or(Some(locale));
+
+			let conflicts = match &expected {
+				Some((_, LanguageSource::Voice)) => !declared.iter().any(|lang| same_language(lang, locale)),
+				Some((lang, _)) => !same_language(lang, locale),
+				None => false
+			};
+			if let (true, Some((lang, source))) = (conflicts, &expected) {
+				self.conflicts.push(LanguageConflict {
+					path: path.to_vec(),
+					voice: name.to_string(),
+					implied: locale.to_string(),
+					expected: lang.clone(),
+					source: *source
+				});
+			}
+		}
+
+		match (&expected, implied) {
+			(Some((_, LanguageSource::Voice)), _) | (_, None) => expected,
+			(_, Some(locale)) => Some((locale.to_string(), LanguageSource::VoiceName))
+		}
+	}
+
+	fn element(&mut self, el: &Element, path: &mut Vec<usize>, context: &Option<(String, LanguageSource)>) {
+		let inner = match el {
+			Element::Lang(el) => {
+				self.reference(el.language(), LanguageSource::Lang, path);
+				Some((el.language().to_string(), LanguageSource::Lang))
+			}
+			Element::Voice(el) => self.voice(el.config(), path, context.clone()),
+			_ => context.clone()
+		};
+		for (i, child) in el.children().unwrap_or_default().iter().enumerate() {
+			path.push(i);
+			self.element(child, path, &inner);
+			path.pop();
+		}
+	}
+
+	/// Returns every reference to a language, in document order.
+	pub fn references(&self) -> &[LanguageRef] {
+		&self.references
+	}
+
+	/// Returns an iterator over each distinct language referenced by the document, ordered by language tag.
+	pub fn languages(&self) -> impl Iterator<Item = &str> {
+		self.references.iter().map(|r| r.lang.as_str()).collect::<BTreeSet<_>>().into_iter()
+	}
+
+	/// Returns the conflicts found in the document, in document order.
+	pub fn conflicts(&self) -> &[LanguageConflict] {
+		&self.conflicts
+	}
+
+	/// Returns `true` if any conflicts were found.
+	pub fn has_conflicts(&self) -> bool {
+		!self.conflicts.is_empty()
+	}
+}
+
+/// Returns whether two language tags share the same primary language subtag, e.g. `en-US` & `en-GB`.
+fn same_language(a: &str, b: &str) -> bool {
+	let primary = |tag: &str| tag.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+	primary(a) == primary(b)
+}
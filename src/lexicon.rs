@@ -0,0 +1,154 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+	Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs}
+};
+
+/// A reference to a pronunciation lexicon, such as a [PLS](https://www.w3.org/TR/pronunciation-lexicon/) document,
+/// defining how words should be pronounced.
+///
+/// In generic SSML, lexicons are children of the [`Speak`](crate::Speak) root and apply to the whole document. ACSS
+/// instead requires each lexicon to be a direct child of the [`Voice`](crate::Voice) it applies to, which
+/// [validation](crate::validate) for
+/// [`Flavor::MicrosoftAzureCognitiveSpeechServices`](crate::Flavor::MicrosoftAzureCognitiveSpeechServices) enforces.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", [
+/// 	ssml::Element::from(ssml::lexicon("https://example.com/acronyms.xml")),
+/// 	"BTW, we will be there at 5 PM.".into()
+/// ])]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+/// 	concat!(
+/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:mstts="http://www.w3.org/2001/mstts">"#,
+/// 		r#"<voice name="en-US-JennyNeural"><lexicon uri="https://example.com/acronyms.xml"/>BTW, we will be there at 5 PM.</voice></speak>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ```
+/// use ssml::{Flavor, validate::Lint};
+///
+/// let doc = ssml::speak(
+/// 	None,
+/// 	[
+/// 		ssml::Element::from(ssml::lexicon("https://example.com/acronyms.xml")),
+/// 		ssml::voice("en-US-JennyNeural", ["BTW, we will be there at 5 PM."]).into()
+/// 	]
+/// );
+/// let lints: Vec<_> = ssml::validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices)
+/// 	.into_iter()
+/// 	.map(|d| (d.lint, d.path))
+/// 	.collect();
+/// assert_eq!(lints, [(Lint::MisplacedLexicon, vec![0])]);
+/// assert!(ssml::validate::validate(&doc, Flavor::Generic).is_empty());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lexicon<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	uri: Cow<'s, str>,
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option"))]
+	id: Option<Cow<'s, str>>,
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option"))]
+	media_type: Option<Cow<'s, str>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Lexicon<'s> {
+	/// Creates a new [`Lexicon`] reference to the lexicon document at `uri`.
+	pub fn new(uri: impl Into<Cow<'s, str>>) -> Self {
+		Self {
+			uri: uri.into(),
+			id: None,
+			media_type: None,
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn uri(&self) -> &str {
+		&self.uri
+	}
+
+	pub fn set_uri(&mut self, uri: impl Into<Cow<'s, str>>) {
+		self.uri = uri.into();
+	}
+
+	/// Sets the `xml:id` of this lexicon, which `lookup` elements can refer to.
+	pub fn with_id(mut self, id: impl Into<Cow<'s, str>>) -> Self {
+		self.id = Some(id.into());
+		self
+	}
+
+	pub fn id(&self) -> Option<&str> {
+		self.id.as_deref()
+	}
+
+	pub fn set_id(&mut self, id: Option<impl Into<Cow<'s, str>>>) {
+		self.id = id.map(Into::into);
+	}
+
+	/// Sets the media type of the lexicon document, e.g. `application/pls+xml`.
+	pub fn with_media_type(mut self, media_type: impl Into<Cow<'s, str>>) -> Self {
+		self.media_type = Some(media_type.into());
+		self
+	}
+
+	pub fn media_type(&self) -> Option<&str> {
+		self.media_type.as_deref()
+	}
+
+	pub fn set_media_type(&mut self, media_type: Option<impl Into<Cow<'s, str>>>) {
+		self.media_type = media_type.map(Into::into);
+	}
+
+	pub fn to_owned(&self) -> Lexicon<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Lexicon<'static> {
+		fn owned(s: Cow<'_, str>) -> Cow<'static, str> {
+			match s {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			}
+		}
+		Lexicon {
+			uri: owned(self.uri),
+			id: self.id.map(owned),
+			media_type: self.media_type.map(owned),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+}
+
+impl<'s> Serialize for Lexicon<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
+		writer.element("lexicon", |writer| {
+			writer.attr("uri", &*self.uri)?;
+			writer.attr_opt("xml:id", self.id.as_deref())?;
+			writer.attr_opt("type", self.media_type.as_deref())?;
+			attrs::write(writer, &self.extra_attrs)
+		})
+	}
+}
+
+/// Creates a new [`Lexicon`] reference to the lexicon document at `uri`.
+pub fn lexicon<'s>(uri: impl Into<Cow<'s, str>>) -> Lexicon<'s> {
+	Lexicon::new(uri)
+}
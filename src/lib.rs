@@ -32,45 +32,107 @@ extern crate core;
 
 use alloc::{
 	borrow::Cow,
-	string::{String, ToString}
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec
 };
-use core::fmt::{Debug, Write};
+use core::fmt::{self, Debug, Display, Write};
 
+use crate::annotations::Annotations;
+
+pub mod annotations;
+mod attrs;
 mod audio;
+pub mod audiobook;
 mod r#break;
+mod capabilities;
+pub mod captions;
+mod desc;
+pub mod diagnostics;
+pub mod duration;
 mod element;
 mod emphasis;
 mod error;
+pub mod events;
+pub mod fingerprint;
 mod group;
+pub mod html;
+pub mod ids;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod interop;
+pub mod ivr;
 mod lang;
+pub mod languages;
+mod lexicon;
+pub mod literal;
 mod mark;
+mod metadata;
+pub mod micro;
 pub mod mstts;
+mod paragraph;
+pub mod parse;
+mod phoneme;
+pub mod plain_text;
+pub mod polly;
 mod prosody;
+pub mod provider_error;
+mod raw;
 mod say_as;
+mod sentence;
+#[cfg(feature = "serde")]
+mod serde_borrow;
+#[cfg(feature = "serde")]
+pub mod serde_str;
+mod serializer;
+pub mod source_map;
 mod speak;
+pub mod span;
+pub mod stats;
+mod sub;
 mod text;
+pub mod timeline;
+pub mod transcript;
+pub mod transform;
+mod tree;
 mod unit;
 pub mod util;
+pub mod validate;
+mod variant;
 pub mod visit;
 pub mod visit_mut;
 mod voice;
+pub mod watson;
+mod word;
 mod xml;
 
 pub use self::{
-	audio::{Audio, AudioRepeat, audio},
+	attrs::ExtraAttrs,
+	audio::{Audio, AudioRepeat, AudioSource, FetchHint, audio},
 	r#break::{Break, BreakStrength, breaks},
+	capabilities::{AttributeRewrite, Capabilities, ElementKind, FlavorSpec, FlavorSupport, Support},
+	desc::{Desc, desc},
 	element::{CustomElement, Element, IntoElement},
 	emphasis::{Emphasis, EmphasisLevel, emphasis},
 	error::{Error, Result},
 	group::{Group, group},
-	lang::{Lang, lang},
+	lang::{Lang, LangFailure, lang},
+	lexicon::{Lexicon, lexicon},
 	mark::{Mark, mark},
+	metadata::{Metadata, MetadataContent},
+	paragraph::{Paragraph, paragraph},
+	phoneme::{Alphabet, Phoneme, phoneme},
 	prosody::{Prosody, ProsodyContour, ProsodyControl, ProsodyPitch, ProsodyRate, ProsodyVolume, prosody},
-	say_as::{DateFormat, SayAs, SpeechFormat, say_as},
-	speak::{Speak, speak},
+	say_as::{DateFormat, SayAs, SpeechFormat, TimeFormat, say_as},
+	sentence::{Sentence, sentence},
+	serializer::{Serializer, serialize_all},
+	speak::{DocumentDefaults, IntoSpeak, Speak, SpeakContext, speak},
+	sub::{Sub, sub},
 	text::{Text, text},
+	transform::retarget_voices,
 	unit::{Decibels, DecibelsError, TimeDesignation, TimeDesignationError},
-	voice::{Voice, VoiceConfig, VoiceGender, voice},
+	variant::{Variant, VariantSelection, variant},
+	voice::{Voice, VoiceAge, VoiceConfig, VoiceConfigError, VoiceGender, VoiceVariant, voice},
+	word::{Word, word},
 	xml::{EscapedDisplay, XmlWriter}
 };
 
@@ -94,32 +156,264 @@ pub enum Flavor {
 	/// Amazon Polly flavored SSML.
 	///
 	/// This will use compatibility checks for Standard voices only. Some SSML elements are not supported by Neural
-	/// voices. See the [Amazon Polly documentation](https://docs.aws.amazon.com/polly/latest/dg/supportedtags.html)
-	/// for more information on what tags Neural voices do not support.
+	/// voices; use a [`polly::Engine`] as a [`FlavorSpec`] to check against another engine. See the
+	/// [Amazon Polly documentation](https://docs.aws.amazon.com/polly/latest/dg/supportedtags.html) for more information
+	/// on what tags Neural voices do not support.
 	AmazonPolly,
 	/// pyke Songbird flavored SSML.
-	PykeSongbird
+	PykeSongbird,
+	/// IBM Watson Text to Speech flavored SSML.
+	///
+	/// Watson's extensions - expressions & voice transformations - are provided by the [`watson`] module.
+	IbmWatson,
+	/// eSpeak NG flavored SSML, for offline speech synthesis.
+	///
+	/// eSpeak NG implements a subset of SSML 1.0: `<audio>`, `<lexicon>` & `<token>` aren't supported, and `<say-as>`
+	/// only supports spelling out characters (`characters`, `tts:char`), key names (`tts:key`) & digits (`tts:digits`).
+	///
+	/// ```
+	/// use ssml::{
+	/// 	Flavor, SpeechFormat,
+	/// 	validate::{self, Lint}
+	/// };
+	///
+	/// let doc =
+	/// 	ssml::speak(None, [ssml::say_as(SpeechFormat::SpellOut, "SSML"), ssml::say_as(SpeechFormat::Currency, "$5")]);
+	/// let diagnostics = validate::validate(&doc, Flavor::EspeakNg);
+	/// assert_eq!(diagnostics.len(), 1);
+	/// assert_eq!((diagnostics[0].lint, &*diagnostics[0].path), (Lint::UnsupportedSayAs, &[1][..]));
+	/// ```
+	EspeakNg,
+	/// ElevenLabs flavored SSML.
+	///
+	/// ElevenLabs only honors `<break>`, and `<phoneme>` with some models; other elements aren't supported. Tags are
+	/// written inline with the text, so documents are written without the root `<speak>` element. Use
+	/// [`SerializeOptions::lower_unsupported`] to drop unsupported markup instead of failing serialization.
+	ElevenLabs
+}
+
+/// A version of this crate's serialized output format, used with [`SerializeOptions::compat`].
+///
+/// Within a version, formatting decisions which don't affect the meaning of a document - whitespace between elements,
+/// the formatting of numbers & time designations, and the order of attributes - are guaranteed not to change between
+/// releases of this crate. This allows serialized SSML to be used in golden files or as a cache key.
+///
+/// New versions are only introduced when such formatting decisions change; the output of a pinned version will keep
+/// being produced (where possible) until that version is removed in a semver-breaking release.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputVersion {
+	/// The output format of `ssml` v0.2.
+	///
+	/// - In minified output, no whitespace is written between elements, except for a single space between two adjacent
+	///   [`Text`] elements.
+	/// - Pretty output is indented with one tab per level, with each element & text run on its own line.
+	/// - Time designations are written in milliseconds with an explicit sign, e.g. `+500ms`.
+	/// - Attributes are written in the order they are defined by the SSML specification (or provider documentation).
+	#[default]
+	V0_2
+}
+
+impl OutputVersion {
+	/// The latest output version, used by default.
+	pub const LATEST: OutputVersion = OutputVersion::V0_2;
+}
+
+/// A version of the SSML specification. See [`SerializeOptions::version`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SsmlVersion {
+	/// [SSML 1.0](https://www.w3.org/TR/2004/REC-speech-synthesis-20040907/).
+	V1_0,
+	/// [SSML 1.1](https://www.w3.org/TR/speech-synthesis11/), which adds e.g. [`Lang`] & [`Word`] elements, audio
+	/// clipping & the `onlangfailure` attribute.
+	V1_1
+}
+
+impl SsmlVersion {
+	/// Returns the value of the `version` attribute for this version, e.g. `1.1`.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			SsmlVersion::V1_0 => "1.0",
+			SsmlVersion::V1_1 => "1.1"
+		}
+	}
+}
+
+impl Display for SsmlVersion {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// Determines how elements which aren't defined by the SSML specification or a [`Flavor`] - namely [`CustomElement`]s
+/// and [`Meta`] - are handled during serialization. See [`SerializeOptions::unknown_elements`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UnknownElementPolicy {
+	/// Serialize all elements as-is.
+	#[default]
+	Allow,
+	/// Fail serialization with [`Error::UnknownElement`] if the document contains any [`CustomElement`] or [`Meta`].
+	Deny,
+	/// Only allow [`CustomElement`]s whose tag is prefixed by one of the given namespaces, e.g. `amazon` for
+	/// `<amazon:breath>`. [`Meta`] elements are always denied, since their contents can't be checked.
+	AllowNamespaces(Vec<Cow<'static, str>>)
+}
+
+impl UnknownElementPolicy {
+	/// Returns whether a [`CustomElement`] with the given tag is permitted by this policy.
+	pub fn permits_tag(&self, tag: &str) -> bool {
+		match self {
+			UnknownElementPolicy::Allow => true,
+			UnknownElementPolicy::Deny => false,
+			UnknownElementPolicy::AllowNamespaces(namespaces) => tag
+				.split_once(':')
+				.is_some_and(|(namespace, _)| namespaces.iter().any(|n| n == namespace))
+		}
+	}
+}
+
+/// Determines how elements which the targeted [`Flavor`] only [partially supports](Support::Partial) - like
+/// `<emphasis>`, which Amazon Polly's Neural voices ignore - are handled during serialization. See
+/// [`SerializeOptions::partial_support`].
+///
+/// Elements which aren't supported at all always fail serialization (when
+/// [`perform_checks`](SerializeOptions::perform_checks) is enabled).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PartialSupportPolicy {
+	/// Serialize partially supported elements as-is.
+	#[default]
+	Allow,
+	/// Serialize partially supported elements as-is, reporting each as a
+	/// [`Diagnostic`](diagnostics::Diagnostic) with [`Error::PartiallySupportedElement`] when serializing with
+	/// [`serialize_best_effort`](diagnostics::serialize_best_effort) or
+	/// [`serialize_with_warnings`](diagnostics::serialize_with_warnings).
+	Warn,
+	/// Treat partially supported elements like unsupported ones, failing serialization with
+	/// [`Error::PartiallySupportedElement`].
+	Deny
+}
+
+/// Determines how container elements without any content (e.g. `<voice name="en-US-JennyNeural"/>`) are written. See
+/// [`SerializeOptions::empty_elements`].
+///
+/// This applies to [`Voice`], [`Prosody`], [`Emphasis`], [`Lang`], [`Paragraph`], [`Sentence`] & [`Word`] elements
+/// which have no children, or whose children are all empty text or likewise empty containers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmptyElementPolicy {
+	/// Write empty elements as self-closing tags, e.g. `<voice name="en-US-JennyNeural"/>`.
+	#[default]
+	SelfClosing,
+	/// Write empty elements with separate start & end tags, e.g. `<voice name="en-US-JennyNeural"></voice>`.
+	Expanded,
+	/// Omit empty elements from the output entirely. Some providers reject documents containing them.
+	Drop
+}
+
+/// A callback invoked for every run of text before it is written; see [`SerializeOptions::with_text_filter`].
+#[derive(Clone)]
+pub struct TextFilter(Arc<TextFilterFn>);
+
+type TextFilterFn = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+impl TextFilter {
+	pub fn new(filter: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+		Self(Arc::new(filter))
+	}
+
+	/// Applies this filter to `text`, returning the replacement text, or `None` if the text should be written as is.
+	pub fn apply(&self, text: &str) -> Option<String> {
+		(self.0)(text)
+	}
+}
+
+impl Debug for TextFilter {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("TextFilter(..)")
+	}
 }
 
 /// Configuration for elements that support [`Serialize`].
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct SerializeOptions {
-	/// The flavor of SSML to output; see [`Flavor`]. When `perform_checks` is enabled (which it is by default), this
-	/// can help catch compatibility issues with different speech synthesis providers.
+	/// The flavor of SSML to output; see [`Flavor`]. When [`perform_checks`](SerializeOptions::perform_checks) is
+	/// enabled (which it is by default), this can help catch compatibility issues with different speech synthesis
+	/// providers.
 	pub flavor: Flavor,
+	/// Whether to fail serialization with [`Error::UnsupportedElement`] when an element isn't supported by the
+	/// configured [`flavor`](SerializeOptions::flavor); see [`SerializeOptions::perform_checks`]. Enabled by default.
+	pub perform_checks: bool,
 	/// Whether or not to format the outputted SSML in a human-readable format.
 	///
 	/// Generally, this should only be used for debugging. Some providers may charge per SSML character (not just spoken
 	/// character), so enabling this option in production may significantly increase costs.
-	pub pretty: bool
+	pub pretty: bool,
+	/// The version of the output format to produce; see [`OutputVersion`]. Defaults to [`OutputVersion::LATEST`].
+	pub compat: OutputVersion,
+	/// How [`CustomElement`]s and [`Meta`] elements are handled; see [`UnknownElementPolicy`]. By default, they are
+	/// allowed.
+	pub unknown_elements: UnknownElementPolicy,
+	/// Which alternative of each [`Variant`] is serialized; see [`VariantSelection`]. By default, the first alternative
+	/// is selected.
+	pub variants: VariantSelection,
+	/// A callback applied to every run of text before it is written; see [`SerializeOptions::with_text_filter`].
+	pub text_filter: Option<TextFilter>,
+	/// The strategy used to escape text & attribute values; see [`SerializeOptions::with_escaper`]. Defaults to
+	/// [`XmlEscaper`](util::XmlEscaper) when `None`.
+	pub escaper: Option<Arc<dyn util::Escaper>>,
+	/// Whether to write every non-ASCII character as a numeric character reference, regardless of the configured
+	/// [`escaper`](SerializeOptions::escaper); see [`SerializeOptions::ascii_only`].
+	pub ascii_only: bool,
+	/// Whether to write the original markup of unmodified parsed elements verbatim; see
+	/// [`SerializeOptions::preserve_raw`].
+	pub preserve_raw: bool,
+	/// How container elements without any content are written; see [`EmptyElementPolicy`]. By default, they are
+	/// written as self-closing tags.
+	pub empty_elements: EmptyElementPolicy,
+	/// The version of the SSML specification the output must conform to; see [`SerializeOptions::version`]. When
+	/// `None` (the default), documents are declared as SSML 1.0 but may use SSML 1.1 features.
+	pub version: Option<SsmlVersion>,
+	/// A custom flavor of SSML, used instead of [`flavor`](SerializeOptions::flavor) where set; see
+	/// [`SerializeOptions::with_flavor_spec`].
+	pub flavor_spec: Option<Arc<dyn FlavorSpec>>,
+	/// Whether to check text & attribute values for double-escaping & unescaped markup; see
+	/// [`SerializeOptions::check_escaping`]. Disabled by default.
+	pub check_escaping: bool,
+	/// How elements which the configured flavor only partially supports are handled; see [`PartialSupportPolicy`]. By
+	/// default, they are allowed.
+	pub partial_support: PartialSupportPolicy,
+	/// Whether to replace elements which aren't supported by the configured flavor with their contents instead of
+	/// failing serialization; see [`SerializeOptions::lower_unsupported`]. Disabled by default.
+	pub lower_unsupported: bool
 }
 
 impl Default for SerializeOptions {
 	fn default() -> Self {
 		SerializeOptions {
 			flavor: Flavor::Generic,
-			pretty: false
+			perform_checks: true,
+			pretty: false,
+			compat: OutputVersion::LATEST,
+			unknown_elements: UnknownElementPolicy::Allow,
+			variants: VariantSelection::First,
+			text_filter: None,
+			escaper: None,
+			ascii_only: false,
+			preserve_raw: false,
+			empty_elements: EmptyElementPolicy::SelfClosing,
+			version: None,
+			flavor_spec: None,
+			check_escaping: false,
+			partial_support: PartialSupportPolicy::Allow,
+			lower_unsupported: false
 		}
 	}
 }
@@ -137,6 +431,344 @@ impl SerializeOptions {
 
 	pub fn flavor(mut self, flavor: Flavor) -> Self {
 		self.flavor = flavor;
+		self.flavor_spec = None;
+		self
+	}
+
+	/// Serializes for a custom flavor of SSML described by a [`FlavorSpec`]. [`flavor`](SerializeOptions::flavor) is
+	/// set to the spec's [`base`](FlavorSpec::base) flavor.
+	pub fn with_flavor_spec(mut self, spec: impl FlavorSpec + 'static) -> Self {
+		self.flavor = spec.base();
+		self.flavor_spec = Some(Arc::new(spec));
+		self
+	}
+
+	/// Returns the [`FlavorSpec`] in use: the custom spec if one is set, or the configured [`Flavor`].
+	pub fn spec(&self) -> &dyn FlavorSpec {
+		match &self.flavor_spec {
+			Some(spec) => &**spec,
+			None => &self.flavor
+		}
+	}
+
+	/// Configures whether elements unsupported by the configured [`Flavor`] fail serialization. When disabled, they are
+	/// written as-is.
+	///
+	/// This only checks which elements a flavor supports; use [`validate::validate`] to check a document more
+	/// thoroughly. With [`serialize_best_effort`](diagnostics::serialize_best_effort), unsupported elements are
+	/// unwrapped instead.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// let doc = ssml::speak(None, [ssml::mstts::express(ssml::mstts::express::Cheerful, ["Hi!"])]);
+	/// let options = ssml::SerializeOptions::default().flavor(ssml::Flavor::AmazonPolly);
+	/// assert!(matches!(doc.serialize_to_string(&options), Err(ssml::Error::UnsupportedElement("mstts:express-as", _))));
+	///
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&options.perform_checks(false))?,
+	/// 	r#"<speak><mstts:express-as style="cheerful" styledegree="1">Hi!</mstts:express-as></speak>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn perform_checks(mut self, perform_checks: bool) -> Self {
+		self.perform_checks = perform_checks;
+		self
+	}
+
+	/// Locks formatting decisions to the given [`OutputVersion`], so that upgrading this crate does not change the
+	/// serialized output of a document.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// let options = ssml::SerializeOptions::default().compat(ssml::OutputVersion::V0_2);
+	/// let doc = ssml::speak(None, ["Hello,", "world!"]) + ssml::breaks("1s");
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&options)?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Hello, world!<break time="+1000ms"/></speak>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn compat(mut self, version: OutputVersion) -> Self {
+		self.compat = version;
+		self
+	}
+
+	/// Configures how elements not defined by the SSML specification or the selected [`Flavor`] are handled.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// use ssml::{CustomElement, UnknownElementPolicy};
+	///
+	/// let doc = ssml::speak(None, [CustomElement::new("amazon:breath")]);
+	/// let options = ssml::SerializeOptions::default().unknown_elements(UnknownElementPolicy::Deny);
+	/// assert!(doc.serialize_to_string(&options).is_err());
+	///
+	/// let options = ssml::SerializeOptions::default()
+	/// 	.unknown_elements(UnknownElementPolicy::AllowNamespaces(vec!["amazon".into()]));
+	/// assert!(doc.serialize_to_string(&options).is_ok());
+	/// ```
+	pub fn unknown_elements(mut self, policy: UnknownElementPolicy) -> Self {
+		self.unknown_elements = policy;
+		self
+	}
+
+	/// Selects which alternative of each [`Variant`] is serialized.
+	pub fn variants(mut self, selection: VariantSelection) -> Self {
+		self.variants = selection;
+		self
+	}
+
+	/// Sets a callback invoked for every run of text (including [`SayAs`] contents & [`Audio`] descriptions) before it
+	/// is written. The callback returns the text to write instead, or `None` to write the text unchanged.
+	///
+	/// This allows redaction or moderation to be performed during serialization, without a separate pass over the
+	/// document.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// let options = ssml::SerializeOptions::default().with_text_filter(|text| {
+	/// 	text.contains(|c: char| c.is_ascii_digit())
+	/// 		.then(|| text.chars().map(|c| if c.is_ascii_digit() { '*' } else { c }).collect())
+	/// });
+	/// let doc = ssml::speak(None, ["Your account number is 12345678."]);
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&options)?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Your account number is ********.</speak>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_text_filter(mut self, filter: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+		self.text_filter = Some(TextFilter::new(filter));
+		self
+	}
+
+	/// Sets the strategy used to escape text & attribute values, e.g. [`AsciiEscaper`](util::AsciiEscaper) for engines
+	/// which can't handle non-ASCII characters.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// let options = ssml::SerializeOptions::default().with_escaper(ssml::util::AsciiEscaper);
+	/// assert_eq!(ssml::mark("größe").serialize_to_string(&options)?, r#"<mark name="gr&#x00F6;&#x00DF;e"/>"#);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_escaper(mut self, escaper: impl util::Escaper + 'static) -> Self {
+		self.escaper = Some(Arc::new(escaper));
+		self
+	}
+
+	/// Writes every non-ASCII character in text & attribute values as a numeric character reference (`&#xNNNN;`), so
+	/// that the output survives transport layers which mangle encodings. ASCII characters are still escaped by the
+	/// configured [`escaper`](SerializeOptions::with_escaper).
+	///
+	/// The contents of [`Meta`] elements are written verbatim, and so may still contain non-ASCII characters.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// let doc = ssml::speak(Some("de-DE"), ["Schöne Grüße & bis bald"]);
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().ascii_only())?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="de-DE">Sch&#x00F6;ne Gr&#x00FC;&#x00DF;e &amp; bis bald</speak>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn ascii_only(mut self) -> Self {
+		self.ascii_only = true;
+		self
+	}
+
+	/// Writes elements parsed with [`parse_str_preserving`](parse::parse_str_preserving) exactly as they appeared in
+	/// the input, as long as they haven't been modified since, preserving the formatting of unedited content.
+	///
+	/// Markup is only preserved for elements which serialize under these options exactly as they would in the
+	/// [`Generic`](Flavor::Generic) flavor with default options, so flavor- or option-specific rewrites (e.g. the root
+	/// element written for Amazon Polly) are never bypassed. Preservation is disabled when a text filter or custom
+	/// escaping is configured, since the raw markup would bypass them, and when producing plain text or a source map
+	/// alongside the output.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// let input = r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name='Jenny'>Hi &#x21;</voice><voice name='Guy'>Bye</voice></speak>"#;
+	/// let mut doc = ssml::parse::parse_str_preserving(input)?;
+	/// let options = ssml::SerializeOptions::default().preserve_raw();
+	/// assert_eq!(doc.serialize_to_string(&options)?, input);
+	///
+	/// let ssml::Element::Voice(voice) = &mut doc.children_mut()[1] else { unreachable!() };
+	/// voice.children_mut()[0] = "See you later".into();
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&options)?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name='Jenny'>Hi &#x21;</voice><voice name="Guy">See you later</voice></speak>"#
+	/// );
+	///
+	/// // Polly's root element differs from the generic one, so only its unchanged children are preserved
+	/// let doc = ssml::parse::parse_str_preserving(r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><p>Hi &#x21;</p></speak>"#)?;
+	/// let options = ssml::SerializeOptions::default().flavor(ssml::Flavor::AmazonPolly).preserve_raw();
+	/// assert_eq!(doc.serialize_to_string(&options)?, "<speak><p>Hi &#x21;</p></speak>");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn preserve_raw(mut self) -> Self {
+		self.preserve_raw = true;
+		self
+	}
+
+	/// Configures how container elements without any content are written.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// use ssml::{EmphasisLevel, EmptyElementPolicy};
+	///
+	/// let doc = ssml::speak(None, [
+	/// 	ssml::Element::from("Hello"),
+	/// 	ssml::voice("en-US-GuyNeural", [ssml::emphasis(EmphasisLevel::Strong, [""])]).into(),
+	/// 	ssml::Element::from("world!")
+	/// ]);
+	///
+	/// let options = ssml::SerializeOptions::default().empty_elements(EmptyElementPolicy::Expanded);
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&options)?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Hello<voice name="en-US-GuyNeural"><emphasis level="strong"></emphasis></voice>world!</speak>"#
+	/// );
+	///
+	/// let options = ssml::SerializeOptions::default().empty_elements(EmptyElementPolicy::Drop);
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&options)?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Hello world!</speak>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn empty_elements(mut self, policy: EmptyElementPolicy) -> Self {
+		self.empty_elements = policy;
+		self
+	}
+
+	/// Targets a specific version of the SSML specification.
+	///
+	/// For flavors which declare the SSML version of the document ([`Flavor::Generic`] &
+	/// [`Flavor::MicrosoftAzureCognitiveSpeechServices`]), this sets the `version` attribute of the `<speak>` element.
+	/// When targeting [`SsmlVersion::V1_0`], serializing a construct introduced in SSML 1.1 (such as a [`Lang`]
+	/// element) fails with [`Error::UnsupportedVersion`].
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// use ssml::{LangFailure, SsmlVersion};
+	///
+	/// let doc = ssml::speak(Some("en-US"), [ssml::lang("fr-FR", ["Bonjour !"])]).with_lang_failure(LangFailure::IgnoreLang);
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().version(SsmlVersion::V1_1))?,
+	/// 	r#"<speak version="1.1" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US" onlangfailure="ignorelang"><lang xml:lang="fr-FR">Bonjour !</lang></speak>"#
+	/// );
+	///
+	/// let err = doc.serialize_to_string(&ssml::SerializeOptions::default().version(SsmlVersion::V1_0)).unwrap_err();
+	/// assert_eq!(err.to_string(), "`onlangfailure` requires SSML 1.1");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn version(mut self, version: SsmlVersion) -> Self {
+		self.version = Some(version);
+		self
+	}
+
+	/// Fails serialization with [`Error::EscapingError`] when a text or attribute value appears to already be escaped
+	/// (e.g. `&amp;`, which would be written as `&amp;amp;`), or when a custom [`EscapedDisplay`] implementation or
+	/// [`Escaper`](util::Escaper) leaves `<`, `>`, `"` or a stray `&` in its output.
+	///
+	/// Checking requires buffering each attribute value, so it's best enabled in tests & debug builds. Regardless of
+	/// this option, debug builds assert that values of the crate's own types which are written without escaping (like
+	/// [`TimeDesignation`]) never contain markup.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// let options = ssml::SerializeOptions::default().check_escaping();
+	/// assert!(ssml::speak(None, ["Fish & chips"]).serialize_to_string(&options).is_ok());
+	///
+	/// let err = ssml::speak(None, ["Fish &amp; chips"]).serialize_to_string(&options).unwrap_err();
+	/// assert_eq!(err.to_string(), r#"text appears to already be escaped: "Fish &amp; chips""#);
+	/// assert!(ssml::audio("https://example.com/?a=1&amp;b=2").serialize_to_string(&options).is_err());
+	/// ```
+	pub fn check_escaping(mut self) -> Self {
+		self.check_escaping = true;
+		self
+	}
+
+	/// Configures how elements which the configured flavor only [partially supports](Support::Partial) are handled. By
+	/// default, they're serialized silently; this can report them as warnings or reject them instead, so that every
+	/// incompatibility with the flavor is surfaced consistently. Has no effect if
+	/// [`perform_checks`](SerializeOptions::perform_checks) is disabled.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// use ssml::{EmphasisLevel, Flavor, PartialSupportPolicy, SerializeOptions, diagnostics::serialize_with_warnings};
+	///
+	/// // Amazon Polly's Neural voices ignore `<emphasis>`
+	/// let doc = ssml::speak(None, [ssml::emphasis(EmphasisLevel::Strong, ["Hello!"])]);
+	/// let options = SerializeOptions::default().flavor(Flavor::AmazonPolly);
+	/// assert!(doc.serialize_to_string(&options).is_ok());
+	///
+	/// let (ssml, warnings) = serialize_with_warnings(&doc, &options.clone().partial_support(PartialSupportPolicy::Warn))?;
+	/// assert_eq!(ssml, r#"<speak><emphasis level="strong">Hello!</emphasis></speak>"#);
+	/// assert_eq!(
+	/// 	warnings[0].to_string(),
+	/// 	"`emphasis` is only partially supported by AmazonPolly (element at [0] was emitted anyway)"
+	/// );
+	///
+	/// let options = options.partial_support(PartialSupportPolicy::Deny);
+	/// assert!(matches!(
+	/// 	doc.serialize_to_string(&options),
+	/// 	Err(ssml::Error::PartiallySupportedElement("emphasis", Flavor::AmazonPolly))
+	/// ));
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn partial_support(mut self, policy: PartialSupportPolicy) -> Self {
+		self.partial_support = policy;
+		self
+	}
+
+	/// Lowers the document to the configured flavor by replacing elements it doesn't support with their contents (i.e.
+	/// dropping their markup but keeping their text), instead of failing serialization with
+	/// [`Error::UnsupportedElement`]. Lowered elements aren't reported as [diagnostics](crate::diagnostics).
+	///
+	/// This is useful for flavors which only support a handful of elements, like [`Flavor::ElevenLabs`].
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// use ssml::{EmphasisLevel, Flavor, SerializeOptions};
+	///
+	/// let doc = ssml::speak(
+	/// 	Some("en-US"),
+	/// 	[ssml::paragraph([
+	/// 		ssml::Element::from(ssml::emphasis(EmphasisLevel::Strong, ["Hold on."])),
+	/// 		ssml::breaks("1500ms").into(),
+	/// 		"Almost there.".into()
+	/// 	])]
+	/// );
+	/// let options = SerializeOptions::default().flavor(Flavor::ElevenLabs);
+	/// assert!(matches!(doc.serialize_to_string(&options), Err(ssml::Error::UnsupportedElement("p", Flavor::ElevenLabs))));
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&options.lower_unsupported())?,
+	/// 	r#"Hold on.<break time="+1500ms"/>Almost there."#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn lower_unsupported(mut self) -> Self {
+		self.lower_unsupported = true;
 		self
 	}
 }
@@ -145,7 +777,7 @@ impl SerializeOptions {
 pub trait Serialize {
 	/// Serialize this SSML element into an `std` [`Write`]r.
 	fn serialize<W: Write>(&self, writer: &mut W, options: &SerializeOptions) -> crate::Result<()> {
-		let mut writer = XmlWriter::new(writer, options.pretty);
+		let mut writer = XmlWriter::for_options(writer, options);
 		self.serialize_xml(&mut writer, options)?;
 		Ok(())
 	}
@@ -168,13 +800,21 @@ pub trait Serialize {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Meta<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
 	raw: Cow<'s, str>,
-	name: Option<Cow<'s, str>>
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option"))]
+	name: Option<Cow<'s, str>>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> Meta<'s> {
 	pub fn new(xml: impl Into<Cow<'s, str>>) -> Self {
-		Meta { raw: xml.into(), name: None }
+		Meta {
+			raw: xml.into(),
+			name: None,
+			annotations: Annotations::default()
+		}
 	}
 
 	pub fn with_name(mut self, name: impl Into<Cow<'s, str>>) -> Self {
@@ -182,6 +822,15 @@ impl<'s> Meta<'s> {
 		self
 	}
 
+	/// Returns the raw XML contents of this element.
+	pub fn raw(&self) -> &str {
+		&self.raw
+	}
+
+	pub fn name(&self) -> Option<&str> {
+		self.name.as_deref()
+	}
+
 	pub fn to_owned(&self) -> Meta<'static> {
 		self.clone().into_owned()
 	}
@@ -196,13 +845,20 @@ impl<'s> Meta<'s> {
 				Some(Cow::Borrowed(b)) => Some(Cow::Owned(b.to_string())),
 				Some(Cow::Owned(b)) => Some(Cow::Owned(b)),
 				None => None
-			}
+			},
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
 
 impl<'s> Serialize for Meta<'s> {
-	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		if options.unknown_elements != UnknownElementPolicy::Allow {
+			let error = crate::Error::UnknownElement(self.name.as_deref().unwrap_or("meta").to_string());
+			if !writer.recover(error, diagnostics::Recovery::Skipped)? {
+				return Ok(());
+			}
+		}
 		writer.raw(&self.raw)
 	}
 }
@@ -0,0 +1,417 @@
+//! Compile-time checked SSML literals, via the [`ssml_str!`](crate::ssml_str) macro.
+//!
+//! Hard-coded SSML snippets are easy to break - a missing closing tag or an unescaped `&` is only noticed once a
+//! provider rejects the document. [`ssml_str!`](crate::ssml_str) checks a string literal for well-formedness while
+//! compiling, and optionally that it only uses elements supported by a [`Flavor`], then yields it as a [`Meta`] which
+//! can be placed anywhere in a document.
+//!
+//! The same checks are available at runtime with [`check`].
+//!
+//! ```
+//! use ssml::{
+//! 	Flavor,
+//! 	literal::{LiteralErrorKind, check}
+//! };
+//!
+//! assert!(check("Hello <emphasis>world</emphasis>!", Flavor::Generic).is_ok());
+//! assert_eq!(check("Hello <emphasis>world</s>!", Flavor::Generic).unwrap_err().kind, LiteralErrorKind::MismatchedTag);
+//! assert_eq!(check("Fish & chips", Flavor::Generic).unwrap_err().kind, LiteralErrorKind::UnknownEntity);
+//! assert_eq!(
+//! 	check("<voice name=\"Joanna\">Hi</voice>", Flavor::AmazonPolly).unwrap_err().kind,
+//! 	LiteralErrorKind::UnsupportedElement
+//! );
+//! ```
+//!
+//! [`Meta`]: crate::Meta
+
+use core::fmt::{self, Display};
+
+use crate::{ElementKind, Flavor, Support};
+
+/// The maximum depth of nested elements in a checked literal.
+pub const MAX_DEPTH: usize = 64;
+
+/// The maximum number of attributes on a single element whose names are checked for duplicates.
+const MAX_ATTRIBUTES: usize = 16;
+
+/// The reason a literal failed to [`check`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LiteralErrorKind {
+	/// The input ended in the middle of markup.
+	UnexpectedEof,
+	/// A character which isn't valid at this position was encountered.
+	UnexpectedChar,
+	/// An entity reference other than the predefined XML entities or a character reference was encountered.
+	UnknownEntity,
+	/// A closing tag didn't match the most recently opened element.
+	MismatchedTag,
+	/// A closing tag was encountered with no open element.
+	UnexpectedClosingTag,
+	/// An element was never closed.
+	UnclosedTag,
+	/// An element specified the same attribute more than once.
+	DuplicateAttribute,
+	/// Elements were nested deeper than [`MAX_DEPTH`].
+	TooDeep,
+	/// An element isn't supported by the flavor the literal was checked against.
+	UnsupportedElement
+}
+
+impl LiteralErrorKind {
+	/// Returns a description of this error. Unlike [`Display`], this can be used in `const` contexts.
+	pub const fn message(self) -> &'static str {
+		match self {
+			LiteralErrorKind::UnexpectedEof => "unexpected end of input",
+			LiteralErrorKind::UnexpectedChar => "unexpected character",
+			LiteralErrorKind::UnknownEntity => "unknown entity",
+			LiteralErrorKind::MismatchedTag => "closing tag doesn't match the open element",
+			LiteralErrorKind::UnexpectedClosingTag => "closing tag without an open element",
+			LiteralErrorKind::UnclosedTag => "element is never closed",
+			LiteralErrorKind::DuplicateAttribute => "duplicate attribute",
+			LiteralErrorKind::TooDeep => "elements are nested too deeply",
+			LiteralErrorKind::UnsupportedElement => "element is not supported by the flavor"
+		}
+	}
+}
+
+impl Display for LiteralErrorKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.message())
+	}
+}
+
+/// An error encountered while [`check`]ing a literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiteralError {
+	pub kind: LiteralErrorKind,
+	/// The byte offset of the offending input.
+	pub offset: usize
+}
+
+impl Display for LiteralError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} at byte {}", self.kind, self.offset)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LiteralError {}
+
+const fn error(kind: LiteralErrorKind, offset: usize) -> Result<(), LiteralError> {
+	Err(LiteralError { kind, offset })
+}
+
+/// Checks that `input` is well-formed SSML markup, and that every element it contains is supported by `flavor`.
+///
+/// `input` may be a whole document or a fragment - any number of elements & text runs, like the children of an
+/// element. Comments, processing instructions, CDATA sections & the document type declaration are skipped. Elements
+/// this crate doesn't know about are always accepted; check against [`Flavor::Generic`] to skip flavor checks.
+pub const fn check(input: &str, flavor: Flavor) -> Result<(), LiteralError> {
+	let b = input.as_bytes();
+	// (start, length) of the name of each open element
+	let mut stack = [(0usize, 0usize); MAX_DEPTH];
+	let mut depth = 0;
+	let mut i = 0;
+	while i < b.len() {
+		if b[i] == b'&' {
+			match entity(b, i) {
+				Ok(end) => i = end,
+				Err(e) => return Err(e)
+			}
+			continue;
+		} else if b[i] != b'<' {
+			i += 1;
+			continue;
+		}
+
+		let skip = if starts_with(b, i, b"<!--") {
+			Some((4, b"-->".as_slice()))
+		} else if starts_with(b, i, b"<![CDATA[") {
+			Some((9, b"]]>".as_slice()))
+		} else if starts_with(b, i, b"<?") {
+			Some((2, b"?>".as_slice()))
+		} else if starts_with(b, i, b"<!") {
+			Some((2, b">".as_slice()))
+		} else {
+			None
+		};
+		if let Some((open, close)) = skip {
+			match find(b, i + open, close) {
+				Some(end) => i = end + close.len(),
+				None => return error(LiteralErrorKind::UnexpectedEof, b.len())
+			}
+			continue;
+		}
+
+		if i + 1 < b.len() && b[i + 1] == b'/' {
+			let start = i + 2;
+			let end = name(b, start);
+			if end == start {
+				return error(LiteralErrorKind::UnexpectedChar, start);
+			}
+			let close = skip_whitespace(b, end);
+			if close >= b.len() {
+				return error(LiteralErrorKind::UnexpectedEof, b.len());
+			} else if b[close] != b'>' {
+				return error(LiteralErrorKind::UnexpectedChar, close);
+			}
+			if depth == 0 {
+				return error(LiteralErrorKind::UnexpectedClosingTag, i);
+			}
+			let (open_start, open_len) = stack[depth - 1];
+			if open_len != end - start || !range_eq(b, open_start, b, start, open_len) {
+				return error(LiteralErrorKind::MismatchedTag, i);
+			}
+			depth -= 1;
+			i = close + 1;
+			continue;
+		}
+
+		let start = i + 1;
+		let end = name(b, start);
+		if end == start {
+			return error(LiteralErrorKind::UnexpectedChar, start);
+		}
+		if !supported(b, start, end - start, flavor) {
+			return error(LiteralErrorKind::UnsupportedElement, i);
+		}
+
+		let mut attributes = [(0usize, 0usize); MAX_ATTRIBUTES];
+		let mut attribute_count = 0;
+		let mut j = end;
+		loop {
+			let next = skip_whitespace(b, j);
+			if next >= b.len() {
+				return error(LiteralErrorKind::UnexpectedEof, b.len());
+			}
+			if b[next] == b'>' {
+				if depth == MAX_DEPTH {
+					return error(LiteralErrorKind::TooDeep, i);
+				}
+				stack[depth] = (start, end - start);
+				depth += 1;
+				i = next + 1;
+				break;
+			} else if b[next] == b'/' {
+				if next + 1 >= b.len() {
+					return error(LiteralErrorKind::UnexpectedEof, b.len());
+				} else if b[next + 1] != b'>' {
+					return error(LiteralErrorKind::UnexpectedChar, next + 1);
+				}
+				i = next + 2;
+				break;
+			}
+
+			// attributes must be separated by whitespace
+			if next == j {
+				return error(LiteralErrorKind::UnexpectedChar, next);
+			}
+			let attr_end = name(b, next);
+			if attr_end == next {
+				return error(LiteralErrorKind::UnexpectedChar, next);
+			}
+			let mut k = 0;
+			while k < attribute_count {
+				let (other_start, other_len) = attributes[k];
+				if other_len == attr_end - next && range_eq(b, other_start, b, next, other_len) {
+					return error(LiteralErrorKind::DuplicateAttribute, next);
+				}
+				k += 1;
+			}
+			if attribute_count < MAX_ATTRIBUTES {
+				attributes[attribute_count] = (next, attr_end - next);
+				attribute_count += 1;
+			}
+
+			let eq = skip_whitespace(b, attr_end);
+			if eq >= b.len() {
+				return error(LiteralErrorKind::UnexpectedEof, b.len());
+			} else if b[eq] != b'=' {
+				return error(LiteralErrorKind::UnexpectedChar, eq);
+			}
+			let quote_start = skip_whitespace(b, eq + 1);
+			if quote_start >= b.len() {
+				return error(LiteralErrorKind::UnexpectedEof, b.len());
+			}
+			let quote = b[quote_start];
+			if quote != b'"' && quote != b'\'' {
+				return error(LiteralErrorKind::UnexpectedChar, quote_start);
+			}
+			let mut v = quote_start + 1;
+			loop {
+				if v >= b.len() {
+					return error(LiteralErrorKind::UnexpectedEof, b.len());
+				} else if b[v] == quote {
+					break;
+				} else if b[v] == b'<' {
+					return error(LiteralErrorKind::UnexpectedChar, v);
+				} else if b[v] == b'&' {
+					match entity(b, v) {
+						Ok(end) => v = end,
+						Err(e) => return Err(e)
+					}
+				} else {
+					v += 1;
+				}
+			}
+			j = v + 1;
+		}
+	}
+
+	if depth > 0 {
+		return error(LiteralErrorKind::UnclosedTag, stack[depth - 1].0 - 1);
+	}
+	Ok(())
+}
+
+/// Checks `input` like [`check`], panicking if it fails. Used by [`ssml_str!`](crate::ssml_str) to fail compilation.
+#[doc(hidden)]
+pub const fn assert_valid(input: &str, flavor: Flavor) {
+	if let Err(e) = check(input, flavor) {
+		panic!("{}", e.kind.message());
+	}
+}
+
+/// Validates & yields a string literal of SSML markup as a [`Meta`](crate::Meta), failing compilation if the markup
+/// isn't well-formed.
+///
+/// An optional [`Flavor`](crate::Flavor) (which must be usable in a `const` context) can be given before the literal
+/// to also reject elements the flavor doesn't support. See [`literal::check`](crate::literal::check) for the checks
+/// performed.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::{Flavor, ssml_str};
+///
+/// let greeting = ssml_str!(Flavor::AmazonPolly, r#"Hello, <amazon:effect name="whispered">world</amazon:effect>!"#);
+/// let doc = ssml::speak(None, [greeting]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().flavor(Flavor::AmazonPolly))?,
+/// 	r#"<speak>Hello, <amazon:effect name="whispered">world</amazon:effect>!</speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Malformed markup is caught while compiling:
+///
+/// ```compile_fail
+/// let greeting = ssml::ssml_str!("<emphasis>Hello, world!");
+/// ```
+///
+/// ```compile_fail
+/// let greeting = ssml::ssml_str!(ssml::Flavor::GoogleCloudTextToSpeech, "<mstts:express-as style=\"cheerful\">Hi!</mstts:express-as>");
+/// ```
+#[macro_export]
+macro_rules! ssml_str {
+	($input:literal) => {
+		$crate::ssml_str!($crate::Flavor::Generic, $input)
+	};
+	($flavor:expr, $input:literal) => {{
+		const _: () = $crate::literal::assert_valid($input, $flavor);
+		$crate::Meta::new($input)
+	}};
+}
+
+const fn starts_with(b: &[u8], at: usize, prefix: &[u8]) -> bool {
+	at + prefix.len() <= b.len() && range_eq(b, at, prefix, 0, prefix.len())
+}
+
+const fn range_eq(a: &[u8], a_start: usize, b: &[u8], b_start: usize, len: usize) -> bool {
+	let mut i = 0;
+	while i < len {
+		if a[a_start + i] != b[b_start + i] {
+			return false;
+		}
+		i += 1;
+	}
+	true
+}
+
+const fn find(b: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+	let mut i = from;
+	while i + needle.len() <= b.len() {
+		if range_eq(b, i, needle, 0, needle.len()) {
+			return Some(i);
+		}
+		i += 1;
+	}
+	None
+}
+
+const fn skip_whitespace(b: &[u8], mut i: usize) -> usize {
+	while i < b.len() && matches!(b[i], b' ' | b'\t' | b'\r' | b'\n') {
+		i += 1;
+	}
+	i
+}
+
+/// Returns the end of the name starting at `start`, or `start` if there is no name.
+const fn name(b: &[u8], start: usize) -> usize {
+	if start >= b.len() || !(b[start].is_ascii_alphabetic() || matches!(b[start], b'_' | b':') || b[start] >= 0x80) {
+		return start;
+	}
+	let mut i = start + 1;
+	while i < b.len() && (b[i].is_ascii_alphanumeric() || matches!(b[i], b'_' | b':' | b'-' | b'.') || b[i] >= 0x80) {
+		i += 1;
+	}
+	i
+}
+
+/// Checks the entity reference at `start`, returning the offset after it.
+pub(crate) const fn entity(b: &[u8], start: usize) -> Result<usize, LiteralError> {
+	let end = match find(b, start, b";") {
+		Some(end) => end,
+		None => {
+			return Err(LiteralError {
+				kind: LiteralErrorKind::UnknownEntity,
+				offset: start
+			});
+		}
+	};
+	let len = end - start - 1;
+	let valid = if len > 1 && b[start + 1] == b'#' {
+		let hex = b[start + 2] == b'x';
+		let mut i = start + if hex { 3 } else { 2 };
+		let mut valid = i < end;
+		while i < end {
+			valid &= if hex { b[i].is_ascii_hexdigit() } else { b[i].is_ascii_digit() };
+			i += 1;
+		}
+		valid
+	} else {
+		let mut valid = false;
+		let entities: [&[u8]; 5] = [b"lt", b"gt", b"amp", b"apos", b"quot"];
+		let mut i = 0;
+		while i < entities.len() {
+			valid |= entities[i].len() == len && range_eq(b, start + 1, entities[i], 0, len);
+			i += 1;
+		}
+		valid
+	};
+	if valid {
+		Ok(end + 1)
+	} else {
+		Err(LiteralError {
+			kind: LiteralErrorKind::UnknownEntity,
+			offset: start
+		})
+	}
+}
+
+/// Returns whether the element named by `b[start..start + len]` is supported by `flavor`.
+const fn supported(b: &[u8], start: usize, len: usize, flavor: Flavor) -> bool {
+	let mut i = 0;
+	while i < ElementKind::ALL.len() {
+		let kind = ElementKind::ALL[i];
+		let tag = kind.tag().as_bytes();
+		let matches = (tag.len() == len && range_eq(b, start, tag, 0, len)) || (matches!(kind, ElementKind::Word) && len == 1 && b[start] == b'w');
+		if matches {
+			return !matches!(flavor.supports(kind), Support::None);
+		}
+		i += 1;
+	}
+	true
+}
@@ -1,17 +1,33 @@
-use alloc::{borrow::Cow, string::ToString};
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
 use core::fmt::Write;
 
-use crate::{Flavor, Serialize, SerializeOptions, XmlWriter};
+use crate::{
+	Flavor, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs}
+};
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mark<'s> {
-	name: Cow<'s, str>
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	name: Cow<'s, str>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> Mark<'s> {
 	pub fn new(name: impl Into<Cow<'s, str>>) -> Self {
-		Self { name: name.into() }
+		Self {
+			name: name.into(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
 	}
 
 	pub fn name(&self) -> &str {
@@ -31,18 +47,24 @@ impl<'s> Mark<'s> {
 			name: match self.name {
 				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
 				Cow::Owned(b) => Cow::Owned(b)
-			}
+			},
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
 
 impl<'s> Serialize for Mark<'s> {
 	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
-		if options.flavor == Flavor::MicrosoftAzureCognitiveSpeechServices {
-			writer.element("bookmark", |writer| writer.attr("mark", &*self.name))
-		} else {
-			writer.element("mark", |writer| writer.attr("name", &*self.name))
-		}
+		let (tag, attr) = match options.flavor {
+			Flavor::MicrosoftAzureCognitiveSpeechServices => ("bookmark", "mark"),
+			_ => ("mark", "name")
+		};
+		writer.element(tag, |writer| {
+			writer.attr(attr, &*self.name)?;
+			attrs::write(writer, &self.extra_attrs)?;
+			Ok(())
+		})
 	}
 }
 
@@ -0,0 +1,160 @@
+use alloc::{
+	borrow::Cow,
+	string::{String, ToString},
+	vec::Vec
+};
+use core::fmt::Write;
+
+use crate::{Serialize, SerializeOptions, XmlWriter};
+
+/// The contents of a [`Metadata`] element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MetadataContent<'s> {
+	/// Raw XML, written into the document as-is (without escaping), e.g. an RDF description.
+	Raw(#[cfg_attr(feature = "serde", serde(borrow))] Cow<'s, str>),
+	/// [Dublin Core](https://www.dublincore.org/specifications/dublin-core/dces/) properties such as `title` or
+	/// `creator`, written as an RDF description of the document.
+	Properties(#[cfg_attr(feature = "serde", serde(borrow))] Vec<(Cow<'s, str>, Cow<'s, str>)>)
+}
+
+/// [`Metadata`] holds information about an SSML document - such as its title, author, or license - which isn't spoken.
+///
+/// Metadata is attached to a document with [`Speak::with_metadata`](crate::Speak::with_metadata) and is always written
+/// before any of the document's content, as required by the SSML specification.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::Metadata;
+///
+/// let metadata = Metadata::new().with_property("title", "Welcome message").with_property("creator", "Jane Doe");
+/// assert_eq!(
+/// 	metadata.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	concat!(
+/// 		r#"<metadata><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dc="http://purl.org/dc/elements/1.1/">"#,
+/// 		r#"<rdf:Description><dc:title>Welcome message</dc:title><dc:creator>Jane Doe</dc:creator></rdf:Description>"#,
+/// 		r#"</rdf:RDF></metadata>"#
+/// 	)
+/// );
+///
+/// let metadata = Metadata::raw(r#"<dc:rights xmlns:dc="http://purl.org/dc/elements/1.1/">CC0</dc:rights>"#);
+/// assert_eq!(
+/// 	metadata.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<metadata><dc:rights xmlns:dc="http://purl.org/dc/elements/1.1/">CC0</dc:rights></metadata>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metadata<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	content: MetadataContent<'s>
+}
+
+impl Default for Metadata<'_> {
+	fn default() -> Self {
+		Metadata::new()
+	}
+}
+
+impl<'s> Metadata<'s> {
+	/// Creates a new [`Metadata`] element with no properties.
+	pub fn new() -> Self {
+		Metadata {
+			content: MetadataContent::Properties(Vec::new())
+		}
+	}
+
+	/// Creates a new [`Metadata`] element containing raw XML, which is written into the document without escaping.
+	pub fn raw(xml: impl Into<Cow<'s, str>>) -> Self {
+		Metadata {
+			content: MetadataContent::Raw(xml.into())
+		}
+	}
+
+	/// Adds a [Dublin Core](https://www.dublincore.org/specifications/dublin-core/dces/) property, e.g. `title`.
+	///
+	/// If this element contains raw XML, the raw XML is discarded.
+	pub fn with_property(mut self, name: impl Into<Cow<'s, str>>, value: impl Into<Cow<'s, str>>) -> Self {
+		self.push_property(name, value);
+		self
+	}
+
+	/// Adds a [Dublin Core](https://www.dublincore.org/specifications/dublin-core/dces/) property, e.g. `title`.
+	///
+	/// If this element contains raw XML, the raw XML is discarded.
+	pub fn push_property(&mut self, name: impl Into<Cow<'s, str>>, value: impl Into<Cow<'s, str>>) {
+		let property = (name.into(), value.into());
+		match &mut self.content {
+			MetadataContent::Properties(properties) => properties.push(property),
+			content => *content = MetadataContent::Properties(alloc::vec![property])
+		}
+	}
+
+	/// Returns the value of the first property with the given name, if this element contains properties.
+	pub fn property(&self, name: &str) -> Option<&str> {
+		match &self.content {
+			MetadataContent::Properties(properties) => properties.iter().find(|(n, _)| n == name).map(|(_, value)| &**value),
+			MetadataContent::Raw(_) => None
+		}
+	}
+
+	pub fn content(&self) -> &MetadataContent<'s> {
+		&self.content
+	}
+
+	pub fn content_mut(&mut self) -> &mut MetadataContent<'s> {
+		&mut self.content
+	}
+
+	pub fn set_content(&mut self, content: MetadataContent<'s>) {
+		self.content = content;
+	}
+
+	pub fn to_owned(&self) -> Metadata<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Metadata<'static> {
+		fn owned(s: Cow<'_, str>) -> Cow<'static, str> {
+			match s {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			}
+		}
+
+		Metadata {
+			content: match self.content {
+				MetadataContent::Raw(xml) => MetadataContent::Raw(owned(xml)),
+				MetadataContent::Properties(properties) => {
+					MetadataContent::Properties(properties.into_iter().map(|(name, value)| (owned(name), owned(value))).collect())
+				}
+			}
+		}
+	}
+}
+
+impl<'s> Serialize for Metadata<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _options: &SerializeOptions) -> crate::Result<()> {
+		writer.without_plain_text(|writer| {
+			writer.element("metadata", |writer| match &self.content {
+				MetadataContent::Raw(xml) if xml.is_empty() => Ok(()),
+				MetadataContent::Raw(xml) => writer.raw(xml),
+				MetadataContent::Properties(properties) => writer.element("rdf:RDF", |writer| {
+					writer.attr("xmlns:rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#")?;
+					writer.attr("xmlns:dc", "http://purl.org/dc/elements/1.1/")?;
+					writer.element("rdf:Description", |writer| {
+						for (name, value) in properties {
+							let mut tag = String::from("dc:");
+							tag.push_str(name);
+							writer.element(tag, |writer| writer.text(value))?;
+						}
+						Ok(())
+					})
+				})
+			})
+		})
+	}
+}
@@ -0,0 +1,160 @@
+//! A fixed-shape document type for latency-critical paths, via [`MicroSpeak`].
+//!
+//! Building a [`Speak`] tree allocates for every element, and serializing it walks the tree with all of the
+//! configurability of [`SerializeOptions`](crate::SerializeOptions). When a hot path always produces the same tiny
+//! shape of document - an optional voice containing a handful of text runs & breaks - a [`MicroSpeak`] borrows its
+//! contents, stores its parts inline in a const-sized array, and writes them with a fixed template which doesn't
+//! allocate at all.
+//!
+//! ```
+//! # use ssml::Serialize;
+//! # fn main() -> ssml::Result<()> {
+//! use ssml::{
+//! 	Flavor,
+//! 	micro::{MicroPart, MicroSpeak}
+//! };
+//!
+//! let doc = MicroSpeak::new(Some("en-US"), Some("en-US-JennyNeural"), [MicroPart::Text("Your code is 1234."), MicroPart::Break("500ms".parse()?)]);
+//!
+//! let mut out = String::new();
+//! doc.serialize(&mut out, Flavor::MicrosoftAzureCognitiveSpeechServices)?;
+//! assert_eq!(
+//! 	out,
+//! 	concat!(
+//! 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US" xmlns:mstts="http://www.w3.org/2001/mstts">"#,
+//! 		r#"<voice name="en-US-JennyNeural">Your code is 1234.<break time="+500ms"/></voice></speak>"#
+//! 	)
+//! );
+//!
+//! // the output is identical to that of the equivalent tree
+//! let options = ssml::SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices);
+//! assert_eq!(doc.to_speak().serialize_to_string(&options)?, out);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{Break, ElementKind, Error, Flavor, Speak, Support, TimeDesignation, util};
+
+/// A part of a [`MicroSpeak`] document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MicroPart<'a> {
+	/// A run of text. Adjacent text parts are separated by a space, as with [`Text`](crate::Text) elements.
+	Text(&'a str),
+	/// A pause of a given length.
+	Break(TimeDesignation)
+}
+
+/// A tiny SSML document of `N` [parts](MicroPart), optionally spoken by a single voice, which serializes without
+/// allocating. See the [module documentation](self).
+///
+/// Only the [`Flavor`] affects serialization; other [`SerializeOptions`](crate::SerializeOptions) (pretty printing,
+/// text filters, custom escaping, ...) aren't supported. Use [`MicroSpeak::to_speak`] for those.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MicroSpeak<'a, const N: usize> {
+	lang: Option<&'a str>,
+	voice: Option<&'a str>,
+	parts: [MicroPart<'a>; N]
+}
+
+impl<'a, const N: usize> MicroSpeak<'a, N> {
+	pub const fn new(lang: Option<&'a str>, voice: Option<&'a str>, parts: [MicroPart<'a>; N]) -> Self {
+		Self { lang, voice, parts }
+	}
+
+	pub fn lang(&self) -> Option<&'a str> {
+		self.lang
+	}
+
+	pub fn voice(&self) -> Option<&'a str> {
+		self.voice
+	}
+
+	pub fn parts(&self) -> &[MicroPart<'a>; N] {
+		&self.parts
+	}
+
+	pub fn parts_mut(&mut self) -> &mut [MicroPart<'a>; N] {
+		&mut self.parts
+	}
+
+	/// Writes this document as SSML of the given `flavor` to `out`.
+	///
+	/// Fails with [`Error::UnsupportedElement`] if the document has a voice and `flavor` doesn't support voices.
+	pub fn serialize<W: Write>(&self, out: &mut W, flavor: Flavor) -> crate::Result<()> {
+		if self.voice.is_some() && flavor.supports(ElementKind::Voice) == Support::None {
+			return Err(Error::UnsupportedElement(ElementKind::Voice.tag(), flavor));
+		}
+
+		out.write_str("<speak")?;
+		if matches!(flavor, Flavor::Generic | Flavor::MicrosoftAzureCognitiveSpeechServices) {
+			out.write_str(r#" version="1.0" xmlns="http://www.w3.org/2001/10/synthesis""#)?;
+		}
+		if let Some(lang) = self.lang {
+			out.write_str(" xml:lang=\"")?;
+			util::escape(out, lang)?;
+			out.write_char('"')?;
+		}
+		if flavor == Flavor::MicrosoftAzureCognitiveSpeechServices {
+			out.write_str(r#" xmlns:mstts="http://www.w3.org/2001/mstts""#)?;
+		}
+
+		if N == 0 && self.voice.is_none() {
+			out.write_str("/>")?;
+			return Ok(());
+		}
+		out.write_char('>')?;
+		if let Some(voice) = self.voice {
+			out.write_str("<voice name=\"")?;
+			util::escape(out, voice)?;
+			out.write_str(if N == 0 { "\"/>" } else { "\">" })?;
+		}
+
+		let mut after_text = false;
+		for part in &self.parts {
+			match part {
+				MicroPart::Text(text) => {
+					if after_text {
+						out.write_char(' ')?;
+					}
+					util::escape(out, text)?;
+				}
+				MicroPart::Break(time) => write!(out, "<break time=\"{time}\"/>")?
+			}
+			after_text = matches!(part, MicroPart::Text(_));
+		}
+
+		if self.voice.is_some() && N > 0 {
+			out.write_str("</voice>")?;
+		}
+		out.write_str("</speak>")?;
+		Ok(())
+	}
+
+	/// Serializes this document to a string of SSML of the given `flavor`.
+	pub fn serialize_to_string(&self, flavor: Flavor) -> crate::Result<String> {
+		let len = self
+			.parts
+			.iter()
+			.map(|part| if let MicroPart::Text(text) = part { text.len() + 1 } else { 32 })
+			.sum::<usize>();
+		let mut out = String::with_capacity(len + 160);
+		self.serialize(&mut out, flavor)?;
+		Ok(out)
+	}
+
+	/// Converts this document into an equivalent [`Speak`] tree, e.g. to serialize it with other
+	/// [`SerializeOptions`](crate::SerializeOptions) or to transform it further.
+	pub fn to_speak(&self) -> Speak<'a> {
+		let parts = self.parts.iter().map(|part| match part {
+			MicroPart::Text(text) => crate::Element::from(*text),
+			MicroPart::Break(time) => Break::new_with_time(time.clone()).into()
+		});
+		match self.voice {
+			Some(voice) => crate::speak(self.lang, [crate::voice(voice, parts)]),
+			None => crate::speak(self.lang, parts)
+		}
+	}
+}
@@ -0,0 +1,193 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+	Serialize, SerializeOptions, TimeDesignation, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs}
+};
+
+/// Plays an audio file in the background of the whole document, in ACSS/MSTTS.
+///
+/// The background audio starts with the first voice and loops or is cut off to match the length of the speech. Only
+/// one background audio element is allowed per document, and it must be a direct child of the
+/// [`Speak`](crate::Speak) root; [validation](crate::validate) for
+/// [`Flavor::MicrosoftAzureCognitiveSpeechServices`](crate::Flavor::MicrosoftAzureCognitiveSpeechServices) enforces
+/// both.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::mstts;
+///
+/// let doc = ssml::speak(
+/// 	None,
+/// 	[
+/// 		ssml::Element::from(
+/// 			mstts::background_audio("https://example.com/rain.wav")
+/// 				.with_volume(0.7)
+/// 				.with_fade_in("3s")
+/// 		),
+/// 		ssml::voice("en-US-JennyNeural", ["The forecast calls for rain."]).into()
+/// 	]
+/// );
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+/// 	concat!(
+/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:mstts="http://www.w3.org/2001/mstts">"#,
+/// 		r#"<mstts:backgroundaudio src="https://example.com/rain.wav" volume="0.7" fadein="3000"/>"#,
+/// 		r#"<voice name="en-US-JennyNeural">The forecast calls for rain.</voice></speak>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ```
+/// use ssml::{Flavor, mstts, validate::Lint};
+///
+/// let doc = ssml::speak(
+/// 	None,
+/// 	[
+/// 		ssml::Element::from(mstts::background_audio("https://example.com/rain.wav")),
+/// 		mstts::background_audio("https://example.com/thunder.wav").into(),
+/// 		ssml::voice(
+/// 			"en-US-JennyNeural",
+/// 			[ssml::Element::from(mstts::background_audio("https://example.com/wind.wav"))]
+/// 		)
+/// 		.into()
+/// 	]
+/// );
+/// let lints: Vec<_> = ssml::validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices)
+/// 	.into_iter()
+/// 	.map(|d| (d.lint, d.path))
+/// 	.collect();
+/// assert_eq!(lints, [(Lint::DuplicateBackgroundAudio, vec![1]), (Lint::MisplacedBackgroundAudio, vec![2, 0])]);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BackgroundAudio<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	src: Cow<'s, str>,
+	volume: Option<f32>,
+	fade_in: Option<TimeDesignation>,
+	fade_out: Option<TimeDesignation>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> BackgroundAudio<'s> {
+	/// Creates a new [`BackgroundAudio`] element with an audio source URI.
+	pub fn new(src: impl Into<Cow<'s, str>>) -> Self {
+		Self {
+			src: src.into(),
+			volume: None,
+			fade_in: None,
+			fade_out: None,
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn src(&self) -> &str {
+		&self.src
+	}
+
+	pub fn set_src(&mut self, src: impl Into<Cow<'s, str>>) {
+		self.src = src.into();
+	}
+
+	/// Sets the volume of the background audio, from `0` (silent) to `100`. ACSS defaults to `1`.
+	pub fn with_volume(mut self, volume: f32) -> Self {
+		self.volume = Some(volume);
+		self
+	}
+
+	pub fn volume(&self) -> Option<f32> {
+		self.volume
+	}
+
+	pub fn set_volume(&mut self, volume: Option<f32>) {
+		self.volume = volume;
+	}
+
+	/// Sets the duration over which the background audio fades in, up to 10 seconds.
+	pub fn with_fade_in(mut self, duration: impl Into<TimeDesignation>) -> Self {
+		self.fade_in = Some(duration.into());
+		self
+	}
+
+	pub fn fade_in(&self) -> Option<&TimeDesignation> {
+		self.fade_in.as_ref()
+	}
+
+	pub fn set_fade_in(&mut self, duration: Option<TimeDesignation>) {
+		self.fade_in = duration;
+	}
+
+	/// Sets the duration over which the background audio fades out, up to 10 seconds.
+	pub fn with_fade_out(mut self, duration: impl Into<TimeDesignation>) -> Self {
+		self.fade_out = Some(duration.into());
+		self
+	}
+
+	pub fn fade_out(&self) -> Option<&TimeDesignation> {
+		self.fade_out.as_ref()
+	}
+
+	pub fn set_fade_out(&mut self, duration: Option<TimeDesignation>) {
+		self.fade_out = duration;
+	}
+
+	pub fn to_owned(&self) -> BackgroundAudio<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> BackgroundAudio<'static> {
+		BackgroundAudio {
+			src: match self.src {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			},
+			volume: self.volume,
+			fade_in: self.fade_in,
+			fade_out: self.fade_out,
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`](crate::Element).
+	pub fn into_el(self) -> crate::Element<'s> {
+		crate::Element::FlavorMSTTS(super::Element::BackgroundAudio(self))
+	}
+}
+
+impl<'s> From<BackgroundAudio<'s>> for crate::Element<'s> {
+	fn from(value: BackgroundAudio<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for BackgroundAudio<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
+		writer.element("mstts:backgroundaudio", |writer| {
+			writer.attr("src", &*self.src)?;
+			writer.attr_opt("volume", self.volume)?;
+			// fades are given in milliseconds, without a unit
+			writer.attr_opt("fadein", self.fade_in.as_ref().map(TimeDesignation::to_millis))?;
+			writer.attr_opt("fadeout", self.fade_out.as_ref().map(TimeDesignation::to_millis))?;
+			attrs::write(writer, &self.extra_attrs)
+		})
+	}
+}
+
+/// Creates a new [`BackgroundAudio`] element with an audio source URI.
+pub fn background_audio<'s>(src: impl Into<Cow<'s, str>>) -> BackgroundAudio<'s> {
+	BackgroundAudio::new(src)
+}
@@ -0,0 +1,209 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use super::{AudioDuration, BackgroundAudio, Silence, Viseme, express::Expression};
+use crate::{
+	Audio, Break, Element, EmphasisLevel, Lexicon, Phoneme, ProsodyControl, SayAs, Speak, Sub, Voice, VoiceConfig, emphasis, mark, paragraph, prosody, sentence
+};
+
+/// Builds a document for ACSS which follows its structural rules by construction: all content is spoken by a voice,
+/// bookmarks, lexicons & voice settings like [`Silence`] are placed directly within a voice, and there is at most one
+/// [`BackgroundAudio`], which is placed at the top level.
+///
+/// Elements passed in whole, like a [`SayAs`] or [`Audio`], are taken as-is.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::mstts::{self, AcssContent, AcssDocumentBuilder, AcssVoice, SilenceType};
+///
+/// let doc = AcssDocumentBuilder::new("en-US")
+/// 	.with_background_audio(mstts::background_audio("https://example.com/music.mp3").with_volume(20.))
+/// 	.with_voice(
+/// 		AcssVoice::new("en-US-JennyNeural")
+/// 			.with_silence(mstts::silence(SilenceType::Leading, "100ms".parse::<ssml::TimeDesignation>()?))
+/// 			.with_content(AcssContent::new().with_text("Welcome back.").with_bookmark("greeted"))
+/// 	)
+/// 	.with_voice(AcssVoice::new("en-US-GuyNeural").with_content(AcssContent::new().with_express(mstts::express::Cheerful, AcssContent::new().with_text("Ready, set, go!"))))
+/// 	.build();
+///
+/// assert!(ssml::validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices).is_empty());
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+/// 	concat!(
+/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US" xmlns:mstts="http://www.w3.org/2001/mstts">"#,
+/// 		r#"<mstts:backgroundaudio src="https://example.com/music.mp3" volume="20"/>"#,
+/// 		r#"<voice name="en-US-JennyNeural"><mstts:silence type="Leading" value="+100ms"/>Welcome back.<bookmark mark="greeted"/></voice>"#,
+/// 		r#"<voice name="en-US-GuyNeural"><mstts:express-as style="cheerful" styledegree="1">Ready, set, go!</mstts:express-as></voice></speak>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AcssDocumentBuilder<'s> {
+	lang: Cow<'s, str>,
+	background_audio: Option<BackgroundAudio<'s>>,
+	voices: Vec<Voice<'s>>
+}
+
+impl<'s> AcssDocumentBuilder<'s> {
+	/// Creates a builder for a document in the given language (e.g. `en-US`), which ACSS requires.
+	pub fn new(lang: impl Into<Cow<'s, str>>) -> Self {
+		AcssDocumentBuilder {
+			lang: lang.into(),
+			background_audio: None,
+			voices: Vec::new()
+		}
+	}
+
+	/// Sets the audio played in the background of the document, replacing any previously set.
+	pub fn with_background_audio(mut self, audio: BackgroundAudio<'s>) -> Self {
+		self.background_audio = Some(audio);
+		self
+	}
+
+	/// Appends a section spoken by a voice.
+	pub fn with_voice(mut self, voice: AcssVoice<'s>) -> Self {
+		self.voices.push(voice.build());
+		self
+	}
+
+	pub fn build(self) -> Speak<'s> {
+		let mut doc = Speak::new(None, self.background_audio.map(BackgroundAudio::into_el));
+		doc.set_lang(self.lang);
+		doc.extend(self.voices);
+		doc
+	}
+}
+
+/// A section of an [`AcssDocumentBuilder`] spoken by a voice.
+#[derive(Debug, Clone)]
+pub struct AcssVoice<'s> {
+	config: VoiceConfig<'s>,
+	lexicons: Vec<Element<'s>>,
+	settings: Vec<Element<'s>>,
+	content: Vec<Element<'s>>
+}
+
+impl<'s> AcssVoice<'s> {
+	pub fn new(config: impl Into<VoiceConfig<'s>>) -> Self {
+		AcssVoice {
+			config: config.into(),
+			lexicons: Vec::new(),
+			settings: Vec::new(),
+			content: Vec::new()
+		}
+	}
+
+	/// Adds a lexicon used by this voice. Lexicons are placed before any other content.
+	pub fn with_lexicon(mut self, lexicon: Lexicon<'s>) -> Self {
+		self.lexicons.push(lexicon.into());
+		self
+	}
+
+	/// Adds silence around this voice's sentences. Voice settings are placed after lexicons, before spoken content.
+	pub fn with_silence(mut self, silence: Silence<'s>) -> Self {
+		self.settings.push(silence.into());
+		self
+	}
+
+	/// Requests viseme events for this voice's speech.
+	pub fn with_viseme(mut self, viseme: Viseme<'s>) -> Self {
+		self.settings.push(viseme.into());
+		self
+	}
+
+	/// Sets the duration of this voice's speech.
+	pub fn with_audio_duration(mut self, duration: AudioDuration<'s>) -> Self {
+		self.settings.push(duration.into());
+		self
+	}
+
+	/// Appends content spoken by this voice.
+	pub fn with_content(mut self, content: AcssContent<'s>) -> Self {
+		self.content.extend(content.0);
+		self
+	}
+
+	fn build(self) -> Voice<'s> {
+		Voice::new(self.config, self.lexicons.into_iter().chain(self.settings).chain(self.content))
+	}
+}
+
+/// Content spoken within an [`AcssVoice`]. Content can't contain voices or voice settings, so it can be nested freely.
+#[derive(Debug, Clone, Default)]
+pub struct AcssContent<'s>(Vec<Element<'s>>);
+
+impl<'s> AcssContent<'s> {
+	pub fn new() -> Self {
+		AcssContent(Vec::new())
+	}
+
+	pub fn with_text(mut self, text: impl Into<Cow<'s, str>>) -> Self {
+		self.0.push(Element::Text(text.into().into()));
+		self
+	}
+
+	/// Adds a bookmark, which ACSS reports as an event when it's reached during synthesis.
+	pub fn with_bookmark(mut self, name: impl Into<Cow<'s, str>>) -> Self {
+		self.0.push(mark(name).into());
+		self
+	}
+
+	pub fn with_break(mut self, value: impl Into<Break>) -> Self {
+		self.0.push(value.into().into());
+		self
+	}
+
+	pub fn with_say_as(mut self, say_as: SayAs<'s>) -> Self {
+		self.0.push(say_as.into());
+		self
+	}
+
+	pub fn with_phoneme(mut self, phoneme: Phoneme<'s>) -> Self {
+		self.0.push(phoneme.into());
+		self
+	}
+
+	pub fn with_sub(mut self, sub: Sub<'s>) -> Self {
+		self.0.push(sub.into());
+		self
+	}
+
+	pub fn with_audio(mut self, audio: Audio<'s>) -> Self {
+		self.0.push(audio.into());
+		self
+	}
+
+	/// Adds content read with the given speaking style.
+	pub fn with_express(mut self, expression: impl Into<Expression>, content: AcssContent<'s>) -> Self {
+		self.0.push(super::express(expression, content.0).into());
+		self
+	}
+
+	pub fn with_prosody(mut self, control: impl Into<ProsodyControl>, content: AcssContent<'s>) -> Self {
+		self.0.push(prosody(control, content.0).into());
+		self
+	}
+
+	pub fn with_emphasis(mut self, level: EmphasisLevel, content: AcssContent<'s>) -> Self {
+		self.0.push(emphasis(level, content.0).into());
+		self
+	}
+
+	/// Adds content spoken in another language, e.g. by a multilingual voice.
+	pub fn with_lang(mut self, lang: impl Into<Cow<'s, str>>, content: AcssContent<'s>) -> Self {
+		self.0.push(crate::lang(lang, content.0).into());
+		self
+	}
+
+	pub fn with_paragraph(mut self, content: AcssContent<'s>) -> Self {
+		self.0.push(paragraph(content.0).into());
+		self
+	}
+
+	pub fn with_sentence(mut self, content: AcssContent<'s>) -> Self {
+		self.0.push(sentence(content.0).into());
+		self
+	}
+}
@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::{
+	Serialize, SerializeOptions, TimeDesignation, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs}
+};
+
+/// Sets the total duration of the speech of a voice, in ACSS/MSTTS.
+///
+/// The speech is sped up or slowed down to fit the given duration, which should be between 0.5 and 2 times its
+/// natural duration. Audio duration elements apply to the whole [`Voice`](crate::Voice) they're placed in, and must be
+/// one of its direct children.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::mstts;
+///
+/// let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", [
+/// 	ssml::Element::from(mstts::audio_duration("10s")),
+/// 	"This sentence will take exactly ten seconds to say.".into()
+/// ])]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+/// 	concat!(
+/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:mstts="http://www.w3.org/2001/mstts">"#,
+/// 		r#"<voice name="en-US-JennyNeural"><mstts:audioduration value="+10000ms"/>This sentence will take exactly ten seconds to say.</voice></speak>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AudioDuration<'s> {
+	value: TimeDesignation,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> AudioDuration<'s> {
+	pub fn new(value: impl Into<TimeDesignation>) -> Self {
+		Self {
+			value: value.into(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn value(&self) -> &TimeDesignation {
+		&self.value
+	}
+
+	pub fn set_value(&mut self, value: impl Into<TimeDesignation>) {
+		self.value = value.into();
+	}
+
+	pub fn to_owned(&self) -> AudioDuration<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> AudioDuration<'static> {
+		AudioDuration {
+			value: self.value,
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`](crate::Element).
+	pub fn into_el(self) -> crate::Element<'s> {
+		crate::Element::FlavorMSTTS(super::Element::AudioDuration(self))
+	}
+}
+
+impl<'s> From<AudioDuration<'s>> for crate::Element<'s> {
+	fn from(value: AudioDuration<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for AudioDuration<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
+		writer.element("mstts:audioduration", |writer| {
+			writer.attr("value", &self.value)?;
+			attrs::write(writer, &self.extra_attrs)
+		})
+	}
+}
+
+/// Creates a new [`AudioDuration`] element.
+pub fn audio_duration<'s>(value: impl Into<TimeDesignation>) -> AudioDuration<'s> {
+	AudioDuration::new(value)
+}
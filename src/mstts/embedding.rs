@@ -0,0 +1,136 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// Speaks a section of an SSML document with an Azure Personal Voice, in ACSS/MSTTS.
+///
+/// A personal voice is selected by the ID of the speaker profile created for it, and must be placed within a
+/// [`Voice`](crate::Voice) naming one of the personal voice base models, e.g. `DragonLatestNeural`. See
+/// [the Azure docs][ms] for more information.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::mstts;
+///
+/// let doc = ssml::speak(Some("en-US"), [ssml::voice("DragonLatestNeural", [mstts::tts_embedding(
+/// 	"5f3d2a6e-4b1c-4e8f-9a7d-0c6b1e2f3a4b",
+/// 	["I am happy to hear that you find me amazing."]
+/// )])]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+/// 	concat!(
+/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US" xmlns:mstts="http://www.w3.org/2001/mstts">"#,
+/// 		r#"<voice name="DragonLatestNeural"><mstts:ttsembedding speakerProfileId="5f3d2a6e-4b1c-4e8f-9a7d-0c6b1e2f3a4b">"#,
+/// 		r#"I am happy to hear that you find me amazing.</mstts:ttsembedding></voice></speak>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [ms]: https://learn.microsoft.com/en-us/azure/ai-services/speech-service/personal-voice-how-to-use
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TtsEmbedding<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	speaker_profile_id: Cow<'s, str>,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> TtsEmbedding<'s> {
+	/// Creates a new [`TtsEmbedding`] section, speaking its elements with the personal voice of the given speaker
+	/// profile.
+	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(speaker_profile_id: impl Into<Cow<'s, str>>, elements: I) -> Self {
+		Self {
+			speaker_profile_id: speaker_profile_id.into(),
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn speaker_profile_id(&self) -> &str {
+		&self.speaker_profile_id
+	}
+
+	pub fn set_speaker_profile_id(&mut self, speaker_profile_id: impl Into<Cow<'s, str>>) {
+		self.speaker_profile_id = speaker_profile_id.into();
+	}
+
+	/// Extend this `ttsembedding` section with an additional element.
+	pub fn push(&mut self, element: impl Into<Element<'s>>) {
+		self.children.push(element.into());
+	}
+
+	/// Extend this `ttsembedding` section with additional elements.
+	pub fn extend<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&mut self, elements: I) {
+		self.children.extend(elements.into_iter().map(|f| f.into()));
+	}
+
+	/// Returns a reference to the elements contained within this `ttsembedding` section.
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	/// Returns a mutable reference to the elements contained within this `ttsembedding` section.
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
+	pub fn to_owned(&self) -> TtsEmbedding<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> TtsEmbedding<'static> {
+		TtsEmbedding {
+			speaker_profile_id: match self.speaker_profile_id {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			},
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`].
+	pub fn into_el(self) -> Element<'s> {
+		Element::FlavorMSTTS(super::Element::TtsEmbedding(self))
+	}
+}
+
+impl<'s> From<TtsEmbedding<'s>> for crate::Element<'s> {
+	fn from(value: TtsEmbedding<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for TtsEmbedding<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.element("mstts:ttsembedding", |writer| {
+			writer.attr("speakerProfileId", &*self.speaker_profile_id)?;
+			attrs::write(writer, &self.extra_attrs)?;
+			util::serialize_elements(writer, &self.children, options)
+		})
+	}
+}
+
+/// Creates a new [`TtsEmbedding`] section, speaking its elements with the personal voice of the given speaker profile.
+pub fn tts_embedding<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(speaker_profile_id: impl Into<Cow<'s, str>>, elements: I) -> TtsEmbedding<'s> {
+	TtsEmbedding::new(speaker_profile_id, elements)
+}
@@ -1,7 +1,12 @@
 use alloc::{borrow::Cow, vec::Vec};
-use core::fmt::Write;
+use core::fmt::{self, Display, Write};
 
-use crate::{Element, Serialize, SerializeOptions, XmlWriter, util};
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
 
 /// A generic expression for use in [`Express`]. Contains the name of the expression and the intensity/degree (default
 /// `1.0`).
@@ -140,6 +145,55 @@ define_expressions! {
 	Unfriendly => "unfriendly"
 }
 
+/// A role for an [`Express`] section to play, in ACSS/MSTTS.
+///
+/// Role-play voices can imitate a different age & gender while keeping the same voice name; see
+/// [`Express::with_role`]. Only some voices support role-play. See [the Azure docs][ms] for more information.
+///
+/// [ms]: https://learn.microsoft.com/en-us/azure/ai-services/speech-service/language-support?tabs=tts#voice-styles-and-roles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExpressRole {
+	/// The voice imitates a girl. (`Girl`)
+	Girl,
+	/// The voice imitates a boy. (`Boy`)
+	Boy,
+	/// The voice imitates a young adult female. (`YoungAdultFemale`)
+	YoungAdultFemale,
+	/// The voice imitates a young adult male. (`YoungAdultMale`)
+	YoungAdultMale,
+	/// The voice imitates an older adult female. (`OlderAdultFemale`)
+	OlderAdultFemale,
+	/// The voice imitates an older adult male. (`OlderAdultMale`)
+	OlderAdultMale,
+	/// The voice imitates a senior female. (`SeniorFemale`)
+	SeniorFemale,
+	/// The voice imitates a senior male. (`SeniorMale`)
+	SeniorMale
+}
+
+impl ExpressRole {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ExpressRole::Girl => "Girl",
+			ExpressRole::Boy => "Boy",
+			ExpressRole::YoungAdultFemale => "YoungAdultFemale",
+			ExpressRole::YoungAdultMale => "YoungAdultMale",
+			ExpressRole::OlderAdultFemale => "OlderAdultFemale",
+			ExpressRole::OlderAdultMale => "OlderAdultMale",
+			ExpressRole::SeniorFemale => "SeniorFemale",
+			ExpressRole::SeniorMale => "SeniorMale"
+		}
+	}
+}
+
+impl Display for ExpressRole {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
 /// Change the speaking style for part of an SSML document, in ACSS/MSTTS.
 ///
 /// Not all neural voices support all expressions. See [the Azure docs][ms] for more information on which voices support
@@ -150,7 +204,16 @@ define_expressions! {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Express<'s> {
 	expression: Expression,
-	children: Vec<Element<'s>>
+	role: Option<ExpressRole>,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> Express<'s> {
@@ -185,7 +248,10 @@ impl<'s> Express<'s> {
 	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(expression: impl Into<Expression>, elements: I) -> Self {
 		Self {
 			expression: expression.into(),
-			children: elements.into_iter().map(|f| f.into()).collect()
+			role: None,
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
 		}
 	}
 
@@ -197,6 +263,44 @@ impl<'s> Express<'s> {
 		self.expression = expression.into();
 	}
 
+	/// Sets the role played by the voice in this section.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// use ssml::mstts::{self, ExpressRole};
+	///
+	/// # fn main() -> ssml::Result<()> {
+	/// let doc = ssml::speak(
+	/// 	Some("zh-CN"),
+	/// 	[ssml::voice(
+	/// 		"zh-CN-XiaomoNeural",
+	/// 		[mstts::express(mstts::express::Cheerful, ["爸爸，我们去公园吧！"]).with_role(ExpressRole::Girl)]
+	/// 	)]
+	/// );
+	///
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().flavor(ssml::Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+	/// 	concat!(
+	/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="zh-CN" xmlns:mstts="http://www.w3.org/2001/mstts">"#,
+	/// 		r#"<voice name="zh-CN-XiaomoNeural"><mstts:express-as style="cheerful" styledegree="1" role="Girl">爸爸，我们去公园吧！</mstts:express-as></voice></speak>"#
+	/// 	)
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_role(mut self, role: ExpressRole) -> Self {
+		self.role = Some(role);
+		self
+	}
+
+	pub fn role(&self) -> Option<ExpressRole> {
+		self.role
+	}
+
+	pub fn set_role(&mut self, role: Option<ExpressRole>) {
+		self.role = role;
+	}
+
 	/// Extend this `express-as` section with an additional element.
 	pub fn push(&mut self, element: impl Into<Element<'s>>) {
 		self.children.push(element.into());
@@ -217,6 +321,20 @@ impl<'s> Express<'s> {
 		&mut self.children
 	}
 
+	pub fn to_owned(&self) -> Express<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Express<'static> {
+		Express {
+			expression: self.expression,
+			role: self.role,
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
 	/// Converts this element into an [`Element`].
 	pub fn into_el(self) -> Element<'s> {
 		Element::FlavorMSTTS(super::Element::Express(self))
@@ -234,6 +352,8 @@ impl<'s> Serialize for Express<'s> {
 		writer.element("mstts:express-as", |writer| {
 			writer.attr("style", &*self.expression.0)?;
 			writer.attr("styledegree", self.expression.1)?;
+			writer.attr_opt("role", self.role.as_ref().map(ExpressRole::as_str))?;
+			attrs::write(writer, &self.extra_attrs)?;
 			util::serialize_elements(writer, &self.children, options)
 		})
 	}
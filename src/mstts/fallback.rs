@@ -0,0 +1,182 @@
+use alloc::{borrow::Cow, collections::BTreeMap, string::ToString, vec::Vec};
+
+use super::express::Expression;
+use crate::{
+	Element, Speak,
+	transform::{BudgetExceeded, PassBudget}
+};
+
+/// Substitutes for each built-in style, from nearest to furthest.
+const SUBSTITUTES: &[(&str, &[&str])] = &[
+	("advertisement_upbeat", &["excited", "cheerful"]),
+	("affectionate", &["friendly", "gentle"]),
+	("angry", &["disgruntled", "unfriendly"]),
+	("assistant", &["customerservice", "friendly", "chat"]),
+	("calm", &["gentle", "narration-relaxed"]),
+	("chat", &["friendly", "assistant"]),
+	("cheerful", &["excited", "friendly"]),
+	("customerservice", &["assistant", "friendly"]),
+	("depressed", &["sad"]),
+	("disgruntled", &["unfriendly", "angry"]),
+	("documentary-narration", &["narration-professional", "narration-relaxed"]),
+	("embarrassed", &["gentle"]),
+	("empathetic", &["gentle", "friendly"]),
+	("envious", &["disgruntled"]),
+	("excited", &["cheerful"]),
+	("fearful", &["terrified", "sad"]),
+	("friendly", &["cheerful", "gentle"]),
+	("gentle", &["calm", "friendly"]),
+	("hopeful", &["cheerful", "friendly"]),
+	("lyrical", &["poetry-reading"]),
+	("narration-professional", &["newscast", "narration-relaxed"]),
+	("narration-relaxed", &["narration-professional", "calm", "gentle"]),
+	("newscast", &["newscast-formal", "newscast-casual", "narration-professional"]),
+	("newscast-casual", &["newscast", "narration-professional"]),
+	("newscast-formal", &["newscast", "narration-professional"]),
+	("poetry-reading", &["lyrical", "narration-relaxed"]),
+	("sad", &["depressed", "gentle"]),
+	("serious", &["calm"]),
+	("shouting", &["excited", "angry"]),
+	("sports_commentary", &["sports_commentary_excited", "newscast-casual"]),
+	("sports_commentary_excited", &["sports_commentary", "excited"]),
+	("terrified", &["fearful"]),
+	("unfriendly", &["disgruntled", "serious"]),
+	("whispering", &["gentle", "calm"])
+];
+
+/// Configures [`fallback_styles`]: which styles each voice supports, and which styles substitute an unsupported one.
+///
+/// The styles available to a voice can differ between Azure regions, so the catalog should be built from the voice
+/// list of the region the document is synthesized in.
+#[derive(Debug, Clone)]
+pub struct StyleFallback {
+	voices: BTreeMap<Cow<'static, str>, Vec<Cow<'static, str>>>,
+	substitutes: BTreeMap<Cow<'static, str>, Vec<Cow<'static, str>>>
+}
+
+impl Default for StyleFallback {
+	fn default() -> Self {
+		StyleFallback::new()
+	}
+}
+
+impl StyleFallback {
+	/// Creates a fallback configuration with no known voices, and built-in substitutes for the styles of
+	/// [`express`](mod@super::express), e.g. `whispering` → `gentle`.
+	pub fn new() -> Self {
+		StyleFallback {
+			voices: BTreeMap::new(),
+			substitutes: SUBSTITUTES
+				.iter()
+				.map(|(style, substitutes)| (Cow::Borrowed(*style), substitutes.iter().map(|s| Cow::Borrowed(*s)).collect()))
+				.collect()
+		}
+	}
+
+	/// Declares the styles supported by the voice `name`. Styles within voices which aren't declared are left as is.
+	pub fn with_voice<S: Into<Cow<'static, str>>>(mut self, name: impl Into<Cow<'static, str>>, styles: impl IntoIterator<Item = S>) -> Self {
+		self.voices.insert(name.into(), styles.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Sets the styles which substitute `style`, from nearest to furthest, replacing any built-in substitutes.
+	pub fn with_substitutes<S: Into<Cow<'static, str>>>(mut self, style: impl Into<Cow<'static, str>>, substitutes: impl IntoIterator<Item = S>) -> Self {
+		self.substitutes.insert(style.into(), substitutes.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Returns the style which should be used in place of `style` for the voice `voice`: `style` itself if it is
+	/// supported (or the voice isn't known), otherwise its nearest supported substitute, or `None` if no substitute is
+	/// supported.
+	pub fn resolve<'a>(&'a self, voice: &str, style: &'a str) -> Option<&'a str> {
+		let Some(supported) = self.voices.get(voice) else {
+			return Some(style);
+		};
+		let is_supported = |style: &str| supported.iter().any(|s| s.eq_ignore_ascii_case(style));
+		if is_supported(style) {
+			return Some(style);
+		}
+		self.substitutes
+			.iter()
+			.find(|(s, _)| s.eq_ignore_ascii_case(style))?
+			.1
+			.iter()
+			.map(|s| &**s)
+			.find(|s| is_supported(s))
+	}
+}
+
+/// Substitutes the style of each [`Express`](super::Express) section which isn't supported by its enclosing voice with
+/// the nearest supported style, according to `fallback`. Returns the number of sections changed.
+///
+/// ACSS silently ignores unsupported styles, so without this, content in an unsupported style is spoken in the voice's
+/// default style. Sections with no supported substitute, or whose voice isn't declared in `fallback`, are left as is;
+/// the degree of a substituted style is kept. [Protected](crate::annotations::Annotated::protected) elements are
+/// skipped.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::mstts::{self, StyleFallback};
+///
+/// let mut doc = ssml::speak(None, [ssml::voice("en-US-AriaNeural", [mstts::express(mstts::express::Whispering, ["Psst!"])])]);
+/// let fallback = StyleFallback::new().with_voice("en-US-AriaNeural", ["chat", "gentle", "shouting"]);
+/// assert_eq!(mstts::fallback_styles(&mut doc, &fallback), 1);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-AriaNeural"><mstts:express-as style="gentle" styledegree="1">Psst!</mstts:express-as></voice></speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn fallback_styles(doc: &mut Speak, fallback: &StyleFallback) -> usize {
+	try_fallback_styles(doc, fallback, &mut PassBudget::new()).unwrap_or_default()
+}
+
+/// Like [`fallback_styles`], but aborts once `budget` is exceeded; see [`PassBudget`].
+pub fn try_fallback_styles(doc: &mut Speak, fallback: &StyleFallback, budget: &mut PassBudget) -> Result<usize, BudgetExceeded> {
+	let voice = doc
+		.defaults()
+		.voice
+		.as_ref()
+		.and_then(|voice| voice.names.as_ref()?.first())
+		.map(|name| name.to_string());
+	fallback_elements(doc.children_mut(), fallback, voice.as_deref(), budget, 1)
+}
+
+fn fallback_elements(
+	elements: &mut [Element],
+	fallback: &StyleFallback,
+	voice: Option<&str>,
+	budget: &mut PassBudget,
+	depth: usize
+) -> Result<usize, BudgetExceeded> {
+	let mut changed = 0;
+	for element in elements.iter_mut().filter(|el| !el.is_protected()) {
+		budget.visit(depth)?;
+		match element {
+			Element::Voice(el) => {
+				let name = el.config().names.as_ref().and_then(|names| names.first()).map(|name| name.to_string());
+				changed += fallback_elements(el.children_mut(), fallback, name.as_deref().or(voice), budget, depth + 1)?;
+			}
+			Element::FlavorMSTTS(super::Element::Express(el)) => {
+				if let Some(voice) = voice {
+					let expression = el.expression();
+					let resolved = fallback.resolve(voice, expression.name()).filter(|style| *style != expression.name());
+					if let Some(style) = resolved {
+						let expression = Expression::new(style.to_string()).with_degree(expression.degree());
+						el.set_expression(expression);
+						changed += 1;
+					}
+				}
+				changed += fallback_elements(el.children_mut(), fallback, voice, budget, depth + 1)?;
+			}
+			element => {
+				if let Some(children) = element.children_mut() {
+					changed += fallback_elements(children, fallback, voice, budget, depth + 1)?;
+				}
+			}
+		}
+	}
+	Ok(changed)
+}
@@ -0,0 +1,128 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+	Flavor, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	literal::{self, LiteralError}
+};
+
+/// The namespace of MathML elements.
+pub const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
+/// A mathematical expression written in [MathML](https://www.w3.org/TR/MathML3/), read aloud by ACSS.
+///
+/// The MathML markup is kept as is, and written within a `<math>` element in the MathML namespace. It is checked to be
+/// well-formed XML when the element is created, but not that it is valid MathML.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::mstts;
+///
+/// let math = mstts::math("<msup><mi>a</mi><mn>2</mn></msup><mo>+</mo><msup><mi>b</mi><mn>2</mn></msup>").unwrap();
+/// let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", [math])]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+/// 	concat!(
+/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:mstts="http://www.w3.org/2001/mstts">"#,
+/// 		r#"<voice name="en-US-JennyNeural"><math xmlns="http://www.w3.org/1998/Math/MathML">"#,
+/// 		r#"<msup><mi>a</mi><mn>2</mn></msup><mo>+</mo><msup><mi>b</mi><mn>2</mn></msup></math></voice></speak>"#
+/// 	)
+/// );
+///
+/// assert!(mstts::math("<mi>a</mi><mo>&lt;</mi>").is_err());
+///
+/// // parsed `<math>` elements keep their markup as is
+/// let doc = ssml::parse::parse_str(
+/// 	r#"<speak><math xmlns="http://www.w3.org/1998/Math/MathML"><mi>x</mi><mo>=</mo><mn>1</mn></math></speak>"#
+/// )?;
+/// let ssml::Element::FlavorMSTTS(mstts::Element::Math(math)) = &doc.children()[0] else {
+/// 	unreachable!()
+/// };
+/// assert_eq!(math.content(), "<mi>x</mi><mo>=</mo><mn>1</mn>");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Math<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	content: Cow<'s, str>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Math<'s> {
+	/// Creates a new [`Math`] element from MathML markup, i.e. the content of the `<math>` element. Fails if `content`
+	/// isn't well-formed.
+	pub fn new(content: impl Into<Cow<'s, str>>) -> Result<Self, LiteralError> {
+		let content = content.into();
+		literal::check(&content, Flavor::Generic)?;
+		Ok(Self {
+			content,
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		})
+	}
+
+	/// Returns the MathML markup of this element.
+	pub fn content(&self) -> &str {
+		&self.content
+	}
+
+	/// Replaces the MathML markup of this element, leaving it unchanged if `content` isn't well-formed.
+	pub fn set_content(&mut self, content: impl Into<Cow<'s, str>>) -> Result<(), LiteralError> {
+		let content = content.into();
+		literal::check(&content, Flavor::Generic)?;
+		self.content = content;
+		Ok(())
+	}
+
+	pub fn to_owned(&self) -> Math<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Math<'static> {
+		Math {
+			content: match self.content {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			},
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`](crate::Element).
+	pub fn into_el(self) -> crate::Element<'s> {
+		crate::Element::FlavorMSTTS(super::Element::Math(self))
+	}
+}
+
+impl<'s> From<Math<'s>> for crate::Element<'s> {
+	fn from(value: Math<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for Math<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
+		writer.element("math", |writer| {
+			writer.attr("xmlns", MATHML_NAMESPACE)?;
+			attrs::write(writer, &self.extra_attrs)?;
+			writer.raw(&*self.content)
+		})
+	}
+}
+
+/// Creates a new [`Math`] element from MathML markup, failing if it isn't well-formed.
+pub fn math<'s>(content: impl Into<Cow<'s, str>>) -> Result<Math<'s>, LiteralError> {
+	Math::new(content)
+}
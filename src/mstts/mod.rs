@@ -1,25 +1,93 @@
 //! Elements exclusive to [`Flavor::MicrosoftAzureCognitiveSpeechServices`] (ACSS/MSTTS).
 
-use alloc::{format, string::ToString};
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
 use core::fmt::{self, Display};
 
-use crate::{Meta, voice::Voice};
+use crate::{Prosody, voice::Voice};
 
+mod background;
+mod builder;
+mod duration;
+mod embedding;
 pub mod express;
-pub use self::express::{Express, express};
+mod fallback;
+mod math;
+mod silence;
+mod viseme;
+pub use self::{
+	background::{BackgroundAudio, background_audio},
+	builder::{AcssContent, AcssDocumentBuilder, AcssVoice},
+	duration::{AudioDuration, audio_duration},
+	embedding::{TtsEmbedding, tts_embedding},
+	express::{Express, ExpressRole, express},
+	fallback::{StyleFallback, fallback_styles, try_fallback_styles},
+	math::{MATHML_NAMESPACE, Math, math},
+	silence::{Silence, SilenceType, silence},
+	viseme::{Viseme, VisemeDimension, viseme}
+};
 
 crate::element::el! {
 	#[derive(Debug, Clone)]
 	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub enum Element<'s> {
-		Express(Express<'s>)
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Express(Express<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Silence(Silence<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		BackgroundAudio(BackgroundAudio<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Viseme(Viseme<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		TtsEmbedding(TtsEmbedding<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		AudioDuration(AudioDuration<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Math(Math<'s>)
 	}
 }
 
-/// Viseme configuration for MSTTS.
+impl<'s> Element<'s> {
+	pub fn to_owned(&self) -> Element<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Element<'static> {
+		match self {
+			Self::Express(el) => Element::Express(el.into_owned()),
+			Self::Silence(el) => Element::Silence(el.into_owned()),
+			Self::BackgroundAudio(el) => Element::BackgroundAudio(el.into_owned()),
+			Self::Viseme(el) => Element::Viseme(el.into_owned()),
+			Self::TtsEmbedding(el) => Element::TtsEmbedding(el.into_owned()),
+			Self::AudioDuration(el) => Element::AudioDuration(el.into_owned()),
+			Self::Math(el) => Element::Math(el.into_owned())
+		}
+	}
+
+	/// Returns a reference to the child elements of this element, if it is a container.
+	pub fn children(&self) -> Option<&[crate::Element<'s>]> {
+		match self {
+			Self::Express(el) => Some(el.children()),
+			Self::TtsEmbedding(el) => Some(el.children()),
+			Self::Silence(_) | Self::BackgroundAudio(_) | Self::Viseme(_) | Self::AudioDuration(_) | Self::Math(_) => None
+		}
+	}
+
+	/// Returns a mutable reference to the child elements of this element, if it is a container.
+	pub fn children_mut(&mut self) -> Option<&mut Vec<crate::Element<'s>>> {
+		match self {
+			Self::Express(el) => Some(el.children_mut()),
+			Self::TtsEmbedding(el) => Some(el.children_mut()),
+			Self::Silence(_) | Self::BackgroundAudio(_) | Self::Viseme(_) | Self::AudioDuration(_) | Self::Math(_) => None
+		}
+	}
+}
+
+/// Viseme configuration for MSTTS; the `type` of a [`Viseme`] element.
 ///
 /// See [`MicrosoftVoiceExt::with_mstts_viseme`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MicrosoftViseme {
 	/// Receive visemes as an ID. (equivalent to `<mstts:viseme type="redlips_front" />`)
 	ById,
@@ -27,12 +95,18 @@ pub enum MicrosoftViseme {
 	FacialExpression
 }
 
-impl Display for MicrosoftViseme {
-	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		f.write_str(match self {
+impl MicrosoftViseme {
+	pub fn as_str(&self) -> &'static str {
+		match self {
 			MicrosoftViseme::ById => "redlips_front",
 			MicrosoftViseme::FacialExpression => "FacialExpression"
-		})
+		}
+	}
+}
+
+impl Display for MicrosoftViseme {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
 	}
 }
 
@@ -44,8 +118,9 @@ impl Display for MicrosoftViseme {
 /// passenger might have to turn up the volume to hear more clearly. To avoid manual operations in such a scenario,
 /// the audio effect processor can make the sound clearer by compensating the distortion of playback.
 ///
-/// See [`MicrosoftVoiceExt::with_mstts_viseme`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// See [`MicrosoftVoiceExt::with_mstts_effect`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum MicrosoftVoiceEffect {
 	/// Optimize the auditory experience when providing high-fidelity speech in cars, buses, and other enclosed
 	/// automobiles.
@@ -53,21 +128,41 @@ pub enum MicrosoftVoiceEffect {
 	/// Optimize the auditory experience for narrowband speech in telecom or telephone scenarios. You should use a
 	/// sampling rate of 8 kHz. If the sample rate isn't 8 kHz, the auditory quality of the output speech isn't
 	/// optimized.
-	Telecom
+	Telecom,
+	/// An effect not (yet) covered by this enum, written as-is to the `effect` attribute.
+	Custom(Cow<'static, str>)
+}
+
+impl MicrosoftVoiceEffect {
+	pub fn as_str(&self) -> &str {
+		match self {
+			MicrosoftVoiceEffect::Automobile => "eq_car",
+			MicrosoftVoiceEffect::Telecom => "eq_telecomhp8k",
+			MicrosoftVoiceEffect::Custom(effect) => effect
+		}
+	}
+}
+
+impl From<&str> for MicrosoftVoiceEffect {
+	fn from(value: &str) -> Self {
+		match value {
+			"eq_car" => MicrosoftVoiceEffect::Automobile,
+			"eq_telecomhp8k" => MicrosoftVoiceEffect::Telecom,
+			effect => MicrosoftVoiceEffect::Custom(Cow::Owned(effect.to_string()))
+		}
+	}
 }
 
 impl Display for MicrosoftVoiceEffect {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		f.write_str(match self {
-			MicrosoftVoiceEffect::Automobile => "eq_car",
-			MicrosoftVoiceEffect::Telecom => "eq_telecomhp8k"
-		})
+		f.write_str(self.as_str())
 	}
 }
 
 /// Extensions for [`Voice`] specific to MSTTS, aka Azure Cognitive Speech Services or ACSS.
 pub trait MicrosoftVoiceExt {
-	/// For ACSS, configures a [`Voice`] section to send back viseme animations in the specified format.
+	/// For ACSS, configures a [`Voice`] section to send back viseme animations in the specified format, by inserting a
+	/// [`Viseme`] element at the start of the section.
 	///
 	/// ```
 	/// # use ssml::{Flavor, mstts::{MicrosoftVoiceExt, MicrosoftViseme}, Serialize};
@@ -128,21 +223,85 @@ pub trait MicrosoftVoiceExt {
 	/// # }
 	/// ```
 	fn with_mstts_effect(self, effect: MicrosoftVoiceEffect) -> Self;
+
+	/// Returns the voice effect applied to this [`Voice`] section, if any.
+	///
+	/// ```
+	/// use ssml::mstts::{MicrosoftVoiceEffect, MicrosoftVoiceExt};
+	///
+	/// let voice = ssml::voice("en-US-JennyNeural", ["Hello!"]).with_mstts_effect(MicrosoftVoiceEffect::Automobile);
+	/// assert_eq!(voice.mstts_effect(), Some(MicrosoftVoiceEffect::Automobile));
+	///
+	/// let voice = voice.with_mstts_effect(MicrosoftVoiceEffect::Custom("eq_headphones".into()));
+	/// assert_eq!(voice.mstts_effect(), Some(MicrosoftVoiceEffect::Custom("eq_headphones".into())));
+	/// ```
+	fn mstts_effect(&self) -> Option<MicrosoftVoiceEffect>;
 }
 
 impl<'s> MicrosoftVoiceExt for Voice<'s> {
 	fn with_mstts_viseme(mut self, config: MicrosoftViseme) -> Self {
-		self.children.insert(
-			0,
-			Meta::new(format!("<mstts:viseme type=\"{config}\" />"))
-				.with_name("MicrosoftViseme")
-				.into()
-		);
+		self.children.insert(0, Viseme::new(config).into());
 		self
 	}
 
 	fn with_mstts_effect(mut self, effect: MicrosoftVoiceEffect) -> Self {
-		self.attrs.push(("effect".into(), effect.to_string().into()));
+		let effect = Cow::Owned(effect.to_string());
+		match self.attrs.iter_mut().find(|(name, _)| name == "effect") {
+			Some((_, value)) => *value = effect,
+			None => self.attrs.push(("effect".into(), effect))
+		}
 		self
 	}
+
+	fn mstts_effect(&self) -> Option<MicrosoftVoiceEffect> {
+		self.attrs
+			.iter()
+			.find(|(name, _)| name == "effect")
+			.map(|(_, value)| MicrosoftVoiceEffect::from(&**value))
+	}
+}
+
+/// Extensions for [`Prosody`] specific to MSTTS, aka Azure Cognitive Speech Services or ACSS.
+///
+/// Attributes set through this trait are written in the `mstts` namespace, and only when serializing for
+/// [`Flavor::MicrosoftAzureCognitiveSpeechServices`](crate::Flavor::MicrosoftAzureCognitiveSpeechServices); other
+/// flavors omit them entirely.
+pub trait MicrosoftProsodyExt<'s> {
+	/// For ACSS, sets an `mstts`-namespaced attribute on a [`Prosody`] section, e.g. to apply style-specific
+	/// adjustments supported by a voice.
+	///
+	/// ```
+	/// # use ssml::{Flavor, mstts::MicrosoftProsodyExt, Serialize};
+	/// # fn main() -> ssml::Result<()> {
+	/// let doc = ssml::speak(Some("en-US"), [ssml::prosody(ssml::ProsodyControl::default().with_rate(ssml::ProsodyRate::Fast), [
+	/// 	"Hello!"
+	/// ])
+	/// .with_mstts_attr("styleadjust", "cheerful")]);
+	///
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US" xmlns:mstts="http://www.w3.org/2001/mstts"><prosody rate="fast" mstts:styleadjust="cheerful">Hello!</prosody></speak>"#
+	/// );
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().flavor(Flavor::GoogleCloudTextToSpeech))?,
+	/// 	r#"<speak xml:lang="en-US"><prosody rate="fast">Hello!</prosody></speak>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	fn with_mstts_attr(self, name: impl Into<Cow<'s, str>>, value: impl Into<Cow<'s, str>>) -> Self;
+
+	/// Returns the `mstts`-namespaced attributes of this element, without their `mstts:` prefix.
+	fn mstts_attrs(&self) -> &[(Cow<'s, str>, Cow<'s, str>)];
+}
+
+impl<'s> MicrosoftProsodyExt<'s> for Prosody<'s> {
+	fn with_mstts_attr(mut self, name: impl Into<Cow<'s, str>>, value: impl Into<Cow<'s, str>>) -> Self {
+		self.mstts_attrs.push((name.into(), value.into()));
+		self
+	}
+
+	fn mstts_attrs(&self) -> &[(Cow<'s, str>, Cow<'s, str>)] {
+		&self.mstts_attrs
+	}
 }
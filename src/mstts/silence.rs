@@ -0,0 +1,164 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Write};
+
+use crate::{
+	Serialize, SerializeOptions, TimeDesignation, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs}
+};
+
+/// Where the silence of a [`Silence`] element is inserted.
+///
+/// The `-exact` variants set the silence to exactly the given value; the others add it to the natural silence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SilenceType {
+	/// Silence at the beginning of the text. (`Leading`)
+	Leading,
+	/// `Leading-exact`
+	LeadingExact,
+	/// Silence at the end of the text. (`Tailing`)
+	Tailing,
+	/// `Tailing-exact`
+	TailingExact,
+	/// Silence between adjacent sentences. (`Sentenceboundary`)
+	SentenceBoundary,
+	/// `Sentenceboundary-exact`
+	SentenceBoundaryExact,
+	/// Silence at half-width or full-width commas. (`Comma-exact`)
+	CommaExact,
+	/// Silence at half-width or full-width semicolons. (`Semicolon-exact`)
+	SemicolonExact,
+	/// Silence at full-width enumeration commas, as used in Chinese. (`Enumerationcomma-exact`)
+	EnumerationCommaExact
+}
+
+impl SilenceType {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			SilenceType::Leading => "Leading",
+			SilenceType::LeadingExact => "Leading-exact",
+			SilenceType::Tailing => "Tailing",
+			SilenceType::TailingExact => "Tailing-exact",
+			SilenceType::SentenceBoundary => "Sentenceboundary",
+			SilenceType::SentenceBoundaryExact => "Sentenceboundary-exact",
+			SilenceType::CommaExact => "Comma-exact",
+			SilenceType::SemicolonExact => "Semicolon-exact",
+			SilenceType::EnumerationCommaExact => "Enumerationcomma-exact"
+		}
+	}
+}
+
+impl Display for SilenceType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// Controls the silence inserted around or within the text of a voice, in ACSS/MSTTS.
+///
+/// Silence elements apply to the whole [`Voice`](crate::Voice) they're placed in, and must be one of its direct
+/// children.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::mstts::{self, SilenceType};
+///
+/// let doc = ssml::speak(
+/// 	None,
+/// 	[ssml::voice("en-US-JennyNeural", [
+/// 		ssml::Element::from(mstts::silence(SilenceType::SentenceBoundary, "200ms")),
+/// 		"Hello. How are you?".into()
+/// 	])]
+/// );
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+/// 	concat!(
+/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:mstts="http://www.w3.org/2001/mstts">"#,
+/// 		r#"<voice name="en-US-JennyNeural"><mstts:silence type="Sentenceboundary" value="+200ms"/>Hello. How are you?</voice></speak>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Silence<'s> {
+	kind: SilenceType,
+	value: TimeDesignation,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Silence<'s> {
+	pub fn new(kind: SilenceType, value: impl Into<TimeDesignation>) -> Self {
+		Self {
+			kind,
+			value: value.into(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn kind(&self) -> SilenceType {
+		self.kind
+	}
+
+	pub fn set_kind(&mut self, kind: SilenceType) {
+		self.kind = kind;
+	}
+
+	pub fn value(&self) -> &TimeDesignation {
+		&self.value
+	}
+
+	pub fn set_value(&mut self, value: impl Into<TimeDesignation>) {
+		self.value = value.into();
+	}
+
+	pub fn to_owned(&self) -> Silence<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Silence<'static> {
+		Silence {
+			kind: self.kind,
+			value: self.value,
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`](crate::Element).
+	pub fn into_el(self) -> crate::Element<'s> {
+		crate::Element::FlavorMSTTS(super::Element::Silence(self))
+	}
+}
+
+impl<'s> From<Silence<'s>> for crate::Element<'s> {
+	fn from(value: Silence<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for Silence<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
+		writer.element("mstts:silence", |writer| {
+			writer.attr("type", self.kind.as_str())?;
+			writer.attr("value", &self.value)?;
+			attrs::write(writer, &self.extra_attrs)
+		})
+	}
+}
+
+/// Creates a new [`Silence`] element.
+pub fn silence<'s>(kind: SilenceType, value: impl Into<TimeDesignation>) -> Silence<'s> {
+	Silence::new(kind, value)
+}
@@ -0,0 +1,218 @@
+use alloc::{format, vec::Vec};
+use core::fmt::{self, Display, Write};
+
+use super::MicrosoftViseme;
+use crate::{
+	Meta, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs}
+};
+
+/// The kind of model viseme animations are generated for; see [`Viseme::set_dimension`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VisemeDimension {
+	/// Animations for a 2D character, e.g. as SVG frames. (`2D`)
+	TwoD,
+	/// Blend shapes for a 3D character. (`3D`)
+	ThreeD
+}
+
+impl VisemeDimension {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			VisemeDimension::TwoD => "2D",
+			VisemeDimension::ThreeD => "3D"
+		}
+	}
+}
+
+impl Display for VisemeDimension {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// Configures a [`Voice`](crate::Voice) section to send back viseme animations in the given format, in ACSS/MSTTS.
+///
+/// Viseme elements apply to the whole voice they're placed in, and must be one of its direct children. See also
+/// [`MicrosoftVoiceExt::with_mstts_viseme`](super::MicrosoftVoiceExt::with_mstts_viseme).
+///
+/// ```
+/// use ssml::mstts::{self, MicrosoftViseme, MicrosoftVoiceExt};
+///
+/// let voice = ssml::voice("en-US-JennyNeural", ["Hello!"]).with_mstts_viseme(MicrosoftViseme::ById);
+/// let ssml::Element::FlavorMSTTS(mstts::Element::Viseme(viseme)) = &voice.children()[0] else {
+/// 	unreachable!()
+/// };
+/// assert_eq!(viseme.kind(), MicrosoftViseme::ById);
+/// ```
+///
+/// Blend shapes ([`MicrosoftViseme::FacialExpression`]) can additionally be configured with a frame rate & the
+/// [`VisemeDimension`] of the model they drive. Neither applies to viseme IDs, which
+/// [validation](crate::validate) reports along with frame rates outside of 1-60 FPS.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::mstts::{self, MicrosoftViseme, VisemeDimension};
+///
+/// let viseme = mstts::viseme(MicrosoftViseme::FacialExpression)
+/// 	.with_frame_rate(30)
+/// 	.with_dimension(VisemeDimension::ThreeD);
+/// assert_eq!(
+/// 	viseme
+/// 		.serialize_to_string(&SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+/// 	r#"<mstts:viseme type="FacialExpression" framerate="30" dimension="3D"/>"#
+/// );
+///
+/// let doc = ssml::speak(
+/// 	None,
+/// 	[ssml::voice(
+/// 		"en-US-JennyNeural",
+/// 		[ssml::Element::from(mstts::viseme(MicrosoftViseme::ById).with_frame_rate(30)), "Hello!".into()]
+/// 	)]
+/// );
+/// let diagnostics = ssml::validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices);
+/// assert_eq!(diagnostics[0].lint, ssml::validate::Lint::InvalidVisemeOptions);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Viseme<'s> {
+	kind: MicrosoftViseme,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+	frame_rate: Option<u32>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+	dimension: Option<VisemeDimension>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Viseme<'s> {
+	pub fn new(kind: MicrosoftViseme) -> Self {
+		Self {
+			kind,
+			frame_rate: None,
+			dimension: None,
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn kind(&self) -> MicrosoftViseme {
+		self.kind
+	}
+
+	pub fn set_kind(&mut self, kind: MicrosoftViseme) {
+		self.kind = kind;
+	}
+
+	/// Sets the number of blend shape frames sent back per second, for [`MicrosoftViseme::FacialExpression`].
+	pub fn with_frame_rate(mut self, fps: u32) -> Self {
+		self.frame_rate = Some(fps);
+		self
+	}
+
+	pub fn frame_rate(&self) -> Option<u32> {
+		self.frame_rate
+	}
+
+	pub fn set_frame_rate(&mut self, fps: impl Into<Option<u32>>) {
+		self.frame_rate = fps.into();
+	}
+
+	/// Sets the kind of model the viseme animations are generated for.
+	pub fn with_dimension(mut self, dimension: VisemeDimension) -> Self {
+		self.dimension = Some(dimension);
+		self
+	}
+
+	pub fn dimension(&self) -> Option<VisemeDimension> {
+		self.dimension
+	}
+
+	pub fn set_dimension(&mut self, dimension: impl Into<Option<VisemeDimension>>) {
+		self.dimension = dimension.into();
+	}
+
+	/// Returns whether the frame rate & dimension are valid for this viseme's [`kind`](Viseme::kind): viseme IDs are
+	/// only available for 2D models without a frame rate, and blend shapes are sent at 1-60 FPS.
+	pub(crate) fn options_valid(&self) -> bool {
+		match self.kind {
+			MicrosoftViseme::ById => self.frame_rate.is_none() && self.dimension != Some(VisemeDimension::ThreeD),
+			MicrosoftViseme::FacialExpression => self.frame_rate.is_none_or(|fps| (1..=60).contains(&fps))
+		}
+	}
+
+	/// Recognizes the raw [`Meta`] element which [`MicrosoftVoiceExt::with_mstts_viseme`] inserted before visemes were
+	/// typed, e.g. in documents deserialized from an older version of this crate.
+	///
+	/// [`MicrosoftVoiceExt::with_mstts_viseme`]: super::MicrosoftVoiceExt::with_mstts_viseme
+	///
+	/// ```
+	/// use ssml::{
+	/// 	Meta,
+	/// 	mstts::{MicrosoftViseme, Viseme}
+	/// };
+	///
+	/// let meta = Meta::new(r#"<mstts:viseme type="FacialExpression" />"#).with_name("MicrosoftViseme");
+	/// assert_eq!(Viseme::from_meta(&meta).map(|viseme| viseme.kind()), Some(MicrosoftViseme::FacialExpression));
+	/// ```
+	pub fn from_meta(meta: &Meta) -> Option<Self> {
+		if meta.name() != Some("MicrosoftViseme") {
+			return None;
+		}
+		[MicrosoftViseme::ById, MicrosoftViseme::FacialExpression]
+			.into_iter()
+			.find(|kind| meta.raw() == format!("<mstts:viseme type=\"{kind}\" />"))
+			.map(Viseme::new)
+	}
+
+	pub fn to_owned(&self) -> Viseme<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Viseme<'static> {
+		Viseme {
+			kind: self.kind,
+			frame_rate: self.frame_rate,
+			dimension: self.dimension,
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`](crate::Element).
+	pub fn into_el(self) -> crate::Element<'s> {
+		crate::Element::FlavorMSTTS(super::Element::Viseme(self))
+	}
+}
+
+impl<'s> From<Viseme<'s>> for crate::Element<'s> {
+	fn from(value: Viseme<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for Viseme<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
+		writer.element("mstts:viseme", |writer| {
+			writer.attr("type", self.kind.as_str())?;
+			writer.attr_opt("framerate", self.frame_rate)?;
+			writer.attr_opt("dimension", self.dimension.map(|d| d.as_str()))?;
+			attrs::write(writer, &self.extra_attrs)
+		})
+	}
+}
+
+/// Creates a new [`Viseme`] element.
+pub fn viseme<'s>(kind: MicrosoftViseme) -> Viseme<'s> {
+	Viseme::new(kind)
+}
@@ -0,0 +1,110 @@
+use alloc::vec::Vec;
+use core::{
+	fmt::Write,
+	ops::{Add, AddAssign}
+};
+
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// [`Paragraph`] marks its children as a paragraph of text, which engines use to apply more natural prosody &
+/// pausing to long-form content. Paragraphs usually contain [`Sentence`](crate::Sentence)s.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let doc = ssml::speak(None, [ssml::paragraph([
+/// 	ssml::sentence(["It was a dark and stormy night."]),
+/// 	ssml::sentence(["The rain fell in torrents."])
+/// ])]);
+///
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><p><s>It was a dark and stormy night.</s><s>The rain fell in torrents.</s></p></speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Paragraph<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Paragraph<'s> {
+	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Self {
+		Self {
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
+	pub fn push(&mut self, element: impl Into<Element<'s>>) {
+		self.children.push(element.into());
+	}
+
+	pub fn extend<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&mut self, elements: I) {
+		self.children.extend(elements.into_iter().map(|f| f.into()));
+	}
+
+	pub fn to_owned(&self) -> Paragraph<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Paragraph<'static> {
+		Paragraph {
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+}
+
+impl<'s> Serialize for Paragraph<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.element("p", |writer| {
+			attrs::write(writer, &self.extra_attrs)?;
+			util::serialize_elements(writer, &self.children, options)
+		})
+	}
+}
+
+impl<'s, 's2: 's, T: Into<Element<'s2>>> Add<T> for Paragraph<'s> {
+	type Output = Paragraph<'s>;
+
+	fn add(mut self, rhs: T) -> Self::Output {
+		self.push(rhs.into());
+		self
+	}
+}
+
+impl<'s, 's2: 's, T: Into<Element<'s2>>> AddAssign<T> for Paragraph<'s> {
+	fn add_assign(&mut self, rhs: T) {
+		self.push(rhs.into());
+	}
+}
+
+pub fn paragraph<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Paragraph<'s> {
+	Paragraph::new(elements)
+}
@@ -0,0 +1,1317 @@
+//! Parsing SSML documents back into [`Speak`] trees.
+//!
+//! [`parse_str`] reads an SSML document, e.g. one received from user input or another service, into the same element
+//! types used to build documents, so it can be inspected, modified (see [`visit_mut`](crate::visit_mut)) &
+//! re-serialized.
+//!
+//! ```
+//! # use ssml::Serialize;
+//! # fn main() -> ssml::Result<()> {
+//! let input = r#"<speak version="1.0" xml:lang="en-US"><voice name="en-US-JennyNeural">Hello, <emphasis level="strong">world</emphasis>!</voice></speak>"#;
+//! let doc = ssml::parse::parse_str(input)?;
+//! assert_eq!(doc.lang(), Some("en-US"));
+//!
+//! let ssml::Element::Voice(voice) = &doc.children()[0] else { unreachable!() };
+//! assert_eq!(voice.config().names.as_deref(), Some(&["en-US-JennyNeural".into()][..]));
+//! assert_eq!(voice.children().len(), 3);
+//!
+//! assert_eq!(
+//! 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+//! 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US"><voice name="en-US-JennyNeural">Hello, <emphasis level="strong">world</emphasis>!</voice></speak>"#
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Elements which aren't known to this crate - or known elements with attribute values which can't be represented by
+//! their typed counterpart - are parsed as [`CustomElement`]s, so no markup is lost. Unrecognized attributes of known
+//! elements are kept as [`ExtraAttrs`](crate::ExtraAttrs). Comments, processing instructions & the document type
+//! declaration are discarded.
+//!
+//! Whitespace which only serves to lay out the document (i.e. leading or trailing whitespace in a text run which
+//! includes a line break) is removed, so documents serialized with
+//! [`SerializeOptions::pretty`](crate::SerializeOptions::pretty) parse to the same tree as their minified counterparts.
+//!
+//! Every parsed element which can hold [`Annotations`](crate::annotations::Annotations) records the [`Span`] of
+//! input it was parsed from.
+
+use alloc::{
+	borrow::Cow,
+	string::{String, ToString},
+	vec::Vec
+};
+use core::{
+	fmt::{self, Display},
+	mem,
+	ops::Range
+};
+
+use crate::{
+	Alphabet, Audio, AudioRepeat, Break, BreakStrength, CustomElement, Decibels, Desc, Element, Emphasis, EmphasisLevel, FetchHint, Lang, Lexicon, Mark,
+	Metadata, Paragraph, Phoneme, Prosody, ProsodyContour, ProsodyControl, ProsodyPitch, ProsodyRate, ProsodyVolume, SayAs, Sentence, Speak, SpeechFormat, Sub,
+	Text, TimeDesignation, Voice, VoiceConfig, VoiceGender, Word,
+	annotations::Annotated,
+	attrs::Attrs,
+	events::XmlEvent,
+	lang::LangFailure,
+	mstts::{self, express::Expression},
+	polly,
+	raw::RawSource,
+	say_as::{DateFormat, TimeFormat},
+	span::{LineColumn, Locator, Span},
+	watson
+};
+
+/// The deepest nesting of elements the parser accepts, counting the root `<speak>` element.
+///
+/// Serializing, validating & even dropping a tree recurses through its elements, so a deeper document - which is only
+/// ever produced maliciously - could overflow the stack. Parsing fails with [`ParseErrorKind::TooDeep`] instead.
+///
+/// ```
+/// use ssml::parse::{MAX_DEPTH, ParseErrorKind};
+///
+/// let nested =
+/// 	|depth: usize| format!("<speak>{}Hi{}</speak>", "<emphasis>".repeat(depth), "</emphasis>".repeat(depth));
+/// assert!(ssml::parse::parse_str(&nested(MAX_DEPTH - 1)).is_ok());
+///
+/// let Err(ssml::Error::ParseError(err)) = ssml::parse::parse_str(&nested(200_000)) else {
+/// 	unreachable!()
+/// };
+/// assert_eq!(err.kind, ParseErrorKind::TooDeep);
+/// ```
+pub const MAX_DEPTH: usize = 128;
+
+/// The reason an SSML document failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+	/// The input ended in the middle of markup.
+	UnexpectedEof,
+	/// A character which isn't valid at this position was encountered.
+	UnexpectedChar(char),
+	/// An entity reference other than the predefined XML entities or a character reference was encountered.
+	UnknownEntity(String),
+	/// A closing tag didn't match the most recently opened element.
+	MismatchedTag { expected: String, found: String },
+	/// An element was never closed.
+	UnclosedTag(String),
+	/// An element specified the same attribute more than once.
+	DuplicateAttribute(String),
+	/// The root element of the document is not `<speak>`.
+	ExpectedSpeak,
+	/// Non-whitespace content was found after the root element.
+	TrailingContent,
+	/// Elements were nested deeper than [`MAX_DEPTH`].
+	TooDeep
+}
+
+impl Display for ParseErrorKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ParseErrorKind::UnexpectedEof => f.write_str("unexpected end of input"),
+			ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+			ParseErrorKind::UnknownEntity(entity) => write!(f, "unknown entity `&{entity};`"),
+			ParseErrorKind::MismatchedTag { expected, found } => write!(f, "expected closing tag `</{expected}>`, found `</{found}>`"),
+			ParseErrorKind::UnclosedTag(tag) => write!(f, "element `<{tag}>` is never closed"),
+			ParseErrorKind::DuplicateAttribute(name) => write!(f, "duplicate attribute `{name}`"),
+			ParseErrorKind::ExpectedSpeak => f.write_str("the root element of an SSML document must be `<speak>`"),
+			ParseErrorKind::TrailingContent => f.write_str("unexpected content after the root element"),
+			ParseErrorKind::TooDeep => write!(f, "elements are nested deeper than {MAX_DEPTH} levels")
+		}
+	}
+}
+
+/// An error encountered while parsing an SSML document.
+///
+/// ```
+/// let err = ssml::parse::parse_str("<speak>\n\t<voice>Hello</speak>").unwrap_err();
+/// let ssml::Error::ParseError(err) = err else {
+/// 	unreachable!()
+/// };
+/// assert_eq!(err.to_string(), "expected closing tag `</voice>`, found `</speak>` at 2:14");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+	pub kind: ParseErrorKind,
+	/// The location of the offending input.
+	pub span: Span
+}
+
+impl Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} at {}", self.kind, self.span.start)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Parses an SSML document. The root element of the document must be `<speak>`.
+///
+/// Text & attribute values which don't contain any entity references borrow from `input`; use
+/// [`Speak::into_owned`] to detach the document from the input.
+///
+/// ```
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::{
+/// 	transcript::{TranscriptOptions, transcript},
+/// 	visit_mut::VisitMut
+/// };
+///
+/// struct Shout;
+/// impl<'a> VisitMut<'a> for Shout {
+/// 	fn visit_text_mut(&mut self, node: &'a mut ssml::Text) {
+/// 		let upper = node.to_uppercase();
+/// 		node.set(upper);
+/// 	}
+/// }
+///
+/// let mut doc = ssml::parse::parse_str("<speak>hello <break time=\"500ms\"/> world &amp; friends</speak>")?;
+/// Shout.visit_speak_mut(&mut doc);
+/// assert_eq!(transcript(&doc, &TranscriptOptions::default()), "HELLO WORLD & FRIENDS");
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_str(input: &str) -> crate::Result<Speak<'_>> {
+	let mut parser = Parser::new(input);
+	parser.skip_misc()?;
+
+	let start = parser.pos;
+	let start_position = parser.locator.locate(start);
+	if !parser.input[start..].starts_with('<') {
+		return Err(parser.error(ParseErrorKind::ExpectedSpeak, start..start).into());
+	}
+	parser.pos += 1;
+	let tag = parser.name()?;
+	if tag != "speak" {
+		return Err(parser.error(ParseErrorKind::ExpectedSpeak, start..parser.pos).into());
+	}
+	let (attrs, self_closing) = parser.attrs()?;
+	let mut children = if self_closing { Vec::new() } else { parser.content(Some("speak"))? };
+	let metadata = take_metadata(input, &mut children);
+
+	let mut doc = Speak::new(None, children);
+	*doc.metadata_mut() = metadata;
+	for (name, value) in attrs {
+		match name {
+			"xml:lang" => doc.set_lang(value),
+			"startmark" => doc.set_start_mark(value),
+			"endmark" => doc.set_end_mark(value),
+			"onlangfailure" => match lang_failure(&value) {
+				Some(behavior) => doc.set_lang_failure(behavior),
+				None => doc.extra_attrs.push((Cow::Borrowed(name), value))
+			},
+			// written by the serializer depending on the flavor
+			"version" | "xmlns" | "xmlns:mstts" | "xmlns:amazon" => {}
+			_ => doc.extra_attrs.push((Cow::Borrowed(name), value))
+		}
+	}
+	doc.annotations.set_span(parser.span(start, start_position));
+
+	parser.skip_misc()?;
+	if parser.pos < input.len() {
+		return Err(parser.error(ParseErrorKind::TrailingContent, parser.pos..input.len()).into());
+	}
+	Ok(doc)
+}
+
+/// Removes the `<metadata>` elements at the start of a document's children, keeping their contents as raw XML.
+fn take_metadata<'s>(input: &'s str, children: &mut Vec<Element<'s>>) -> Vec<Metadata<'s>> {
+	let count = children
+		.iter()
+		.take_while(|el| matches!(el, Element::Custom(el) if el.tag() == "metadata"))
+		.count();
+	children
+		.drain(..count)
+		.map(|el| {
+			let source = el.span().map_or("", |span| &input[span.range.clone()]);
+			// the contents are between the end of the start tag & the start of the end tag; self-closing tags have none
+			let contents = match (source.find('>'), source.rfind("</")) {
+				(Some(start), Some(end)) if start < end => &source[start + 1..end],
+				_ => ""
+			};
+			Metadata::raw(contents)
+		})
+		.collect()
+}
+
+/// Parses an SSML document like [`parse_str`], additionally recording the markup each element was parsed from.
+///
+/// When serialized with [`SerializeOptions::preserve_raw`](crate::SerializeOptions::preserve_raw), elements which
+/// haven't been modified since parsing are written exactly as they appeared in `input`, so unedited parts of a document
+/// keep their original formatting. The recorded markup borrows from `input`.
+///
+/// Detecting modifications requires fingerprinting every element, so parsing takes time proportional to the size of the
+/// document multiplied by its depth.
+pub fn parse_str_preserving(input: &str) -> crate::Result<Speak<'_>> {
+	let mut doc = parse_str(input)?;
+	for el in doc.children_mut() {
+		capture_raw(input, el);
+	}
+	if let Some(raw) = doc.span().and_then(|span| RawSource::capture(&doc, &input[span.range.clone()])) {
+		doc.annotations.set_raw(raw);
+	}
+	Ok(doc)
+}
+
+fn capture_raw<'s>(input: &'s str, el: &mut Element<'s>) {
+	if let Some(children) = el.children_mut() {
+		for child in children {
+			capture_raw(input, child);
+		}
+	}
+	if let Some(raw) = el.span().and_then(|span| RawSource::capture(el, &input[span.range.clone()])) {
+		if let Some(annotations) = el.annotations_mut() {
+			annotations.set_raw(raw);
+		}
+	}
+}
+
+/// Parses a fragment of SSML - text & elements without a surrounding `<speak>` element.
+///
+/// ```
+/// # fn main() -> ssml::Result<()> {
+/// let elements = ssml::parse::parse_fragment("Press <say-as interpret-as=\"digits\">1</say-as> to continue.")?;
+/// assert_eq!(elements.len(), 3);
+/// assert!(matches!(&elements[1], ssml::Element::SayAs(s) if s.text() == "1"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_fragment(input: &str) -> crate::Result<Vec<Element<'_>>> {
+	Ok(Parser::new(input).content(None)?)
+}
+
+/// Splits well-formed XML into a stream of [`XmlEvent`]s. Comments, processing instructions & the document type
+/// declaration are discarded, and whitespace is kept as-is.
+pub(crate) fn events(input: &str) -> Result<Vec<XmlEvent<'_>>, ParseError> {
+	let mut parser = Parser::new(input);
+	let mut events = Vec::new();
+	let mut open: Vec<&str> = Vec::new();
+	loop {
+		let rest = parser.rest();
+		if rest.is_empty() {
+			return match open.pop() {
+				Some(tag) => Err(parser.error(ParseErrorKind::UnclosedTag(tag.to_string()), parser.pos..parser.pos)),
+				None => Ok(events)
+			};
+		} else if rest.starts_with("<!--") {
+			parser.skip_past("-->")?;
+		} else if rest.starts_with("<?") {
+			parser.skip_past("?>")?;
+		} else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+			let Some(len) = cdata.find("]]>") else {
+				return Err(parser.error(ParseErrorKind::UnexpectedEof, input.len()..input.len()));
+			};
+			events.push(XmlEvent::Text(Cow::Borrowed(&cdata[..len])));
+			parser.pos += 9 + len + 3;
+		} else if rest.starts_with("<!") {
+			parser.skip_past(">")?;
+		} else if rest.starts_with("</") {
+			let start = parser.pos;
+			parser.pos += 2;
+			let tag = parser.name()?;
+			parser.skip_whitespace();
+			parser.expect('>')?;
+			match open.pop() {
+				Some(expected) if expected == tag => events.push(XmlEvent::End { name: Cow::Borrowed(tag) }),
+				expected => {
+					let expected = expected.unwrap_or_default().to_string();
+					return Err(parser.error(ParseErrorKind::MismatchedTag { expected, found: tag.to_string() }, start..parser.pos));
+				}
+			}
+		} else if rest.starts_with('<') {
+			parser.pos += 1;
+			let tag = parser.name()?;
+			let (attrs, self_closing) = parser.attrs()?;
+			events.push(XmlEvent::Start {
+				name: Cow::Borrowed(tag),
+				attrs: attrs.into_iter().map(|(name, value)| (Cow::Borrowed(name), value)).collect()
+			});
+			if self_closing {
+				events.push(XmlEvent::End { name: Cow::Borrowed(tag) });
+			} else {
+				open.push(tag);
+			}
+		} else {
+			let len = rest.find('<').unwrap_or(rest.len());
+			events.push(XmlEvent::Text(parser.unescape(parser.pos..parser.pos + len)?));
+			parser.pos += len;
+		}
+	}
+}
+
+/// A parsed attribute name & its unescaped value.
+type Attr<'s> = (&'s str, Cow<'s, str>);
+
+struct Frame<'s> {
+	tag: &'s str,
+	attrs: Vec<Attr<'s>>,
+	start: usize,
+	start_position: LineColumn,
+	children: Vec<Element<'s>>
+}
+
+struct Parser<'s> {
+	input: &'s str,
+	pos: usize,
+	locator: Locator<'s>
+}
+
+impl<'s> Parser<'s> {
+	fn new(input: &'s str) -> Self {
+		Parser {
+			input,
+			pos: 0,
+			locator: Locator::new(input)
+		}
+	}
+
+	/// Returns the span from `start` to the current position. Spans are requested in order of position while parsing,
+	/// so the locator never has to rescan the input.
+	fn span(&mut self, start: usize, start_position: LineColumn) -> Span {
+		Span {
+			range: start..self.pos,
+			start: start_position,
+			end: self.locator.locate(self.pos)
+		}
+	}
+
+	fn error(&self, kind: ParseErrorKind, range: Range<usize>) -> ParseError {
+		ParseError {
+			kind,
+			span: Span::from_range(self.input, range)
+		}
+	}
+
+	fn rest(&self) -> &'s str {
+		&self.input[self.pos..]
+	}
+
+	fn unexpected(&self) -> ParseError {
+		match self.rest().chars().next() {
+			Some(c) => self.error(ParseErrorKind::UnexpectedChar(c), self.pos..self.pos + c.len_utf8()),
+			None => self.error(ParseErrorKind::UnexpectedEof, self.pos..self.pos)
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		let rest = self.rest();
+		self.pos += rest.len() - rest.trim_start().len();
+	}
+
+	/// Skips past the next occurrence of `terminator`.
+	fn skip_past(&mut self, terminator: &str) -> Result<(), ParseError> {
+		match self.rest().find(terminator) {
+			Some(i) => {
+				self.pos += i + terminator.len();
+				Ok(())
+			}
+			None => Err(self.error(ParseErrorKind::UnexpectedEof, self.input.len()..self.input.len()))
+		}
+	}
+
+	/// Skips whitespace, comments, processing instructions & document type declarations.
+	fn skip_misc(&mut self) -> Result<(), ParseError> {
+		loop {
+			self.skip_whitespace();
+			let rest = self.rest();
+			if rest.starts_with("<!--") {
+				self.skip_past("-->")?;
+			} else if rest.starts_with("<?") {
+				self.skip_past("?>")?;
+			} else if rest.starts_with("<!") && !rest.starts_with("<![CDATA[") {
+				self.skip_past(">")?;
+			} else {
+				return Ok(());
+			}
+		}
+	}
+
+	fn name(&mut self) -> Result<&'s str, ParseError> {
+		let rest = self.rest();
+		let len = rest
+			.find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '=' | '<' | '"' | '\''))
+			.unwrap_or(rest.len());
+		if len == 0 {
+			return Err(self.unexpected());
+		}
+		self.pos += len;
+		Ok(&rest[..len])
+	}
+
+	fn expect(&mut self, c: char) -> Result<(), ParseError> {
+		if self.rest().starts_with(c) {
+			self.pos += c.len_utf8();
+			Ok(())
+		} else {
+			Err(self.unexpected())
+		}
+	}
+
+	/// Parses the attributes of a start tag up to & including its closing `>` or `/>`. Returns the attributes & whether
+	/// the tag was self-closing.
+	fn attrs(&mut self) -> Result<(Vec<Attr<'s>>, bool), ParseError> {
+		let mut attrs: Vec<Attr<'s>> = Vec::new();
+		loop {
+			self.skip_whitespace();
+			let rest = self.rest();
+			if rest.starts_with("/>") {
+				self.pos += 2;
+				return Ok((attrs, true));
+			} else if rest.starts_with('>') {
+				self.pos += 1;
+				return Ok((attrs, false));
+			}
+
+			let start = self.pos;
+			let name = self.name()?;
+			if attrs.iter().any(|(n, _)| *n == name) {
+				return Err(self.error(ParseErrorKind::DuplicateAttribute(name.to_string()), start..self.pos));
+			}
+			self.skip_whitespace();
+			self.expect('=')?;
+			self.skip_whitespace();
+			let quote = match self.rest().chars().next() {
+				Some(q @ ('"' | '\'')) => q,
+				_ => return Err(self.unexpected())
+			};
+			self.pos += 1;
+			let value_start = self.pos;
+			let Some(len) = self.rest().find(quote) else {
+				return Err(self.error(ParseErrorKind::UnexpectedEof, self.input.len()..self.input.len()));
+			};
+			self.pos += len + 1;
+			attrs.push((name, self.unescape(value_start..value_start + len)?));
+		}
+	}
+
+	/// Resolves entity references in the given range of the input, borrowing from the input if there are none.
+	fn unescape(&self, range: Range<usize>) -> Result<Cow<'s, str>, ParseError> {
+		let raw = &self.input[range.clone()];
+		if !raw.contains('&') {
+			return Ok(Cow::Borrowed(raw));
+		}
+
+		let mut out = String::with_capacity(raw.len());
+		let mut rest = raw;
+		while let Some(i) = rest.find('&') {
+			out.push_str(&rest[..i]);
+			let offset = range.start + (raw.len() - rest.len()) + i;
+			let Some(len) = rest[i..].find(';') else {
+				return Err(self.error(ParseErrorKind::UnexpectedEof, range.end..range.end));
+			};
+			let entity = &rest[i + 1..i + len];
+			let char = match entity {
+				"lt" => Some('<'),
+				"gt" => Some('>'),
+				"amp" => Some('&'),
+				"quot" => Some('"'),
+				"apos" => Some('\''),
+				_ => match entity.strip_prefix('#') {
+					Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok().and_then(char::from_u32),
+					Some(dec) => dec.parse().ok().and_then(char::from_u32),
+					None => None
+				}
+			};
+			match char {
+				Some(char) => out.push(char),
+				None => return Err(self.error(ParseErrorKind::UnknownEntity(entity.to_string()), offset..offset + len + 1))
+			}
+			rest = &rest[i + len + 1..];
+		}
+		out.push_str(rest);
+		Ok(Cow::Owned(out))
+	}
+
+	/// Parses content until the end of the input, or until the closing tag of `root` if one is given.
+	///
+	/// Open elements are tracked on an explicit stack rather than by recursion, and may be nested at most [`MAX_DEPTH`]
+	/// deep, including `root`.
+	fn content(&mut self, root: Option<&'s str>) -> Result<Vec<Element<'s>>, ParseError> {
+		let mut top = Vec::new();
+		let mut stack: Vec<Frame<'s>> = Vec::new();
+		loop {
+			let rest = self.rest();
+			if rest.is_empty() {
+				return match (stack.pop(), root) {
+					(Some(frame), _) => Err(self.error(ParseErrorKind::UnclosedTag(frame.tag.to_string()), frame.start..frame.start)),
+					(None, Some(root)) => Err(self.error(ParseErrorKind::UnclosedTag(root.to_string()), self.pos..self.pos)),
+					(None, None) => Ok(top)
+				};
+			} else if rest.starts_with("<!--") {
+				self.skip_past("-->")?;
+			} else if rest.starts_with("<?") {
+				self.skip_past("?>")?;
+			} else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+				let Some(len) = cdata.find("]]>") else {
+					return Err(self.error(ParseErrorKind::UnexpectedEof, self.input.len()..self.input.len()));
+				};
+				push_text(stack.last_mut().map_or(&mut top, |frame| &mut frame.children), Cow::Borrowed(&cdata[..len]));
+				self.pos += 9 + len + 3;
+			} else if rest.starts_with("</") {
+				let start = self.pos;
+				self.pos += 2;
+				let tag = self.name()?;
+				self.skip_whitespace();
+				self.expect('>')?;
+				match stack.pop() {
+					Some(frame) if frame.tag == tag => {
+						let span = self.span(frame.start, frame.start_position);
+						let el = build(frame.tag, frame.attrs, frame.children, &self.input[span.range.clone()], span);
+						stack.last_mut().map_or(&mut top, |frame| &mut frame.children).push(el);
+					}
+					None if root == Some(tag) => return Ok(top),
+					frame => {
+						let expected = frame.map(|f| f.tag).or(root).unwrap_or_default().to_string();
+						return Err(self.error(ParseErrorKind::MismatchedTag { expected, found: tag.to_string() }, start..self.pos));
+					}
+				}
+			} else if rest.starts_with('<') {
+				let start = self.pos;
+				self.pos += 1;
+				let tag = self.name()?;
+				let (attrs, self_closing) = self.attrs()?;
+				let start_position = self.locator.locate(start);
+				if stack.len() + usize::from(root.is_some()) >= MAX_DEPTH {
+					return Err(self.error(ParseErrorKind::TooDeep, start..self.pos));
+				}
+				if self_closing {
+					let span = self.span(start, start_position);
+					let el = build(tag, attrs, Vec::new(), &self.input[span.range.clone()], span);
+					stack.last_mut().map_or(&mut top, |frame| &mut frame.children).push(el);
+				} else {
+					stack.push(Frame {
+						tag,
+						attrs,
+						start,
+						start_position,
+						children: Vec::new()
+					});
+				}
+			} else {
+				let len = rest.find('<').unwrap_or(rest.len());
+				let range = trim_layout(rest, self.pos..self.pos + len);
+				if !range.is_empty() {
+					let text = self.unescape(range)?;
+					push_text(stack.last_mut().map_or(&mut top, |frame| &mut frame.children), text);
+				}
+				self.pos += len;
+			}
+		}
+	}
+}
+
+/// Narrows the range of a text run to exclude leading or trailing whitespace which includes a line break.
+fn trim_layout(rest: &str, range: Range<usize>) -> Range<usize> {
+	let text = &rest[..range.len()];
+	let start = text.len() - text.trim_start().len();
+	let start = if text[..start].contains('\n') { start } else { 0 };
+	let trimmed = text.trim_end().len();
+	let end = if text[trimmed..].contains('\n') { trimmed } else { text.len() };
+	if start >= end { range.start..range.start } else { range.start + start..range.start + end }
+}
+
+/// Appends text to a list of children, merging it with a preceding text element (e.g. text before a CDATA section).
+fn push_text<'s>(children: &mut Vec<Element<'s>>, text: Cow<'s, str>) {
+	if let Some(Element::Text(last)) = children.last_mut() {
+		let mut merged = String::from(last.as_str());
+		merged.push_str(&text);
+		last.set(merged);
+	} else {
+		children.push(Element::Text(Text::from(text)));
+	}
+}
+
+fn build<'s>(tag: &'s str, attrs: Vec<Attr<'s>>, mut children: Vec<Element<'s>>, source: &'s str, span: Span) -> Element<'s> {
+	let typed = match tag {
+		// MathML is kept as raw markup rather than parsed into custom elements
+		"math" => math(&attrs, source),
+		_ => typed(tag, &attrs, &mut children)
+	};
+	let mut el = match typed {
+		Some(el) => el,
+		None => {
+			let mut el = CustomElement::new(tag).with_children(children);
+			for (name, value) in attrs {
+				el = el.with_attr(name, value);
+			}
+			Element::Custom(el)
+		}
+	};
+	if let Some(annotations) = el.annotations_mut() {
+		annotations.set_span(span);
+	}
+	el
+}
+
+/// Converts an element to its typed representation, or returns `None` if it has no typed representation. `children`
+/// is only taken if conversion succeeds.
+fn typed<'s>(tag: &str, attrs: &[Attr<'s>], children: &mut Vec<Element<'s>>) -> Option<Element<'s>> {
+	match tag {
+		"audio" => {
+			let mut audio = Audio::default();
+			let mut has_src = false;
+			let mut extra = Vec::new();
+			for (name, value) in attrs {
+				match *name {
+					"src" => {
+						audio.set_src(value.clone());
+						has_src = true;
+					}
+					"clipBegin" => audio.set_clip_begin(value.parse::<TimeDesignation>().ok()?),
+					"clipEnd" => audio.set_clip_end(value.parse::<TimeDesignation>().ok()?),
+					"repeatDur" => audio.set_repeat(AudioRepeat::Duration(value.parse().ok()?)),
+					"times" | "repeatCount" => audio.set_repeat(AudioRepeat::Times(value.parse().ok()?)),
+					"soundLevel" => audio.set_sound_level(value.parse::<Decibels>().ok()?),
+					"speed" => audio.set_speed(percentage(value)?),
+					"fetchtimeout" => audio.set_fetch_timeout(value.parse::<TimeDesignation>().ok()?),
+					"fetchhint" => audio.set_fetch_hint(fetch_hint(value)?),
+					"maxage" => audio.set_max_age(value.parse().ok()?),
+					"maxstale" => audio.set_max_stale(value.parse().ok()?),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			if !has_src {
+				return None;
+			}
+			let mut alternate = Vec::new();
+			for child in mem::take(children) {
+				match desc(&child) {
+					Some(desc) => audio.descs_mut().push(desc),
+					None => alternate.push(child)
+				}
+			}
+			*audio.alternate_mut() = alternate;
+			audio.extra_attrs = extra;
+			Some(Element::Audio(audio))
+		}
+		"voice" => {
+			let mut config = VoiceConfig::default();
+			let mut extra = Vec::new();
+			for (name, value) in attrs {
+				match *name {
+					"name" => config.names = Some(split_list(value)),
+					"gender" => {
+						config.gender = Some(match &**value {
+							"neutral" => VoiceGender::Neutral,
+							"female" => VoiceGender::Female,
+							"male" => VoiceGender::Male,
+							_ => return None
+						})
+					}
+					"age" => config.age = Some(value.parse().ok()?),
+					"variant" => config.variant = Some(value.parse().ok()?),
+					"language" => config.languages = Some(split_list(value)),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			let mut voice = Voice::new(config, mem::take(children));
+			voice.attrs = extra;
+			Some(Element::Voice(voice))
+		}
+		"break" => {
+			let mut el = Break::new();
+			for (name, value) in attrs {
+				match *name {
+					"strength" if el.strength().is_none() => el.set_strength(break_strength(value)?),
+					"time" if el.time().is_none() => el.set_time(value.parse::<TimeDesignation>().ok()?),
+					_ => return None
+				}
+			}
+			if !children.is_empty() {
+				return None;
+			}
+			Some(Element::Break(el))
+		}
+		"emphasis" => {
+			let mut level = EmphasisLevel::default();
+			let mut extra = Vec::new();
+			for (name, value) in attrs {
+				match *name {
+					"level" => {
+						level = match &**value {
+							"reduced" => EmphasisLevel::Reduced,
+							"none" => EmphasisLevel::None,
+							"moderate" => EmphasisLevel::Moderate,
+							"strong" => EmphasisLevel::Strong,
+							_ => return None
+						}
+					}
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			let mut emphasis = Emphasis::new(level, mem::take(children));
+			emphasis.extra_attrs = extra;
+			Some(Element::Emphasis(emphasis))
+		}
+		"mark" | "bookmark" => {
+			let key = if tag == "mark" { "name" } else { "mark" };
+			let (mut mark_name, mut extra) = (None, Vec::new());
+			for (name, value) in attrs {
+				match *name {
+					name if name == key => mark_name = Some(value.clone()),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			if !children.is_empty() {
+				return None;
+			}
+			let mut mark = Mark::new(mark_name?);
+			mark.extra_attrs = extra;
+			Some(Element::Mark(mark))
+		}
+		"lexicon" => {
+			let (mut uri, mut id, mut media_type) = (None, None, None);
+			let mut extra = Vec::new();
+			for (name, value) in attrs {
+				match *name {
+					"uri" => uri = Some(value.clone()),
+					"xml:id" => id = Some(value.clone()),
+					"type" => media_type = Some(value.clone()),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			if !children.is_empty() {
+				return None;
+			}
+			let mut lexicon = Lexicon::new(uri?);
+			lexicon.set_id(id);
+			lexicon.set_media_type(media_type);
+			lexicon.extra_attrs = extra;
+			Some(Element::Lexicon(lexicon))
+		}
+		"say-as" => {
+			let (mut interpret_as, mut format, mut detail) = (None, None, None);
+			let mut extra = Vec::new();
+			for (name, value) in attrs {
+				match *name {
+					"interpret-as" => interpret_as = Some(&**value),
+					"format" => format = Some(&**value),
+					"detail" => detail = Some(&**value),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			let mut say_as = SayAs::with_elements(speech_format(interpret_as?, format, detail), mem::take(children));
+			say_as.extra_attrs = extra;
+			Some(Element::SayAs(say_as))
+		}
+		"phoneme" => {
+			let (mut alphabet, mut ph) = (None, None);
+			let mut extra = Vec::new();
+			for (name, value) in attrs {
+				match *name {
+					"alphabet" => {
+						alphabet = Some(match &**value {
+							"ipa" => Alphabet::Ipa,
+							"x-sampa" => Alphabet::XSampa,
+							_ => Alphabet::Custom(Cow::Owned(value.to_string()))
+						})
+					}
+					"ph" => ph = Some(value.clone()),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			let text = text_content(children)?;
+			let mut phoneme = Phoneme::new(alphabet?, ph?, text);
+			phoneme.extra_attrs = extra;
+			Some(Element::Phoneme(phoneme))
+		}
+		"p" | "paragraph" => {
+			let mut paragraph = Paragraph::new(mem::take(children));
+			paragraph.extra_attrs = extra_attrs(attrs);
+			Some(Element::Paragraph(paragraph))
+		}
+		"s" | "sentence" => {
+			let mut sentence = Sentence::new(mem::take(children));
+			sentence.extra_attrs = extra_attrs(attrs);
+			Some(Element::Sentence(sentence))
+		}
+		"w" | "token" => {
+			let (mut role, mut extra) = (None, Vec::new());
+			for (name, value) in attrs {
+				match *name {
+					"role" => role = Some(value.clone()),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			let mut word = Word::new(mem::take(children));
+			word.extra_attrs = extra;
+			if let Some(role) = role {
+				word.set_role(role);
+			}
+			Some(Element::Word(word))
+		}
+		"sub" => {
+			let (mut alias, mut extra) = (None, Vec::new());
+			for (name, value) in attrs {
+				match *name {
+					"alias" => alias = Some(value.clone()),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			let mut sub = Sub::new(alias?, text_content(children)?);
+			sub.extra_attrs = extra;
+			Some(Element::Sub(sub))
+		}
+		"lang" => {
+			let (mut language, mut failure) = (None, None);
+			let mut extra = Vec::new();
+			for (name, value) in attrs {
+				match *name {
+					"xml:lang" => language = Some(value.clone()),
+					"onlangfailure" => failure = Some(lang_failure(value)?),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			let mut lang = Lang::new(language?, mem::take(children));
+			lang.extra_attrs = extra;
+			if let Some(failure) = failure {
+				lang.set_failure_behavior(failure);
+			}
+			Some(Element::Lang(lang))
+		}
+		"prosody" => {
+			let mut control = ProsodyControl::default();
+			let (mut mstts_attrs, mut extra) = (Vec::new(), Vec::new());
+			for (name, value) in attrs {
+				match *name {
+					"pitch" => control.pitch = Some(prosody_pitch(value)?),
+					"contour" => control.contour = Some(prosody_contour(value)?),
+					"range" => control.range = Some(prosody_pitch(value)?),
+					"rate" => control.rate = Some(prosody_rate(value)?),
+					"duration" => control.duration = Some(value.parse().ok()?),
+					"volume" => control.volume = Some(prosody_volume(value)?),
+					_ => match name.strip_prefix("mstts:") {
+						Some(name) => mstts_attrs.push((Cow::Borrowed(name), value.clone())),
+						None => extra.push((Cow::Borrowed(*name), value.clone()))
+					}
+				}
+			}
+			let mut prosody = Prosody::new(control, mem::take(children));
+			prosody.mstts_attrs = mstts_attrs;
+			prosody.extra_attrs = extra;
+			Some(Element::Prosody(prosody))
+		}
+		"mstts:express-as" => {
+			let (mut style, mut degree, mut role) = (None, None, None);
+			let mut extra = Vec::new();
+			for (name, value) in attrs {
+				match *name {
+					"style" => style = Some(value.to_string()),
+					"styledegree" => degree = Some(value.parse::<f32>().ok()?),
+					"role" => role = Some(express_role(value)?),
+					_ => extra.push((Cow::Borrowed(*name), value.clone()))
+				}
+			}
+			let mut expression = Expression::new(style?);
+			if let Some(degree) = degree {
+				expression.set_degree(degree);
+			}
+			let mut express = mstts::express(expression, mem::take(children));
+			express.set_role(role);
+			express.extra_attrs = extra;
+			Some(express.into_el())
+		}
+		"mstts:silence" => {
+			let (mut kind, mut value) = (None, None);
+			let mut extra = Vec::new();
+			for (name, attr) in attrs {
+				match *name {
+					"type" => kind = Some(silence_type(attr)?),
+					"value" => value = Some(attr.parse::<TimeDesignation>().ok()?),
+					_ => extra.push((Cow::Borrowed(*name), attr.clone()))
+				}
+			}
+			let mut silence = mstts::silence(kind?, value?);
+			silence.extra_attrs = extra;
+			Some(silence.into_el())
+		}
+		"mstts:ttsembedding" => {
+			let mut speaker_profile_id = None;
+			let mut extra = Vec::new();
+			for (name, attr) in attrs {
+				match *name {
+					"speakerProfileId" => speaker_profile_id = Some(attr.clone()),
+					_ => extra.push((Cow::Borrowed(*name), attr.clone()))
+				}
+			}
+			let mut embedding = mstts::tts_embedding(speaker_profile_id?, mem::take(children));
+			embedding.extra_attrs = extra;
+			Some(embedding.into_el())
+		}
+		"mstts:audioduration" => {
+			let mut value = None;
+			let mut extra = Vec::new();
+			for (name, attr) in attrs {
+				match *name {
+					"value" => value = Some(attr.parse::<TimeDesignation>().ok()?),
+					_ => extra.push((Cow::Borrowed(*name), attr.clone()))
+				}
+			}
+			let mut duration = mstts::audio_duration(value?);
+			duration.extra_attrs = extra;
+			Some(duration.into_el())
+		}
+		"mstts:viseme" => {
+			let (mut kind, mut frame_rate, mut dimension) = (None, None, None);
+			let mut extra = Vec::new();
+			for (name, attr) in attrs {
+				match *name {
+					"type" => kind = Some(viseme_type(attr)?),
+					"framerate" => frame_rate = Some(attr.parse::<u32>().ok()?),
+					"dimension" => dimension = Some(viseme_dimension(attr)?),
+					_ => extra.push((Cow::Borrowed(*name), attr.clone()))
+				}
+			}
+			let mut viseme = mstts::viseme(kind?);
+			viseme.set_frame_rate(frame_rate);
+			viseme.set_dimension(dimension);
+			viseme.extra_attrs = extra;
+			Some(viseme.into_el())
+		}
+		"mstts:backgroundaudio" => {
+			let (mut src, mut volume, mut fade_in, mut fade_out) = (None, None, None, None);
+			let mut extra = Vec::new();
+			for (name, attr) in attrs {
+				match *name {
+					"src" => src = Some(attr.clone()),
+					"volume" => volume = Some(attr.parse::<f32>().ok()?),
+					// fades are given in milliseconds, without a unit
+					"fadein" => fade_in = Some(TimeDesignation::from_millis(attr.parse::<f32>().ok()?)),
+					"fadeout" => fade_out = Some(TimeDesignation::from_millis(attr.parse::<f32>().ok()?)),
+					_ => extra.push((Cow::Borrowed(*name), attr.clone()))
+				}
+			}
+			let mut background = mstts::background_audio(src?);
+			background.set_volume(volume);
+			background.set_fade_in(fade_in);
+			background.set_fade_out(fade_out);
+			background.extra_attrs = extra;
+			Some(background.into_el())
+		}
+		"amazon:domain" => {
+			let mut name = None;
+			let mut extra = Vec::new();
+			for (attr, value) in attrs {
+				match *attr {
+					"name" => name = Some(polly::DomainName::from(&**value)),
+					_ => extra.push((Cow::Borrowed(*attr), value.clone()))
+				}
+			}
+			let mut domain = polly::domain(name?, mem::take(children));
+			domain.extra_attrs = extra;
+			Some(domain.into_el())
+		}
+		"amazon:effect" => {
+			let mut kind = None;
+			let mut extra = Vec::new();
+			for (attr, value) in attrs {
+				match (*attr, &**value) {
+					("name", "whispered") => kind = Some(polly::EffectKind::Whispered),
+					("name", "drc") => kind = Some(polly::EffectKind::DynamicRangeCompression),
+					("phonation", "soft") => kind = Some(polly::EffectKind::SoftPhonation),
+					// only relative changes are modeled
+					("vocal-tract-length", value) if value.starts_with(['+', '-']) => {
+						kind = Some(polly::EffectKind::VocalTractLength(value.strip_suffix('%')?.parse().ok()?))
+					}
+					_ => extra.push((Cow::Borrowed(*attr), value.clone()))
+				}
+			}
+			let mut effect = polly::effect(kind?, mem::take(children));
+			effect.extra_attrs = extra;
+			Some(effect.into_el())
+		}
+		"express-as" => {
+			let mut expression = None;
+			let mut extra = Vec::new();
+			for (attr, value) in attrs {
+				match *attr {
+					"type" => expression = Some(watson::ExpressionType::from(&**value)),
+					_ => extra.push((Cow::Borrowed(*attr), value.clone()))
+				}
+			}
+			let mut express = watson::express_as(expression?, mem::take(children));
+			express.extra_attrs = extra;
+			Some(express.into_el())
+		}
+		"voice-transformation" => {
+			let kind = match attrs.iter().find(|(name, _)| *name == "type").map(|(_, value)| &**value)? {
+				"Young" => watson::TransformationType::Young,
+				"Soft" => watson::TransformationType::Soft,
+				"Custom" => watson::TransformationType::Custom,
+				_ => return None
+			};
+			let mut transformation = watson::voice_transformation(kind, mem::take(children));
+			for (attr, value) in attrs {
+				// parameters given as keywords (like `pitch="x-low"`) aren't modeled, & are kept as-is
+				let percent = value.strip_suffix('%').and_then(|v| v.parse::<f32>().ok());
+				match (*attr, percent) {
+					("type", _) => {}
+					("strength", Some(percent)) => transformation.set_strength(percent),
+					("breathiness", Some(percent)) => transformation.set_breathiness(percent),
+					("pitch", Some(percent)) => transformation.set_pitch(percent),
+					("pitch_range", Some(percent)) => transformation.set_pitch_range(percent),
+					("glottal_tension", Some(percent)) => transformation.set_glottal_tension(percent),
+					("rate", Some(percent)) => transformation.set_rate(percent),
+					("timbre_extent", Some(percent)) => transformation.set_timbre_extent(percent),
+					("timbre", _) => transformation.set_timbre(Some(value.clone())),
+					_ => transformation.extra_attrs.push((Cow::Borrowed(*attr), value.clone()))
+				}
+			}
+			Some(transformation.into_el())
+		}
+		_ => None
+	}
+}
+
+/// Converts a `<math>` element to a [`mstts::Math`] element holding its contents as raw markup.
+fn math<'s>(attrs: &[Attr<'s>], source: &'s str) -> Option<Element<'s>> {
+	// the contents are between the end of the start tag & the start of the end tag; self-closing tags have none
+	let contents = match (source.find('>'), source.rfind("</")) {
+		(Some(start), Some(end)) if start < end => &source[start + 1..end],
+		_ => ""
+	};
+	let mut math = mstts::math(contents).ok()?;
+	math.extra_attrs = attrs
+		.iter()
+		.filter(|(name, _)| *name != "xmlns")
+		.map(|(name, value)| (Cow::Borrowed(*name), value.clone()))
+		.collect();
+	Some(math.into_el())
+}
+
+/// Collects attributes which aren't modeled by an element, to be re-emitted as-is; see
+/// [`ExtraAttrs`](crate::ExtraAttrs).
+fn extra_attrs<'s>(attrs: &[Attr<'s>]) -> Attrs<'s> {
+	attrs.iter().map(|(name, value)| (Cow::Borrowed(*name), value.clone())).collect()
+}
+
+/// Converts a `<desc>` element to a [`Desc`], or returns `None` if it isn't one or has unsupported attributes.
+fn desc<'s>(el: &Element<'s>) -> Option<Desc<'s>> {
+	let Element::Custom(el) = el else {
+		return None;
+	};
+	if el.tag() != "desc" {
+		return None;
+	}
+	let mut desc = Desc::new(text_content(el.children())?);
+	for (name, value) in el.attrs() {
+		match &**name {
+			"xml:lang" => desc.set_lang(value.clone()),
+			_ => return None
+		}
+	}
+	Some(desc)
+}
+
+/// Returns the concatenated text of `children`, or `None` if any child is not text.
+fn text_content<'s>(children: &[Element<'s>]) -> Option<Cow<'s, str>> {
+	match children {
+		[] => Some(Cow::Borrowed("")),
+		[Element::Text(text)] => Some(text.0.clone()),
+		_ => None
+	}
+}
+
+fn split_list<'s>(value: &Cow<'s, str>) -> Vec<Cow<'s, str>> {
+	match value {
+		Cow::Borrowed(b) => b.split_whitespace().map(Cow::Borrowed).collect(),
+		Cow::Owned(o) => o.split_whitespace().map(|s| Cow::Owned(s.to_string())).collect()
+	}
+}
+
+fn lang_failure(value: &str) -> Option<LangFailure> {
+	Some(match value {
+		"changevoice" => LangFailure::ChangeVoice,
+		"ignoretext" => LangFailure::IgnoreText,
+		"ignorelang" => LangFailure::IgnoreLang,
+		"processorchoice" => LangFailure::ProcessorChoice,
+		_ => return None
+	})
+}
+
+fn express_role(value: &str) -> Option<mstts::ExpressRole> {
+	use mstts::ExpressRole;
+
+	Some(match value.to_ascii_lowercase().as_str() {
+		"girl" => ExpressRole::Girl,
+		"boy" => ExpressRole::Boy,
+		"youngadultfemale" => ExpressRole::YoungAdultFemale,
+		"youngadultmale" => ExpressRole::YoungAdultMale,
+		"olderadultfemale" => ExpressRole::OlderAdultFemale,
+		"olderadultmale" => ExpressRole::OlderAdultMale,
+		"seniorfemale" => ExpressRole::SeniorFemale,
+		"seniormale" => ExpressRole::SeniorMale,
+		_ => return None
+	})
+}
+
+fn viseme_type(value: &str) -> Option<mstts::MicrosoftViseme> {
+	match value {
+		"redlips_front" => Some(mstts::MicrosoftViseme::ById),
+		"FacialExpression" => Some(mstts::MicrosoftViseme::FacialExpression),
+		_ => None
+	}
+}
+
+fn viseme_dimension(value: &str) -> Option<mstts::VisemeDimension> {
+	match value {
+		"2D" => Some(mstts::VisemeDimension::TwoD),
+		"3D" => Some(mstts::VisemeDimension::ThreeD),
+		_ => None
+	}
+}
+
+fn silence_type(value: &str) -> Option<mstts::SilenceType> {
+	use mstts::SilenceType;
+
+	Some(match value.to_ascii_lowercase().as_str() {
+		"leading" => SilenceType::Leading,
+		"leading-exact" => SilenceType::LeadingExact,
+		"tailing" => SilenceType::Tailing,
+		"tailing-exact" => SilenceType::TailingExact,
+		"sentenceboundary" => SilenceType::SentenceBoundary,
+		"sentenceboundary-exact" => SilenceType::SentenceBoundaryExact,
+		"comma-exact" => SilenceType::CommaExact,
+		"semicolon-exact" => SilenceType::SemicolonExact,
+		"enumerationcomma-exact" => SilenceType::EnumerationCommaExact,
+		_ => return None
+	})
+}
+
+fn percentage(value: &str) -> Option<f32> {
+	// Signed percentages are relative changes, which the typed model can't represent; leave them to `CustomElement`.
+	let value = value.strip_suffix('%')?;
+	if value.starts_with(['+', '-']) {
+		return None;
+	}
+	value.parse::<f32>().ok().filter(|v| v.is_finite()).map(|v| v / 100.)
+}
+
+fn fetch_hint(value: &str) -> Option<FetchHint> {
+	Some(match value {
+		"prefetch" => FetchHint::Prefetch,
+		"safe" => FetchHint::Safe,
+		_ => return None
+	})
+}
+
+fn break_strength(value: &str) -> Option<BreakStrength> {
+	Some(match value {
+		"none" => BreakStrength::None,
+		"x-weak" => BreakStrength::ExtraWeak,
+		"weak" => BreakStrength::Weak,
+		"medium" => BreakStrength::Medium,
+		"strong" => BreakStrength::Strong,
+		"x-strong" => BreakStrength::ExtraStrong,
+		_ => return None
+	})
+}
+
+fn speech_format(interpret_as: &str, format: Option<&str>, detail: Option<&str>) -> SpeechFormat {
+	let known = match (interpret_as, format, detail) {
+		("spell-out" | "characters", None, None) => Some(SpeechFormat::SpellOut),
+		("currency", None, None) => Some(SpeechFormat::Currency),
+		("currency", None, Some(code)) => Some(SpeechFormat::CurrencyWith { code: code.into(), language: None }),
+		("cardinal", None, None) => Some(SpeechFormat::Cardinal),
+		("ordinal", None, None) => Some(SpeechFormat::Ordinal),
+		("digits" | "number_digit", None, None) => Some(SpeechFormat::Digits),
+		("time", format, None) => match format {
+			Some(format) => time_format(format).map(SpeechFormat::TimeWith),
+			None => Some(SpeechFormat::Time)
+		},
+		("telephone", None, None) => Some(SpeechFormat::Telephone),
+		("fraction", None, None) => Some(SpeechFormat::Fraction),
+		("unit", None, None) => Some(SpeechFormat::Unit),
+		("expletive", None, None) => Some(SpeechFormat::Expletive),
+		("address", None, None) => Some(SpeechFormat::Address),
+		("name", None, None) => Some(SpeechFormat::Name),
+		("date", Some(format), None) => date_format(format).map(SpeechFormat::Date),
+		_ => None
+	};
+	known.unwrap_or_else(|| SpeechFormat::Custom {
+		interpret_as: interpret_as.into(),
+		format: format.map(Into::into),
+		detail: detail.map(Into::into)
+	})
+}
+
+fn date_format(value: &str) -> Option<DateFormat> {
+	Some(match value {
+		"dmy" => DateFormat::DateMonthYear,
+		"mdy" => DateFormat::MonthDateYear,
+		"ymd" => DateFormat::YearMonthDate,
+		"ym" => DateFormat::YearMonth,
+		"my" => DateFormat::MonthYear,
+		"md" => DateFormat::MonthDate,
+		"dm" => DateFormat::DateMonth,
+		"d" => DateFormat::Date,
+		"m" => DateFormat::Month,
+		"y" => DateFormat::Year,
+		"yyyymmdd" => DateFormat::YearMonthDateCompact,
+		_ => return None
+	})
+}
+
+fn time_format(value: &str) -> Option<TimeFormat> {
+	Some(match value {
+		"hms12" => TimeFormat::Hms12,
+		"hms24" => TimeFormat::Hms24,
+		"hms" => TimeFormat::Hms,
+		"hm" => TimeFormat::Hm,
+		"h" => TimeFormat::Hour,
+		_ => return None
+	})
+}
+
+fn prosody_pitch(value: &str) -> Option<ProsodyPitch> {
+	Some(match value {
+		"x-low" => ProsodyPitch::Lower,
+		"low" => ProsodyPitch::Low,
+		"medium" => ProsodyPitch::Medium,
+		"default" => ProsodyPitch::Default,
+		"high" => ProsodyPitch::High,
+		"x-high" => ProsodyPitch::Higher,
+		_ => {
+			if let Some(st) = value.strip_suffix("st") {
+				ProsodyPitch::Semitone(st.parse().ok()?)
+			} else {
+				ProsodyPitch::Hz(value.strip_suffix("Hz")?.parse().ok()?)
+			}
+		}
+	})
+}
+
+fn prosody_contour(value: &str) -> Option<ProsodyContour> {
+	let mut contour = ProsodyContour::new();
+	for point in value.split_whitespace() {
+		let (time, pitch) = point.strip_prefix('(')?.strip_suffix(')')?.split_once(',')?;
+		contour.push(percentage(time)?, prosody_pitch(pitch)?);
+	}
+	Some(contour)
+}
+
+fn prosody_rate(value: &str) -> Option<ProsodyRate> {
+	Some(match value {
+		"x-slow" => ProsodyRate::Slower,
+		"slow" => ProsodyRate::Slow,
+		"medium" => ProsodyRate::Medium,
+		"default" => ProsodyRate::Default,
+		"fast" => ProsodyRate::Fast,
+		"x-fast" => ProsodyRate::Faster,
+		_ => ProsodyRate::Rate(percentage(value)?)
+	})
+}
+
+fn prosody_volume(value: &str) -> Option<ProsodyVolume> {
+	Some(match value {
+		"silent" => ProsodyVolume::Silent,
+		"x-soft" => ProsodyVolume::Softer,
+		"soft" => ProsodyVolume::Soft,
+		"medium" => ProsodyVolume::Medium,
+		"default" => ProsodyVolume::Default,
+		"loud" => ProsodyVolume::Loud,
+		"x-loud" => ProsodyVolume::Louder,
+		_ => ProsodyVolume::Db(value.parse().ok()?)
+	})
+}
@@ -0,0 +1,149 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+	Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// The phonetic alphabet used by the pronunciation of a [`Phoneme`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alphabet {
+	/// The International Phonetic Alphabet.
+	Ipa,
+	/// The Extended Speech Assessment Methods Phonetic Alphabet, an ASCII representation of the IPA.
+	XSampa,
+	/// A vendor-specific alphabet, e.g. `sapi` or `ups` for ACSS.
+	Custom(Cow<'static, str>)
+}
+
+impl Alphabet {
+	pub fn as_str(&self) -> &str {
+		match self {
+			Self::Ipa => "ipa",
+			Self::XSampa => "x-sampa",
+			Self::Custom(alphabet) => alphabet
+		}
+	}
+}
+
+/// [`Phoneme`] provides a phonetic pronunciation for the contained text.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Phoneme<'s> {
+	alphabet: Alphabet,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	ph: Cow<'s, str>,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) text: Cow<'s, str>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Phoneme<'s> {
+	/// Creates a new [`Phoneme`] element, pronouncing `text` as `ph`.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// let phoneme = ssml::phoneme(ssml::Alphabet::Ipa, "ˈpiː.kæn", "pecan");
+	///
+	/// assert_eq!(
+	/// 	phoneme.serialize_to_string(&ssml::SerializeOptions::default())?,
+	/// 	r#"<phoneme alphabet="ipa" ph="ˈpiː.kæn">pecan</phoneme>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn new(alphabet: Alphabet, ph: impl Into<Cow<'s, str>>, text: impl Into<Cow<'s, str>>) -> Self {
+		Self {
+			alphabet,
+			ph: ph.into(),
+			text: text.into(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn alphabet(&self) -> &Alphabet {
+		&self.alphabet
+	}
+
+	pub fn set_alphabet(&mut self, alphabet: Alphabet) {
+		self.alphabet = alphabet;
+	}
+
+	/// Returns the phonetic pronunciation of the text.
+	pub fn ph(&self) -> &str {
+		&self.ph
+	}
+
+	pub fn set_ph(&mut self, ph: impl Into<Cow<'s, str>>) {
+		self.ph = ph.into();
+	}
+
+	pub fn text(&self) -> &str {
+		&self.text
+	}
+
+	pub fn set_text(&mut self, text: impl Into<Cow<'s, str>>) {
+		self.text = text.into();
+	}
+
+	pub fn to_owned(&self) -> Phoneme<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Phoneme<'static> {
+		Phoneme {
+			alphabet: self.alphabet,
+			ph: match self.ph {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			},
+			text: match self.text {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			},
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+}
+
+impl<'s> Serialize for Phoneme<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.element("phoneme", |writer| {
+			writer.attr("alphabet", self.alphabet.as_str())?;
+			writer.attr("ph", &*self.ph)?;
+			attrs::write(writer, &self.extra_attrs)?;
+			util::write_text(writer, &self.text, options)
+		})
+	}
+}
+
+/// Creates a new [`Phoneme`] element, pronouncing `text` as `ph`.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let phoneme = ssml::phoneme(ssml::Alphabet::XSampa, "\"sIn@k", "Cinnaq");
+///
+/// assert_eq!(
+/// 	phoneme.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<phoneme alphabet="x-sampa" ph="&quot;sIn@k">Cinnaq</phoneme>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn phoneme<'s>(alphabet: Alphabet, ph: impl Into<Cow<'s, str>>, text: impl Into<Cow<'s, str>>) -> Phoneme<'s> {
+	Phoneme::new(alphabet, ph, text)
+}
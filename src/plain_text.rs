@@ -0,0 +1,193 @@
+//! Produce SSML and the plain text it contains in a single serialization pass.
+//!
+//! Applications often need both the SSML to send to a speech synthesis service and the plain text being spoken (e.g.
+//! for subtitles or search indexing). [`serialize_with_plain_text`] produces both at once, along with a
+//! correspondence map between the two.
+//!
+//! ```
+//! use ssml::plain_text::serialize_with_plain_text;
+//!
+//! # fn main() -> ssml::Result<()> {
+//! let doc = ssml::speak(None, ["Tom & Jerry"]) + ssml::mark("pause") + "are back!";
+//! let (ssml, plain) = serialize_with_plain_text(&doc, &ssml::SerializeOptions::default())?;
+//! assert_eq!(
+//! 	ssml,
+//! 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Tom &amp; Jerry<mark name="pause"/>are back!</speak>"#
+//! );
+//! assert_eq!(plain.text(), "Tom & Jerry are back!");
+//!
+//! // Map the offset of "Jerry" in the plain text to its offset in the SSML.
+//! let offset = plain.text().find("Jerry").unwrap();
+//! assert_eq!(plain.to_ssml_offset(offset), ssml.find("Jerry"));
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+	Serialize, SerializeOptions, Speak, XmlWriter,
+	util::{self, Escaper}
+};
+
+/// A run of text which appears in both the serialized SSML and the plain text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlainTextSegment {
+	/// The path of the element this text was written by; see [`Speak::element_at`].
+	pub path: Vec<usize>,
+	/// The range of bytes occupied by the (escaped) text in the serialized SSML.
+	pub ssml: Range<usize>,
+	/// The range of bytes occupied by the text in the plain text.
+	pub plain: Range<usize>
+}
+
+/// A run of text within a serialized document, yielded by [`PlainText::runs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextRun<'p> {
+	/// The path of the element this text was written by; see [`Speak::element_at`].
+	pub path: &'p [usize],
+	/// The (unescaped) text of this run.
+	pub text: &'p str,
+	/// The range of bytes occupied by the (escaped) text in the serialized SSML.
+	pub ssml: Range<usize>,
+	/// The number of bytes of serialized SSML written since the end of the previous run (or the start of the
+	/// document), including this run and any markup preceding it.
+	pub contribution: usize
+}
+
+impl TextRun<'_> {
+	/// Returns the length of the serialized SSML up to the end of this run.
+	pub fn cumulative_len(&self) -> usize {
+		self.ssml.end
+	}
+}
+
+/// The plain text contained within a document, produced by [`serialize_with_plain_text`].
+#[derive(Debug, Clone, Default)]
+pub struct PlainText {
+	text: String,
+	segments: Vec<PlainTextSegment>,
+	/// The escaper the document was serialized with, used to map offsets within escaped text.
+	escaper: Option<Arc<dyn Escaper>>
+}
+
+// the escaper is configuration, not part of the text itself
+impl PartialEq for PlainText {
+	fn eq(&self, other: &Self) -> bool {
+		self.text == other.text && self.segments == other.segments
+	}
+}
+
+impl Eq for PlainText {}
+
+impl PlainText {
+	pub(crate) fn push(&mut self, text: &str, ssml: Range<usize>, path: &[usize]) {
+		if text.is_empty() {
+			return;
+		}
+		// separate adjacent runs, e.g. text on either side of a `<mark>`, like a speech synthesizer would
+		if self.text.ends_with(|c: char| !c.is_whitespace()) && text.starts_with(|c: char| !c.is_whitespace()) {
+			self.text.push(' ');
+		}
+		let start = self.text.len();
+		self.text.push_str(text);
+		self.segments.push(PlainTextSegment {
+			path: path.to_vec(),
+			ssml,
+			plain: start..self.text.len()
+		});
+	}
+
+	/// Returns the plain text.
+	pub fn text(&self) -> &str {
+		&self.text
+	}
+
+	/// Returns the segments of text shared between the plain text and the serialized SSML, in order.
+	pub fn segments(&self) -> &[PlainTextSegment] {
+		&self.segments
+	}
+
+	/// Returns an iterator over the runs of text in the document, along with their contribution to the length of the
+	/// serialized SSML.
+	///
+	/// This can be used to find safe points at which to split a document for providers which limit the length of
+	/// requests, without re-serializing prefixes of the document.
+	///
+	/// ```
+	/// use ssml::plain_text::serialize_with_plain_text;
+	///
+	/// # fn main() -> ssml::Result<()> {
+	/// let doc = ssml::speak(None, ["First sentence.", "Second sentence.", "Third sentence."]);
+	/// let (ssml, plain) = serialize_with_plain_text(&doc, &ssml::SerializeOptions::default())?;
+	///
+	/// // find the last run which fits within 100 bytes
+	/// let cut = plain.runs().take_while(|run| run.cumulative_len() <= 100).last().unwrap();
+	/// assert_eq!(cut.text, "Second sentence.");
+	/// assert_eq!(&ssml[cut.ssml.clone()], "Second sentence.");
+	/// assert_eq!(
+	/// 	plain.runs().map(|run| run.contribution).sum::<usize>(),
+	/// 	ssml.find("Third").unwrap() + "Third sentence.".len()
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn runs(&self) -> impl Iterator<Item = TextRun<'_>> {
+		let mut end = 0;
+		self.segments.iter().map(move |segment| {
+			let contribution = segment.ssml.end - end;
+			end = segment.ssml.end;
+			TextRun {
+				path: &segment.path,
+				text: &self.text[segment.plain.clone()],
+				ssml: segment.ssml.clone(),
+				contribution
+			}
+		})
+	}
+
+	/// Converts a byte offset in the plain text to the corresponding byte offset in the serialized SSML.
+	///
+	/// Returns `None` if the offset falls between segments, i.e. on an inserted separator.
+	pub fn to_ssml_offset(&self, offset: usize) -> Option<usize> {
+		let segment = self.segments.iter().find(|s| s.plain.start <= offset && offset <= s.plain.end)?;
+		let text = self.text.get(segment.plain.start..offset)?;
+		Some(segment.ssml.start + text.chars().map(|c| util::escaped_len_with(self.escaper.as_deref(), c)).sum::<usize>())
+	}
+
+	/// Converts a byte offset in the serialized SSML to the corresponding byte offset in the plain text.
+	///
+	/// Returns `None` if the offset doesn't fall within text, e.g. if it points into a tag. Offsets which point into an
+	/// escaped character resolve to the start of that character.
+	pub fn to_plain_offset(&self, offset: usize) -> Option<usize> {
+		let segment = self.segments.iter().find(|s| s.ssml.start <= offset && offset <= s.ssml.end)?;
+		let mut position = segment.ssml.start;
+		for (i, char) in self.text[segment.plain.clone()].char_indices() {
+			position += util::escaped_len_with(self.escaper.as_deref(), char);
+			if offset < position {
+				return Some(segment.plain.start + i);
+			}
+		}
+		Some(segment.plain.end)
+	}
+
+	/// Consumes this [`PlainText`], returning the plain text as a string.
+	pub fn into_string(self) -> String {
+		self.text
+	}
+}
+
+/// Serializes a document to a string, returning it alongside the plain text contained within the document.
+pub fn serialize_with_plain_text(doc: &Speak, options: &SerializeOptions) -> crate::Result<(String, PlainText)> {
+	let mut out = String::new();
+	let mut writer = XmlWriter::for_options(&mut out, options);
+	writer.plain = Some(PlainText {
+		escaper: writer.escaper.clone(),
+		..PlainText::default()
+	});
+	doc.serialize_xml(&mut writer, options)?;
+
+	let plain = writer.plain.take().unwrap_or_default();
+	Ok((out, plain))
+}
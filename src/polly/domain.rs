@@ -0,0 +1,174 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::{self, Display, Write};
+
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// A speaking style for a [`Domain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DomainName {
+	/// The style of a news anchor.
+	News,
+	/// A more conversational style, as if speaking to friends & family.
+	Conversational,
+	/// A style suited to music-related content, like introducing tracks.
+	Music,
+	/// A style suited to long-form content, like articles.
+	LongForm,
+	/// A domain not (yet) covered by this enum, written as-is to the `name` attribute.
+	Custom(Cow<'static, str>)
+}
+
+impl DomainName {
+	pub fn as_str(&self) -> &str {
+		match self {
+			DomainName::News => "news",
+			DomainName::Conversational => "conversational",
+			DomainName::Music => "music",
+			DomainName::LongForm => "long-form",
+			DomainName::Custom(name) => name
+		}
+	}
+}
+
+impl From<&str> for DomainName {
+	fn from(value: &str) -> Self {
+		match value {
+			"news" => DomainName::News,
+			"conversational" => DomainName::Conversational,
+			"music" => DomainName::Music,
+			"long-form" => DomainName::LongForm,
+			name => DomainName::Custom(Cow::Owned(name.to_string()))
+		}
+	}
+}
+
+impl Display for DomainName {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// Reads a section of a document in a speaking style suited to a domain, e.g. like a news anchor, with Amazon Polly.
+///
+/// Domains are only supported by some Neural voices; see [the Amazon Polly docs][polly] for more information.
+///
+/// [polly]: https://docs.aws.amazon.com/polly/latest/dg/newscaster-style.html
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::polly::{self, DomainName};
+///
+/// let doc = ssml::speak(None, [polly::domain(DomainName::News, ["Stocks rallied today."])]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::AmazonPolly))?,
+/// 	r#"<speak><amazon:domain name="news">Stocks rallied today.</amazon:domain></speak>"#
+/// );
+///
+/// // other flavors don't support `amazon:domain`
+/// assert!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::GoogleCloudTextToSpeech))
+/// 		.is_err()
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Domain<'s> {
+	name: DomainName,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Domain<'s> {
+	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(name: impl Into<DomainName>, elements: I) -> Self {
+		Self {
+			name: name.into(),
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn name(&self) -> &DomainName {
+		&self.name
+	}
+
+	pub fn set_name(&mut self, name: impl Into<DomainName>) {
+		self.name = name.into();
+	}
+
+	/// Extend this domain section with an additional element.
+	pub fn push(&mut self, element: impl Into<Element<'s>>) {
+		self.children.push(element.into());
+	}
+
+	/// Extend this domain section with additional elements.
+	pub fn extend<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&mut self, elements: I) {
+		self.children.extend(elements.into_iter().map(|f| f.into()));
+	}
+
+	/// Returns a reference to the elements contained within this domain section.
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	/// Returns a mutable reference to the elements contained within this domain section.
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
+	pub fn to_owned(&self) -> Domain<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Domain<'static> {
+		Domain {
+			name: self.name,
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`].
+	pub fn into_el(self) -> Element<'s> {
+		Element::FlavorPolly(super::Element::Domain(self))
+	}
+}
+
+impl<'s> From<Domain<'s>> for crate::Element<'s> {
+	fn from(value: Domain<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for Domain<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.element("amazon:domain", |writer| {
+			writer.attr("name", self.name.as_str())?;
+			attrs::write(writer, &self.extra_attrs)?;
+			util::serialize_elements(writer, &self.children, options)
+		})
+	}
+}
+
+/// Creates a new [`Domain`] section, read in the speaking style of the given domain.
+pub fn domain<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(name: impl Into<DomainName>, elements: I) -> Domain<'s> {
+	Domain::new(name, elements)
+}
@@ -0,0 +1,179 @@
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// A voice effect applied by an [`Effect`] section.
+///
+/// Effects are only supported by Amazon Polly's Standard voices.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EffectKind {
+	/// Speak in a whisper. (`name="whispered"`)
+	Whispered,
+	/// Apply dynamic range compression, making speech louder & more clearly audible in noisy environments.
+	/// (`name="drc"`)
+	DynamicRangeCompression,
+	/// Speak softly, in a breathier voice. (`phonation="soft"`)
+	SoftPhonation,
+	/// Change the length of the speaker's vocal tract by a percentage, e.g. `15.0` for a longer & deeper-sounding vocal
+	/// tract or `-10.0` for a shorter one. (`vocal-tract-length="+15%"`)
+	VocalTractLength(f32)
+}
+
+impl EffectKind {
+	fn write_attr<W: Write>(&self, writer: &mut XmlWriter<W>) -> crate::Result<()> {
+		match self {
+			EffectKind::Whispered => writer.attr("name", "whispered"),
+			EffectKind::DynamicRangeCompression => writer.attr("name", "drc"),
+			EffectKind::SoftPhonation => writer.attr("phonation", "soft"),
+			EffectKind::VocalTractLength(percent) => writer.attr("vocal-tract-length", alloc::format!("{percent:+}%"))
+		}
+	}
+}
+
+/// Applies a voice effect to a section of a document with Amazon Polly.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::polly;
+///
+/// let doc = ssml::speak(
+/// 	None,
+/// 	[polly::whispered(["Can you keep a secret?"]).into(), ssml::Element::from(polly::vocal_tract_length(15., ["I am a giant!"]))]
+/// );
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::AmazonPolly))?,
+/// 	concat!(
+/// 		r#"<speak><amazon:effect name="whispered">Can you keep a secret?</amazon:effect>"#,
+/// 		r#"<amazon:effect vocal-tract-length="+15%">I am a giant!</amazon:effect></speak>"#
+/// 	)
+/// );
+///
+/// let doc = ssml::parse::parse_str(r#"<speak><amazon:effect phonation="soft">Shh.</amazon:effect></speak>"#)?;
+/// assert!(matches!(
+/// 	&doc.children()[0],
+/// 	ssml::Element::FlavorPolly(polly::Element::Effect(effect)) if effect.kind() == polly::EffectKind::SoftPhonation
+/// ));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Effect<'s> {
+	kind: EffectKind,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Effect<'s> {
+	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(kind: EffectKind, elements: I) -> Self {
+		Self {
+			kind,
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn kind(&self) -> EffectKind {
+		self.kind
+	}
+
+	pub fn set_kind(&mut self, kind: EffectKind) {
+		self.kind = kind;
+	}
+
+	/// Extend this effect section with an additional element.
+	pub fn push(&mut self, element: impl Into<Element<'s>>) {
+		self.children.push(element.into());
+	}
+
+	/// Extend this effect section with additional elements.
+	pub fn extend<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&mut self, elements: I) {
+		self.children.extend(elements.into_iter().map(|f| f.into()));
+	}
+
+	/// Returns a reference to the elements contained within this effect section.
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	/// Returns a mutable reference to the elements contained within this effect section.
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
+	pub fn to_owned(&self) -> Effect<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Effect<'static> {
+		Effect {
+			kind: self.kind,
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`].
+	pub fn into_el(self) -> Element<'s> {
+		Element::FlavorPolly(super::Element::Effect(self))
+	}
+}
+
+impl<'s> From<Effect<'s>> for crate::Element<'s> {
+	fn from(value: Effect<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for Effect<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.element("amazon:effect", |writer| {
+			self.kind.write_attr(writer)?;
+			attrs::write(writer, &self.extra_attrs)?;
+			util::serialize_elements(writer, &self.children, options)
+		})
+	}
+}
+
+/// Creates a new [`Effect`] section, applying the given effect.
+pub fn effect<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(kind: EffectKind, elements: I) -> Effect<'s> {
+	Effect::new(kind, elements)
+}
+
+/// Creates a new [`Effect`] section which is whispered.
+pub fn whispered<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Effect<'s> {
+	Effect::new(EffectKind::Whispered, elements)
+}
+
+/// Creates a new [`Effect`] section with dynamic range compression applied.
+pub fn drc<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Effect<'s> {
+	Effect::new(EffectKind::DynamicRangeCompression, elements)
+}
+
+/// Creates a new [`Effect`] section which is spoken softly.
+pub fn soft<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Effect<'s> {
+	Effect::new(EffectKind::SoftPhonation, elements)
+}
+
+/// Creates a new [`Effect`] section with the speaker's vocal tract length changed by `percent`.
+pub fn vocal_tract_length<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(percent: f32, elements: I) -> Effect<'s> {
+	Effect::new(EffectKind::VocalTractLength(percent), elements)
+}
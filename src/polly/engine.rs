@@ -0,0 +1,63 @@
+use crate::{ElementKind, Flavor, FlavorSpec, Support};
+
+/// An Amazon Polly voice engine.
+///
+/// [`Flavor::AmazonPolly`] only models Standard voices. Each engine supports a different subset of SSML, so engines
+/// implement [`FlavorSpec`] to enable checks for the engine in use, both when serializing (with
+/// [`SerializeOptions::with_flavor_spec`](crate::SerializeOptions::with_flavor_spec)) and when validating (with
+/// [`LintConfig::flavor_spec`](crate::validate::LintConfig::flavor_spec)).
+///
+/// See the [Amazon Polly documentation](https://docs.aws.amazon.com/polly/latest/dg/supportedtags.html) for the tags
+/// supported by each engine.
+///
+/// ```
+/// # use ssml::Serialize;
+/// use ssml::{ElementKind, FlavorSpec, Support, polly::Engine};
+///
+/// assert_eq!(Engine::Standard.supports(ElementKind::Emphasis), Support::Full);
+/// assert_eq!(Engine::Neural.supports(ElementKind::Emphasis), Support::None);
+///
+/// let doc = ssml::speak(None, [ssml::emphasis(ssml::EmphasisLevel::Strong, ["Wow!"])]);
+/// let options = ssml::SerializeOptions::default().with_flavor_spec(Engine::Neural);
+/// assert!(matches!(doc.serialize_to_string(&options), Err(ssml::Error::UnsupportedElement("emphasis", _))));
+///
+/// let diagnostics =
+/// 	ssml::validate::validate_with(&doc, &ssml::validate::LintConfig::default().flavor_spec(Engine::Neural));
+/// assert_eq!(diagnostics[0].lint, ssml::validate::Lint::UnsupportedElement);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Engine {
+	#[default]
+	Standard,
+	Neural,
+	LongForm,
+	Generative
+}
+
+impl FlavorSpec for Engine {
+	fn base(&self) -> Flavor {
+		Flavor::AmazonPolly
+	}
+
+	fn supports(&self, kind: ElementKind) -> Support {
+		match (self, kind) {
+			(_, ElementKind::Audio | ElementKind::Voice | ElementKind::Lexicon) => Support::None,
+			(_, kind) if kind.is_mstts() => Support::None,
+			(Engine::Standard, ElementKind::AmazonDomain) => Support::None,
+			(Engine::Standard, _) => Support::Full,
+			(_, ElementKind::Emphasis) => Support::None,
+			// `news` & `conversational` are only available for some Neural voices
+			(Engine::Neural, ElementKind::AmazonDomain) => Support::Partial,
+			(Engine::LongForm | Engine::Generative, ElementKind::AmazonDomain) => Support::None,
+			// only `drc` is available outside of Standard voices
+			(Engine::Neural | Engine::LongForm, ElementKind::AmazonEffect) => Support::Partial,
+			(Engine::Generative, ElementKind::AmazonEffect) => Support::None,
+			// pitch isn't supported, and only some `interpret-as` values are supported by Generative voices
+			(_, ElementKind::Prosody) => Support::Partial,
+			(Engine::Generative, ElementKind::SayAs) => Support::Partial,
+			_ => Support::Full
+		}
+	}
+}
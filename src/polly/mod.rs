@@ -0,0 +1,70 @@
+//! Elements exclusive to [`Flavor::AmazonPolly`](crate::Flavor::AmazonPolly).
+//!
+//! Amazon Polly predeclares the `amazon` namespace, so these elements are written without an `xmlns:amazon`
+//! declaration on the root `<speak>` element; one is accepted (and dropped) when parsing.
+//!
+//! ```
+//! # use ssml::{Flavor, Serialize, SerializeOptions};
+//! # fn main() -> ssml::Result<()> {
+//! let doc = ssml::parse::parse_str(
+//! 	r#"<speak xmlns:amazon="https://aws.amazon.com/polly"><amazon:domain name="conversational">Hey!</amazon:domain></speak>"#
+//! )?;
+//! assert!(matches!(doc.children()[0], ssml::Element::FlavorPolly(ssml::polly::Element::Domain(_))));
+//! assert_eq!(
+//! 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::AmazonPolly))?,
+//! 	r#"<speak><amazon:domain name="conversational">Hey!</amazon:domain></speak>"#
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+
+pub mod domain;
+pub mod effect;
+mod engine;
+pub use self::{
+	domain::{Domain, DomainName, domain},
+	effect::{Effect, EffectKind, drc, effect, soft, vocal_tract_length, whispered},
+	engine::Engine
+};
+
+crate::element::el! {
+	#[derive(Debug, Clone)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub enum Element<'s> {
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Domain(Domain<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		Effect(Effect<'s>)
+	}
+}
+
+impl<'s> Element<'s> {
+	pub fn to_owned(&self) -> Element<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Element<'static> {
+		match self {
+			Self::Domain(el) => Element::Domain(el.into_owned()),
+			Self::Effect(el) => Element::Effect(el.into_owned())
+		}
+	}
+
+	/// Returns a reference to the child elements of this element, if it is a container.
+	pub fn children(&self) -> Option<&[crate::Element<'s>]> {
+		match self {
+			Self::Domain(el) => Some(el.children()),
+			Self::Effect(el) => Some(el.children())
+		}
+	}
+
+	/// Returns a mutable reference to the child elements of this element, if it is a container.
+	pub fn children_mut(&mut self) -> Option<&mut Vec<crate::Element<'s>>> {
+		match self {
+			Self::Domain(el) => Some(el.children_mut()),
+			Self::Effect(el) => Some(el.children_mut())
+		}
+	}
+}
@@ -1,10 +1,17 @@
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, format, string::ToString, vec::Vec};
 use core::{
 	fmt::{self, Display, Write},
 	ops::{Add, AddAssign}
 };
 
-use crate::{Decibels, Element, Serialize, SerializeOptions, TimeDesignation, XmlWriter, unit::SpeedFormatter, util, xml::TrustedNoEscape};
+use crate::{
+	Decibels, Element, Flavor, Serialize, SerializeOptions, TimeDesignation, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	unit::SpeedFormatter,
+	util,
+	xml::TrustedNoEscape
+};
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -255,14 +262,29 @@ impl From<ProsodyVolume> for ProsodyControl {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Prosody<'s> {
 	control: ProsodyControl,
-	pub(crate) children: Vec<Element<'s>>
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) children: Vec<Element<'s>>,
+	/// Attributes in the `mstts` namespace, only written for ACSS; see
+	/// [`MicrosoftProsodyExt`](crate::mstts::MicrosoftProsodyExt).
+	#[cfg_attr(feature = "serde", serde(borrow, deserialize_with = "crate::serde_borrow::pairs"))]
+	pub(crate) mstts_attrs: Vec<(Cow<'s, str>, Cow<'s, str>)>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> Prosody<'s> {
 	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(control: impl Into<ProsodyControl>, elements: I) -> Self {
 		Self {
 			control: control.into(),
-			children: elements.into_iter().map(|f| f.into()).collect()
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			mstts_attrs: Vec::new(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
 		}
 	}
 
@@ -331,7 +353,25 @@ impl<'s> Prosody<'s> {
 	pub fn into_owned(self) -> Prosody<'static> {
 		Prosody {
 			control: self.control,
-			children: self.children.into_iter().map(Element::into_owned).collect()
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			mstts_attrs: self
+				.mstts_attrs
+				.into_iter()
+				.map(|(k, v)| {
+					(
+						match k {
+							Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+							Cow::Owned(b) => Cow::Owned(b)
+						},
+						match v {
+							Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+							Cow::Owned(b) => Cow::Owned(b)
+						}
+					)
+				})
+				.collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
@@ -340,10 +380,17 @@ impl<'s> Serialize for Prosody<'s> {
 	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
 		writer.element("prosody", |writer| {
 			writer.attr_opt("pitch", self.control.pitch.as_ref())?;
+			writer.attr_opt("contour", self.control.contour.as_ref())?;
 			writer.attr_opt("range", self.control.range.as_ref())?;
 			writer.attr_opt("rate", self.control.rate.as_ref())?;
 			writer.attr_opt("duration", self.control.duration.as_ref())?;
 			writer.attr_opt("volume", self.control.volume.as_ref())?;
+			if options.flavor == Flavor::MicrosoftAzureCognitiveSpeechServices {
+				for (name, value) in &self.mstts_attrs {
+					writer.attr(format!("mstts:{name}"), &**value)?;
+				}
+			}
+			attrs::write(writer, &self.extra_attrs)?;
 			util::serialize_elements(writer, &self.children, options)
 		})
 	}
@@ -0,0 +1,287 @@
+//! Map error messages returned by speech synthesis services back to the elements which likely caused them.
+//!
+//! When a provider rejects a request, its error message can only describe the problem in terms of the SSML it received:
+//! a line & column, or the name of an element, attribute or voice. [`ProviderError::parse`] extracts what it can from
+//! the error messages of ACSS & Amazon Polly (e.g. `InvalidSsmlException`), and [`ProviderError::locate`] resolves it
+//! to elements of the original document using the [`SourceMap`] recorded while serializing it.
+//!
+//! ```
+//! # fn main() -> ssml::Result<()> {
+//! use ssml::{
+//! 	Flavor,
+//! 	provider_error::{ProviderError, ProviderErrorKind},
+//! 	source_map::serialize_with_source_map
+//! };
+//!
+//! let doc = ssml::speak(
+//! 	None,
+//! 	[ssml::voice("en-US-JennyNeural", ["Hello!"]), ssml::voice("en-US-NotARealNeural", ["Goodbye!"])]
+//! );
+//! let options = ssml::SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices);
+//! let (ssml, map) = serialize_with_source_map(&doc, &options)?;
+//!
+//! // ... the request is rejected with:
+//! let error =
+//! 	ProviderError::parse(Flavor::MicrosoftAzureCognitiveSpeechServices, "Unsupported voice en-US-NotARealNeural.");
+//! assert_eq!(error.kind, ProviderErrorKind::UnsupportedVoice(Some("en-US-NotARealNeural".into())));
+//! assert_eq!(error.locate(&ssml, &map), [vec![1]]);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{
+	string::{String, ToString},
+	vec::Vec
+};
+use core::fmt::{self, Display};
+
+use crate::{Flavor, source_map::SourceMap, span::LineColumn, validate::Lint};
+
+/// The problem described by a [`ProviderError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProviderErrorKind {
+	/// The SSML couldn't be parsed or was otherwise rejected as invalid.
+	InvalidSsml,
+	/// An element isn't supported, e.g. by the requested voice. Holds the element's tag.
+	UnsupportedElement(String),
+	/// An attribute or its value isn't supported. Holds the attribute's name.
+	UnsupportedAttribute(String),
+	/// The requested voice doesn't exist or isn't available. Holds the voice's name, if the message included it.
+	UnsupportedVoice(Option<String>),
+	/// A problem this module doesn't recognize.
+	Other
+}
+
+impl ProviderErrorKind {
+	/// Returns the lint which would have caught this problem before the request was made, if any.
+	pub fn lint(&self) -> Option<Lint> {
+		match self {
+			ProviderErrorKind::UnsupportedElement(_) => Some(Lint::UnsupportedElement),
+			ProviderErrorKind::UnsupportedAttribute(_) => Some(Lint::UnsupportedAttribute),
+			_ => None
+		}
+	}
+}
+
+/// An error message returned by a speech synthesis service, parsed with [`ProviderError::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderError {
+	/// The flavor of the service which returned the error.
+	pub flavor: Flavor,
+	pub kind: ProviderErrorKind,
+	/// The position in the submitted SSML the error was reported at, if any.
+	pub position: Option<LineColumn>,
+	/// The original error message.
+	pub message: String
+}
+
+impl ProviderError {
+	/// Parses an error message returned by the service of the given `flavor`.
+	///
+	/// Parsing is best-effort: messages which aren't recognized are parsed as [`ProviderErrorKind::Other`], though any
+	/// position they contain (e.g. `Line: 1, Column: 57`) is still extracted.
+	///
+	/// ```
+	/// use ssml::{
+	/// 	Flavor,
+	/// 	provider_error::{ProviderError, ProviderErrorKind},
+	/// 	span::LineColumn
+	/// };
+	///
+	/// let error = ProviderError::parse(
+	/// 	Flavor::MicrosoftAzureCognitiveSpeechServices,
+	/// 	"Connection was closed by the remote host. Error code: 1007. Error details: Ssml parsing failed. Line: 1, Column: 120"
+	/// );
+	/// assert_eq!(error.kind, ProviderErrorKind::InvalidSsml);
+	/// assert_eq!(error.position, Some(LineColumn { line: 1, column: 120 }));
+	///
+	/// let error = ProviderError::parse(
+	/// 	Flavor::AmazonPolly,
+	/// 	"An error occurred (InvalidSsmlException) when calling the SynthesizeSpeech operation: Invalid SSML request"
+	/// );
+	/// assert_eq!(error.kind, ProviderErrorKind::InvalidSsml);
+	/// ```
+	pub fn parse(flavor: Flavor, message: &str) -> ProviderError {
+		let lower = message.to_ascii_lowercase();
+		let position = number_after(&lower, "line").and_then(|line| {
+			let column = ["column", "position", "col"].into_iter().find_map(|key| number_after(&lower, key))?;
+			Some(LineColumn { line, column })
+		});
+
+		let kind = if let Some(voice) = lower.find("unsupported voice").map(|i| word_at(message, i + "unsupported voice".len())) {
+			ProviderErrorKind::UnsupportedVoice(voice.map(ToString::to_string))
+		} else if lower.contains("voice") && (lower.contains("not found") || lower.contains("does not exist") || lower.contains("not supported")) {
+			ProviderErrorKind::UnsupportedVoice(quoted_after(message, "voice").map(ToString::to_string))
+		} else if let Some(name) = quoted_after(message, "attribute") {
+			ProviderErrorKind::UnsupportedAttribute(name.to_string())
+		} else if let Some(tag) = quoted_after(message, "element")
+			.or_else(|| quoted_after(message, "tag"))
+			.or_else(|| tag_in(message))
+		{
+			ProviderErrorKind::UnsupportedElement(tag.to_string())
+		} else if lower.contains("invalidssml")
+			|| lower.contains("invalid ssml")
+			|| lower.contains("ssml pars")
+			|| lower.contains("failed to parse")
+			|| lower.contains("error code: 1007")
+		{
+			ProviderErrorKind::InvalidSsml
+		} else {
+			ProviderErrorKind::Other
+		};
+
+		ProviderError {
+			flavor,
+			kind,
+			position,
+			message: message.to_string()
+		}
+	}
+
+	/// Returns the paths of the elements which likely caused this error, most likely first; see
+	/// [`Speak::element_at`](crate::Speak::element_at).
+	///
+	/// `ssml` & `map` must be the output of [`serialize_with_source_map`](crate::source_map::serialize_with_source_map)
+	/// for the request which failed. The element at the reported position is returned first, followed by every element
+	/// matching the tag, attribute or voice named by the error. Returns an empty list if nothing could be located.
+	///
+	/// ```
+	/// # fn main() -> ssml::Result<()> {
+	/// use ssml::{Flavor, provider_error::ProviderError, source_map::serialize_with_source_map};
+	///
+	/// let doc = ssml::speak(
+	/// 	None,
+	/// 	[ssml::voice(
+	/// 		"en-US-JennyNeural",
+	/// 		[ssml::Element::from("Hello, "), ssml::CustomElement::new("mstts:emotion").with_child("world").into()]
+	/// 	)]
+	/// );
+	/// let options = ssml::SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices);
+	/// let (ssml, map) = serialize_with_source_map(&doc, &options)?;
+	///
+	/// let column = ssml.find("<mstts:emotion").unwrap() + 1;
+	/// let message = format!("Error code: 1007. Error details: Ssml parsing failed. Line: 1, Column: {column}");
+	/// let error = ProviderError::parse(Flavor::MicrosoftAzureCognitiveSpeechServices, &message);
+	/// assert_eq!(error.locate(&ssml, &map), [vec![0, 1]]);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn locate(&self, ssml: &str, map: &SourceMap) -> Vec<Vec<usize>> {
+		let mut paths: Vec<Vec<usize>> = Vec::new();
+		if let Some(span) = self
+			.position
+			.and_then(|position| offset_of(ssml, position))
+			.and_then(|offset| map.element_at_offset(offset))
+		{
+			if !span.path.is_empty() {
+				paths.push(span.path.clone());
+			}
+		}
+
+		for span in map.spans().iter().filter(|span| !span.path.is_empty()) {
+			let Some(start_tag) = ssml.get(span.range.clone()).and_then(start_tag) else {
+				continue;
+			};
+			let matches = match &self.kind {
+				ProviderErrorKind::UnsupportedElement(tag) => tag_name(start_tag) == tag,
+				ProviderErrorKind::UnsupportedAttribute(name) => has_attr(start_tag, name, None),
+				ProviderErrorKind::UnsupportedVoice(Some(name)) => tag_name(start_tag) == "voice" && has_attr(start_tag, "name", Some(name)),
+				_ => false
+			};
+			if matches && !paths.contains(&span.path) {
+				paths.push(span.path.clone());
+			}
+		}
+		paths
+	}
+}
+
+impl Display for ProviderError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.position {
+			Some(position) => write!(f, "{} (at {position})", self.message),
+			None => f.write_str(&self.message)
+		}
+	}
+}
+
+/// Parses the number following `key` in `lower`, skipping a `:` and whitespace, e.g. `line: 1`.
+fn number_after(lower: &str, key: &str) -> Option<usize> {
+	lower.match_indices(key).find_map(|(i, _)| {
+		let rest = lower[i + key.len()..].trim_start_matches([':', ' ', '=']);
+		let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+		rest[..digits].parse().ok()
+	})
+}
+
+/// Returns the first whitespace-delimited word after `offset`, without quotes or trailing punctuation.
+fn word_at(message: &str, offset: usize) -> Option<&str> {
+	let word = message[offset..].split_whitespace().next()?;
+	let word = word.trim_matches(|c: char| matches!(c, '\'' | '"' | '`' | '.' | ',' | ';' | ':'));
+	(!word.is_empty()).then_some(word)
+}
+
+/// Returns the quoted name following the first occurrence of `key` (case-insensitively), e.g. `element 'prosody'`.
+fn quoted_after<'m>(message: &'m str, key: &str) -> Option<&'m str> {
+	let i = message.to_ascii_lowercase().find(key)?;
+	let rest = message[i + key.len()..].trim_start_matches([':', ' ']);
+	let quote = rest.chars().next().filter(|c| matches!(c, '\'' | '"' | '`' | '<'))?;
+	let close = if quote == '<' { '>' } else { quote };
+	let name = &rest[1..];
+	let name = &name[..name.find(close)?];
+	let name = name.trim_start_matches('<').trim_end_matches(['/', '>']).trim();
+	(!name.is_empty() && !name.contains(char::is_whitespace)).then_some(name)
+}
+
+/// Returns the name of the first tag written in angle brackets in `message`, e.g. `<amazon:breath>`.
+fn tag_in(message: &str) -> Option<&str> {
+	message.match_indices('<').find_map(|(i, _)| {
+		let rest = &message[i + 1..];
+		let len = rest
+			.find(|c: char| !(c.is_alphanumeric() || matches!(c, ':' | '-' | '_')))
+			.unwrap_or(rest.len());
+		(len > 0 && rest.chars().next().is_some_and(char::is_alphabetic)).then_some(&rest[..len])
+	})
+}
+
+/// Converts a 1-based line & column (counted in characters) into a byte offset into `ssml`.
+fn offset_of(ssml: &str, position: LineColumn) -> Option<usize> {
+	let line_start = match position.line.checked_sub(1)? {
+		0 => 0,
+		line => ssml.match_indices('\n').nth(line - 1)?.0 + 1
+	};
+	let line = ssml[line_start..].split('\n').next()?;
+	line.char_indices()
+		.map(|(i, _)| i)
+		.chain([line.len()])
+		.nth(position.column.checked_sub(1)?)
+		.map(|i| line_start + i)
+}
+
+/// Returns the start tag at the beginning of `markup`, without its angle brackets.
+fn start_tag(markup: &str) -> Option<&str> {
+	let markup = markup.strip_prefix('<')?;
+	Some(markup[..markup.find('>')?].trim_end_matches('/'))
+}
+
+fn tag_name(start_tag: &str) -> &str {
+	start_tag.split_whitespace().next().unwrap_or_default()
+}
+
+/// Returns whether `start_tag` has an attribute called `name`, optionally with the given (unescaped) value.
+fn has_attr(start_tag: &str, name: &str, value: Option<&str>) -> bool {
+	start_tag.match_indices(name).any(|(i, _)| {
+		let preceded_by_space = start_tag[..i].ends_with(char::is_whitespace);
+		let rest = start_tag[i + name.len()..].trim_start();
+		let Some(rest) = rest.strip_prefix('=') else {
+			return false;
+		};
+		let rest = rest.trim_start();
+		preceded_by_space
+			&& value.is_none_or(|value| {
+				rest.strip_prefix(['"', '\''])
+					.is_some_and(|rest| rest.starts_with(value) && rest[value.len()..].starts_with(['"', '\'']))
+			})
+	})
+}
@@ -0,0 +1,114 @@
+use alloc::{borrow::Cow, string::ToString};
+use core::fmt::{self, Write};
+
+use crate::{Element, Serialize, SerializeOptions, XmlWriter, annotations::Annotations};
+
+/// The markup an element was parsed from, along with a fingerprint of the element's generic serialization at the time
+/// it was parsed, used to detect whether it has since been modified. See [`SerializeOptions::preserve_raw`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawSource<'s> {
+	pub(crate) source: Cow<'s, str>,
+	fingerprint: u64
+}
+
+impl<'s> RawSource<'s> {
+	/// Captures `source` as the raw markup of `el`, or returns `None` if `el` can't be serialized.
+	pub(crate) fn capture(el: &impl Serialize, source: &'s str) -> Option<Self> {
+		Some(RawSource {
+			source: Cow::Borrowed(source),
+			fingerprint: fingerprint(el, &SerializeOptions::default())?
+		})
+	}
+
+	pub(crate) fn into_owned(self) -> RawSource<'static> {
+		RawSource {
+			source: match self.source {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			},
+			fingerprint: self.fingerprint
+		}
+	}
+}
+
+/// A 64-bit FNV-1a hasher.
+struct Fnv(u64);
+
+impl Write for Fnv {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for b in s.bytes() {
+			self.0 = (self.0 ^ b as u64).wrapping_mul(0x100000001b3);
+		}
+		Ok(())
+	}
+}
+
+/// Hashes the minified serialization of an element under `options`, so that elements which serialize identically share
+/// a fingerprint. Annotations aren't serialized, and so don't affect the fingerprint.
+fn fingerprint(el: &impl Serialize, options: &SerializeOptions) -> Option<u64> {
+	let mut hasher = Fnv(0xcbf29ce484222325);
+	let mut writer = XmlWriter::new(&mut hasher, false);
+	el.serialize_xml(&mut writer, options).ok()?;
+	Some(hasher.0)
+}
+
+/// Elements which may hold the raw markup they were parsed from.
+pub(crate) trait RawSourced {
+	fn raw_annotations(&self) -> Option<&Annotations<'_>>;
+}
+
+impl RawSourced for Element<'_> {
+	fn raw_annotations(&self) -> Option<&Annotations<'_>> {
+		self.annotations()
+	}
+}
+
+impl RawSourced for crate::mstts::Element<'_> {
+	fn raw_annotations(&self) -> Option<&Annotations<'_>> {
+		// MSTTS elements are always wrapped in an `Element::FlavorMSTTS`, which has already checked them
+		None
+	}
+}
+
+impl RawSourced for crate::polly::Element<'_> {
+	fn raw_annotations(&self) -> Option<&Annotations<'_>> {
+		// likewise, Polly elements are always wrapped in an `Element::FlavorPolly`
+		None
+	}
+}
+
+impl RawSourced for crate::watson::Element<'_> {
+	fn raw_annotations(&self) -> Option<&Annotations<'_>> {
+		// ...and Watson elements in an `Element::FlavorWatson`
+		None
+	}
+}
+
+/// Writes the raw markup of an element instead of serializing it, if [`SerializeOptions::preserve_raw`] is enabled &
+/// the element serializes under `options` exactly as it did when it was parsed. Returns whether the raw markup was
+/// written.
+pub(crate) fn write_preserved<W: Write>(
+	el: &impl Serialize,
+	annotations: Option<&Annotations>,
+	writer: &mut XmlWriter<W>,
+	options: &SerializeOptions
+) -> crate::Result<bool> {
+	// raw markup bypasses text filters & escaping, and its contents can't be attributed to individual elements
+	let bypassed = options.text_filter.is_some() || options.escaper.is_some() || options.ascii_only;
+	if !options.preserve_raw || bypassed || writer.plain.is_some() || writer.spans.is_some() {
+		return Ok(false);
+	}
+	let Some(raw) = annotations.and_then(Annotations::raw) else {
+		return Ok(false);
+	};
+	// the element must serialize under `options` exactly as it did when it was captured; this catches both modified
+	// elements & flavor- or option-specific rewrites which the raw markup wouldn't reflect
+	let mut canonical = options.clone();
+	canonical.pretty = false;
+	canonical.preserve_raw = false;
+	if fingerprint(el, &canonical) != Some(raw.fingerprint) {
+		return Ok(false);
+	}
+	writer.raw(&*raw.source)?;
+	Ok(true)
+}
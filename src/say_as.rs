@@ -1,7 +1,18 @@
-use alloc::{borrow::Cow, boxed::Box, string::ToString};
+use alloc::{
+	borrow::Cow,
+	boxed::Box,
+	string::{String, ToString},
+	vec,
+	vec::Vec
+};
 use core::fmt::Write;
 
-use crate::{Flavor, Serialize, SerializeOptions, XmlWriter};
+use crate::{
+	Element, Flavor, Serialize, SerializeOptions, Text, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -15,7 +26,10 @@ pub enum DateFormat {
 	DateMonth,
 	Date,
 	Month,
-	Year
+	Year,
+	/// A year, month & date written without separators, e.g. `20150922`. Supported by Amazon Polly; `????` can be used
+	/// for unknown components.
+	YearMonthDateCompact
 }
 
 impl DateFormat {
@@ -30,22 +44,134 @@ impl DateFormat {
 			Self::DateMonth => "dm",
 			Self::Date => "d",
 			Self::Month => "m",
-			Self::Year => "y"
+			Self::Year => "y",
+			Self::YearMonthDateCompact => "yyyymmdd"
 		}
 	}
 }
 
+/// The format of the text of a [`SpeechFormat::TimeWith`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeFormat {
+	/// Hours, minutes & seconds on a 12-hour clock, e.g. `2:30pm`. Supported by ACSS & Google Cloud Text-to-Speech.
+	Hms12,
+	/// Hours, minutes & seconds on a 24-hour clock, e.g. `14:30`. Supported by ACSS & Google Cloud Text-to-Speech.
+	Hms24,
+	/// Hours, minutes & seconds, as in the W3C `say-as` note.
+	Hms,
+	/// Hours & minutes, as in the W3C `say-as` note.
+	Hm,
+	/// Hours only, as in the W3C `say-as` note.
+	Hour
+}
+
+impl TimeFormat {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Hms12 => "hms12",
+			Self::Hms24 => "hms24",
+			Self::Hms => "hms",
+			Self::Hm => "hm",
+			Self::Hour => "h"
+		}
+	}
+}
+
+/// How the text of a [`SayAs`] element should be interpreted.
+///
+/// The `interpret-as` value written for each format depends on the [`Flavor`]; see [`SpeechFormat::interpret_as`].
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions, SpeechFormat, DateFormat, TimeFormat};
+/// # fn main() -> ssml::Result<()> {
+/// let options = SerializeOptions::default().flavor(Flavor::AmazonPolly);
+/// let polly = |format, text| ssml::say_as(format, text).serialize_to_string(&options);
+/// assert_eq!(polly(SpeechFormat::Fraction, "3+1/2")?, r#"<say-as interpret-as="fraction">3+1/2</say-as>"#);
+/// assert_eq!(polly(SpeechFormat::Unit, "17.5lb")?, r#"<say-as interpret-as="unit">17.5lb</say-as>"#);
+/// assert_eq!(polly(SpeechFormat::Expletive, "damn")?, r#"<say-as interpret-as="expletive">damn</say-as>"#);
+/// assert_eq!(polly(SpeechFormat::Digits, "1234")?, r#"<say-as interpret-as="digits">1234</say-as>"#);
+/// assert_eq!(polly(SpeechFormat::Cardinal, "1234")?, r#"<say-as interpret-as="cardinal">1234</say-as>"#);
+/// assert_eq!(polly(SpeechFormat::Ordinal, "1234")?, r#"<say-as interpret-as="ordinal">1234</say-as>"#);
+/// assert_eq!(
+/// 	polly(SpeechFormat::Telephone, "2122241555")?,
+/// 	r#"<say-as interpret-as="telephone">2122241555</say-as>"#
+/// );
+/// assert_eq!(
+/// 	polly(SpeechFormat::Address, "440 Terry Ave N")?,
+/// 	r#"<say-as interpret-as="address">440 Terry Ave N</say-as>"#
+/// );
+/// assert_eq!(
+/// 	polly(SpeechFormat::Date(DateFormat::YearMonthDateCompact), "2015????")?,
+/// 	r#"<say-as interpret-as="date" format="yyyymmdd">2015????</say-as>"#
+/// );
+/// assert_eq!(polly(SpeechFormat::Time, "2:30")?, r#"<say-as interpret-as="time">2:30</say-as>"#);
+///
+/// let options = SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices);
+/// let acss = |format, text| ssml::say_as(format, text).serialize_to_string(&options);
+/// assert_eq!(acss(SpeechFormat::Name, "王小明")?, r#"<say-as interpret-as="name">王小明</say-as>"#);
+/// assert_eq!(acss(SpeechFormat::Digits, "1234")?, r#"<say-as interpret-as="number_digit">1234</say-as>"#);
+/// assert_eq!(
+/// 	acss(SpeechFormat::TimeWith(TimeFormat::Hms12), "4:00pm")?,
+/// 	r#"<say-as interpret-as="time" format="hms12">4:00pm</say-as>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SpeechFormat {
 	SpellOut,
 	Currency,
+	/// An amount of the given currency, e.g. `42.50` read as "forty-two dollars and fifty cents".
+	///
+	/// The currency is written to the `detail` attribute as its ISO 4217 code (e.g. `USD`). Google Cloud Text-to-Speech
+	/// instead infers the currency from the text, but reads it in the given `language` (a BCP 47 language tag like
+	/// `en-US`), which is written to its `language` attribute.
+	///
+	/// ```
+	/// # use ssml::{Flavor, Serialize, SerializeOptions, SpeechFormat};
+	/// # fn main() -> ssml::Result<()> {
+	/// let format = SpeechFormat::CurrencyWith {
+	/// 	code: "EUR".into(),
+	/// 	language: Some("de-DE".into())
+	/// };
+	/// let say_as = ssml::say_as(format, "42.50");
+	/// assert_eq!(
+	/// 	say_as
+	/// 		.serialize_to_string(&SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices))?,
+	/// 	r#"<say-as interpret-as="currency" detail="EUR">42.50</say-as>"#
+	/// );
+	/// assert_eq!(
+	/// 	say_as.serialize_to_string(&SerializeOptions::default().flavor(Flavor::GoogleCloudTextToSpeech))?,
+	/// 	r#"<say-as interpret-as="currency" language="de-DE">42.50</say-as>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	CurrencyWith {
+		code: Box<str>,
+		language: Option<Box<str>>
+	},
 	Cardinal,
 	Ordinal,
 	Digits,
 	Date(DateFormat),
 	Time,
+	/// A time of day in the given format.
+	TimeWith(TimeFormat),
 	Telephone,
+	/// A fraction or mixed number, e.g. `3/20` or `3+1/2`.
+	Fraction,
+	/// A number followed by a unit of measure, e.g. `17.5lb`.
+	Unit,
+	/// Text which should be bleeped out.
+	Expletive,
+	/// A street address.
+	Address,
+	/// A person's name, e.g. so that Chinese names are read with the right pronunciation of surnames. Supported by
+	/// Microsoft Azure Cognitive Speech Services.
+	Name,
 	Custom {
 		interpret_as: Box<str>,
 		format: Option<Box<str>>,
@@ -56,27 +182,37 @@ pub enum SpeechFormat {
 impl SpeechFormat {
 	pub fn interpret_as(&self, flavor: Flavor) -> &str {
 		match self {
-			Self::SpellOut => "spell-out",
-			Self::Currency => "currency",
+			Self::SpellOut => match flavor {
+				Flavor::EspeakNg => "characters",
+				_ => "spell-out"
+			},
+			Self::Currency | Self::CurrencyWith { .. } => "currency",
 			Self::Cardinal => "cardinal",
 			Self::Ordinal => "ordinal",
 			Self::Digits => {
 				match flavor {
 					Flavor::MicrosoftAzureCognitiveSpeechServices => "number_digit",
 					Flavor::GoogleCloudTextToSpeech => "spell-out", // they don't have digits, but maybe this will work...?
+					Flavor::EspeakNg => "tts:digits",
 					_ => "digits"
 				}
 			}
 			Self::Date(_) => "date",
-			Self::Time => "time",
+			Self::Time | Self::TimeWith(_) => "time",
 			Self::Telephone => "telephone",
-			Self::Custom { interpret_as, .. } => &*interpret_as
+			Self::Fraction => "fraction",
+			Self::Unit => "unit",
+			Self::Expletive => "expletive",
+			Self::Address => "address",
+			Self::Name => "name",
+			Self::Custom { interpret_as, .. } => interpret_as
 		}
 	}
 
 	pub fn format(&self) -> Option<&str> {
 		match self {
 			Self::Date(format) => Some(format.as_str()),
+			Self::TimeWith(format) => Some(format.as_str()),
 			Self::Custom { format, .. } => format.as_deref(),
 			_ => None
 		}
@@ -84,22 +220,117 @@ impl SpeechFormat {
 
 	pub fn detail(&self) -> Option<&str> {
 		match self {
+			Self::CurrencyWith { code, .. } => Some(code),
 			Self::Custom { detail, .. } => detail.as_deref(),
 			_ => None
 		}
 	}
+
+	/// Returns the language a currency should be read in, if given.
+	pub fn language(&self) -> Option<&str> {
+		match self {
+			Self::CurrencyWith { language, .. } => language.as_deref(),
+			_ => None
+		}
+	}
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SayAs<'s> {
 	format: SpeechFormat,
-	pub(crate) text: Cow<'s, str>
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> SayAs<'s> {
+	/// Creates a new [`SayAs`] containing only `text`.
 	pub fn new(format: SpeechFormat, text: impl Into<Cow<'s, str>>) -> Self {
-		Self { format, text: text.into() }
+		Self::with_elements(format, [Text::from(text)])
+	}
+
+	/// Creates a new [`SayAs`] with mixed content, e.g. a [`Sub`](crate::Sub) within the interpreted text. Support for
+	/// elements within `say-as` varies between engines.
+	///
+	/// ```
+	/// # use ssml::{SayAs, Serialize, SerializeOptions, SpeechFormat};
+	/// # fn main() -> ssml::Result<()> {
+	/// let say_as = SayAs::with_elements(
+	/// 	SpeechFormat::Telephone,
+	/// 	[ssml::Element::from("+1 "), ssml::sub("five five five", "(555)").into()]
+	/// );
+	/// assert_eq!(say_as.text(), "+1 (555)");
+	/// assert_eq!(
+	/// 	say_as.serialize_to_string(&SerializeOptions::default())?,
+	/// 	r#"<say-as interpret-as="telephone">+1 <sub alias="five five five">(555)</sub></say-as>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_elements<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(format: SpeechFormat, elements: I) -> Self {
+		Self {
+			format,
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	/// Creates a [`SayAs`] which reads `n` as a cardinal number, written with the digit grouping of `locale` (a BCP 47
+	/// language tag like `en-US`) so that engines don't read long numbers digit by digit.
+	///
+	/// ```
+	/// # use ssml::{SayAs, Serialize, SerializeOptions};
+	/// # fn main() -> ssml::Result<()> {
+	/// assert_eq!(SayAs::cardinal(1234567, "en-US").text(), "1,234,567");
+	/// assert_eq!(SayAs::cardinal(-1234567, "de-DE").text(), "-1.234.567");
+	/// assert_eq!(SayAs::cardinal(1234567, "de-CH").text(), "1’234’567");
+	/// assert_eq!(SayAs::cardinal(1234567, "fr-FR").text(), "1\u{202f}234\u{202f}567");
+	/// assert_eq!(SayAs::cardinal(1234567, "en-IN").text(), "12,34,567");
+	/// // Spanish & Polish don't group 4-digit numbers
+	/// assert_eq!(SayAs::cardinal(1234, "es-ES").text(), "1234");
+	/// assert_eq!(SayAs::cardinal(12345, "es-ES").text(), "12.345");
+	///
+	/// assert_eq!(
+	/// 	SayAs::cardinal(1234567, "en-US").serialize_to_string(&SerializeOptions::default())?,
+	/// 	r#"<say-as interpret-as="cardinal">1,234,567</say-as>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cardinal(n: i64, locale: &str) -> SayAs<'static> {
+		let format = NumberFormat::of(locale);
+		let mut text = format.group(n.unsigned_abs());
+		if n < 0 {
+			text.insert(0, '-');
+		}
+		SayAs::new(SpeechFormat::Cardinal, text)
+	}
+
+	/// Creates a [`SayAs`] which reads `n` as an ordinal number, written with the digit grouping of `locale` (a BCP 47
+	/// language tag like `en-US`). In languages which write ordinals with a trailing period, like German, the period is
+	/// included.
+	///
+	/// ```
+	/// # use ssml::SayAs;
+	/// assert_eq!(SayAs::ordinal(3, "en-US").text(), "3");
+	/// assert_eq!(SayAs::ordinal(3, "de-DE").text(), "3.");
+	/// assert_eq!(SayAs::ordinal(1000, "nb-NO").text(), "1\u{a0}000.");
+	/// ```
+	pub fn ordinal(n: u64, locale: &str) -> SayAs<'static> {
+		let format = NumberFormat::of(locale);
+		let mut text = format.group(n);
+		if format.ordinal_period {
+			text.push('.');
+		}
+		SayAs::new(SpeechFormat::Ordinal, text)
 	}
 
 	pub fn format(&self) -> &SpeechFormat {
@@ -110,12 +341,42 @@ impl<'s> SayAs<'s> {
 		self.format = format;
 	}
 
-	pub fn text(&self) -> &str {
-		&self.text
+	/// Returns the written text to be interpreted: the text of all of this element's descendants.
+	pub fn text(&self) -> Cow<'_, str> {
+		match &*self.children {
+			[] => Cow::Borrowed(""),
+			[Element::Text(text)] => Cow::Borrowed(text.as_str()),
+			children => {
+				let mut out = String::new();
+				push_text(&mut out, children);
+				Cow::Owned(out)
+			}
+		}
 	}
 
+	/// Replaces the contents of this element with `text`.
 	pub fn set_text(&mut self, text: impl Into<Cow<'s, str>>) {
-		self.text = text.into();
+		self.children = vec![Element::Text(Text::from(text))];
+	}
+
+	/// Extend this `say-as` element with an additional element.
+	pub fn push(&mut self, element: impl Into<Element<'s>>) {
+		self.children.push(element.into());
+	}
+
+	/// Extend this `say-as` element with additional elements.
+	pub fn extend<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&mut self, elements: I) {
+		self.children.extend(elements.into_iter().map(|f| f.into()));
+	}
+
+	/// Returns a reference to the elements contained within this `say-as` element.
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	/// Returns a mutable reference to the elements contained within this `say-as` element.
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
 	}
 
 	pub fn to_owned(&self) -> SayAs<'static> {
@@ -125,10 +386,9 @@ impl<'s> SayAs<'s> {
 	pub fn into_owned(self) -> SayAs<'static> {
 		SayAs {
 			format: self.format.clone(),
-			text: match self.text {
-				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
-				Cow::Owned(b) => Cow::Owned(b)
-			}
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
@@ -138,12 +398,101 @@ impl<'s> Serialize for SayAs<'s> {
 		writer.element("say-as", |writer| {
 			writer.attr("interpret-as", self.format.interpret_as(options.flavor))?;
 			writer.attr_opt("format", self.format.format())?;
-			writer.attr_opt("detail", self.format.detail())?;
-			writer.text(&self.text)
+			// Google infers the currency from the text, & only takes the language to read it in
+			if options.flavor == Flavor::GoogleCloudTextToSpeech && matches!(self.format, SpeechFormat::CurrencyWith { .. }) {
+				writer.attr_opt("language", self.format.language())?;
+			} else {
+				writer.attr_opt("detail", self.format.detail())?;
+			}
+			attrs::write(writer, &self.extra_attrs)?;
+			util::serialize_elements(writer, &self.children, options)
 		})
 	}
 }
 
+/// Appends the written text of `elements` to `out`.
+fn push_text(out: &mut String, elements: &[Element]) {
+	for el in elements {
+		match el {
+			Element::Text(text) => out.push_str(text),
+			Element::SayAs(el) => out.push_str(&el.text()),
+			Element::Phoneme(el) => out.push_str(el.text()),
+			Element::Sub(el) => out.push_str(el.text()),
+			el => push_text(out, el.children().unwrap_or_default())
+		}
+	}
+}
+
+/// How integers are written in a locale.
+struct NumberFormat {
+	separator: char,
+	/// Whether digits are grouped in the Indian style, i.e. the first group has 3 digits & the rest have 2.
+	indian: bool,
+	/// The minimum number of digits before the first separator; `2` means 4-digit numbers aren't grouped.
+	min_grouping: usize,
+	/// Whether ordinals are written with a trailing period.
+	ordinal_period: bool
+}
+
+impl NumberFormat {
+	fn of(locale: &str) -> Self {
+		let mut subtags = locale.split(['-', '_']);
+		let language = subtags.next().unwrap_or_default().to_ascii_lowercase();
+		let region = subtags.find(|tag| tag.len() == 2 || tag.len() == 3 && tag.bytes().all(|b| b.is_ascii_digit()));
+		let region = region.map(|region| region.to_ascii_uppercase()).unwrap_or_default();
+
+		let mut format = NumberFormat {
+			separator: ',',
+			indian: false,
+			min_grouping: 1,
+			ordinal_period: matches!(&*language, "de" | "da" | "nb" | "nn" | "no" | "fi" | "cs" | "sk" | "pl" | "hu" | "tr" | "sl" | "hr" | "sr" | "et" | "lv")
+		};
+		match (&*language, &*region) {
+			("de" | "it", "CH" | "LI") => format.separator = '’',
+			("es", "MX" | "US" | "419" | "GT" | "HN" | "NI" | "PA" | "PE" | "PR" | "SV" | "DO") => {}
+			("es", _) => {
+				format.separator = '.';
+				format.min_grouping = 2;
+			}
+			("pt", "PT") => {
+				format.separator = '\u{a0}';
+				format.min_grouping = 2;
+			}
+			("de" | "it" | "pt" | "nl" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl" | "sr" | "vi", _) => format.separator = '.',
+			("fr", _) => format.separator = '\u{202f}',
+			("pl", _) => {
+				format.separator = '\u{a0}';
+				format.min_grouping = 2;
+			}
+			("ru" | "uk" | "cs" | "sk" | "sv" | "nb" | "nn" | "no" | "fi" | "hu" | "bg" | "lt" | "lv" | "et", _) => format.separator = '\u{a0}',
+			("en", "IN") | ("hi" | "bn" | "mr" | "ta" | "te" | "gu" | "kn" | "ml", _) => format.indian = true,
+			_ => {}
+		}
+		format
+	}
+
+	fn group(&self, n: u64) -> String {
+		let digits = n.to_string();
+		if digits.len() < 4 + self.min_grouping - 1 {
+			return digits;
+		}
+		let mut out = String::with_capacity(digits.len() * 2);
+		for (i, digit) in digits.chars().enumerate() {
+			let remaining = digits.len() - i;
+			let boundary = if self.indian && remaining > 3 {
+				(remaining - 3).is_multiple_of(2)
+			} else {
+				remaining.is_multiple_of(3)
+			};
+			if i > 0 && boundary {
+				out.push(self.separator);
+			}
+			out.push(digit);
+		}
+		out
+	}
+}
+
 pub fn say_as<'s>(format: SpeechFormat, text: impl Into<Cow<'s, str>>) -> SayAs<'s> {
 	SayAs::new(format, text)
 }
@@ -0,0 +1,107 @@
+use alloc::vec::Vec;
+use core::{
+	fmt::Write,
+	ops::{Add, AddAssign}
+};
+
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// [`Sentence`] marks its children as a single sentence. This is useful when punctuation alone is ambiguous, e.g. for
+/// text containing abbreviations like "Dr.", or for text without any punctuation at all.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let doc = ssml::speak(None, [ssml::sentence(["Please see Dr. Smith"]), ssml::sentence(["Room 3 is on the left"])]);
+///
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><s>Please see Dr. Smith</s><s>Room 3 is on the left</s></speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sentence<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Sentence<'s> {
+	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Self {
+		Self {
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
+	pub fn push(&mut self, element: impl Into<Element<'s>>) {
+		self.children.push(element.into());
+	}
+
+	pub fn extend<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&mut self, elements: I) {
+		self.children.extend(elements.into_iter().map(|f| f.into()));
+	}
+
+	pub fn to_owned(&self) -> Sentence<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Sentence<'static> {
+		Sentence {
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+}
+
+impl<'s> Serialize for Sentence<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.element("s", |writer| {
+			attrs::write(writer, &self.extra_attrs)?;
+			util::serialize_elements(writer, &self.children, options)
+		})
+	}
+}
+
+impl<'s, 's2: 's, T: Into<Element<'s2>>> Add<T> for Sentence<'s> {
+	type Output = Sentence<'s>;
+
+	fn add(mut self, rhs: T) -> Self::Output {
+		self.push(rhs.into());
+		self
+	}
+}
+
+impl<'s, 's2: 's, T: Into<Element<'s2>>> AddAssign<T> for Sentence<'s> {
+	fn add_assign(&mut self, rhs: T) {
+		self.push(rhs.into());
+	}
+}
+
+pub fn sentence<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Sentence<'s> {
+	Sentence::new(elements)
+}
@@ -0,0 +1,35 @@
+//! `deserialize_with` helpers which borrow strings nested in containers from the input where possible.
+//!
+//! `#[serde(borrow)]` only borrows `Cow<str>` fields directly; `Cow`s inside an `Option`, `Vec` or tuple are always
+//! deserialized as owned. These helpers deserialize through a borrowing wrapper instead.
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use serde::{Deserialize, Deserializer};
+
+type CowPair<'s> = (Cow<'s, str>, Cow<'s, str>);
+type OptionCowPair<'s> = (Option<Cow<'s, str>>, Option<Cow<'s, str>>);
+
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+pub(crate) fn option<'de: 's, 's, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Cow<'s, str>>, D::Error> {
+	Ok(Option::<Borrowed>::deserialize(deserializer)?.map(|b| b.0))
+}
+
+pub(crate) fn option_vec<'de: 's, 's, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<Cow<'s, str>>>, D::Error> {
+	Ok(Option::<Vec<Borrowed>>::deserialize(deserializer)?.map(|v| v.into_iter().map(|b| b.0).collect()))
+}
+
+pub(crate) fn option_pair<'de: 's, 's, D: Deserializer<'de>>(deserializer: D) -> Result<OptionCowPair<'s>, D::Error> {
+	let (a, b) = <(Option<Borrowed>, Option<Borrowed>)>::deserialize(deserializer)?;
+	Ok((a.map(|b| b.0), b.map(|b| b.0)))
+}
+
+pub(crate) fn pairs<'de: 's, 's, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<CowPair<'s>>, D::Error> {
+	Ok(Vec::<(Borrowed, Borrowed)>::deserialize(deserializer)?
+		.into_iter()
+		.map(|(k, v)| (k.0, v.0))
+		.collect())
+}
@@ -0,0 +1,106 @@
+//! `serde(with)` helpers which (de)serialize values as strings in SSML syntax, using their [`Display`] & [`FromStr`]
+//! implementations.
+//!
+//! By default, units like [`TimeDesignation`](crate::TimeDesignation) & [`Decibels`](crate::Decibels) are
+//! (de)serialized as their underlying numbers, which doesn't read very naturally in configuration files. With these
+//! helpers, they're written as e.g. `"+750ms"` or `"+3dB"` instead; deserialization accepts anything the SSML parser
+//! accepts, like `"1.5s"`.
+//!
+//! ```
+//! use serde::{
+//! 	Deserialize,
+//! 	de::value::{Error, MapDeserializer}
+//! };
+//! use ssml::{Decibels, TimeDesignation};
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//! 	#[serde(with = "ssml::serde_str")]
+//! 	pause: TimeDesignation,
+//! 	#[serde(default, with = "ssml::serde_str::option")]
+//! 	gain: Option<Decibels>
+//! }
+//!
+//! let config =
+//! 	Config::deserialize(MapDeserializer::<_, Error>::new([("pause", "1.5s"), ("gain", "+3dB")].into_iter()))?;
+//! assert_eq!(config.pause, TimeDesignation::from_millis(1500.));
+//! assert_eq!(config.gain, Some(Decibels::new(3.)));
+//! # Ok::<(), Error>(())
+//! ```
+
+use alloc::string::String;
+use core::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+pub fn serialize<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.collect_str(value)
+}
+
+pub fn deserialize<'de, T, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error>
+where
+	T: FromStr,
+	T::Err: Display
+{
+	String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+}
+
+/// Like [`serde_str`](self), but for `Option`al values. `None` is (de)serialized as a unit/`null`.
+pub mod option {
+	use core::{
+		fmt::{self, Display},
+		marker::PhantomData,
+		str::FromStr
+	};
+
+	use serde::{
+		Deserializer, Serializer,
+		de::{Error, Visitor}
+	};
+
+	pub fn serialize<T: Display, S: Serializer>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+		match value {
+			Some(value) => serializer.collect_str(value),
+			None => serializer.serialize_none()
+		}
+	}
+
+	pub fn deserialize<'de, T, D: Deserializer<'de>>(deserializer: D) -> Result<Option<T>, D::Error>
+	where
+		T: FromStr,
+		T::Err: Display
+	{
+		deserializer.deserialize_option(OptionVisitor(PhantomData))
+	}
+
+	struct OptionVisitor<T>(PhantomData<T>);
+
+	impl<'de, T> Visitor<'de> for OptionVisitor<T>
+	where
+		T: FromStr,
+		T::Err: Display
+	{
+		type Value = Option<T>;
+
+		fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+			formatter.write_str("an optional string")
+		}
+
+		fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+			Ok(None)
+		}
+
+		fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+			Ok(None)
+		}
+
+		fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+			super::deserialize(deserializer).map(Some)
+		}
+
+		// formats which aren't self-describing may hand us the string directly
+		fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+			value.parse().map(Some).map_err(E::custom)
+		}
+	}
+}
@@ -0,0 +1,515 @@
+use alloc::{string::String, vec::Vec};
+use core::mem;
+
+use crate::{
+	Element, Serialize, SerializeOptions, Speak, XmlWriter,
+	util::{self, ChildMode},
+	xml::WriterState
+};
+
+/// A reusable serializer which writes documents into an internal buffer.
+///
+/// When serializing many documents, reusing a `Serializer` avoids allocating a new [`String`] for each document, as
+/// [`Serialize::serialize_to_string`] does.
+///
+/// ```
+/// # fn main() -> ssml::Result<()> {
+/// let mut serializer = ssml::Serializer::new(ssml::SerializeOptions::default());
+/// for name in ["Alice", "Bob"] {
+/// 	let doc = ssml::speak(None, [format!("Hello, {name}!")]);
+/// 	let ssml = serializer.serialize_into(&doc)?;
+/// 	assert!(ssml.ends_with(&format!(">Hello, {name}!</speak>")));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Serializer {
+	options: SerializeOptions,
+	buffer: String
+}
+
+impl Serializer {
+	/// Creates a new [`Serializer`] with the given options.
+	pub fn new(options: SerializeOptions) -> Self {
+		Serializer { options, buffer: String::new() }
+	}
+
+	/// Creates a new [`Serializer`] with the given options, and an internal buffer with at least the given capacity.
+	pub fn with_capacity(options: SerializeOptions, capacity: usize) -> Self {
+		Serializer {
+			options,
+			buffer: String::with_capacity(capacity)
+		}
+	}
+
+	pub fn options(&self) -> &SerializeOptions {
+		&self.options
+	}
+
+	pub fn options_mut(&mut self) -> &mut SerializeOptions {
+		&mut self.options
+	}
+
+	/// Serializes an element into the internal buffer, replacing its previous contents, and returns the serialized
+	/// string.
+	///
+	/// If serialization fails, the contents of the buffer are unspecified.
+	pub fn serialize_into(&mut self, element: &impl Serialize) -> crate::Result<&str> {
+		self.buffer.clear();
+		element.serialize(&mut self.buffer, &self.options)?;
+		Ok(&self.buffer)
+	}
+
+	/// Consumes this [`Serializer`], returning its internal buffer, which contains the last serialized element.
+	pub fn into_inner(self) -> String {
+		self.buffer
+	}
+}
+
+/// Serializes `doc` once for each of the given options, e.g. to publish the same prompt to several speech synthesis
+/// providers in one step.
+///
+/// The document is traversed only once: each element is visited a single time, writing its markup to every output
+/// before moving on to its children. Each output is independent, so an element which isn't supported by one flavor
+/// fails only that flavor's output, and the outputs are identical to those of [`Serialize::serialize_to_string`].
+///
+/// ```
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::{Flavor, SerializeOptions};
+///
+/// let doc = ssml::speak(None, [ssml::Element::from(ssml::mark("start")), "Hello!".into()]);
+/// let outputs = ssml::serialize_all(
+/// 	&doc,
+/// 	&[
+/// 		SerializeOptions::default().flavor(Flavor::MicrosoftAzureCognitiveSpeechServices),
+/// 		SerializeOptions::default().flavor(Flavor::GoogleCloudTextToSpeech),
+/// 		SerializeOptions::default().flavor(Flavor::AmazonPolly)
+/// 	]
+/// )
+/// .into_iter()
+/// .collect::<ssml::Result<Vec<_>>>()?;
+/// assert_eq!(
+/// 	outputs[0],
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:mstts="http://www.w3.org/2001/mstts"><bookmark mark="start"/>Hello!</speak>"#
+/// );
+/// assert_eq!(outputs[1], r#"<speak><mark name="start"/>Hello!</speak>"#);
+/// assert_eq!(outputs[2], outputs[1]);
+///
+/// // Polly doesn't support `<voice>`, but the other outputs are unaffected
+/// let doc = ssml::speak(None, [ssml::voice("en-US-Wavenet-D", ["Hello!"])]);
+/// let outputs = ssml::serialize_all(
+/// 	&doc,
+/// 	&[SerializeOptions::default().flavor(Flavor::GoogleCloudTextToSpeech), SerializeOptions::default().flavor(Flavor::AmazonPolly)]
+/// );
+/// assert!(outputs[0].is_ok());
+/// assert!(matches!(outputs[1], Err(ssml::Error::UnsupportedElement("voice", _))));
+/// # Ok(())
+/// # }
+/// ```
+pub fn serialize_all(doc: &Speak, options: &[SerializeOptions]) -> Vec<crate::Result<String>> {
+	fan_out(doc, options).0
+}
+
+/// Implements [`serialize_all`], also returning the number of elements visited.
+fn fan_out(doc: &Speak, options: &[SerializeOptions]) -> (Vec<crate::Result<String>>, usize) {
+	let mut fan_out = FanOut {
+		outputs: options
+			.iter()
+			.map(|options| Output {
+				writer: XmlWriter::for_options(String::new(), options),
+				options,
+				result: Ok(())
+			})
+			.collect(),
+		visits: 0
+	};
+	let participants: Vec<_> = (0..options.len()).map(|output| (output, ChildMode::Write, false)).collect();
+	fan_out.visit(Node::Speak(doc), &participants);
+	let outputs = fan_out
+		.outputs
+		.into_iter()
+		.map(|output| output.result.map(|()| output.writer.into_inner()))
+		.collect();
+	(outputs, fan_out.visits)
+}
+
+/// Where an element writes its children, recorded in place of writing them while [fanning out](FanOutHook).
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Placeholder {
+	/// A run of elements written by `util::serialize_elements_at`, identified by the address of its first element.
+	Run { elements: usize, len: usize, offset: usize },
+	/// A single child written as-is by `util::serialize_child`, identified by its address.
+	Child { element: usize, index: usize }
+}
+
+/// Set on an [`XmlWriter`] while [`serialize_all`] writes a single element's own markup.
+///
+/// Rather than writing its children, the element records a [`Placeholder`] wherever they would be written, so they can
+/// be visited once for all outputs. The element's markup is then written in segments between its children: each pass
+/// re-runs the element's serialization with the output muted, except for the segment between placeholders
+/// `target - 1` & `target`.
+#[derive(Debug, Default)]
+pub(crate) struct FanOutHook {
+	pub(crate) target: usize,
+	pub(crate) seen: usize,
+	pub(crate) record: bool,
+	pub(crate) placeholders: Vec<Placeholder>,
+	/// The writer's state after the children preceding this segment were written, restored once the segment starts.
+	pub(crate) resume: Option<WriterState>,
+	/// The writer's state at the end of this segment, where the following children are to be written.
+	pub(crate) captured: Option<WriterState>
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Node<'a, 's> {
+	Speak(&'a Speak<'s>),
+	Element(&'a Element<'s>)
+}
+
+impl<'a, 's> Node<'a, 's> {
+	fn children(self) -> &'a [Element<'s>] {
+		match self {
+			Node::Speak(doc) => doc.children(),
+			Node::Element(el) => el.children().unwrap_or_default()
+		}
+	}
+
+	fn serialize_xml(self, writer: &mut XmlWriter<String>, options: &SerializeOptions) -> crate::Result<()> {
+		match self {
+			Node::Speak(doc) => doc.serialize_xml(writer, options),
+			Node::Element(el) => el.serialize_xml(writer, options)
+		}
+	}
+}
+
+/// A step in writing an element to one output.
+#[derive(Debug, Clone, Copy)]
+enum Item<'a, 's> {
+	/// The segment of the element's own markup following the placeholder with the given index.
+	Segment(usize),
+	/// The start of a run of elements.
+	Run,
+	/// A child element, which is checked & formatted as part of a run if `checked` is set.
+	Child { element: &'a Element<'s>, index: usize, checked: bool }
+}
+
+impl Item<'_, '_> {
+	fn address(&self) -> Option<usize> {
+		match self {
+			Item::Child { element, .. } => Some(*element as *const Element as usize),
+			_ => None
+		}
+	}
+}
+
+struct Output<'o> {
+	writer: XmlWriter<String>,
+	options: &'o SerializeOptions,
+	result: crate::Result<()>
+}
+
+/// The progress of one output through an element.
+struct Visit<'a, 's> {
+	output: usize,
+	/// The writer's state before the element was written; `None` if the element is unwrapped.
+	entry: Option<WriterState>,
+	items: Vec<Item<'a, 's>>,
+	next: usize,
+	after_text: bool
+}
+
+struct FanOut<'o> {
+	outputs: Vec<Output<'o>>,
+	visits: usize
+}
+
+impl FanOut<'_> {
+	/// Writes `node` to each of the given outputs, visiting each of its children once.
+	fn visit<'a, 's>(&mut self, node: Node<'a, 's>, participants: &[(usize, ChildMode, bool)]) {
+		self.visits += 1;
+		let children = node.children();
+		let mut visits = Vec::with_capacity(participants.len());
+		for &(output, mode, _) in participants {
+			let (entry, items) = if mode == ChildMode::Unwrap {
+				let mut items = Vec::with_capacity(children.len() + 1);
+				push_run(&mut items, children, 0);
+				(None, items)
+			} else {
+				match self.open(output, node, children) {
+					Ok(Some((entry, items))) => (Some(entry), items),
+					Ok(None) => continue,
+					Err(e) => {
+						self.outputs[output].result = Err(e);
+						continue;
+					}
+				}
+			};
+			visits.push(Visit {
+				output,
+				entry,
+				items,
+				next: 0,
+				after_text: false
+			});
+		}
+
+		loop {
+			// children are written in document order, so the next child to visit is the one with the lowest address
+			let mut address = None;
+			for visit in &mut visits {
+				self.advance(node, visit);
+				if let Some(next) = visit.items.get(visit.next).and_then(Item::address) {
+					address = Some(address.map_or(next, |address: usize| address.min(next)));
+				}
+			}
+			let Some(address) = address else {
+				break;
+			};
+
+			let mut child = None;
+			let mut next = Vec::new();
+			for visit in &mut visits {
+				let Some(&Item::Child { element, index, checked }) = visit.items.get(visit.next) else {
+					continue;
+				};
+				if element as *const Element as usize != address {
+					continue;
+				}
+				visit.next += 1;
+				child = Some(element);
+
+				let output = &mut self.outputs[visit.output];
+				let mode = if checked {
+					util::begin_child(&mut output.writer, element, index, &mut visit.after_text, output.options)
+				} else {
+					output.writer.enter_child(index);
+					Ok(ChildMode::Write)
+				};
+				match mode {
+					Ok(ChildMode::Skip) => {}
+					Ok(mode) => next.push((visit.output, mode, checked)),
+					Err(e) => output.result = Err(e)
+				}
+			}
+			if let (Some(child), false) = (child, next.is_empty()) {
+				self.visit(Node::Element(child), &next);
+			}
+			for &(output, _, checked) in &next {
+				let writer = &mut self.outputs[output].writer;
+				if checked {
+					util::end_child(writer);
+				} else {
+					writer.exit_child();
+				}
+			}
+		}
+	}
+
+	/// Writes `node`'s markup to an output up to where its children start, returning the writer's state beforehand &
+	/// the steps left to write it. Returns `None` if the node had to be written in full.
+	fn open<'a, 's>(&mut self, output: usize, node: Node<'a, 's>, children: &'a [Element<'s>]) -> crate::Result<Option<(WriterState, Vec<Item<'a, 's>>)>> {
+		let Output { writer, options, .. } = &mut self.outputs[output];
+		let entry = writer.save_state();
+		let (len, count) = (writer.write.inner.len(), writer.write.count);
+
+		writer.fan_out = Some(FanOutHook {
+			record: true,
+			..FanOutHook::default()
+		});
+		let result = node.serialize_xml(writer, options);
+		let hook = writer.fan_out.take().unwrap_or_default();
+		writer.write.muted = false;
+		result?;
+		if let Some(state) = hook.captured {
+			writer.restore_state(state);
+		}
+
+		let mut items = Vec::new();
+		for (i, placeholder) in hook.placeholders.iter().enumerate() {
+			if i > 0 {
+				items.push(Item::Segment(i));
+			}
+			if !resolve(*placeholder, children, &mut items) {
+				// the element writes elements other than its children; fall back to writing it in full
+				writer.write.inner.truncate(len);
+				writer.write.count = count;
+				writer.restore_state(entry);
+				node.serialize_xml(writer, options)?;
+				return Ok(None);
+			}
+		}
+		if !hook.placeholders.is_empty() {
+			items.push(Item::Segment(hook.placeholders.len()));
+		}
+		Ok(Some((entry, items)))
+	}
+
+	/// Performs the steps of `visit` up to its next child.
+	fn advance(&mut self, node: Node, visit: &mut Visit) {
+		while let Some(item) = visit.items.get(visit.next) {
+			if self.outputs[visit.output].result.is_err() {
+				visit.next = visit.items.len();
+				return;
+			}
+			match *item {
+				Item::Child { .. } => return,
+				Item::Run => visit.after_text = false,
+				Item::Segment(target) => {
+					if let Some(entry) = &visit.entry {
+						if let Err(e) = self.segment(visit.output, node, entry, target) {
+							self.outputs[visit.output].result = Err(e);
+						}
+					}
+				}
+			}
+			visit.next += 1;
+		}
+	}
+
+	/// Writes the segment of `node`'s markup following its placeholder `target - 1`.
+	fn segment(&mut self, output: usize, node: Node, entry: &WriterState, target: usize) -> crate::Result<()> {
+		let Output { writer, options, .. } = &mut self.outputs[output];
+		let resume = writer.save_state();
+		writer.restore_state(entry.clone());
+		writer.write.muted = true;
+
+		writer.fan_out = Some(FanOutHook {
+			target,
+			resume: Some(resume),
+			..FanOutHook::default()
+		});
+		let result = node.serialize_xml(writer, options);
+		let hook = writer.fan_out.take().unwrap_or_default();
+		writer.write.muted = false;
+		result?;
+		if let Some(state) = hook.captured {
+			writer.restore_state(state);
+		}
+		Ok(())
+	}
+}
+
+/// Resolves a placeholder to the children it stands for, returning `false` if they aren't within `children`.
+fn resolve<'a, 's>(placeholder: Placeholder, children: &'a [Element<'s>], items: &mut Vec<Item<'a, 's>>) -> bool {
+	let base = children.as_ptr() as usize;
+	let position = |address: usize| {
+		let offset = address.checked_sub(base)?;
+		(offset % mem::size_of::<Element>() == 0).then_some(offset / mem::size_of::<Element>())
+	};
+	match placeholder {
+		Placeholder::Run { len: 0, .. } => true,
+		Placeholder::Run { elements, len, offset } => match position(elements).and_then(|start| children.get(start..start + len)) {
+			Some(run) => {
+				push_run(items, run, offset);
+				true
+			}
+			None => false
+		},
+		Placeholder::Child { element, index } => match position(element).and_then(|i| children.get(i)) {
+			Some(element) => {
+				items.push(Item::Child { element, index, checked: false });
+				true
+			}
+			None => false
+		}
+	}
+}
+
+fn push_run<'a, 's>(items: &mut Vec<Item<'a, 's>>, run: &'a [Element<'s>], offset: usize) {
+	items.push(Item::Run);
+	items.extend(run.iter().enumerate().map(|(i, element)| Item::Child {
+		element,
+		index: offset + i,
+		checked: true
+	}));
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::{vec, vec::Vec};
+
+	use super::fan_out;
+	use crate::{
+		DocumentDefaults, Element, EmphasisLevel, EmptyElementPolicy, Flavor, ProsodyControl, ProsodyRate, Serialize, SerializeOptions, Speak, VariantSelection
+	};
+
+	fn document() -> Speak<'static> {
+		crate::speak(
+			Some("en-US"),
+			[
+				Element::from("Hello"),
+				"there".into(),
+				crate::paragraph([crate::sentence([
+					Element::from("A "),
+					crate::emphasis(EmphasisLevel::Strong, ["strong"]).into(),
+					" word, ".into(),
+					crate::breaks("500ms").into(),
+					crate::mark("middle").into()
+				])])
+				.into(),
+				crate::voice(
+					"en-US-Neural2-F",
+					[
+						Element::from(crate::prosody(ProsodyControl::default().with_rate(ProsodyRate::Fast), ["Quickly now."])),
+						"Then slowly.".into()
+					]
+				)
+				.into(),
+				crate::group([Element::from("grouped"), crate::sub("World Wide Web", "WWW").into()]).into(),
+				crate::audio("https://example.com/a.ogg").with_alternate(["Fallback", "text"]).into()
+			]
+		)
+	}
+
+	fn options() -> Vec<SerializeOptions> {
+		vec![
+			SerializeOptions::default(),
+			SerializeOptions::default().pretty().flavor(Flavor::GoogleCloudTextToSpeech),
+			SerializeOptions::default()
+				.flavor(Flavor::MicrosoftAzureCognitiveSpeechServices)
+				.empty_elements(EmptyElementPolicy::Expanded),
+			SerializeOptions::default().flavor(Flavor::AmazonPolly),
+			SerializeOptions::default().pretty().flavor(Flavor::AmazonPolly).lower_unsupported(),
+			SerializeOptions::default().flavor(Flavor::ElevenLabs),
+			SerializeOptions::default().variants(VariantSelection::Key("b".into())),
+		]
+	}
+
+	fn count(elements: &[Element]) -> usize {
+		elements.iter().map(|el| 1 + count(el.children().unwrap_or_default())).sum()
+	}
+
+	#[test]
+	fn matches_individual_serialization() {
+		let variant = crate::variant()
+			.with_alternative("a", 1, crate::sentence(["First", "choice"]))
+			.with_alternative("b", 1, crate::emphasis(EmphasisLevel::Reduced, ["Second"]));
+		let mut with_variant = document();
+		with_variant.children_mut().push(variant.into());
+		let with_defaults = document().with_defaults(DocumentDefaults::default().with_voice("en-US-Wavenet-D"));
+		let empty = crate::speak(None, [Element::from(crate::paragraph::<Element, _>([])), crate::sentence(["after"]).into()]);
+
+		let options = options();
+		for doc in [document(), with_variant, with_defaults, empty] {
+			let (outputs, _) = fan_out(&doc, &options);
+			for (output, options) in outputs.iter().zip(&options) {
+				match (output, doc.serialize_to_string(options)) {
+					(Ok(output), Ok(expected)) => assert_eq!(*output, expected),
+					(Err(e), Err(expected)) => assert_eq!(e.to_string(), expected.to_string()),
+					(output, expected) => panic!("expected {expected:?}, got {output:?}")
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn visits_each_element_once() {
+		let doc = document();
+		let options = options();
+		let (_, single) = fan_out(&doc, &options[..1]);
+		let (_, all) = fan_out(&doc, &options);
+		assert_eq!(single, 1 + count(doc.children()));
+		assert_eq!(all, single);
+	}
+}
@@ -0,0 +1,153 @@
+//! Map offsets in serialized SSML back to elements of the original document.
+//!
+//! Some speech synthesis services (namely ACSS & GCTTS) report word boundary or viseme events with offsets into the
+//! submitted SSML. By serializing with [`serialize_with_source_map`], these offsets can be resolved back to the
+//! elements (and text ranges) they originated from.
+//!
+//! ```
+//! use ssml::source_map::serialize_with_source_map;
+//!
+//! # fn main() -> ssml::Result<()> {
+//! let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", ["Tom & Jerry"])]);
+//! let (ssml, map) = serialize_with_source_map(&doc, &ssml::SerializeOptions::default())?;
+//!
+//! // A word boundary event for "Jerry", as reported by the provider:
+//! let offset = ssml.find("Jerry").unwrap();
+//! let location = map.resolve_text(&doc, offset..offset + 5).unwrap();
+//! assert_eq!(location.path, [0, 0]);
+//! assert_eq!(location.range, 6..11);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+	Element, Serialize, SerializeOptions, Speak, XmlWriter,
+	util::{self, Escaper}
+};
+
+/// The byte range of an element in serialized SSML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceSpan {
+	/// The path of the element in the document; see [`Speak::element_at`].
+	///
+	/// The root [`Speak`] element has an empty path.
+	pub path: Vec<usize>,
+	/// The range of bytes in the serialized output occupied by this element.
+	pub range: Range<usize>
+}
+
+/// A range of text within a [`Text`](crate::Text) element of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextLocation {
+	/// The path of the text element in the document; see [`Speak::element_at`].
+	pub path: Vec<usize>,
+	/// The byte range within the element's text.
+	pub range: Range<usize>
+}
+
+/// A mapping from byte ranges of serialized SSML to the elements of a document.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+	spans: Vec<SourceSpan>,
+	/// The escaper the document was serialized with, used to map offsets within escaped text.
+	escaper: Option<Arc<dyn Escaper>>
+}
+
+// the escaper is configuration, not part of the mapping itself
+impl PartialEq for SourceMap {
+	fn eq(&self, other: &Self) -> bool {
+		self.spans == other.spans
+	}
+}
+
+impl Eq for SourceMap {}
+
+impl SourceMap {
+	/// Returns the spans of all elements in the document, in document order.
+	pub fn spans(&self) -> &[SourceSpan] {
+		&self.spans
+	}
+
+	/// Returns the span of the element at the given path.
+	pub fn span(&self, path: &[usize]) -> Option<&SourceSpan> {
+		self.spans.iter().find(|s| s.path == path)
+	}
+
+	/// Returns the span of the innermost element containing the given byte offset.
+	pub fn element_at_offset(&self, offset: usize) -> Option<&SourceSpan> {
+		self.spans.iter().filter(|s| s.range.contains(&offset)).max_by_key(|s| s.path.len())
+	}
+
+	/// Resolves a byte range of the serialized SSML, e.g. a word boundary reported by a speech synthesis service, to a
+	/// range of text in the original document.
+	///
+	/// Returns `None` if the range does not start inside a [`Text`](crate::Text) element.
+	pub fn resolve_text(&self, doc: &Speak, range: Range<usize>) -> Option<TextLocation> {
+		let span = self.element_at_offset(range.start)?;
+		let Some(Element::Text(text)) = doc.element_at(&span.path) else {
+			return None;
+		};
+
+		let mut position = span.range.start;
+		let (mut start, mut end) = (None, None);
+		for (i, char) in text.char_indices() {
+			let next = position + util::escaped_len_with(self.escaper.as_deref(), char);
+			if start.is_none() && range.start < next {
+				start = Some(i);
+			}
+			if range.end <= position {
+				end = Some(i);
+				break;
+			}
+			position = next;
+		}
+
+		Some(TextLocation {
+			path: span.path.clone(),
+			range: start?..end.unwrap_or(text.len())
+		})
+	}
+}
+
+/// Serializes a document to a string, returning it alongside a [`SourceMap`] of the output.
+pub fn serialize_with_source_map(doc: &Speak, options: &SerializeOptions) -> crate::Result<(String, SourceMap)> {
+	let mut out = String::new();
+	let mut writer = XmlWriter::for_options(&mut out, options);
+	writer.spans = Some(Vec::new());
+	doc.serialize_xml(&mut writer, options)?;
+
+	let mut spans = writer.spans.take().unwrap_or_default();
+	spans.insert(
+		0,
+		SourceSpan {
+			path: Vec::new(),
+			range: 0..writer.position()
+		}
+	);
+	let escaper = writer.escaper.take();
+	Ok((out, SourceMap { spans, escaper }))
+}
+
+/// Converts an offset in characters (Unicode scalar values) into the given string to an offset in bytes, for providers
+/// which report character offsets.
+pub fn char_to_byte_offset(ssml: &str, char_offset: usize) -> Option<usize> {
+	ssml.char_indices().map(|(i, _)| i).chain([ssml.len()]).nth(char_offset)
+}
+
+/// Converts an offset in UTF-16 code units into the given string to an offset in bytes, for providers which report
+/// offsets into UTF-16 strings (e.g. the ACSS SDKs for .NET & JavaScript).
+pub fn utf16_to_byte_offset(ssml: &str, utf16_offset: usize) -> Option<usize> {
+	let mut units = 0;
+	for (i, char) in ssml.char_indices().chain([(ssml.len(), '\0')]) {
+		if units == utf16_offset {
+			return Some(i);
+		} else if units > utf16_offset {
+			return None;
+		}
+		units += char.len_utf16();
+	}
+	None
+}
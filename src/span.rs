@@ -0,0 +1,108 @@
+//! Locations of elements within an input document.
+//!
+//! Elements built by [`parse`](crate::parse) record the [`Span`] of input they were parsed from, which can be retrieved
+//! with [`Annotated::span`](crate::annotations::Annotated::span). This allows diagnostics to point at the exact
+//! location of an element in the user's input.
+//!
+//! ```
+//! use ssml::span::{LineColumn, Span};
+//!
+//! let input = "<speak>\n\t<mark name=\"a\"/>\n</speak>";
+//! let start = input.find("<mark").unwrap();
+//! let span = Span::from_range(input, start..start + 16);
+//! assert_eq!(span.start, LineColumn { line: 2, column: 2 });
+//! assert_eq!(span.end, LineColumn { line: 2, column: 18 });
+//! ```
+
+use core::{
+	fmt::{self, Display},
+	ops::Range
+};
+
+/// A 1-based line & column position in an input document. Columns are counted in characters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineColumn {
+	pub line: usize,
+	pub column: usize
+}
+
+impl Display for LineColumn {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}:{}", self.line, self.column)
+	}
+}
+
+/// The location of an element in an input document.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+	/// The byte range of the element in the input.
+	pub range: Range<usize>,
+	/// The line & column of the first character of the element.
+	pub start: LineColumn,
+	/// The line & column just past the last character of the element.
+	pub end: LineColumn
+}
+
+impl Span {
+	/// Creates a [`Span`] from a byte range of the given input, calculating its line & column positions.
+	///
+	/// The range is clamped to the length of the input.
+	pub fn from_range(input: &str, range: Range<usize>) -> Span {
+		let end = range.end.min(input.len());
+		let start = range.start.min(end);
+		Span {
+			start: line_column(input, start),
+			end: line_column(input, end),
+			range: start..end
+		}
+	}
+}
+
+impl Display for Span {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}-{}", self.start, self.end)
+	}
+}
+
+fn line_column(input: &str, offset: usize) -> LineColumn {
+	Locator::new(input).locate(offset)
+}
+
+/// Calculates the line & column positions of offsets in an input, resuming from the previous offset so that locating
+/// a series of increasing offsets only scans the input once.
+pub(crate) struct Locator<'s> {
+	input: &'s str,
+	offset: usize,
+	position: LineColumn
+}
+
+impl<'s> Locator<'s> {
+	pub(crate) fn new(input: &'s str) -> Self {
+		Locator {
+			input,
+			offset: 0,
+			position: LineColumn { line: 1, column: 1 }
+		}
+	}
+
+	pub(crate) fn locate(&mut self, offset: usize) -> LineColumn {
+		if offset < self.offset {
+			*self = Locator::new(self.input);
+		}
+		let mut end = self.input.len();
+		for (i, char) in self.input[self.offset..].char_indices() {
+			if self.offset + i >= offset {
+				end = self.offset + i;
+				break;
+			}
+			if char == '\n' {
+				self.position.line += 1;
+				self.position.column = 1;
+			} else {
+				self.position.column += 1;
+			}
+		}
+		self.offset = end;
+		self.position
+	}
+}
@@ -1,18 +1,134 @@
-use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use alloc::{
+	borrow::Cow,
+	format,
+	string::{String, ToString},
+	vec,
+	vec::Vec
+};
 use core::{
 	fmt::{Debug, Write},
 	ops::{Add, AddAssign}
 };
 
-use crate::{Element, Flavor, Serialize, SerializeOptions, XmlWriter, util};
+use crate::{
+	AudioSource, Element, ElementKind, Flavor, LangFailure, Metadata, ProsodyRate, ProsodyVolume, Serialize, SerializeOptions, SsmlVersion, VoiceConfig,
+	XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	audio, raw, util
+};
 
 /// The root element of an SSML document.
 #[derive(Clone, Default, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Speak<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
 	children: Vec<Element<'s>>,
+	#[cfg_attr(feature = "serde", serde(borrow, deserialize_with = "crate::serde_borrow::option_pair"))]
 	marks: (Option<Cow<'s, str>>, Option<Cow<'s, str>>),
-	lang: Option<Cow<'s, str>>
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option"))]
+	lang: Option<Cow<'s, str>>,
+	#[cfg_attr(feature = "serde", serde(borrow, default, skip_serializing_if = "DocumentDefaults::is_empty"))]
+	defaults: DocumentDefaults<'s>,
+	#[cfg_attr(feature = "serde", serde(borrow, default, skip_serializing_if = "Vec::is_empty"))]
+	metadata: Vec<Metadata<'s>>,
+	#[cfg_attr(feature = "serde", serde(default))]
+	lang_failure: Option<LangFailure>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+/// Document-level settings applied to a [`Speak`] document during serialization. See [`Speak::with_defaults`].
+#[derive(Clone, Default, Debug)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocumentDefaults<'s> {
+	/// Voice used for content which isn't already inside a [`Voice`](crate::Voice).
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub voice: Option<VoiceConfig<'s>>,
+	/// Speaking rate used for content which isn't already inside a [`Voice`](crate::Voice).
+	pub rate: Option<ProsodyRate>,
+	/// Volume used for content which isn't already inside a [`Voice`](crate::Voice).
+	pub volume: Option<ProsodyVolume>,
+	/// Language of the document, used if the document doesn't specify its own language.
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option"))]
+	pub lang: Option<Cow<'s, str>>
+}
+
+impl<'s> DocumentDefaults<'s> {
+	pub fn with_voice(mut self, voice: impl Into<VoiceConfig<'s>>) -> Self {
+		self.voice = Some(voice.into());
+		self
+	}
+
+	pub fn with_rate(mut self, rate: impl Into<ProsodyRate>) -> Self {
+		self.rate = Some(rate.into());
+		self
+	}
+
+	pub fn with_volume(mut self, volume: impl Into<ProsodyVolume>) -> Self {
+		self.volume = Some(volume.into());
+		self
+	}
+
+	pub fn with_lang(mut self, lang: impl Into<Cow<'s, str>>) -> Self {
+		self.lang = Some(lang.into());
+		self
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.voice.is_none() && self.rate.is_none() && self.volume.is_none() && self.lang.is_none()
+	}
+
+	pub fn to_owned(&self) -> DocumentDefaults<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> DocumentDefaults<'static> {
+		DocumentDefaults {
+			voice: self.voice.map(VoiceConfig::into_owned),
+			rate: self.rate,
+			volume: self.volume,
+			lang: match self.lang {
+				Some(Cow::Borrowed(b)) => Some(Cow::Owned(b.to_string())),
+				Some(Cow::Owned(b)) => Some(Cow::Owned(b)),
+				None => None
+			}
+		}
+	}
+
+	fn has_prosody(&self) -> bool {
+		self.rate.is_some() || self.volume.is_some()
+	}
+
+	/// Serializes a run of the document's direct children which aren't voices, wrapping them in the default voice &
+	/// prosody.
+	fn serialize_run<W: Write>(&self, writer: &mut XmlWriter<W>, children: &[Element], offset: usize, options: &SerializeOptions) -> crate::Result<()> {
+		// the wrappers are checked like any other element, so e.g. Polly's lack of `<voice>` support isn't bypassed
+		let prosody = |writer: &mut XmlWriter<W>| {
+			if self.has_prosody() && util::check_support(writer, Some(ElementKind::Prosody), options)? {
+				writer.element("prosody", |writer| {
+					writer.attr_opt("rate", self.rate.as_ref())?;
+					writer.attr_opt("volume", self.volume.as_ref())?;
+					util::serialize_elements_at(writer, children, offset, options)
+				})
+			} else {
+				util::serialize_elements_at(writer, children, offset, options)
+			}
+		};
+		match &self.voice {
+			Some(voice) if util::check_support(writer, Some(ElementKind::Voice), options)? => writer.element("voice", |writer| {
+				voice.serialize_xml(writer, options)?;
+				prosody(writer)
+			}),
+			_ => prosody(writer)
+		}
+	}
 }
 
 impl<'s> Speak<'s> {
@@ -27,11 +143,39 @@ impl<'s> Speak<'s> {
 	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(lang: Option<&'s str>, elements: I) -> Self {
 		Self {
 			children: elements.into_iter().map(|f| f.into()).collect(),
-			lang: lang.map(|f| Cow::Borrowed(f)),
+			lang: lang.map(Cow::Borrowed),
 			..Speak::default()
 		}
 	}
 
+	/// Returns the language of the spoken text contained within the document, if specified.
+	pub fn lang(&self) -> Option<&str> {
+		self.lang.as_deref()
+	}
+
+	pub fn set_lang(&mut self, lang: impl Into<Cow<'s, str>>) {
+		self.lang = Some(lang.into());
+	}
+
+	/// Sets the behavior of the synthesizer when it encounters text in a language it can't speak, for the whole
+	/// document. This requires SSML 1.1; see [`SerializeOptions::version`].
+	pub fn with_lang_failure(mut self, behavior: LangFailure) -> Self {
+		self.lang_failure = Some(behavior);
+		self
+	}
+
+	pub fn lang_failure(&self) -> Option<LangFailure> {
+		self.lang_failure
+	}
+
+	pub fn set_lang_failure(&mut self, behavior: LangFailure) {
+		self.lang_failure = Some(behavior);
+	}
+
+	pub fn take_lang_failure(&mut self) -> Option<LangFailure> {
+		self.lang_failure.take()
+	}
+
 	pub fn with_start_mark(mut self, mark: impl Into<Cow<'s, str>>) -> Self {
 		self.marks.0 = Some(mark.into());
 		self
@@ -77,8 +221,7 @@ impl<'s> Speak<'s> {
 	/// assert_eq!(
 	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().pretty())?,
 	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US">
-	/// 	Hello, world!
-	/// 	This is an SSML document.
+	/// 	Hello, world! This is an SSML document.
 	/// </speak>"#
 	/// );
 	/// # Ok(())
@@ -99,8 +242,7 @@ impl<'s> Speak<'s> {
 	/// assert_eq!(
 	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().pretty())?,
 	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US">
-	/// 	Hello, world!
-	/// 	This is an SSML document.
+	/// 	Hello, world! This is an SSML document.
 	/// </speak>"#
 	/// );
 	/// # Ok(())
@@ -110,6 +252,94 @@ impl<'s> Speak<'s> {
 		self.children.extend(elements.into_iter().map(|f| f.into()));
 	}
 
+	/// Sets the document-level defaults of this document, which are materialized into wrapper elements during
+	/// serialization.
+	///
+	/// Consecutive direct children of the document which aren't [`Voice`](crate::Voice)s are wrapped in the default
+	/// voice & prosody; voices are left untouched, since their content has already been given a voice.
+	///
+	/// ```
+	/// # use ssml::{DocumentDefaults, ProsodyRate, Serialize};
+	/// # fn main() -> ssml::Result<()> {
+	/// let doc = ssml::speak(
+	/// 	None,
+	/// 	["Hello!".into(), ssml::voice("en-US-GuyNeural", ["Hi!"]).into(), ssml::Element::from("Bye!")]
+	/// )
+	/// .with_defaults(
+	/// 	DocumentDefaults::default()
+	/// 		.with_voice("en-US-JennyNeural")
+	/// 		.with_rate(ProsodyRate::Fast)
+	/// 		.with_lang("en-US")
+	/// );
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().pretty())?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US">
+	/// 	<voice name="en-US-JennyNeural">
+	/// 		<prosody rate="fast">
+	/// 			Hello!
+	/// 		</prosody>
+	/// 	</voice>
+	/// 	<voice name="en-US-GuyNeural">
+	/// 		Hi!
+	/// 	</voice>
+	/// 	<voice name="en-US-JennyNeural">
+	/// 		<prosody rate="fast">
+	/// 			Bye!
+	/// 		</prosody>
+	/// 	</voice>
+	/// </speak>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_defaults(mut self, defaults: DocumentDefaults<'s>) -> Self {
+		self.defaults = defaults;
+		self
+	}
+
+	pub fn defaults(&self) -> &DocumentDefaults<'s> {
+		&self.defaults
+	}
+
+	pub fn defaults_mut(&mut self) -> &mut DocumentDefaults<'s> {
+		&mut self.defaults
+	}
+
+	pub fn set_defaults(&mut self, defaults: DocumentDefaults<'s>) {
+		self.defaults = defaults;
+	}
+
+	/// Attaches [`Metadata`] to this document. Metadata is written before the document's content, in the order it was
+	/// attached.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// let doc = ssml::speak(None, ["Hello!"]).with_metadata(ssml::Metadata::new().with_property("title", "Greeting"));
+	/// assert_eq!(
+	/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+	/// 	concat!(
+	/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><metadata>"#,
+	/// 		r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dc="http://purl.org/dc/elements/1.1/">"#,
+	/// 		r#"<rdf:Description><dc:title>Greeting</dc:title></rdf:Description></rdf:RDF></metadata>Hello!</speak>"#
+	/// 	)
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_metadata(mut self, metadata: Metadata<'s>) -> Self {
+		self.metadata.push(metadata);
+		self
+	}
+
+	pub fn metadata(&self) -> &[Metadata<'s>] {
+		&self.metadata
+	}
+
+	pub fn metadata_mut(&mut self) -> &mut Vec<Metadata<'s>> {
+		&mut self.metadata
+	}
+
 	/// Returns a reference to the document's direct children.
 	pub fn children(&self) -> &[Element<'s>] {
 		&self.children
@@ -120,6 +350,159 @@ impl<'s> Speak<'s> {
 		&mut self.children
 	}
 
+	/// Returns a copy of this document's root element & settings, without its children.
+	pub(crate) fn without_children(&self) -> Speak<'s> {
+		Speak {
+			children: Vec::new(),
+			marks: self.marks.clone(),
+			lang: self.lang.clone(),
+			defaults: self.defaults.clone(),
+			metadata: self.metadata.clone(),
+			lang_failure: self.lang_failure,
+			extra_attrs: self.extra_attrs.clone(),
+			annotations: self.annotations.clone()
+		}
+	}
+
+	/// Returns the element at the given path, where each item of `path` is the index of a child element, starting from
+	/// the document's direct children.
+	///
+	/// ```
+	/// let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", ["Hello, world!"])]);
+	/// assert!(matches!(doc.element_at(&[0, 0]), Some(ssml::Element::Text(_))));
+	/// assert!(doc.element_at(&[1]).is_none());
+	/// ```
+	pub fn element_at(&self, path: &[usize]) -> Option<&Element<'s>> {
+		let (first, rest) = path.split_first()?;
+		let mut element = self.children.get(*first)?;
+		for index in rest {
+			element = element.children()?.get(*index)?;
+		}
+		Some(element)
+	}
+
+	/// Returns a mutable reference to the element at the given path. See [`Speak::element_at`].
+	pub fn element_at_mut(&mut self, path: &[usize]) -> Option<&mut Element<'s>> {
+		let (first, rest) = path.split_first()?;
+		let mut element = self.children.get_mut(*first)?;
+		for index in rest {
+			element = element.children_mut()?.get_mut(*index)?;
+		}
+		Some(element)
+	}
+
+	/// Returns every audio file referenced by the document, along with the clip & repeat settings it is played with, in
+	/// document order, e.g. to prefetch them before the document is sent to the synthesizer.
+	///
+	/// Audio elements which share the same source & settings are only returned once. Audio nested within another audio
+	/// element's alternate content is included.
+	///
+	/// ```
+	/// # use ssml::AudioRepeat;
+	/// let doc = ssml::speak(
+	/// 	None,
+	/// 	[
+	/// 		ssml::audio("https://example.com/chime.wav").into(),
+	/// 		ssml::Element::from("Your order has shipped."),
+	/// 		ssml::audio("https://example.com/chime.wav").into(),
+	/// 		ssml::audio("https://example.com/music.mp3").with_repeat(AudioRepeat::Times(2.)).into()
+	/// 	]
+	/// );
+	///
+	/// let sources = doc.audio_sources();
+	/// assert_eq!(sources.len(), 2);
+	/// assert_eq!(sources[0].src, "https://example.com/chime.wav");
+	/// assert_eq!(sources[1].src, "https://example.com/music.mp3");
+	/// assert_eq!(sources[1].repeat, Some(&AudioRepeat::Times(2.)));
+	/// ```
+	pub fn audio_sources(&self) -> Vec<AudioSource<'_>> {
+		audio::sources(self)
+	}
+
+	/// Replaces every occurrence of each key of `replacements` in the document's text with its value, e.g. to fill in
+	/// per-user placeholders.
+	///
+	/// Text, [`Phoneme`](crate::Phoneme) & [`Sub`](crate::Sub) text and audio descriptions are replaced. To produce
+	/// several variants of a common base document, clone the base & replace the text of each clone; text which borrows
+	/// from the base's input (e.g. a document parsed with [`parse_str`](crate::parse::parse_str)) keeps borrowing from
+	/// it unless it's replaced.
+	///
+	/// ```
+	/// # use ssml::Serialize;
+	/// # fn main() -> ssml::Result<()> {
+	/// let base = ssml::speak(None, [ssml::voice("en-US-JennyNeural", [
+	/// 	ssml::Element::from("Hi {name}, your order has shipped."),
+	/// 	ssml::breaks("250ms").into(),
+	/// 	"Thanks for shopping with us!".into()
+	/// ])]);
+	///
+	/// for name in ["Alice", "Bob"] {
+	/// 	let mut variant = base.clone();
+	/// 	variant.replace_text([("{name}", name)]);
+	/// 	assert_eq!(
+	/// 		variant.serialize_to_string(&ssml::SerializeOptions::default())?,
+	/// 		format!(
+	/// 			r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-JennyNeural">Hi {name}, your order has shipped.<break time="+250ms"/>Thanks for shopping with us!</voice></speak>"#
+	/// 		)
+	/// 	);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn replace_text<K: Into<String>, V: Into<String>>(&mut self, replacements: impl IntoIterator<Item = (K, V)>) {
+		let replacements: Vec<(String, String)> = replacements
+			.into_iter()
+			.map(|(from, to)| (from.into(), to.into()))
+			.filter(|(from, _)| !from.is_empty())
+			.collect();
+		if replacements.is_empty() {
+			return;
+		}
+		for child in &mut self.children {
+			replace_in(child, &replacements);
+		}
+	}
+
+	/// Serializes this document for `flavor` with each element on its own line, indented by depth.
+	///
+	/// Pretty & [minified](Speak::to_min_string) output always [parse](crate::parse::parse_str) back to the same
+	/// document, so the choice of formatting never changes what is spoken.
+	///
+	/// ```
+	/// # use ssml::Flavor;
+	/// # fn main() -> ssml::Result<()> {
+	/// let doc = ssml::speak(Some("en-US"), [ssml::paragraph([ssml::sentence(["Hello there."]), ssml::sentence(["How are you?"])])]);
+	///
+	/// let pretty = doc.to_pretty_string(Flavor::GoogleCloudTextToSpeech)?;
+	/// assert_eq!(
+	/// 	pretty,
+	/// 	"<speak xml:lang=\"en-US\">\n\t<p>\n\t\t<s>\n\t\t\tHello there.\n\t\t</s>\n\t\t<s>\n\t\t\tHow are you?\n\t\t</s>\n\t</p>\n</speak>"
+	/// );
+	/// assert_eq!(
+	/// 	ssml::parse::parse_str(&pretty)?.to_min_string(Flavor::GoogleCloudTextToSpeech)?,
+	/// 	doc.to_min_string(Flavor::GoogleCloudTextToSpeech)?
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn to_pretty_string(&self, flavor: Flavor) -> crate::Result<String> {
+		self.serialize_to_string(&SerializeOptions::default().flavor(flavor).pretty())
+	}
+
+	/// Serializes this document for `flavor` without any whitespace between elements.
+	///
+	/// ```
+	/// # use ssml::Flavor;
+	/// # fn main() -> ssml::Result<()> {
+	/// let doc = ssml::speak(None, [ssml::sentence(["Hello there."]), ssml::sentence(["How are you?"])]);
+	/// assert_eq!(doc.to_min_string(Flavor::AmazonPolly)?, "<speak><s>Hello there.</s><s>How are you?</s></speak>");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn to_min_string(&self, flavor: Flavor) -> crate::Result<String> {
+		self.serialize_to_string(&SerializeOptions::default().flavor(flavor).min())
+	}
+
 	pub fn to_owned(&self) -> Speak<'static> {
 		self.clone().into_owned()
 	}
@@ -143,33 +526,137 @@ impl<'s> Speak<'s> {
 				Some(Cow::Borrowed(b)) => Some(Cow::Owned(b.to_string())),
 				Some(Cow::Owned(b)) => Some(Cow::Owned(b)),
 				None => None
-			}
+			},
+			defaults: self.defaults.into_owned(),
+			metadata: self.metadata.into_iter().map(Metadata::into_owned).collect(),
+			lang_failure: self.lang_failure,
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
 
 impl<'s> Serialize for Speak<'s> {
 	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		if raw::write_preserved(self, Some(&self.annotations), writer, options)? {
+			return Ok(());
+		}
+		// ElevenLabs takes markup inline with the text, without a root element
+		if options.flavor == Flavor::ElevenLabs {
+			return self.serialize_children(writer, options);
+		}
 		writer.element("speak", |writer| {
 			if matches!(options.flavor, Flavor::Generic | Flavor::MicrosoftAzureCognitiveSpeechServices) {
-				writer.attr("version", "1.0")?;
+				writer.attr("version", options.version.unwrap_or(SsmlVersion::V1_0).as_str())?;
 				writer.attr("xmlns", "http://www.w3.org/2001/10/synthesis")?;
 			}
 
-			writer.attr_opt("xml:lang", self.lang.as_deref())?;
-			// Include `mstts` namespace for ACSS.
-			if options.flavor == Flavor::MicrosoftAzureCognitiveSpeechServices {
-				writer.attr("xmlns:mstts", "http://www.w3.org/2001/mstts")?;
+			writer.attr_opt("xml:lang", self.lang.as_deref().or(self.defaults.lang.as_deref()))?;
+			// e.g. the `mstts` namespace for ACSS
+			for (prefix, uri) in options.spec().namespaces() {
+				writer.attr(format!("xmlns:{prefix}"), uri)?;
+			}
+			if let Some(behavior) = self.lang_failure {
+				util::require_v1_1(options, "onlangfailure")?;
+				writer.attr("onlangfailure", behavior.as_str())?;
 			}
 
 			writer.attr_opt("startmark", self.marks.0.as_deref())?;
 			writer.attr_opt("endmark", self.marks.1.as_deref())?;
+			attrs::write(writer, &self.extra_attrs)?;
 
-			util::serialize_elements(writer, &self.children, options)
+			for metadata in &self.metadata {
+				metadata.serialize_xml(writer, options)?;
+			}
+
+			self.serialize_children(writer, options)
 		})
 	}
 }
 
+impl Speak<'_> {
+	/// Writes the children of this document, wrapping runs of elements outside of a [`Voice`] with the document's
+	/// defaults.
+	fn serialize_children<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		if self.defaults.voice.is_none() && !self.defaults.has_prosody() {
+			return util::serialize_elements(writer, &self.children, options);
+		}
+		let mut start = 0;
+		while start < self.children.len() {
+			if let Element::Voice(_) = &self.children[start] {
+				util::serialize_elements_at(writer, &self.children[start..=start], start, options)?;
+				start += 1;
+				continue;
+			}
+			let end = self.children[start..]
+				.iter()
+				.position(|el| matches!(el, Element::Voice(_)))
+				.map_or(self.children.len(), |i| start + i);
+			self.defaults.serialize_run(writer, &self.children[start..end], start, options)?;
+			start = end;
+		}
+		Ok(())
+	}
+}
+
+/// Applies `replacements` to all text within `el` & its children.
+fn replace_in(el: &mut Element, replacements: &[(String, String)]) {
+	match el {
+		Element::Text(text) => {
+			if let Some(replaced) = replace_all(replacements, text) {
+				text.set(replaced);
+			}
+		}
+		Element::Phoneme(phoneme) => {
+			if let Some(replaced) = replace_all(replacements, phoneme.text()) {
+				phoneme.set_text(replaced);
+			}
+		}
+		Element::Sub(sub) => {
+			if let Some(replaced) = replace_all(replacements, sub.text()) {
+				sub.set_text(replaced);
+			}
+		}
+		Element::Audio(audio) => {
+			for desc in audio.descs_mut() {
+				if let Some(replaced) = replace_all(replacements, desc.text()) {
+					desc.set_text(replaced);
+				}
+			}
+		}
+		_ => {}
+	}
+	if let Some(children) = el.children_mut() {
+		for child in children {
+			replace_in(child, replacements);
+		}
+	}
+}
+
+/// Replaces every occurrence of each `(from, to)` pair in `text` in a single pass, preferring earlier pairs where
+/// several match at the same position.
+fn replace_all(replacements: &[(String, String)], text: &str) -> Option<String> {
+	let mut out: Option<String> = None;
+	let mut copied = 0;
+	let mut i = 0;
+	while i < text.len() {
+		let rest = &text[i..];
+		match replacements.iter().find(|(from, _)| rest.starts_with(&**from)) {
+			Some((from, to)) => {
+				let out = out.get_or_insert_with(|| String::with_capacity(text.len()));
+				out.push_str(&text[copied..i]);
+				out.push_str(to);
+				i += from.len();
+				copied = i;
+			}
+			None => i += rest.chars().next().map_or(1, char::len_utf8)
+		}
+	}
+	let mut out = out?;
+	out.push_str(&text[copied..]);
+	Some(out)
+}
+
 impl<'s, 's2: 's, T: Into<Element<'s2>>> Add<T> for Speak<'s> {
 	type Output = Speak<'s>;
 
@@ -208,7 +695,284 @@ impl<'s, 's2: 's, T: Into<Element<'s2>>> AddAssign<T> for Speak<'s> {
 pub fn speak<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(lang: Option<&'s str>, elements: I) -> Speak<'s> {
 	Speak {
 		children: elements.into_iter().map(|f| f.into()).collect(),
-		lang: lang.map(|f| Cow::Borrowed(f)),
+		lang: lang.map(Cow::Borrowed),
 		..Speak::default()
 	}
 }
+
+/// Context passed to [`IntoSpeak::into_speak`], describing the document being produced.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct SpeakContext<'s> {
+	/// The default language of the document, e.g. `en-US`.
+	pub lang: Option<Cow<'s, str>>,
+	/// The flavor of SSML the document will be serialized as, if known. When set, [`IntoSpeak::serialize_speak`] uses
+	/// it in place of the flavor of its [`SerializeOptions`].
+	pub flavor: Option<Flavor>
+}
+
+impl<'s> SpeakContext<'s> {
+	pub fn with_lang(mut self, lang: impl Into<Cow<'s, str>>) -> Self {
+		self.lang = Some(lang.into());
+		self
+	}
+
+	pub fn with_flavor(mut self, flavor: Flavor) -> Self {
+		self.flavor = Some(flavor);
+		self
+	}
+}
+
+/// Trait for types which can render themselves as a full SSML document.
+///
+/// This is implemented for [`Speak`] itself, and for anything that can be converted into an [`Element`], which is
+/// wrapped in a document using the context's language.
+///
+/// ```
+/// use ssml::{IntoSpeak, SpeakContext};
+///
+/// struct Greeting<'a> {
+/// 	name: &'a str
+/// }
+///
+/// impl<'s> IntoSpeak<'s> for Greeting<'s> {
+/// 	fn into_speak(self, ctx: &SpeakContext<'s>) -> ssml::Speak<'s> {
+/// 		ssml::speak(None, ["Hello,", self.name]).into_speak(ctx)
+/// 	}
+/// }
+///
+/// # fn main() -> ssml::Result<()> {
+/// let ctx = SpeakContext::default().with_lang("en-US");
+/// assert_eq!(
+/// 	Greeting { name: "Alice" }.serialize_speak(&ctx, &ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US">Hello, Alice</speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub trait IntoSpeak<'s> {
+	/// Renders this value as an SSML document.
+	fn into_speak(self, ctx: &SpeakContext<'s>) -> Speak<'s>;
+
+	/// Renders this value as an SSML document and serializes it to a string.
+	///
+	/// If the context specifies a [`Flavor`], it's used instead of the flavor of `options`.
+	///
+	/// ```
+	/// use ssml::{Flavor, IntoSpeak, SerializeOptions, SpeakContext};
+	///
+	/// # fn main() -> ssml::Result<()> {
+	/// let polly = SerializeOptions::default().flavor(Flavor::AmazonPolly);
+	/// assert_eq!(ssml::speak(None, ["Hi!"]).serialize_speak(&SpeakContext::default(), &polly)?, "<speak>Hi!</speak>");
+	///
+	/// // even the generic flavor is respected when given explicitly
+	/// let ctx = SpeakContext::default().with_flavor(Flavor::Generic);
+	/// assert_eq!(
+	/// 	ssml::speak(None, ["Hi!"]).serialize_speak(&ctx, &polly)?,
+	/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Hi!</speak>"#
+	/// );
+	/// # Ok(())
+	/// # }
+	/// ```
+	fn serialize_speak(self, ctx: &SpeakContext<'s>, options: &SerializeOptions) -> crate::Result<String>
+	where
+		Self: Sized
+	{
+		let doc = self.into_speak(ctx);
+		match ctx.flavor {
+			Some(flavor) => doc.serialize_to_string(&options.clone().flavor(flavor)),
+			None => doc.serialize_to_string(options)
+		}
+	}
+}
+
+impl<'s> IntoSpeak<'s> for Speak<'s> {
+	/// Returns this document, using the context's language if the document doesn't specify one.
+	fn into_speak(mut self, ctx: &SpeakContext<'s>) -> Speak<'s> {
+		if self.lang.is_none() {
+			self.lang.clone_from(&ctx.lang);
+		}
+		self
+	}
+}
+
+impl<'s, T: Into<Element<'s>>> IntoSpeak<'s> for T {
+	fn into_speak(self, ctx: &SpeakContext<'s>) -> Speak<'s> {
+		Speak {
+			children: vec![self.into()],
+			lang: ctx.lang.clone(),
+			..Speak::default()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::vec;
+
+	use super::Speak;
+	use crate::{Element, EmphasisLevel, FetchHint, Flavor, ProsodyControl, ProsodyRate, SpeechFormat, mstts};
+
+	fn documents() -> Vec<Speak<'static>> {
+		vec![
+			crate::speak(Some("en-US"), ["Hello, world!", "This is an SSML document."]),
+			crate::speak(
+				Some("en-US"),
+				[crate::paragraph([
+					crate::sentence([
+						Element::from("Hello "),
+						crate::emphasis(EmphasisLevel::Strong, ["there"]).into(),
+						" friend, ".into(),
+						crate::breaks("500ms").into(),
+						" how are you?".into()
+					]),
+					crate::sentence([
+						Element::from("Call"),
+						crate::say_as(SpeechFormat::Telephone, "555-0100").into(),
+						"or visit".into(),
+						crate::sub("World Wide Web", "WWW").into()
+					])
+				])]
+			),
+			crate::speak(
+				None,
+				[
+					Element::from(crate::voice(
+						"en-US-Neural2-F",
+						[
+							Element::from(crate::prosody(ProsodyControl::default().with_rate(ProsodyRate::Fast), ["Quickly now."])),
+							crate::mark("end").into(),
+							"  Spaced  out  ".into()
+						]
+					)),
+					crate::audio("https://example.com/a.ogg")
+						.with_fetch_hint(FetchHint::Safe)
+						.with_max_stale(60)
+						.with_alternate(["Fallback & more."])
+						.into()
+				]
+			),
+		]
+	}
+
+	fn assert_round_trip(doc: &Speak, flavor: Flavor) -> crate::Result<()> {
+		let min = doc.to_min_string(flavor)?;
+		let pretty = doc.to_pretty_string(flavor)?;
+		assert_eq!(crate::parse::parse_str(&min)?.to_min_string(flavor)?, min, "minified output changed when parsed");
+		assert_eq!(crate::parse::parse_str(&pretty)?.to_min_string(flavor)?, min, "pretty output differs from minified:\n{pretty}");
+		Ok(())
+	}
+
+	#[test]
+	fn pretty_and_min_round_trip() -> crate::Result<()> {
+		for flavor in [Flavor::Generic, Flavor::GoogleCloudTextToSpeech, Flavor::AmazonPolly, Flavor::MicrosoftAzureCognitiveSpeechServices] {
+			for doc in documents() {
+				// not every flavor supports every element
+				if doc.to_min_string(flavor).is_ok() {
+					assert_round_trip(&doc, flavor)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn pretty_and_min_round_trip_acss() -> crate::Result<()> {
+		let doc = crate::speak(
+			Some("en-US"),
+			[crate::voice(
+				"en-US-JennyNeural",
+				[
+					Element::from(mstts::express(mstts::express::Cheerful, [Element::from("Great "), crate::mark("news").into(), "!".into()])),
+					crate::lang("fr-FR", ["bonjour"]).into()
+				]
+			)]
+		);
+		assert_round_trip(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices)
+	}
+
+	#[test]
+	fn prosody_round_trip() -> crate::Result<()> {
+		let input = r#"<speak><prosody contour="(0%,120Hz) (50%,2st)" rate="80%">contoured</prosody></speak>"#;
+		let doc = crate::parse::parse_str(input)?;
+		let Element::Prosody(prosody) = &doc.children()[0] else {
+			panic!("expected prosody")
+		};
+		assert_eq!(prosody.control().contour.as_ref().map(|c| c.points().len()), Some(2));
+		assert_round_trip(&doc, Flavor::Generic)?;
+		assert!(doc.to_min_string(Flavor::Generic)?.contains(r#"contour="(0%,+120Hz) (50%,+2st)""#));
+
+		// relative rates can't be represented by `ProsodyRate`, so they're kept verbatim
+		for rate in ["-50%", "+10%"] {
+			let input = alloc::format!(r#"<speak><prosody rate="{rate}">relative</prosody></speak>"#);
+			let doc = crate::parse::parse_str(&input)?;
+			assert!(matches!(doc.children()[0], Element::Custom(_)));
+			assert!(doc.to_min_string(Flavor::Generic)?.contains(&alloc::format!(r#"rate="{rate}""#)));
+		}
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn speak_deserialize_borrows() {
+		use alloc::borrow::Cow;
+
+		use crate::{CustomElement, ExtraAttrs, VoiceConfig};
+
+		let borrowed = |s: &Cow<str>| matches!(s, Cow::Borrowed(_));
+
+		let mut voice = crate::voice(
+			VoiceConfig {
+				names: Some(vec!["en-US-JennyNeural".into()]),
+				languages: Some(vec!["en-US".into(), "fr-FR".into()]),
+				..VoiceConfig::default()
+			},
+			[Element::from("Hello, world!"), CustomElement::new("custom").with_attr("key", "value").into()]
+		);
+		voice.attrs.push(("effect".into(), "eq_car".into()));
+		let mut doc = crate::speak(Some("en-US"), [voice]).with_start_mark("start");
+		doc.set_extra_attr("data-id", "42");
+
+		let json = serde_json::to_string(&doc).unwrap();
+		let doc: Speak = serde_json::from_str(&json).unwrap();
+		assert!(doc.lang.as_ref().is_some_and(borrowed));
+		assert!(doc.marks.0.as_ref().is_some_and(borrowed));
+		assert!(doc.extra_attrs.iter().all(|(name, value)| borrowed(name) && borrowed(value)));
+
+		let Element::Voice(voice) = &doc.children()[0] else {
+			panic!("expected voice")
+		};
+		assert!(voice.config().names.iter().chain(&voice.config().languages).flatten().all(borrowed));
+		assert!(voice.attrs.iter().all(|(name, value)| borrowed(name) && borrowed(value)));
+		assert!(matches!(&voice.children()[0], Element::Text(text) if borrowed(&text.0)));
+		let Element::Custom(custom) = &voice.children()[1] else {
+			panic!("expected custom element")
+		};
+		assert!(custom.attrs().iter().all(|(name, value)| borrowed(name) && borrowed(value)));
+
+		// elements deserialize on their own, too
+		let json = serde_json::to_string(&doc.children()[0]).unwrap();
+		let Element::Voice(voice) = serde_json::from_str::<Element>(&json).unwrap() else {
+			panic!("expected voice")
+		};
+		assert!(voice.config().names.iter().flatten().all(borrowed));
+		assert!(matches!(&voice.children()[0], Element::Text(text) if borrowed(&text.0)));
+	}
+
+	#[test]
+	fn defaults_are_checked() -> crate::Result<()> {
+		use crate::{DocumentDefaults, Error, Serialize, SerializeOptions};
+
+		let options = SerializeOptions::default().flavor(Flavor::AmazonPolly);
+		let rate = DocumentDefaults::default().with_rate(ProsodyRate::Fast);
+		let doc = crate::speak(None, [Element::from("hi")]).with_defaults(rate.clone());
+		assert_eq!(doc.serialize_to_string(&options)?, r#"<speak><prosody rate="fast">hi</prosody></speak>"#);
+
+		// Polly doesn't support `<voice>`, whether it's a child of the document or written for its defaults
+		let doc = crate::speak(None, [Element::from("hi"), crate::voice("Joanna", ["x"]).into()]).with_defaults(rate);
+		assert!(matches!(doc.serialize_to_string(&options), Err(Error::UnsupportedElement("voice", Flavor::AmazonPolly))));
+		let doc = crate::speak(None, ["hi"]).with_defaults(DocumentDefaults::default().with_voice("Joanna"));
+		assert!(matches!(doc.serialize_to_string(&options), Err(Error::UnsupportedElement("voice", Flavor::AmazonPolly))));
+		Ok(())
+	}
+}
@@ -0,0 +1,177 @@
+//! Break down the spoken content of a document by voice & language.
+//!
+//! Speech synthesis services bill by the number of characters synthesized, and some (e.g. ACSS) bill different voices
+//! at different rates. [`Statistics`] attributes the characters & estimated duration (see [`DurationModel`]) of each
+//! part of a document to the voice & language speaking it, for per-voice cost attribution.
+//!
+//! ```
+//! use ssml::{duration::DurationModel, stats::Statistics};
+//!
+//! let doc = ssml::speak(
+//! 	Some("en-US"),
+//! 	[
+//! 		ssml::voice("en-US-JennyNeural", ["Hello!"]),
+//! 		ssml::voice("en-US-GuyNeural", [ssml::lang("de-DE", ["Guten Tag!"])])
+//! 	]
+//! );
+//! let stats = Statistics::new(&doc, &DurationModel::default());
+//!
+//! assert_eq!(stats.total().characters, 16);
+//! assert_eq!(stats.voice(Some("en-US-JennyNeural")).unwrap().characters, 6);
+//! assert_eq!(stats.voice(Some("en-US-GuyNeural")).unwrap().characters, 10);
+//! assert_eq!(stats.language(Some("en-US")).unwrap().characters, 6);
+//! assert_eq!(stats.language(Some("de-DE")).unwrap().duration.to_millis(), 1100.);
+//! ```
+
+use alloc::{
+	borrow::Cow,
+	collections::BTreeMap,
+	string::{String, ToString},
+	vec::Vec
+};
+
+use crate::{
+	Element, Speak, TimeDesignation,
+	duration::{DurationModel, EstimateSink, Estimator},
+	visit::Visit
+};
+
+/// The amount of content spoken by a voice or in a language.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Usage {
+	/// The number of characters (Unicode scalar values) of spoken text.
+	pub characters: usize,
+	/// The estimated time spent speaking, including breaks & audio.
+	pub duration: TimeDesignation
+}
+
+impl Usage {
+	fn add(&mut self, characters: usize, millis: f32) {
+		self.characters += characters;
+		self.duration = TimeDesignation::from_millis(self.duration.to_millis() + millis);
+	}
+}
+
+/// Character counts & estimated durations of a document, broken down by voice & language.
+///
+/// Voices are identified by their first name (see [`VoiceConfig::names`](crate::VoiceConfig::names)); content which
+/// isn't spoken by a named voice (including voices selected only by e.g. gender) is attributed to `None`. Likewise,
+/// content without a known language is attributed to `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Statistics {
+	total: Usage,
+	voices: BTreeMap<Option<String>, Usage>,
+	languages: BTreeMap<Option<String>, Usage>
+}
+
+impl Statistics {
+	/// Collects statistics for the given document, estimating durations with the given [`DurationModel`].
+	pub fn new(doc: &Speak, model: &DurationModel) -> Statistics {
+		let defaults = doc.defaults();
+		let voice = defaults.voice.as_ref().and_then(|voice| first(&voice.names));
+		let lang = doc.lang().or(defaults.lang.as_deref()).map(ToString::to_string);
+
+		let mut estimator = Estimator::new(
+			model,
+			Collector {
+				stats: Statistics::default(),
+				root: (voice, lang),
+				stack: Vec::new(),
+				last: 0.
+			}
+		);
+		estimator.visit_speak(doc);
+		let elapsed = estimator.elapsed;
+		let mut collector = estimator.sink;
+		// trailing breaks at the document level
+		collector.attribute(elapsed, 0);
+		collector.stats
+	}
+
+	/// Returns the usage of the whole document.
+	pub fn total(&self) -> &Usage {
+		&self.total
+	}
+
+	/// Returns the usage of the voice with the given name, or of content not spoken by a named voice if `None`.
+	pub fn voice(&self, name: Option<&str>) -> Option<&Usage> {
+		self.voices.get(&name.map(ToString::to_string))
+	}
+
+	/// Returns an iterator over the usage of each voice, ordered by name.
+	pub fn voices(&self) -> impl Iterator<Item = (Option<&str>, &Usage)> {
+		self.voices.iter().map(|(name, usage)| (name.as_deref(), usage))
+	}
+
+	/// Returns the usage of the given language, or of content with no known language if `None`.
+	pub fn language(&self, lang: Option<&str>) -> Option<&Usage> {
+		self.languages.get(&lang.map(ToString::to_string))
+	}
+
+	/// Returns an iterator over the usage of each language, ordered by language tag.
+	pub fn languages(&self) -> impl Iterator<Item = (Option<&str>, &Usage)> {
+		self.languages.iter().map(|(lang, usage)| (lang.as_deref(), usage))
+	}
+}
+
+fn first(list: &Option<Vec<Cow<'_, str>>>) -> Option<String> {
+	list.as_ref()?.first().map(|item| item.to_string())
+}
+
+/// The voice & language in effect.
+type Context = (Option<String>, Option<String>);
+
+struct Collector {
+	stats: Statistics,
+	root: Context,
+	/// The context introduced by each entered element, if it changes the voice or language.
+	stack: Vec<Option<Context>>,
+	/// The time up to which usage has been attributed.
+	last: f32
+}
+
+impl Collector {
+	fn context(&self) -> &Context {
+		self.stack.iter().rev().find_map(Option::as_ref).unwrap_or(&self.root)
+	}
+
+	/// Attributes the time since the last call, plus the given number of characters, to the current context.
+	fn attribute(&mut self, at: f32, characters: usize) {
+		let millis = (at - self.last).max(0.);
+		self.last = self.last.max(at);
+		if millis == 0. && characters == 0 {
+			return;
+		}
+		let (voice, lang) = self.context().clone();
+		self.stats.total.add(characters, millis);
+		self.stats.voices.entry(voice).or_default().add(characters, millis);
+		self.stats.languages.entry(lang).or_default().add(characters, millis);
+	}
+}
+
+impl EstimateSink for Collector {
+	fn text(&mut self, text: &str, start: f32, end: f32) {
+		self.attribute(start, 0);
+		self.attribute(end, text.chars().count());
+	}
+
+	fn enter(&mut self, _path: &[usize], el: &Element, at: f32) {
+		self.attribute(at, 0);
+		let (voice, lang) = self.context();
+		let context = match el {
+			Element::Voice(el) => {
+				let config = el.config();
+				Some((first(&config.names), first(&config.languages).or_else(|| lang.clone())))
+			}
+			Element::Lang(el) => Some((voice.clone(), Some(el.language().to_string()))),
+			_ => None
+		};
+		self.stack.push(context);
+	}
+
+	fn exit(&mut self, _path: &[usize], _el: &Element, at: f32) {
+		self.attribute(at, 0);
+		self.stack.pop();
+	}
+}
@@ -0,0 +1,106 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+	Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// [`Sub`] substitutes the contained text with an alias when spoken, e.g. to expand an abbreviation. The contained text
+/// is still used for display purposes, such as captions.
+///
+/// `<sub>` is supported by all of the major providers, so it is serialized the same way for every
+/// [`Flavor`](crate::Flavor).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sub<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	alias: Cow<'s, str>,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) text: Cow<'s, str>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Sub<'s> {
+	/// Creates a new [`Sub`] element, speaking `alias` in place of `text`.
+	pub fn new(alias: impl Into<Cow<'s, str>>, text: impl Into<Cow<'s, str>>) -> Self {
+		Self {
+			alias: alias.into(),
+			text: text.into(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	/// Returns the text which is spoken in place of the contained text.
+	pub fn alias(&self) -> &str {
+		&self.alias
+	}
+
+	pub fn set_alias(&mut self, alias: impl Into<Cow<'s, str>>) {
+		self.alias = alias.into();
+	}
+
+	pub fn text(&self) -> &str {
+		&self.text
+	}
+
+	pub fn set_text(&mut self, text: impl Into<Cow<'s, str>>) {
+		self.text = text.into();
+	}
+
+	pub fn to_owned(&self) -> Sub<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Sub<'static> {
+		Sub {
+			alias: match self.alias {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			},
+			text: match self.text {
+				Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+				Cow::Owned(b) => Cow::Owned(b)
+			},
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+}
+
+impl<'s> Serialize for Sub<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.element("sub", |writer| {
+			writer.attr("alias", &*self.alias)?;
+			attrs::write(writer, &self.extra_attrs)?;
+			util::write_text(writer, &self.text, options)
+		})
+	}
+}
+
+/// Creates a new [`Sub`] element, speaking `alias` in place of `text`.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let doc = ssml::speak(None, [ssml::Element::from("Welcome to the "), ssml::sub("World Wide Web", "WWW").into()]);
+///
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Welcome to the <sub alias="World Wide Web">WWW</sub></speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn sub<'s>(alias: impl Into<Cow<'s, str>>, text: impl Into<Cow<'s, str>>) -> Sub<'s> {
+	Sub::new(alias, text)
+}
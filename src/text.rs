@@ -1,12 +1,13 @@
 use alloc::{borrow::Cow, string::ToString};
 use core::{fmt::Write, ops::Deref};
 
-use crate::{Serialize, SerializeOptions, XmlWriter};
+use crate::{Serialize, SerializeOptions, XmlWriter, util};
 
 /// A non-marked-up string of text for use as a spoken element.
 #[derive(Default, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Text<'s>(Cow<'s, str>);
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Text<'s>(#[cfg_attr(feature = "serde", serde(borrow))] pub(crate) Cow<'s, str>);
 
 impl<'s> Text<'s> {
 	pub fn to_owned(&self) -> Text<'static> {
@@ -14,7 +15,7 @@ impl<'s> Text<'s> {
 	}
 
 	pub fn as_str(&self) -> &str {
-		&*self.0
+		&self.0
 	}
 
 	pub fn set(&mut self, text: impl Into<Cow<'s, str>>) {
@@ -33,7 +34,7 @@ impl Deref for Text<'_> {
 	type Target = str;
 
 	fn deref(&self) -> &Self::Target {
-		&*self.0
+		&self.0
 	}
 }
 
@@ -44,8 +45,8 @@ impl<'s, T: Into<Cow<'s, str>>> From<T> for Text<'s> {
 }
 
 impl Serialize for Text<'_> {
-	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
-		writer.text(&self.0)
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		util::write_text(writer, &self.0, options)
 	}
 }
 
@@ -64,4 +65,16 @@ mod tests {
 		assert_eq!(text("One & two").serialize_to_string(&SerializeOptions::default())?, "One &amp; two");
 		Ok(())
 	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn text_deserialize_borrows() {
+		use alloc::borrow::Cow;
+
+		use serde::{Deserialize, de::value::BorrowedStrDeserializer};
+
+		let input = "Hello, world!";
+		let text = super::Text::deserialize(BorrowedStrDeserializer::<serde::de::value::Error>::new(input)).unwrap();
+		assert!(matches!(text.0, Cow::Borrowed(s) if s == input));
+	}
 }
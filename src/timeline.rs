@@ -0,0 +1,115 @@
+//! Map the elements of a document to their estimated position in time.
+//!
+//! A [`Timeline`] is built with a [`DurationModel`] before any audio is synthesized, which allows applications to e.g.
+//! display a scrubber with element positions ahead of time.
+//!
+//! ```
+//! use ssml::{TimeDesignation, duration::DurationModel, timeline::Timeline};
+//!
+//! let doc = ssml::speak(None, ["Hello there."]) + ssml::breaks("500ms") + ssml::mark("end") + "How are you?";
+//! let timeline = Timeline::new(&doc, &DurationModel::default());
+//!
+//! // `Hello there.` takes 1.1s, followed by a 500ms break.
+//! assert_eq!(timeline.entry(&[3]).unwrap().start, TimeDesignation::from_millis(1600.));
+//! assert_eq!(timeline.mark("end"), Some(&TimeDesignation::from_millis(1600.)));
+//! assert_eq!(timeline.duration(), &TimeDesignation::from_millis(3100.));
+//! ```
+
+use alloc::{
+	string::{String, ToString},
+	vec::Vec
+};
+
+use crate::{
+	Element, Speak, TimeDesignation,
+	duration::{DurationModel, EstimateSink, Estimator},
+	visit::Visit
+};
+
+/// The estimated position of an element in a [`Timeline`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry {
+	/// The path of the element in the document; see [`Speak::element_at`].
+	pub path: Vec<usize>,
+	pub start: TimeDesignation,
+	pub end: TimeDesignation
+}
+
+/// Estimated start & end offsets for each element and mark in a document.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Timeline {
+	entries: Vec<TimelineEntry>,
+	marks: Vec<(String, TimeDesignation)>,
+	duration: TimeDesignation
+}
+
+impl Timeline {
+	/// Builds a timeline for the given document using the given [`DurationModel`].
+	pub fn new(doc: &Speak, model: &DurationModel) -> Timeline {
+		let mut estimator = Estimator::new(model, Timeline::default());
+		estimator.visit_speak(doc);
+
+		let mut timeline = estimator.sink;
+		if let Some(start) = doc.start_mark() {
+			timeline.marks.insert(0, (start.to_string(), TimeDesignation::default()));
+		}
+		if let Some(end) = doc.end_mark() {
+			timeline.marks.push((end.to_string(), TimeDesignation::from_millis(estimator.elapsed)));
+		}
+		timeline.duration = TimeDesignation::from_millis(estimator.elapsed);
+		timeline
+	}
+
+	/// Returns the entries of all elements in the document, in document order (parents before their children).
+	pub fn entries(&self) -> &[TimelineEntry] {
+		&self.entries
+	}
+
+	/// Returns the entry of the element at the given path.
+	pub fn entry(&self, path: &[usize]) -> Option<&TimelineEntry> {
+		self.entries.iter().find(|e| e.path == path)
+	}
+
+	/// Returns the entries of all elements being spoken at the given time.
+	pub fn entries_at(&self, time: &TimeDesignation) -> impl Iterator<Item = &TimelineEntry> {
+		let time = time.to_millis();
+		self.entries
+			.iter()
+			.filter(move |e| e.start.to_millis() <= time && time < e.end.to_millis())
+	}
+
+	/// Returns the names & estimated offsets of all marks in the document, in document order.
+	pub fn marks(&self) -> &[(String, TimeDesignation)] {
+		&self.marks
+	}
+
+	/// Returns the estimated offset of the mark with the given name.
+	pub fn mark(&self, name: &str) -> Option<&TimeDesignation> {
+		self.marks.iter().find(|(n, _)| n == name).map(|(_, t)| t)
+	}
+
+	/// Returns the estimated duration of the entire document.
+	pub fn duration(&self) -> &TimeDesignation {
+		&self.duration
+	}
+}
+
+impl EstimateSink for Timeline {
+	fn mark(&mut self, name: &str, at: f32) {
+		self.marks.push((name.to_string(), TimeDesignation::from_millis(at)));
+	}
+
+	fn enter(&mut self, path: &[usize], _el: &Element, at: f32) {
+		self.entries.push(TimelineEntry {
+			path: path.to_vec(),
+			start: TimeDesignation::from_millis(at),
+			end: TimeDesignation::from_millis(at)
+		});
+	}
+
+	fn exit(&mut self, path: &[usize], _el: &Element, at: f32) {
+		if let Some(entry) = self.entries.iter_mut().rev().find(|e| e.path == path) {
+			entry.end = TimeDesignation::from_millis(at);
+		}
+	}
+}
@@ -0,0 +1,484 @@
+//! Generate plain-language transcripts of SSML documents.
+//!
+//! A transcript contains the text spoken by a document, along with descriptions of any embedded audio, making it
+//! suitable for accessibility purposes or for archiving alongside synthesized audio.
+//!
+//! ```
+//! use ssml::{
+//! 	IntoElement,
+//! 	transcript::{TranscriptOptions, transcript}
+//! };
+//!
+//! let doc = ssml::speak(
+//! 	Some("en-US"),
+//! 	[
+//! 		ssml::text("Welcome to").into_element(),
+//! 		ssml::say_as(ssml::SpeechFormat::SpellOut, "SSML").into(),
+//! 		ssml::audio("cat_purr.ogg").with_desc("a purring cat").into()
+//! 	]
+//! );
+//!
+//! assert_eq!(transcript(&doc, &TranscriptOptions::default()), "Welcome to S S M L [a purring cat]");
+//! ```
+
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec
+};
+
+use crate::{
+	Audio, CustomElement, DateFormat, Element, Lang, Phoneme, SayAs, Speak, SpeechFormat, Sub, Text, Variant, VariantSelection,
+	visit::{self, Visit}
+};
+
+/// Configuration for [`transcript`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct TranscriptOptions {
+	/// Whether to replace [`Audio`] elements with their description (see [`Audio::with_desc`]), enclosed in square
+	/// brackets. If an audio element has no description, or this option is disabled, its alternate content is
+	/// transcribed instead.
+	pub describe_audio: bool,
+	/// Whether to expand the content of [`SayAs`] elements to better reflect how it is spoken, e.g. `SSML` spelled out
+	/// as `S S M L`.
+	///
+	/// Spelled-out text & digits are spelled character by character, and telephone numbers digit by digit. Cardinal &
+	/// ordinal numbers, dates & times are only expanded into words in English text, i.e. where the language of the
+	/// document or enclosing [`Lang`] is English or unspecified; otherwise they're transcribed as written, as is the
+	/// content of any other format.
+	pub expand_say_as: bool,
+	/// Which alternative of each [`Variant`] is transcribed. This should match the
+	/// [`SerializeOptions::variants`](crate::SerializeOptions::variants) the document is serialized with, so the
+	/// transcript reflects what is actually spoken.
+	pub variants: VariantSelection
+}
+
+impl Default for TranscriptOptions {
+	fn default() -> Self {
+		TranscriptOptions {
+			describe_audio: true,
+			expand_say_as: true,
+			variants: VariantSelection::First
+		}
+	}
+}
+
+impl TranscriptOptions {
+	pub fn with_describe_audio(mut self, describe_audio: bool) -> Self {
+		self.describe_audio = describe_audio;
+		self
+	}
+
+	/// Sets whether the content of [`SayAs`] elements is expanded; see [`TranscriptOptions::expand_say_as`].
+	///
+	/// ```
+	/// use ssml::{
+	/// 	DateFormat, SpeechFormat,
+	/// 	transcript::{TranscriptOptions, transcript}
+	/// };
+	///
+	/// let doc = ssml::speak(
+	/// 	Some("en-US"),
+	/// 	[
+	/// 		ssml::say_as(SpeechFormat::Date(DateFormat::MonthDateYear), "9/22/2015"),
+	/// 		ssml::say_as(SpeechFormat::Time, "2:05pm"),
+	/// 		ssml::say_as(SpeechFormat::Cardinal, "1,234"),
+	/// 		ssml::say_as(SpeechFormat::Ordinal, "21st"),
+	/// 		ssml::say_as(SpeechFormat::Telephone, "555-0100")
+	/// 	]
+	/// );
+	/// assert_eq!(
+	/// 	transcript(&doc, &TranscriptOptions::default()),
+	/// 	"September twenty-second twenty fifteen two oh five p m one thousand two hundred thirty-four twenty-first 5 5 5 0 1 0 0"
+	/// );
+	/// assert_eq!(
+	/// 	transcript(&doc, &TranscriptOptions::default().with_expand_say_as(false)),
+	/// 	"9/22/2015 2:05pm 1,234 21st 555-0100"
+	/// );
+	///
+	/// // numbers are only read out in English
+	/// let doc = ssml::speak(Some("de-DE"), [ssml::say_as(SpeechFormat::Cardinal, "1.234"), ssml::say_as(SpeechFormat::Telephone, "030 1234")]);
+	/// assert_eq!(transcript(&doc, &TranscriptOptions::default()), "1.234 0 3 0 1 2 3 4");
+	/// ```
+	pub fn with_expand_say_as(mut self, expand_say_as: bool) -> Self {
+		self.expand_say_as = expand_say_as;
+		self
+	}
+
+	/// Sets which alternative of each [`Variant`] is transcribed; see [`TranscriptOptions::variants`].
+	///
+	/// ```
+	/// use ssml::{
+	/// 	SerializeOptions, VariantSelection,
+	/// 	transcript::{TranscriptOptions, transcript}
+	/// };
+	///
+	/// let doc = ssml::speak(
+	/// 	None,
+	/// 	[ssml::variant()
+	/// 		.with_alternative("formal", 1, "Good day.")
+	/// 		.with_alternative("casual", 1, "Hey!")]
+	/// );
+	/// let selection = VariantSelection::Key("casual".into());
+	///
+	/// let ssml = ssml::Serialize::serialize_to_string(&doc, &SerializeOptions::default().variants(selection.clone()))?;
+	/// assert!(ssml.ends_with(">Hey!</speak>"));
+	/// assert_eq!(transcript(&doc, &TranscriptOptions::default().with_variants(selection)), "Hey!");
+	/// # Ok::<(), ssml::Error>(())
+	/// ```
+	pub fn with_variants(mut self, selection: VariantSelection) -> Self {
+		self.variants = selection;
+		self
+	}
+}
+
+struct Transcriber<'o> {
+	out: String,
+	options: &'o TranscriptOptions,
+	/// Whether the text in each enclosing scope with a language is in English.
+	english: Vec<bool>
+}
+
+impl Transcriber<'_> {
+	fn is_english(&self) -> bool {
+		self.english.last().copied().unwrap_or(true)
+	}
+
+	fn push_words(&mut self, text: &str) {
+		for word in text.split_whitespace() {
+			if !self.out.is_empty() {
+				self.out.push(' ');
+			}
+			self.out.push_str(word);
+		}
+	}
+
+	fn push_spelled(&mut self, text: &str) {
+		for char in text.chars().filter(|c| !c.is_whitespace()) {
+			if !self.out.is_empty() {
+				self.out.push(' ');
+			}
+			self.out.push(char);
+		}
+	}
+}
+
+impl<'s> Visit<'s> for Transcriber<'_> {
+	fn visit_text(&mut self, node: &'s Text) {
+		self.push_words(node.as_str());
+	}
+
+	fn visit_speak(&mut self, node: &'s Speak) {
+		match node.lang().or(node.defaults().lang.as_deref()) {
+			Some(lang) => {
+				self.english.push(is_english(lang));
+				visit::visit_speak(self, node);
+				self.english.pop();
+			}
+			None => visit::visit_speak(self, node)
+		}
+	}
+
+	fn visit_lang(&mut self, node: &'s Lang) {
+		self.english.push(is_english(node.language()));
+		visit::visit_lang(self, node);
+		self.english.pop();
+	}
+
+	fn visit_say_as(&mut self, node: &'s SayAs) {
+		let text = node.text();
+		if !self.options.expand_say_as {
+			return self.push_words(&text);
+		}
+		let expanded = match node.format() {
+			SpeechFormat::SpellOut | SpeechFormat::Digits => return self.push_spelled(&text),
+			SpeechFormat::Telephone => return self.push_spelled(&text.replace(|c: char| !c.is_alphanumeric() && c != '+', "")),
+			_ if !self.is_english() => None,
+			SpeechFormat::Cardinal => cardinal(&text),
+			SpeechFormat::Ordinal => ordinal(&text),
+			SpeechFormat::Date(format) => date(&text, *format),
+			SpeechFormat::Time | SpeechFormat::TimeWith(_) => time(&text),
+			_ => None
+		};
+		self.push_words(expanded.as_deref().unwrap_or(&text));
+	}
+
+	fn visit_phoneme(&mut self, node: &'s Phoneme) {
+		self.push_words(node.text());
+	}
+
+	fn visit_sub(&mut self, node: &'s Sub) {
+		// the transcript is what's spoken, so use the alias rather than the written form
+		self.push_words(node.alias());
+	}
+
+	fn visit_audio(&mut self, node: &'s Audio) {
+		match node.desc() {
+			Some(desc) if self.options.describe_audio => {
+				if !self.out.is_empty() {
+					self.out.push(' ');
+				}
+				self.out.push('[');
+				self.out.push_str(desc.trim());
+				self.out.push(']');
+			}
+			_ => visit::visit_audio(self, node)
+		}
+	}
+
+	fn visit_custom(&mut self, node: &'s CustomElement) {
+		for child in node.children() {
+			self.visit_element(child);
+		}
+	}
+
+	fn visit_variant(&mut self, node: &'s Variant) {
+		if let Some(i) = node.select(&self.options.variants) {
+			self.visit_element(&node.children()[i]);
+		}
+	}
+
+	fn visit_element(&mut self, node: &'s Element) {
+		match node {
+			Element::Group(group) if !group.is_enabled() => {}
+			node => visit::visit_element(self, node)
+		}
+	}
+}
+
+/// Generates a plain-language transcript of the given document.
+///
+/// Text is joined with single spaces, and markup that does not contribute spoken content (such as [`Meta`] or
+/// [`Break`] elements) is omitted.
+///
+/// [`Meta`]: crate::Meta
+/// [`Break`]: crate::Break
+pub fn transcript(doc: &Speak, options: &TranscriptOptions) -> String {
+	let mut transcriber = Transcriber {
+		out: String::new(),
+		options,
+		english: Vec::new()
+	};
+	transcriber.visit_speak(doc);
+	transcriber.out
+}
+
+fn is_english(lang: &str) -> bool {
+	let primary = lang.split(['-', '_']).next().unwrap_or_default();
+	primary.eq_ignore_ascii_case("en")
+}
+
+const ONES: [&str; 20] = [
+	"zero",
+	"one",
+	"two",
+	"three",
+	"four",
+	"five",
+	"six",
+	"seven",
+	"eight",
+	"nine",
+	"ten",
+	"eleven",
+	"twelve",
+	"thirteen",
+	"fourteen",
+	"fifteen",
+	"sixteen",
+	"seventeen",
+	"eighteen",
+	"nineteen"
+];
+const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+const SCALES: [&str; 7] = ["", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion"];
+const MONTHS: [&str; 12] = ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+
+fn push_word(out: &mut String, word: &str) {
+	if !out.is_empty() {
+		out.push(' ');
+	}
+	out.push_str(word);
+}
+
+/// Writes `n`, which must be less than 1000, in English words.
+fn push_below_thousand(out: &mut String, mut n: usize) {
+	if n >= 100 {
+		push_word(out, ONES[n / 100]);
+		push_word(out, "hundred");
+		n %= 100;
+		if n == 0 {
+			return;
+		}
+	}
+	match (n / 10, n % 10) {
+		(0 | 1, _) => push_word(out, ONES[n]),
+		(tens, 0) => push_word(out, TENS[tens]),
+		(tens, ones) => push_word(out, &format!("{}-{}", TENS[tens], ONES[ones]))
+	}
+}
+
+/// Writes `n` in English words, e.g. `1234` as `one thousand two hundred thirty-four`.
+fn number_words(mut n: u64) -> String {
+	if n == 0 {
+		return ONES[0].to_string();
+	}
+	let mut groups = Vec::new();
+	while n > 0 {
+		groups.push((n % 1000) as usize);
+		n /= 1000;
+	}
+	let mut out = String::new();
+	for (scale, &group) in groups.iter().enumerate().rev() {
+		if group > 0 {
+			push_below_thousand(&mut out, group);
+			if scale > 0 {
+				push_word(&mut out, SCALES[scale]);
+			}
+		}
+	}
+	out
+}
+
+/// Turns a number written in English words into its ordinal form, e.g. `twenty-one` into `twenty-first`.
+fn ordinal_words(words: &str) -> String {
+	let split = words.rfind([' ', '-']).map_or(0, |i| i + 1);
+	let (head, last) = words.split_at(split);
+	let last = match last {
+		"one" => "first".to_string(),
+		"two" => "second".to_string(),
+		"three" => "third".to_string(),
+		"five" => "fifth".to_string(),
+		"eight" => "eighth".to_string(),
+		"nine" => "ninth".to_string(),
+		"twelve" => "twelfth".to_string(),
+		word => match word.strip_suffix('y') {
+			Some(stem) => format!("{stem}ieth"),
+			None => format!("{word}th")
+		}
+	};
+	format!("{head}{last}")
+}
+
+/// Parses an integer written with optional `,` digit grouping, as in English.
+fn parse_integer(text: &str) -> Option<u64> {
+	if text.is_empty() || text.starts_with(',') || !text.bytes().all(|b| b.is_ascii_digit() || b == b',') {
+		return None;
+	}
+	text.replace(',', "").parse().ok()
+}
+
+fn cardinal(text: &str) -> Option<String> {
+	let text = text.trim();
+	let (negative, text) = match text.strip_prefix('-') {
+		Some(text) => (true, text),
+		None => (false, text)
+	};
+	let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+	let mut out = number_words(parse_integer(integer)?);
+	if negative {
+		out.insert_str(0, "minus ");
+	}
+	if !fraction.is_empty() {
+		if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+			return None;
+		}
+		push_word(&mut out, "point");
+		for digit in fraction.bytes() {
+			push_word(&mut out, ONES[(digit - b'0') as usize]);
+		}
+	}
+	Some(out)
+}
+
+fn ordinal(text: &str) -> Option<String> {
+	let text = text.trim();
+	let number = ["st", "nd", "rd", "th", "."]
+		.iter()
+		.find_map(|suffix| text.strip_suffix(suffix))
+		.unwrap_or(text);
+	Some(ordinal_words(&number_words(parse_integer(number)?)))
+}
+
+/// Writes a year the way it's usually read in English, e.g. `1905` as `nineteen oh five`.
+fn year_words(year: u64) -> String {
+	if !(1000..=9999).contains(&year) {
+		return number_words(year);
+	}
+	let (century, rest) = (year / 100, year % 100);
+	match (century % 10, rest) {
+		// e.g. `two thousand`, `two thousand nine`
+		(0, 0..10) => number_words(year),
+		(_, 0) => format!("{} hundred", number_words(century)),
+		(_, 1..10) => format!("{} oh {}", number_words(century), ONES[rest as usize]),
+		_ => format!("{} {}", number_words(century), number_words(rest))
+	}
+}
+
+fn date(text: &str, format: DateFormat) -> Option<String> {
+	let text = text.trim();
+	let (components, fields): (Vec<&str>, &str) = match format {
+		DateFormat::YearMonthDateCompact => (alloc::vec![text.get(..4)?, text.get(4..6)?, text.get(6..)?], "ymd"),
+		format => (text.split(['-', '/', '.', ',', ' ']).filter(|c| !c.is_empty()).collect(), format.as_str())
+	};
+	if components.len() != fields.len() {
+		return None;
+	}
+	let mut out = String::new();
+	for (field, component) in fields.chars().zip(components) {
+		// Polly allows `?` for unknown components
+		if component.contains('?') {
+			continue;
+		}
+		let words = match (field, component.parse::<u64>()) {
+			('d', Ok(date @ 1..=31)) => ordinal_words(&number_words(date)),
+			('m', Ok(month @ 1..=12)) => MONTHS[month as usize - 1].to_string(),
+			('m', Err(_)) if component.chars().all(char::is_alphabetic) => component.to_string(),
+			('y', Ok(year)) => year_words(year),
+			_ => return None
+		};
+		push_word(&mut out, &words);
+	}
+	Some(out)
+}
+
+fn time(text: &str) -> Option<String> {
+	let text = text.trim().to_ascii_lowercase();
+	let (clock, suffix) = match ["am", "a.m.", "pm", "p.m."]
+		.iter()
+		.find_map(|suffix| text.strip_suffix(suffix).map(|clock| (clock, suffix)))
+	{
+		Some((clock, suffix)) => (clock.trim_end(), Some(if suffix.starts_with('a') { "a m" } else { "p m" })),
+		None => (text.as_str(), None)
+	};
+	let mut parts = clock.split(':').map(|part| if part.len() <= 2 { part.parse::<u64>().ok() } else { None });
+	let hour = parts.next()?.filter(|&hour| hour < 24)?;
+	let minute = parts.next().map(|minute| minute.filter(|&minute| minute < 60)).unwrap_or(Some(0))?;
+	let second = parts.next().map(|second| second.filter(|&second| second < 60)).unwrap_or(Some(0))?;
+	if parts.next().is_some() {
+		return None;
+	}
+
+	let mut out = number_words(hour);
+	match minute {
+		0 if suffix.is_some() => {}
+		// e.g. `fourteen hundred`, on a 24-hour clock
+		0 if hour == 0 || hour > 12 => push_word(&mut out, "hundred"),
+		0 => push_word(&mut out, "o'clock"),
+		1..10 => {
+			push_word(&mut out, "oh");
+			push_word(&mut out, ONES[minute as usize]);
+		}
+		_ => push_word(&mut out, &number_words(minute))
+	}
+	if second > 0 {
+		push_word(&mut out, "and");
+		push_word(&mut out, &number_words(second));
+		push_word(&mut out, if second == 1 { "second" } else { "seconds" });
+	}
+	if let Some(suffix) = suffix {
+		push_word(&mut out, suffix);
+	}
+	Some(out)
+}
@@ -0,0 +1,803 @@
+//! Whole-document transformations.
+//!
+//! All passes skip elements which are [protected](crate::annotations::Annotated::protected), along with their children.
+//!
+//! Each pass has a `try_` variant which takes a [`PassBudget`] limiting how much work it may do, so documents from
+//! untrusted sources can be transformed without risking a stack overflow or runaway processing time.
+
+use alloc::{
+	borrow::Cow,
+	collections::BTreeMap,
+	string::{String, ToString},
+	vec::Vec
+};
+use core::{
+	borrow::Borrow,
+	fmt::{self, Display}
+};
+
+use crate::{
+	Break, BreakStrength, Element, Flavor, Lang, Speak, Text, TimeDesignation, Voice,
+	duration::DurationModel,
+	mstts,
+	validate::{Diagnostic, Fix}
+};
+
+/// Limits on the work done by the `try_` variants of transformation passes.
+///
+/// A pass aborts with [`BudgetExceeded`] as soon as it reaches an element nested deeper than the budget's maximum
+/// depth (where the children of the document root are at depth 1), or runs out of fuel. One unit of fuel is spent on
+/// each element a pass visits or inserts, and fuel spent by one pass is gone for all later passes run with the same
+/// budget, so a single budget can bound a whole pipeline. A pass which aborts may leave the document partially
+/// transformed.
+///
+/// ```
+/// use ssml::{
+/// 	EmphasisLevel,
+/// 	transform::{BreakStrengthMap, BudgetExceeded, PassBudget, try_resolve_break_strengths}
+/// };
+///
+/// let mut el = ssml::Element::from("Hello!");
+/// for _ in 0..1000 {
+/// 	el = ssml::emphasis(EmphasisLevel::Strong, [el]).into();
+/// }
+/// let mut doc = ssml::speak(None, [el]);
+///
+/// let mut budget = PassBudget::new().with_max_depth(64);
+/// assert_eq!(
+/// 	try_resolve_break_strengths(&mut doc, &BreakStrengthMap::new(), &mut budget),
+/// 	Err(BudgetExceeded::Depth)
+/// );
+/// ```
+///
+/// ```
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::{
+/// 	BreakStrength, Flavor,
+/// 	transform::{BreakRules, BreakStrengthMap, PassBudget, try_insert_breaks, try_resolve_break_strengths}
+/// };
+///
+/// let mut doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", ["Hello. How are you?"])]);
+/// let mut budget = PassBudget::new().with_fuel(8);
+/// try_insert_breaks(
+/// 	&mut doc,
+/// 	&BreakRules::new().with_after_sentence(BreakStrength::Medium).into(),
+/// 	Flavor::Generic,
+/// 	&mut budget
+/// )?;
+/// try_resolve_break_strengths(&mut doc, &BreakStrengthMap::new(), &mut budget)?;
+/// assert_eq!((budget.used(), budget.fuel()), (8, Some(0)));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PassBudget {
+	max_depth: Option<usize>,
+	fuel: Option<usize>,
+	used: usize
+}
+
+impl PassBudget {
+	/// Creates a budget with no limits.
+	pub const fn new() -> Self {
+		Self { max_depth: None, fuel: None, used: 0 }
+	}
+
+	/// Aborts passes which reach elements nested deeper than `depth`.
+	pub fn with_max_depth(mut self, depth: usize) -> Self {
+		self.max_depth = Some(depth);
+		self
+	}
+
+	/// Aborts passes which reach elements nested deeper than `depth`, or removes the limit if `None`.
+	pub fn set_max_depth(&mut self, depth: Option<usize>) {
+		self.max_depth = depth;
+	}
+
+	pub fn max_depth(&self) -> Option<usize> {
+		self.max_depth
+	}
+
+	/// Sets the fuel remaining for passes run with this budget.
+	pub fn with_fuel(mut self, fuel: usize) -> Self {
+		self.fuel = Some(fuel);
+		self
+	}
+
+	/// Sets the fuel remaining for passes run with this budget, or removes the limit if `None`.
+	pub fn set_fuel(&mut self, fuel: Option<usize>) {
+		self.fuel = fuel;
+	}
+
+	/// Returns the fuel remaining, or `None` if fuel is unlimited.
+	pub fn fuel(&self) -> Option<usize> {
+		self.fuel
+	}
+
+	/// Returns the total fuel spent by passes run with this budget.
+	pub fn used(&self) -> usize {
+		self.used
+	}
+
+	/// Spends fuel on visiting an element at `depth`.
+	pub(crate) fn visit(&mut self, depth: usize) -> Result<(), BudgetExceeded> {
+		if self.max_depth.is_some_and(|max| depth > max) {
+			return Err(BudgetExceeded::Depth);
+		}
+		self.spend(1)
+	}
+
+	pub(crate) fn spend(&mut self, amount: usize) -> Result<(), BudgetExceeded> {
+		if let Some(fuel) = &mut self.fuel {
+			*fuel = fuel.checked_sub(amount).ok_or(BudgetExceeded::Fuel)?;
+		}
+		self.used += amount;
+		Ok(())
+	}
+}
+
+/// The error returned by a transformation pass which exceeded its [`PassBudget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BudgetExceeded {
+	/// An element was nested deeper than the budget's maximum depth.
+	Depth,
+	/// The budget ran out of fuel.
+	Fuel
+}
+
+impl Display for BudgetExceeded {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			BudgetExceeded::Depth => f.write_str("transformation pass exceeded its maximum depth"),
+			BudgetExceeded::Fuel => f.write_str("transformation pass ran out of fuel")
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BudgetExceeded {}
+
+/// A mapping from source voice names to target voice names, used by [`retarget_voices`].
+///
+/// This is implemented for [`HashMap`](std::collections::HashMap) (with the `std` feature) and [`BTreeMap`] with string
+/// keys & values.
+pub trait VoiceMapping {
+	/// Returns the name of the voice which should replace `name`, or `None` if it should be left as is.
+	fn map_voice(&self, name: &str) -> Option<&str>;
+}
+
+impl<K: Borrow<str> + Ord, V: AsRef<str>> VoiceMapping for BTreeMap<K, V> {
+	fn map_voice(&self, name: &str) -> Option<&str> {
+		self.get(name).map(AsRef::as_ref)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<K: Borrow<str> + core::hash::Hash + Eq, V: AsRef<str>, S: core::hash::BuildHasher> VoiceMapping for std::collections::HashMap<K, V, S> {
+	fn map_voice(&self, name: &str) -> Option<&str> {
+		self.get(name).map(AsRef::as_ref)
+	}
+}
+
+impl<M: VoiceMapping + ?Sized> VoiceMapping for &M {
+	fn map_voice(&self, name: &str) -> Option<&str> {
+		(**self).map_voice(name)
+	}
+}
+
+/// Extracts the locale from a voice name following the `<language>-<region>-<name>` convention used by e.g. ACSS &
+/// Google Cloud TTS.
+///
+/// ```
+/// use ssml::transform::voice_locale;
+///
+/// assert_eq!(voice_locale("en-US-JennyNeural"), Some("en-US"));
+/// assert_eq!(voice_locale("cmn-CN-Wavenet-A"), Some("cmn-CN"));
+/// assert_eq!(voice_locale("Joanna"), None);
+/// ```
+pub fn voice_locale(name: &str) -> Option<&str> {
+	let mut parts = name.splitn(3, '-');
+	let language = parts.next()?;
+	let region = parts.next()?;
+	if !(2..=3).contains(&language.len()) || !language.bytes().all(|b| b.is_ascii_alphabetic()) {
+		return None;
+	}
+	let valid_region = match region.len() {
+		2 => region.bytes().all(|b| b.is_ascii_alphabetic()),
+		3 => region.bytes().all(|b| b.is_ascii_digit()),
+		_ => false
+	};
+	if !valid_region {
+		return None;
+	}
+	Some(&name[..language.len() + 1 + region.len()])
+}
+
+/// Rewrites the names of all [`Voice`]s in a document according to `mapping`, so a document authored for one
+/// provider's voices can be replayed on another.
+///
+/// Voice names not present in `mapping` are left unchanged.
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let mut doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", ["Hello, world!"])]);
+/// ssml::retarget_voices(&mut doc, &BTreeMap::from([("en-US-JennyNeural", "en-US-Neural2-F")]));
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-Neural2-F">Hello, world!</voice></speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn retarget_voices(doc: &mut Speak, mapping: impl VoiceMapping) {
+	let _ = try_retarget_voices(doc, mapping, &mut PassBudget::new());
+}
+
+/// Like [`retarget_voices`], but aborts once `budget` is exceeded; see [`PassBudget`].
+pub fn try_retarget_voices(doc: &mut Speak, mapping: impl VoiceMapping, budget: &mut PassBudget) -> Result<(), BudgetExceeded> {
+	let lang = doc.lang().map(ToString::to_string);
+	retarget_elements(doc.children_mut(), &mapping, lang.as_deref(), false, budget, 1)
+}
+
+/// Like [`retarget_voices`], but additionally wraps the content of a retargeted voice in a [`Lang`] element when the
+/// target voice's locale differs from the content's, so multilingual target voices keep speaking the original
+/// language.
+///
+/// The content's locale is taken from the source voice's name or declared languages, falling back to the document's
+/// `xml:lang`. Locales are inferred from voice names with [`voice_locale`]; voices whose locale can't be determined are
+/// never wrapped.
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let mut doc = ssml::speak(None, [ssml::voice("de-DE-KatjaNeural", ["Guten Tag!"])]);
+/// ssml::transform::retarget_voices_with_lang(&mut doc, &BTreeMap::from([("de-DE-KatjaNeural", "en-US-AvaMultilingualNeural")]));
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-AvaMultilingualNeural"><lang xml:lang="de-DE">Guten Tag!</lang></voice></speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn retarget_voices_with_lang(doc: &mut Speak, mapping: impl VoiceMapping) {
+	let _ = try_retarget_voices_with_lang(doc, mapping, &mut PassBudget::new());
+}
+
+/// Like [`retarget_voices_with_lang`], but aborts once `budget` is exceeded; see [`PassBudget`].
+pub fn try_retarget_voices_with_lang(doc: &mut Speak, mapping: impl VoiceMapping, budget: &mut PassBudget) -> Result<(), BudgetExceeded> {
+	let lang = doc.lang().map(ToString::to_string);
+	retarget_elements(doc.children_mut(), &mapping, lang.as_deref(), true, budget, 1)
+}
+
+fn retarget_elements(
+	elements: &mut [Element],
+	mapping: &impl VoiceMapping,
+	lang: Option<&str>,
+	wrap_lang: bool,
+	budget: &mut PassBudget,
+	depth: usize
+) -> Result<(), BudgetExceeded> {
+	for element in elements {
+		if element.is_protected() {
+			continue;
+		}
+		budget.visit(depth)?;
+		match element {
+			Element::Voice(voice) => retarget_voice(voice, mapping, lang, wrap_lang, budget, depth)?,
+			Element::Lang(el) => {
+				let lang = el.language().to_string();
+				retarget_elements(el.children_mut(), mapping, Some(&lang), wrap_lang, budget, depth + 1)?;
+			}
+			element => {
+				if let Some(children) = element.children_mut() {
+					retarget_elements(children, mapping, lang, wrap_lang, budget, depth + 1)?;
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+fn retarget_voice(
+	voice: &mut Voice,
+	mapping: &impl VoiceMapping,
+	lang: Option<&str>,
+	wrap_lang: bool,
+	budget: &mut PassBudget,
+	depth: usize
+) -> Result<(), BudgetExceeded> {
+	let config = voice.config_mut();
+	let source_lang = config
+		.names
+		.as_ref()
+		.and_then(|n| n.first())
+		.and_then(|n| voice_locale(n))
+		.or_else(|| config.languages.as_ref().and_then(|l| l.first()).map(|l| &**l))
+		.or(lang)
+		.map(ToString::to_string);
+
+	let mut target_lang = None;
+	for (i, name) in config.names.iter_mut().flatten().enumerate() {
+		if let Some(target) = mapping.map_voice(name) {
+			if i == 0 {
+				target_lang = voice_locale(target).map(ToString::to_string);
+			}
+			*name = Cow::Owned(target.to_string());
+		}
+	}
+
+	retarget_elements(voice.children_mut(), mapping, source_lang.as_deref(), wrap_lang, budget, depth + 1)?;
+
+	if let (true, Some(source_lang), Some(target_lang)) = (wrap_lang, source_lang, target_lang) {
+		if !source_lang.eq_ignore_ascii_case(&target_lang) {
+			budget.spend(1)?;
+			let children = core::mem::take(voice.children_mut());
+			voice.children_mut().push(Lang::new(source_lang, children).into());
+		}
+	}
+	Ok(())
+}
+
+/// Rules describing where [`insert_breaks`] should insert [`Break`]s.
+///
+/// ```
+/// # use ssml::Serialize;
+/// use ssml::{BreakStrength, Flavor, transform::{BreakRules, insert_breaks}};
+///
+/// # fn main() -> ssml::Result<()> {
+/// let mut doc = ssml::speak(None, ["You will need:\n1. Flour\n2. Eggs"]);
+/// insert_breaks(&mut doc, &BreakRules::new().with_after_list_item(BreakStrength::Strong).into(), Flavor::Generic);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	"<speak version=\"1.0\" xmlns=\"http://www.w3.org/2001/10/synthesis\">You will need:\n1. Flour<break strength=\"strong\"/>\n2. Eggs</speak>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct BreakRules {
+	/// Break to insert between sentences within a text element.
+	pub after_sentence: Option<Break>,
+	/// Break to insert after each item of a plain-text list, i.e. lines starting with `-`, `*`, `•`, or a number
+	/// followed by `.` or `)`.
+	pub after_list_item: Option<Break>,
+	/// Minimum pause between two adjacent [`Voice`]s with different names.
+	pub voice_switch: Option<TimeDesignation>
+}
+
+impl BreakRules {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_after_sentence(mut self, el: impl Into<Break>) -> Self {
+		self.after_sentence = Some(el.into());
+		self
+	}
+
+	pub fn with_after_list_item(mut self, el: impl Into<Break>) -> Self {
+		self.after_list_item = Some(el.into());
+		self
+	}
+
+	pub fn with_voice_switch(mut self, time: impl Into<TimeDesignation>) -> Self {
+		self.voice_switch = Some(time.into());
+		self
+	}
+}
+
+/// A set of [`BreakRules`] used by [`insert_breaks`], optionally overridden for specific [`Flavor`]s.
+///
+/// ```
+/// use ssml::{
+/// 	Flavor,
+/// 	transform::{BreakPolicy, BreakRules}
+/// };
+///
+/// let policy = BreakPolicy::new(BreakRules::new().with_voice_switch("250ms"))
+/// 	.with_flavor(Flavor::AmazonPolly, BreakRules::new().with_voice_switch("500ms"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BreakPolicy {
+	rules: BreakRules,
+	flavors: Vec<(Flavor, BreakRules)>
+}
+
+impl BreakPolicy {
+	/// Creates a new policy applying `rules` for all flavors.
+	pub fn new(rules: BreakRules) -> Self {
+		Self { rules, flavors: Vec::new() }
+	}
+
+	/// Use `rules` instead of the default rules when inserting breaks for `flavor`.
+	pub fn with_flavor(mut self, flavor: Flavor, rules: BreakRules) -> Self {
+		self.set_flavor(flavor, rules);
+		self
+	}
+
+	/// Use `rules` instead of the default rules when inserting breaks for `flavor`.
+	pub fn set_flavor(&mut self, flavor: Flavor, rules: BreakRules) {
+		match self.flavors.iter_mut().find(|(f, _)| *f == flavor) {
+			Some((_, r)) => *r = rules,
+			None => self.flavors.push((flavor, rules))
+		}
+	}
+
+	/// Returns the rules which apply to `flavor`.
+	pub fn rules(&self, flavor: Flavor) -> &BreakRules {
+		self.flavors.iter().find(|(f, _)| *f == flavor).map_or(&self.rules, |(_, r)| r)
+	}
+}
+
+impl From<BreakRules> for BreakPolicy {
+	fn from(rules: BreakRules) -> Self {
+		BreakPolicy::new(rules)
+	}
+}
+
+/// Inserts [`Break`]s into a document according to the rules of `policy` for `flavor`.
+///
+/// Sentence & list item breaks are only inserted within a text element, never at its start or end. For voice switches,
+/// an existing timed break between the two voices is lengthened to the minimum pause if it is shorter; an existing
+/// break with a strength is left as is.
+///
+/// ```
+/// # use ssml::Serialize;
+/// use ssml::{Flavor, transform::{BreakRules, insert_breaks}};
+///
+/// # fn main() -> ssml::Result<()> {
+/// let mut doc = ssml::speak(None, [
+/// 	ssml::voice("en-US-JennyNeural", ["Hello. How are you?"]),
+/// 	ssml::voice("en-US-GuyNeural", ["Great!"])
+/// ]);
+/// insert_breaks(&mut doc, &BreakRules::new().with_after_sentence("200ms").with_voice_switch("500ms").into(), Flavor::Generic);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-JennyNeural">Hello.<break time="+200ms"/> How are you?</voice><break time="+500ms"/><voice name="en-US-GuyNeural">Great!</voice></speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn insert_breaks(doc: &mut Speak, policy: &BreakPolicy, flavor: Flavor) {
+	let _ = try_insert_breaks(doc, policy, flavor, &mut PassBudget::new());
+}
+
+/// Like [`insert_breaks`], but aborts once `budget` is exceeded; see [`PassBudget`].
+pub fn try_insert_breaks(doc: &mut Speak, policy: &BreakPolicy, flavor: Flavor, budget: &mut PassBudget) -> Result<(), BudgetExceeded> {
+	insert_breaks_into(doc.children_mut(), policy.rules(flavor), budget, 1)
+}
+
+fn insert_breaks_into(elements: &mut Vec<Element>, rules: &BreakRules, budget: &mut PassBudget, depth: usize) -> Result<(), BudgetExceeded> {
+	for element in elements.iter_mut().filter(|el| !el.is_protected()) {
+		budget.visit(depth)?;
+		// breaks within a `say-as` would interrupt the interpreted text
+		if matches!(element, Element::SayAs(_)) {
+			continue;
+		}
+		if let Some(children) = element.children_mut() {
+			insert_breaks_into(children, rules, budget, depth + 1)?;
+		}
+	}
+
+	if rules.after_sentence.is_some() || rules.after_list_item.is_some() {
+		let mut i = 0;
+		while i < elements.len() {
+			let Element::Text(text) = &elements[i] else {
+				i += 1;
+				continue;
+			};
+			let splits = text_breaks(text, rules);
+			if splits.is_empty() {
+				i += 1;
+				continue;
+			}
+			budget.spend(splits.len() * 2)?;
+			let mut replacement = Vec::with_capacity(splits.len() * 2 + 1);
+			let mut start = 0;
+			for (position, el) in splits {
+				replacement.push(Element::Text(Text::from(text[start..position].to_string())));
+				replacement.push(Element::Break(el.clone()));
+				start = position;
+			}
+			replacement.push(Element::Text(Text::from(text[start..].to_string())));
+			let len = replacement.len();
+			elements.splice(i..=i, replacement);
+			i += len;
+		}
+	}
+
+	if let Some(min) = &rules.voice_switch {
+		let mut i = 0;
+		let mut last_voice: Option<String> = None;
+		let mut gap_break: Option<usize> = None;
+		while i < elements.len() {
+			match &mut elements[i] {
+				Element::Voice(voice) => {
+					let name = voice.config().names.as_ref().and_then(|n| n.first()).map(|n| n.to_string());
+					if let (Some(last), Some(name)) = (&last_voice, &name) {
+						if last != name {
+							match gap_break {
+								Some(index) => {
+									if let Element::Break(el) = &mut elements[index] {
+										if el.time().is_some_and(|time| time.to_millis() < min.to_millis()) {
+											el.set_time(min.clone());
+										}
+									}
+								}
+								None => {
+									budget.spend(1)?;
+									elements.insert(i, Element::Break(Break::new_with_time(min.clone())));
+									i += 1;
+								}
+							}
+						}
+					}
+					last_voice = name;
+					gap_break = None;
+				}
+				Element::Break(_) => gap_break = Some(i),
+				Element::Text(text) if text.trim().is_empty() => {}
+				_ => {
+					last_voice = None;
+					gap_break = None;
+				}
+			}
+			i += 1;
+		}
+	}
+	Ok(())
+}
+
+/// Finds the byte positions within `text` at which breaks should be inserted.
+fn text_breaks<'r>(text: &str, rules: &'r BreakRules) -> Vec<(usize, &'r Break)> {
+	let mut splits: Vec<(usize, &Break)> = Vec::new();
+	if let Some(el) = &rules.after_list_item {
+		let mut offset = 0;
+		for line in text.split_inclusive('\n') {
+			let end = offset + line.trim_end().len();
+			if is_list_item(line) && !text[end..].trim().is_empty() {
+				splits.push((end, el));
+			}
+			offset += line.len();
+		}
+	}
+	if let Some(el) = &rules.after_sentence {
+		let mut chars = text.char_indices().peekable();
+		let mut word_start = 0;
+		while let Some((i, c)) = chars.next() {
+			if c.is_whitespace() {
+				word_start = i + c.len_utf8();
+				continue;
+			}
+			if !matches!(c, '.' | '!' | '?') || !chars.peek().is_some_and(|(_, c)| c.is_whitespace()) {
+				continue;
+			}
+			// skip list item numbering such as `1.`
+			let word = &text[word_start..i];
+			if !word.is_empty() && word.bytes().all(|b| b.is_ascii_digit()) {
+				continue;
+			}
+			let end = i + c.len_utf8();
+			if !text[end..].trim().is_empty() && !splits.iter().any(|(p, _)| *p == end) {
+				splits.push((end, el));
+			}
+		}
+	}
+	splits.sort_by_key(|(p, _)| *p);
+	splits
+}
+
+fn is_list_item(line: &str) -> bool {
+	let line = line.trim_start();
+	if let Some(rest) = line.strip_prefix(['-', '*', '•']) {
+		return rest.starts_with(' ');
+	}
+	let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+	digits > 0 && line[digits..].starts_with(['.', ')']) && line[digits + 1..].starts_with(' ')
+}
+
+/// A mapping from each [`BreakStrength`] to a concrete time, used by [`resolve_break_strengths`].
+///
+/// Defaults to the same times as [`DurationModel::break_strength_millis`]: `0ms` for [`BreakStrength::None`], then
+/// `100ms`, `250ms`, `500ms`, `750ms` & `1000ms` for [`BreakStrength::ExtraStrong`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakStrengthMap {
+	times: [TimeDesignation; 6]
+}
+
+impl Default for BreakStrengthMap {
+	fn default() -> Self {
+		BreakStrengthMap::from(&DurationModel::default())
+	}
+}
+
+impl From<&DurationModel> for BreakStrengthMap {
+	fn from(model: &DurationModel) -> Self {
+		BreakStrengthMap {
+			times: model.break_strength_millis.map(TimeDesignation::from_millis)
+		}
+	}
+}
+
+impl BreakStrengthMap {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Maps `strength` to `time`.
+	pub fn with(mut self, strength: BreakStrength, time: impl Into<TimeDesignation>) -> Self {
+		self.set(strength, time);
+		self
+	}
+
+	/// Maps `strength` to `time`.
+	pub fn set(&mut self, strength: BreakStrength, time: impl Into<TimeDesignation>) {
+		self.times[strength as usize] = time.into();
+	}
+
+	/// Returns the time `strength` is mapped to.
+	pub fn time(&self, strength: BreakStrength) -> &TimeDesignation {
+		&self.times[strength as usize]
+	}
+}
+
+/// Replaces every [`Break`] specified by strength with a timed break according to `map`, for engines which ignore
+/// named strengths. Returns the number of breaks replaced.
+///
+/// ```
+/// # use ssml::Serialize;
+/// use ssml::{
+/// 	BreakStrength,
+/// 	transform::{BreakStrengthMap, resolve_break_strengths}
+/// };
+///
+/// # fn main() -> ssml::Result<()> {
+/// let mut doc = ssml::speak(
+/// 	None,
+/// 	[ssml::Element::from("Wait..."), ssml::breaks(BreakStrength::Strong).into(), "for it.".into()]
+/// );
+/// assert_eq!(resolve_break_strengths(&mut doc, &BreakStrengthMap::new().with(BreakStrength::Strong, "1200ms")), 1);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Wait...<break time="+1200ms"/>for it.</speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn resolve_break_strengths(doc: &mut Speak, map: &BreakStrengthMap) -> usize {
+	try_resolve_break_strengths(doc, map, &mut PassBudget::new()).unwrap_or_default()
+}
+
+/// Like [`resolve_break_strengths`], but aborts once `budget` is exceeded; see [`PassBudget`].
+pub fn try_resolve_break_strengths(doc: &mut Speak, map: &BreakStrengthMap, budget: &mut PassBudget) -> Result<usize, BudgetExceeded> {
+	resolve_break_strengths_in(doc.children_mut(), map, budget, 1)
+}
+
+fn resolve_break_strengths_in(elements: &mut [Element], map: &BreakStrengthMap, budget: &mut PassBudget, depth: usize) -> Result<usize, BudgetExceeded> {
+	let mut resolved = 0;
+	for element in elements.iter_mut().filter(|el| !el.is_protected()) {
+		budget.visit(depth)?;
+		match element {
+			Element::Break(el) => {
+				if el.time().is_none() {
+					let strength = el.take_strength().unwrap_or_default();
+					el.set_time(map.time(strength).clone());
+					resolved += 1;
+				}
+			}
+			element => {
+				if let Some(children) = element.children_mut() {
+					resolved += resolve_break_strengths_in(children, map, budget, depth + 1)?;
+				}
+			}
+		}
+	}
+	Ok(resolved)
+}
+
+/// Applies the [`Fix`]es suggested by `diagnostics` to `doc`, returning the number of fixes applied.
+///
+/// `diagnostics` must have been produced by validating `doc` itself (e.g. with
+/// [`validate`](crate::validate::validate)), since fixes are located by the path of their element. If several
+/// diagnostics suggest moving or removing the same element, only the first is applied, though fixes which only modify
+/// an element (like [`Fix::SetBreakTime`]) are applied alongside it. Fixes whose element no longer exists, or is within
+/// a protected element, are skipped.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::{Flavor, validate};
+///
+/// let mut doc = ssml::speak(None, [
+/// 	ssml::Element::from(ssml::voice("en-US-JennyNeural", ["Hello!"])),
+/// 	ssml::breaks("10s").into(),
+/// 	"How are you?".into()
+/// ]);
+///
+/// let diagnostics = validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices);
+/// assert_eq!(ssml::transform::apply_fixes(&mut doc, &diagnostics), 4);
+/// assert!(validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices).is_empty());
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default())?,
+/// 	concat!(
+/// 		r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis"><voice name="en-US-JennyNeural">Hello!</voice>"#,
+/// 		r#"<voice name="en-US-JennyNeural"><break time="+5000ms"/></voice><voice name="en-US-JennyNeural">How are you?</voice></speak>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn apply_fixes(doc: &mut Speak, diagnostics: &[Diagnostic]) -> usize {
+	try_apply_fixes(doc, diagnostics, &mut PassBudget::new()).unwrap_or_default()
+}
+
+/// Like [`apply_fixes`], but aborts once `budget` is exceeded; see [`PassBudget`]. Each fix is charged as a visit to
+/// the element it applies to.
+pub fn try_apply_fixes(doc: &mut Speak, diagnostics: &[Diagnostic], budget: &mut PassBudget) -> Result<usize, BudgetExceeded> {
+	let mut fixes: Vec<(&[usize], &Fix)> = diagnostics
+		.iter()
+		.filter_map(|diagnostic| Some((&*diagnostic.path, diagnostic.fix.as_ref()?)))
+		.collect();
+	// fix later elements first, so that removing or unwrapping an element doesn't move the elements of later fixes, and
+	// modify an element before moving it
+	let moves = |fix: &Fix| matches!(fix, Fix::Remove | Fix::Unwrap | Fix::WrapInVoice(_));
+	fixes.sort_by(|(a, a_fix), (b, b_fix)| b.cmp(a).then(moves(a_fix).cmp(&moves(b_fix))));
+
+	let mut applied = 0;
+	let mut moved: Option<&[usize]> = None;
+	for (path, fix) in fixes {
+		if moved == Some(path) {
+			continue;
+		}
+		budget.visit(path.len())?;
+		if apply_fix(doc, path, fix).is_some() {
+			applied += 1;
+			if moves(fix) {
+				moved = Some(path);
+			}
+		}
+	}
+	Ok(applied)
+}
+
+fn apply_fix(doc: &mut Speak, path: &[usize], fix: &Fix) -> Option<()> {
+	if (1..=path.len()).any(|len| doc.element_at(&path[..len]).is_some_and(Element::is_protected)) {
+		return None;
+	}
+	let (&index, parent) = path.split_last()?;
+	let siblings = match parent {
+		[] => doc.children_mut(),
+		parent => doc.element_at_mut(parent)?.children_mut()?
+	};
+	if index >= siblings.len() {
+		return None;
+	}
+
+	match (fix, &mut siblings[index]) {
+		(Fix::Remove, _) => {
+			siblings.remove(index);
+		}
+		(Fix::Unwrap, _) => {
+			let mut element = siblings.remove(index);
+			let children = element.children_mut().map(core::mem::take).unwrap_or_default();
+			siblings.splice(index..index, children);
+		}
+		(Fix::WrapInVoice(name), _) => {
+			let element = siblings.remove(index);
+			siblings.insert(index, Voice::new(name.clone(), [element]).into());
+		}
+		(Fix::SetBreakTime(time), Element::Break(el)) => el.set_time(time.clone()),
+		(Fix::SetStyleDegree(degree), Element::FlavorMSTTS(mstts::Element::Express(el))) => {
+			let mut expression = el.expression().clone();
+			expression.set_degree(*degree);
+			el.set_expression(expression);
+		}
+		_ => return None
+	}
+	Some(())
+}
@@ -0,0 +1,284 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{Display, Write};
+
+use crate::{Element, Flavor, Speak, mstts, polly, watson};
+
+/// Maximum number of characters of text shown in a label before it is truncated.
+const MAX_TEXT_LEN: usize = 40;
+
+fn quoted(out: &mut String, text: &str) {
+	out.push('"');
+	for (i, char) in text.chars().enumerate() {
+		if i == MAX_TEXT_LEN {
+			out.push('…');
+			break;
+		}
+		match char {
+			'\n' => out.push_str("\\n"),
+			'"' => out.push_str("\\\""),
+			c => out.push(c)
+		}
+	}
+	out.push('"');
+}
+
+fn attr(out: &mut String, name: &str, value: impl Display) {
+	let _ = write!(out, " {name}={value}");
+}
+
+/// Describes an element's kind and its key attributes on a single line.
+fn label(el: &Element) -> String {
+	let mut out = String::new();
+	match el {
+		Element::Text(text) => {
+			out.push_str("text ");
+			quoted(&mut out, text);
+		}
+		Element::Audio(audio) => {
+			out.push_str("audio src=");
+			quoted(&mut out, audio.src());
+			if let Some(desc) = audio.desc() {
+				out.push_str(" desc=");
+				quoted(&mut out, desc);
+			}
+		}
+		Element::Voice(voice) => {
+			out.push_str("voice");
+			let config = voice.config();
+			if let Some(names) = &config.names {
+				attr(&mut out, "name", names.join(","));
+			}
+			if let Some(gender) = &config.gender {
+				attr(&mut out, "gender", gender);
+			}
+			if let Some(age) = config.age {
+				attr(&mut out, "age", age);
+			}
+			if let Some(languages) = &config.languages {
+				attr(&mut out, "languages", languages.join(","));
+			}
+		}
+		Element::Meta(meta) => {
+			out.push_str("meta");
+			if let Some(name) = meta.name() {
+				attr(&mut out, "name", name);
+			}
+		}
+		Element::Break(el) => {
+			out.push_str("break");
+			if let Some(strength) = el.strength() {
+				attr(&mut out, "strength", strength.as_str());
+			}
+			if let Some(time) = el.time() {
+				attr(&mut out, "time", time);
+			}
+		}
+		Element::Emphasis(emphasis) => {
+			out.push_str("emphasis");
+			attr(&mut out, "level", emphasis.level().as_str());
+		}
+		Element::Mark(mark) => {
+			out.push_str("mark name=");
+			quoted(&mut out, mark.name());
+		}
+		Element::Lexicon(lexicon) => {
+			out.push_str("lexicon");
+			attr(&mut out, "uri", lexicon.uri());
+		}
+		Element::SayAs(say_as) => {
+			out.push_str("say-as");
+			attr(&mut out, "interpret-as", say_as.format().interpret_as(Flavor::Generic));
+			if let Some(format) = say_as.format().format() {
+				attr(&mut out, "format", format);
+			}
+		}
+		Element::Phoneme(phoneme) => {
+			out.push_str("phoneme");
+			attr(&mut out, "alphabet", phoneme.alphabet().as_str());
+			out.push_str(" ph=");
+			quoted(&mut out, phoneme.ph());
+			out.push(' ');
+			quoted(&mut out, phoneme.text());
+		}
+		Element::Sub(sub) => {
+			out.push_str("sub alias=");
+			quoted(&mut out, sub.alias());
+			out.push(' ');
+			quoted(&mut out, sub.text());
+		}
+		Element::Paragraph(_) => out.push('p'),
+		Element::Sentence(_) => out.push('s'),
+		Element::Word(word) => {
+			out.push_str("token");
+			if let Some(role) = word.role() {
+				attr(&mut out, "role", role);
+			}
+		}
+		Element::Group(group) => {
+			out.push_str("group");
+			if let Some(label) = group.label() {
+				out.push_str(" label=");
+				quoted(&mut out, label);
+			}
+			if !group.is_enabled() {
+				out.push_str(" disabled");
+			}
+		}
+		Element::Variant(variant) => {
+			out.push_str("variant");
+			let keys: Vec<_> = variant.alternatives().map(|(key, weight, _)| format!("{key}:{weight}")).collect();
+			attr(&mut out, "alternatives", keys.join(","));
+		}
+		Element::Lang(lang) => {
+			out.push_str("lang");
+			attr(&mut out, "xml:lang", lang.language());
+		}
+		Element::Prosody(prosody) => {
+			out.push_str("prosody");
+			let control = prosody.control();
+			if let Some(pitch) = &control.pitch {
+				attr(&mut out, "pitch", pitch);
+			}
+			if let Some(contour) = &control.contour {
+				attr(&mut out, "contour", contour);
+			}
+			if let Some(range) = &control.range {
+				attr(&mut out, "range", range);
+			}
+			if let Some(rate) = &control.rate {
+				attr(&mut out, "rate", rate);
+			}
+			if let Some(duration) = &control.duration {
+				attr(&mut out, "duration", duration);
+			}
+			if let Some(volume) = &control.volume {
+				attr(&mut out, "volume", volume);
+			}
+		}
+		Element::FlavorMSTTS(mstts::Element::Express(express)) => {
+			out.push_str("mstts:express-as");
+			attr(&mut out, "style", express.expression().name());
+			if let Some(role) = express.role() {
+				attr(&mut out, "role", role);
+			}
+		}
+		Element::FlavorMSTTS(mstts::Element::Silence(silence)) => {
+			out.push_str("mstts:silence");
+			attr(&mut out, "type", silence.kind());
+			attr(&mut out, "value", silence.value());
+		}
+		Element::FlavorMSTTS(mstts::Element::BackgroundAudio(background)) => {
+			out.push_str("mstts:backgroundaudio");
+			attr(&mut out, "src", background.src());
+		}
+		Element::FlavorMSTTS(mstts::Element::Viseme(viseme)) => {
+			out.push_str("mstts:viseme");
+			attr(&mut out, "type", viseme.kind());
+		}
+		Element::FlavorMSTTS(mstts::Element::TtsEmbedding(embedding)) => {
+			out.push_str("mstts:ttsembedding");
+			attr(&mut out, "speakerProfileId", embedding.speaker_profile_id());
+		}
+		Element::FlavorMSTTS(mstts::Element::AudioDuration(duration)) => {
+			out.push_str("mstts:audioduration");
+			attr(&mut out, "value", duration.value());
+		}
+		Element::FlavorMSTTS(mstts::Element::Math(math)) => {
+			out.push_str("math ");
+			out.push_str(math.content());
+		}
+		Element::FlavorPolly(polly::Element::Domain(domain)) => {
+			out.push_str("amazon:domain");
+			attr(&mut out, "name", domain.name());
+		}
+		Element::FlavorPolly(polly::Element::Effect(effect)) => {
+			out.push_str("amazon:effect");
+			let _ = write!(out, " {:?}", effect.kind());
+		}
+		Element::FlavorWatson(watson::Element::ExpressAs(express)) => {
+			out.push_str("express-as");
+			attr(&mut out, "type", express.expression());
+		}
+		Element::FlavorWatson(watson::Element::VoiceTransformation(transformation)) => {
+			out.push_str("voice-transformation");
+			attr(&mut out, "type", transformation.kind().as_str());
+		}
+		Element::Custom(custom) => {
+			let _ = write!(out, "<{}>", custom.tag());
+		}
+	}
+	out
+}
+
+fn speak_label(doc: &Speak) -> String {
+	let mut out = String::from("speak");
+	if let Some(lang) = doc.lang() {
+		attr(&mut out, "xml:lang", lang);
+	}
+	out
+}
+
+fn dump_elements(out: &mut String, elements: &[Element], depth: usize) {
+	for el in elements {
+		for _ in 0..depth {
+			out.push_str("  ");
+		}
+		out.push_str(&label(el));
+		out.push('\n');
+		if let Some(children) = el.children() {
+			dump_elements(out, children, depth + 1);
+		}
+	}
+}
+
+fn dot_escape(label: &str) -> String {
+	label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn dot_elements(out: &mut String, elements: &[Element], parent: usize, next_id: &mut usize) {
+	for el in elements {
+		let id = *next_id;
+		*next_id += 1;
+		let _ = writeln!(out, "\tn{id} [label=\"{}\"];", dot_escape(&label(el)));
+		let _ = writeln!(out, "\tn{parent} -> n{id};");
+		if let Some(children) = el.children() {
+			dot_elements(out, children, id, next_id);
+		}
+	}
+}
+
+impl Speak<'_> {
+	/// Returns an indented, structural dump of this document, showing the kind & key attributes of each element, for
+	/// debugging.
+	///
+	/// ```
+	/// let doc = ssml::speak(Some("en-US"), [ssml::voice("en-US-JennyNeural", ["Hello, world!"])]) + ssml::breaks("1s");
+	/// assert_eq!(
+	/// 	doc.debug_tree(),
+	/// 	"speak xml:lang=en-US\n  voice name=en-US-JennyNeural\n    text \"Hello, world!\"\n  break time=+1000ms\n"
+	/// );
+	/// ```
+	pub fn debug_tree(&self) -> String {
+		let mut out = speak_label(self);
+		out.push('\n');
+		dump_elements(&mut out, self.children(), 1);
+		out
+	}
+
+	/// Returns a Graphviz DOT graph of the structure of this document, for debugging.
+	///
+	/// ```
+	/// let doc = ssml::speak(None, [ssml::mark("start")]);
+	/// assert_eq!(
+	/// 	doc.to_dot(),
+	/// 	"digraph ssml {\n\tn0 [label=\"speak\"];\n\tn1 [label=\"mark name=\\\"start\\\"\"];\n\tn0 -> n1;\n}\n"
+	/// );
+	/// ```
+	pub fn to_dot(&self) -> String {
+		let mut out = String::from("digraph ssml {\n");
+		let _ = writeln!(out, "\tn0 [label=\"{}\"];", dot_escape(&speak_label(self)));
+		dot_elements(&mut out, self.children(), 0, &mut 1);
+		out.push_str("}\n");
+		out
+	}
+}
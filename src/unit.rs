@@ -53,13 +53,43 @@ pub struct TimeDesignation {
 }
 
 impl TimeDesignation {
+	/// A time designation of no time at all.
+	pub const ZERO: TimeDesignation = TimeDesignation::from_millis(0.);
+
 	/// Create a [`TimeDesignation`] from a set number of milliseconds.
-	pub fn from_millis(millis: f32) -> Self {
+	///
+	/// All constructors are `const`, so commonly used values can be defined as constants:
+	///
+	/// ```
+	/// # use ssml::TimeDesignation;
+	/// const SHORT_PAUSE: TimeDesignation = TimeDesignation::from_millis_u32(250);
+	/// const LONG_PAUSE: TimeDesignation = TimeDesignation::from_secs_f32(1.5);
+	///
+	/// assert_eq!(SHORT_PAUSE, "250ms".into());
+	/// assert_eq!(LONG_PAUSE, TimeDesignation::from_millis(1500.));
+	/// assert_eq!(TimeDesignation::from_secs(2), "2s".into());
+	/// ```
+	pub const fn from_millis(millis: f32) -> Self {
 		Self { millis }
 	}
 
+	/// Create a [`TimeDesignation`] from a whole number of milliseconds.
+	pub const fn from_millis_u32(millis: u32) -> Self {
+		Self::from_millis(millis as f32)
+	}
+
+	/// Create a [`TimeDesignation`] from a (fractional) number of seconds.
+	pub const fn from_secs_f32(secs: f32) -> Self {
+		Self::from_millis(secs * 1000.)
+	}
+
+	/// Create a [`TimeDesignation`] from a whole number of seconds.
+	pub const fn from_secs(secs: u32) -> Self {
+		Self::from_millis(secs as f32 * 1000.)
+	}
+
 	/// Convert this time designation to milliseconds.
-	pub fn to_millis(&self) -> f32 {
+	pub const fn to_millis(&self) -> f32 {
 		self.millis
 	}
 }
@@ -1,39 +1,306 @@
 //! Utilities for serializing XML.
 
-use alloc::string::String;
-use core::fmt::{self, Write};
+use alloc::{borrow::Cow, string::String, sync::Arc, vec::Vec};
+use core::fmt::{self, Debug, Write};
 
-use crate::{Element, Serialize, SerializeOptions, XmlWriter};
+use crate::{
+	Element, ElementKind, EmptyElementPolicy, Error, PartialSupportPolicy, Serialize, SerializeOptions, Speak, SsmlVersion, Support, XmlWriter,
+	diagnostics::Recovery, literal, serializer::Placeholder
+};
 
 /// Serialize a slice of elements, inserting spaces between adjacent text elements where needed.
 pub fn serialize_elements<'s, W: Write>(writer: &mut XmlWriter<W>, elements: impl AsRef<[Element<'s>]>, options: &SerializeOptions) -> crate::Result<()> {
-	let elements = elements.as_ref();
-	for i in 0..elements.len() {
-		let el = &elements[i];
-		el.serialize_xml(writer, options)?;
+	serialize_elements_at(writer, elements.as_ref(), 0, options)
+}
 
-		if !writer.pretty && matches!(el, Element::Text(_)) {
-			if let Some(x) = elements.get(i + 1) {
-				if matches!(x, Element::Text(_)) {
-					writer.write.write_char(' ')?;
-				}
-			}
+/// Like [`serialize_elements`], but for a slice of elements starting at index `offset` of their parent.
+pub(crate) fn serialize_elements_at<W: Write>(writer: &mut XmlWriter<W>, elements: &[Element], offset: usize, options: &SerializeOptions) -> crate::Result<()> {
+	if writer.placeholder(Placeholder::Run {
+		elements: elements.as_ptr() as usize,
+		len: elements.len(),
+		offset
+	}) {
+		return Ok(());
+	}
+	let mut after_text = false;
+	for (i, el) in elements.iter().enumerate() {
+		match begin_child(writer, el, offset + i, &mut after_text, options)? {
+			ChildMode::Skip => continue,
+			ChildMode::Unwrap => serialize_elements(writer, el.children().unwrap_or_default(), options)?,
+			ChildMode::Write => el.serialize_xml(writer, options)?
 		}
+		end_child(writer);
+	}
+	Ok(())
+}
+
+/// Serializes the child element with the given index of the element currently being written as-is, without the
+/// checks & formatting of [`serialize_elements`].
+pub(crate) fn serialize_child<W: Write>(writer: &mut XmlWriter<W>, el: &Element, index: usize, options: &SerializeOptions) -> crate::Result<()> {
+	if writer.placeholder(Placeholder::Child {
+		element: el as *const Element as usize,
+		index
+	}) {
+		return Ok(());
 	}
+	writer.enter_child(index);
+	el.serialize_xml(writer, options)?;
+	writer.exit_child();
 	Ok(())
 }
 
+/// How a child element is written; see [`begin_child`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChildMode {
+	/// The element is dropped entirely.
+	Skip,
+	/// Only the element's children are written.
+	Unwrap,
+	/// The element is written as usual.
+	Write
+}
+
+/// Prepares to write `el`, the child with the given index of the element currently being written, as part of a run of
+/// elements. Unless the element is skipped, [`end_child`] must be called once it has been written.
+pub(crate) fn begin_child<W: Write>(
+	writer: &mut XmlWriter<W>,
+	el: &Element,
+	index: usize,
+	after_text: &mut bool,
+	options: &SerializeOptions
+) -> crate::Result<ChildMode> {
+	let empty = is_empty_container(el);
+	if empty && options.empty_elements == EmptyElementPolicy::Drop {
+		return Ok(ChildMode::Skip);
+	}
+
+	let is_text = matches!(el, Element::Text(_));
+	if *after_text && is_text {
+		writer.write.write_char(' ')?;
+		// keep pretty-printed text on the same line, so the space isn't parsed as layout
+		writer.inline = writer.pretty;
+	}
+	*after_text = is_text;
+
+	writer.enter_child(index);
+	if !check_support(writer, ElementKind::of(el), options)? {
+		return Ok(ChildMode::Unwrap);
+	}
+	writer.expand_empty = empty && options.empty_elements == EmptyElementPolicy::Expanded;
+	Ok(ChildMode::Write)
+}
+
+/// Finishes writing a child element started with [`begin_child`].
+pub(crate) fn end_child<W: Write>(writer: &mut XmlWriter<W>) {
+	writer.expand_empty = false;
+	writer.exit_child();
+}
+
+/// Checks that an element of the given kind is supported by the configured flavor, failing, recovering or warning
+/// according to `options`. Returns `false` if the element should be unwrapped, writing only its children.
+pub(crate) fn check_support<W: Write>(writer: &mut XmlWriter<W>, kind: Option<ElementKind>, options: &SerializeOptions) -> crate::Result<bool> {
+	let Some(kind) = kind.filter(|_| options.perform_checks) else {
+		return Ok(true);
+	};
+	let error = match (options.spec().supports(kind), options.partial_support) {
+		(Support::None, _) => Error::UnsupportedElement(kind.tag(), options.flavor),
+		(Support::Partial, PartialSupportPolicy::Deny) => Error::PartiallySupportedElement(kind.tag(), options.flavor),
+		(Support::Partial, PartialSupportPolicy::Warn) => {
+			writer.warn(Error::PartiallySupportedElement(kind.tag(), options.flavor));
+			return Ok(true);
+		}
+		(Support::Full, _) | (Support::Partial, PartialSupportPolicy::Allow) => return Ok(true)
+	};
+	let lowered = options.lower_unsupported && matches!(error, Error::UnsupportedElement(..));
+	Ok(!lowered && writer.recover(error, Recovery::Unwrapped)?)
+}
+
+/// Returns whether `el` is a container with no content; see [`EmptyElementPolicy`].
+fn is_empty_container(el: &Element) -> bool {
+	let children = match el {
+		Element::Voice(el) => el.children(),
+		Element::Prosody(el) => el.children(),
+		Element::Emphasis(el) => el.children(),
+		Element::Lang(el) => el.children(),
+		Element::Paragraph(el) => el.children(),
+		Element::Sentence(el) => el.children(),
+		Element::Word(el) => el.children(),
+		_ => return false
+	};
+	children.iter().all(|child| match child {
+		Element::Text(text) => text.as_str().is_empty(),
+		child => is_empty_container(child)
+	})
+}
+
+/// Fails with [`Error::UnsupportedVersion`](crate::Error::UnsupportedVersion) if `construct` was introduced in SSML
+/// 1.1, but the serializer is targeting SSML 1.0.
+pub(crate) fn require_v1_1(options: &SerializeOptions, construct: &'static str) -> crate::Result<()> {
+	match options.version {
+		Some(version) if version < SsmlVersion::V1_1 => Err(crate::Error::UnsupportedVersion(construct, SsmlVersion::V1_1)),
+		_ => Ok(())
+	}
+}
+
+/// Writes a run of text, applying the [`TextFilter`](crate::TextFilter) configured in `options`, if any.
+pub(crate) fn write_text<W: Write>(writer: &mut XmlWriter<W>, text: &str, options: &SerializeOptions) -> crate::Result<()> {
+	match options.text_filter.as_ref().and_then(|filter| filter.apply(text)) {
+		Some(text) => writer.text(text),
+		None => writer.text(text)
+	}
+}
+
+/// A strategy for escaping characters in text & attribute values; see
+/// [`SerializeOptions::with_escaper`](crate::SerializeOptions::with_escaper).
+///
+/// Implementations must escape at least the characters escaped by [`XmlEscaper`], otherwise the output may not be
+/// well-formed XML. The contents of [`Meta`](crate::Meta) elements are never escaped.
+pub trait Escaper: Debug + Send + Sync {
+	/// Writes `char` to `out`, escaping it if necessary.
+	fn write_char(&self, out: &mut dyn Write, char: char) -> fmt::Result;
+}
+
+/// The default [`Escaper`], which only escapes characters with special meaning in XML.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct XmlEscaper;
+
+impl Escaper for XmlEscaper {
+	fn write_char(&self, out: &mut dyn Write, char: char) -> fmt::Result {
+		match escape_char(char) {
+			Some(entity) => out.write_str(entity),
+			None => out.write_char(char)
+		}
+	}
+}
+
+/// An [`Escaper`] which additionally writes every non-ASCII character as a numeric character reference, for engines
+/// or transport layers which can't handle UTF-8.
+///
+/// ```
+/// # use ssml::Serialize;
+/// # fn main() -> ssml::Result<()> {
+/// let options = ssml::SerializeOptions::default().with_escaper(ssml::util::AsciiEscaper);
+/// let doc = ssml::speak(Some("fr-FR"), ["Où est le café ?"]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&options)?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="fr-FR">O&#x00F9; est le caf&#x00E9; ?</speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AsciiEscaper;
+
+impl Escaper for AsciiEscaper {
+	fn write_char(&self, out: &mut dyn Write, char: char) -> fmt::Result {
+		if char.is_ascii() { XmlEscaper.write_char(out, char) } else { write_char_reference(out, char) }
+	}
+}
+
+/// Writes non-ASCII characters as numeric character references, delegating ASCII characters to another [`Escaper`].
+/// Used for [`SerializeOptions::ascii_only`](crate::SerializeOptions::ascii_only).
+#[derive(Debug)]
+pub(crate) struct AsciiOnly(pub(crate) Arc<dyn Escaper>);
+
+impl Escaper for AsciiOnly {
+	fn write_char(&self, out: &mut dyn Write, char: char) -> fmt::Result {
+		if char.is_ascii() { self.0.write_char(out, char) } else { write_char_reference(out, char) }
+	}
+}
+
+fn write_char_reference(out: &mut dyn Write, char: char) -> fmt::Result {
+	write!(out, "&#x{:04X};", char as u32)
+}
+
+/// A [`Write`] adapter which escapes everything written through it with an [`Escaper`].
+pub(crate) struct EscapingWrite<'a, W> {
+	pub(crate) inner: &'a mut W,
+	pub(crate) escaper: &'a dyn Escaper
+}
+
+impl<W: Write> Write for EscapingWrite<'_, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for char in s.chars() {
+			self.escaper.write_char(self.inner, char)?;
+		}
+		Ok(())
+	}
+}
+
+/// Returns whether `text` contains an entity or character reference, suggesting that it has already been escaped and
+/// would be double-escaped if written.
+pub(crate) fn contains_entity(text: &str) -> bool {
+	let b = text.as_bytes();
+	b.iter().enumerate().any(|(i, c)| *c == b'&' && literal::entity(b, i).is_ok())
+}
+
+/// Returns whether `escaped` can be safely written as text or an attribute value: it contains no markup characters,
+/// and every `&` begins an entity or character reference.
+pub(crate) fn is_escaped(escaped: &str) -> bool {
+	let b = escaped.as_bytes();
+	b.iter().enumerate().all(|(i, c)| match c {
+		b'<' | b'>' | b'"' => false,
+		b'&' => literal::entity(b, i).is_ok(),
+		_ => true
+	})
+}
+
+/// A [`Write`] adapter for values which are trusted not to need escaping, asserting that in debug builds.
+pub(crate) struct AssertNoEscape<'a, W>(pub(crate) &'a mut W);
+
+impl<W: Write> Write for AssertNoEscape<'_, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		debug_assert!(!s.contains(|c| escape_char(c).is_some()), "value trusted not to need escaping contains markup: {s:?}");
+		self.0.write_str(s)
+	}
+}
+
+/// Counts the bytes written to it.
+struct Counter(usize);
+
+impl Write for Counter {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.0 += s.len();
+		Ok(())
+	}
+}
+
+/// Returns the length in bytes of the given character once escaped by `escaper`, or by the default escaping if `None`.
+pub(crate) fn escaped_len_with(escaper: Option<&dyn Escaper>, char: char) -> usize {
+	match escaper {
+		Some(escaper) => {
+			let mut counter = Counter(0);
+			let _ = escaper.write_char(&mut counter, char);
+			counter.0
+		}
+		None => escaped_len(char)
+	}
+}
+
+/// Returns the XML entity used to escape the given character, if it needs escaping.
+pub(crate) fn escape_char(char: char) -> Option<&'static str> {
+	match char {
+		'"' => Some("&quot;"),
+		'\'' => Some("&apos;"),
+		'<' => Some("&lt;"),
+		'>' => Some("&gt;"),
+		'&' => Some("&amp;"),
+		_ => None
+	}
+}
+
+/// Returns the length in bytes of the given character once escaped.
+pub(crate) fn escaped_len(char: char) -> usize {
+	escape_char(char).map_or(char.len_utf8(), str::len)
+}
+
 /// Escape the given text for use in XML.
 pub fn escape<W: Write>(writer: &mut W, text: impl AsRef<str>) -> fmt::Result {
 	let text = text.as_ref();
 	for char in text.chars() {
-		match char {
-			'"' => writer.write_str("&quot;")?,
-			'\'' => writer.write_str("&apos;")?,
-			'<' => writer.write_str("&lt;")?,
-			'>' => writer.write_str("&gt;")?,
-			'&' => writer.write_str("&amp;")?,
-			_ => writer.write_char(char)?
+		match escape_char(char) {
+			Some(entity) => writer.write_str(entity)?,
+			None => writer.write_char(char)?
 		}
 	}
 	Ok(())
@@ -45,3 +312,185 @@ pub fn escape_to_string(text: impl AsRef<str>) -> Result<String, fmt::Error> {
 	escape(&mut out, text)?;
 	Ok(out)
 }
+
+/// Returns the longest prefix of `text` which takes at most `limit` bytes once escaped, cut at a word boundary if
+/// possible.
+///
+/// Unlike slicing the escaped string, this never cuts an entity like `&amp;` in half.
+///
+/// ```
+/// # use ssml::util::truncate_text_to_bytes;
+/// assert_eq!(truncate_text_to_bytes("Fish & chips, please", 12), "Fish &");
+/// assert_eq!(truncate_text_to_bytes("Fish & chips, please", 9), "Fish");
+/// assert_eq!(truncate_text_to_bytes("Supercalifragilistic", 5), "Super");
+/// assert_eq!(truncate_text_to_bytes("short", 10), "short");
+/// ```
+pub fn truncate_text_to_bytes(text: &str, limit: usize) -> &str {
+	let mut len = 0;
+	let mut end = text.len();
+	for (i, char) in text.char_indices() {
+		len += escaped_len(char);
+		if len > limit {
+			end = i;
+			break;
+		}
+	}
+	&text[..word_boundary(text, end)]
+}
+
+/// Returns the index at which to cut `text` so that it's at most `end` bytes long, moving back to the end of the last
+/// whole word if `end` falls in the middle of one.
+fn word_boundary(text: &str, end: usize) -> usize {
+	if end == text.len() || text[end..].starts_with(char::is_whitespace) {
+		return text[..end].trim_end().len();
+	}
+	match text[..end].rfind(char::is_whitespace) {
+		Some(space) => text[..space].trim_end().len(),
+		// a single word longer than the limit; cutting it is better than nothing
+		None => end
+	}
+}
+
+/// Truncates the spoken content of `doc` so that it serializes with `options` to at most `limit` bytes, e.g. to fit a
+/// provider's request size limit. Returns `true` if the document was truncated.
+///
+/// Content is removed from the end of the document: text is cut at a word boundary where possible, and elements which
+/// don't fit (including [`SayAs`](crate::SayAs), [`Sub`](crate::Sub) & [`Phoneme`](crate::Phoneme), which are never
+/// cut) are dropped along with everything after them. Since the tree itself is truncated, the output never ends in the
+/// middle of a tag or an escape sequence. If given, `ellipsis` (e.g. a short [`Break`](crate::Break) or `"…"`) is
+/// placed where content was removed, inside the innermost enclosing element which still has content.
+///
+/// If the markup alone exceeds `limit`, all spoken content is removed, and the document may still be too long.
+///
+/// ```
+/// # use ssml::{Serialize, SerializeOptions, Flavor};
+/// # fn main() -> ssml::Result<()> {
+/// let options = SerializeOptions::default().flavor(Flavor::GoogleCloudTextToSpeech);
+/// let mut doc =
+/// 	ssml::speak(None, [ssml::voice("en-US-Neural2-F", ["Salt & pepper are on the table.", "Enjoy your meal!"])]);
+///
+/// assert!(ssml::util::truncate_to_bytes(&mut doc, 80, Some("…".into()), &options)?);
+/// let out = doc.serialize_to_string(&options)?;
+/// assert_eq!(out, r#"<speak><voice name="en-US-Neural2-F">Salt &amp; pepper are …</voice></speak>"#);
+/// assert!(out.len() <= 80);
+///
+/// // elements left with no content are dropped, keeping the ellipsis
+/// let mut doc = ssml::speak(
+/// 	None,
+/// 	[ssml::voice(
+/// 		"en-US-Neural2-F",
+/// 		[ssml::Element::from("Hello."), ssml::emphasis(ssml::EmphasisLevel::Strong, ["Goodbye!"]).into()]
+/// 	)]
+/// );
+/// assert!(ssml::util::truncate_to_bytes(&mut doc, 70, Some("…".into()), &options)?);
+/// assert_eq!(doc.serialize_to_string(&options)?, r#"<speak><voice name="en-US-Neural2-F">Hello. …</voice></speak>"#);
+///
+/// // documents within the limit are left alone
+/// assert!(!ssml::util::truncate_to_bytes(&mut doc, 80, None, &options)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn truncate_to_bytes<'s>(doc: &mut Speak<'s>, limit: usize, ellipsis: Option<Element<'s>>, options: &SerializeOptions) -> crate::Result<bool> {
+	let serialized_len = |doc: &Speak| -> crate::Result<usize> {
+		let mut counter = Counter(0);
+		doc.serialize(&mut counter, options)?;
+		Ok(counter.0)
+	};
+	if serialized_len(doc)? <= limit {
+		return Ok(false);
+	}
+
+	// the serialized length only grows with the amount of spoken content kept, so find the most that fits
+	let truncated = |keep: usize| {
+		let mut doc = doc.clone();
+		let mut budget = keep;
+		truncate_elements(doc.children_mut(), &mut budget, &mut ellipsis.clone());
+		doc
+	};
+	let (mut low, mut high) = (0, spoken_len(doc.children()));
+	let mut best = truncated(0);
+	while low < high {
+		let mid = low + (high - low) / 2;
+		let candidate = truncated(mid);
+		if serialized_len(&candidate)? <= limit {
+			best = candidate;
+			low = mid + 1;
+		} else {
+			high = mid;
+		}
+	}
+	*doc = best;
+	Ok(true)
+}
+
+/// Returns the number of bytes of spoken content in `elements`, as counted by [`truncate_elements`].
+fn spoken_len(elements: &[Element]) -> usize {
+	elements
+		.iter()
+		.map(|el| atomic_len(el).unwrap_or_else(|| spoken_len(el.children().unwrap_or_default())))
+		.sum()
+}
+
+/// Returns the number of bytes of spoken content in `el` if it's a leaf element, which can't be truncated any further.
+fn atomic_len(el: &Element) -> Option<usize> {
+	match el {
+		Element::Text(text) => Some(text.len()),
+		Element::SayAs(el) => Some(el.text().len()),
+		Element::Sub(el) => Some(el.text().len()),
+		Element::Phoneme(el) => Some(el.text().len()),
+		el if el.children().is_some() => None,
+		_ => Some(0)
+	}
+}
+
+/// Keeps the first `budget` bytes of spoken content in `elements`, placing `ellipsis` where content was removed.
+/// If anything was removed, returns the number of elements kept before the ellipsis.
+fn truncate_elements<'s>(elements: &mut Vec<Element<'s>>, budget: &mut usize, ellipsis: &mut Option<Element<'s>>) -> Option<usize> {
+	for i in 0..elements.len() {
+		let keep = match &mut elements[i] {
+			Element::Text(text) => {
+				if text.len() <= *budget {
+					*budget -= text.len();
+					continue;
+				}
+				let end = word_boundary(text, text.floor_char_boundary(*budget));
+				match &mut text.0 {
+					Cow::Borrowed(b) => *b = &b[..end],
+					Cow::Owned(s) => s.truncate(end)
+				}
+				end > 0
+			}
+			el => match atomic_len(el) {
+				Some(len) => {
+					if len <= *budget {
+						*budget -= len;
+						continue;
+					}
+					false
+				}
+				None => {
+					let Some(children) = el.children_mut() else {
+						continue;
+					};
+					match truncate_elements(children, budget, ellipsis) {
+						None => continue,
+						// nothing but the ellipsis is left, so drop the element & place the ellipsis here instead
+						Some(0) => {
+							*ellipsis = children.pop();
+							false
+						}
+						Some(_) => true
+					}
+				}
+			}
+		};
+		let kept = if keep { i + 1 } else { i };
+		elements.truncate(kept);
+		*budget = 0;
+		if let Some(ellipsis) = ellipsis.take() {
+			elements.push(ellipsis);
+		}
+		return Some(kept);
+	}
+	None
+}
@@ -0,0 +1,1057 @@
+//! Validation of SSML elements.
+//!
+//! Elements can be validated explicitly with [`Validate::validate`], or at construction time by converting them into an
+//! [`Element`] through the [`Strict`] wrapper:
+//!
+//! ```
+//! use ssml::{Element, validate::Strict};
+//!
+//! let audio: Result<Element, _> = Strict(ssml::audio("")).try_into();
+//! assert!(matches!(audio, Err(ssml::Error::InvalidElement(_))));
+//!
+//! let audio: Result<Element, _> = Strict(ssml::audio("https://example.com/chime.ogg")).try_into();
+//! assert!(audio.is_ok());
+//! ```
+//!
+//! Each check is a [`Lint`] whose [`Level`] can be configured with a [`LintConfig`], much like Clippy's lints. Lints
+//! at [`Level::Error`] fail validation, lints at [`Level::Warn`] are reported by [`Validate::lint`] without failing
+//! it, and lints at [`Level::Allow`] aren't checked at all:
+//!
+//! ```
+//! use ssml::validate::{Level, Lint, LintConfig, Validate};
+//!
+//! # fn main() -> ssml::Result<()> {
+//! let doc = ssml::speak(None, [ssml::Element::from(ssml::breaks("8s")), ssml::Meta::new("<amazon:breath/>").into()]);
+//!
+//! // `Lint::LongBreak` is a warning by default, and raw `Meta` markup is allowed.
+//! let warnings = doc.lint(&LintConfig::default())?;
+//! assert_eq!(warnings.len(), 1);
+//! assert_eq!(warnings[0].lint, Lint::LongBreak);
+//! assert_eq!(warnings[0].path, [0]);
+//!
+//! let config = LintConfig::default()
+//! 	.level(Lint::RawMeta, Level::Error)
+//! 	.level(Lint::LongBreak, Level::Allow);
+//! assert!(matches!(doc.lint(&config), Err(ssml::Error::InvalidElement(_))));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! To check a whole document against a [`Flavor`] without stopping at the first error, use [`validate()`], which
+//! returns a [`Diagnostic`] for every violated lint:
+//!
+//! ```
+//! use ssml::{Flavor, validate::Lint};
+//!
+//! let doc = ssml::speak(None, ["Hello!"]);
+//! let diagnostics = ssml::validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices);
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].lint, Lint::ContentOutsideVoice);
+//!
+//! let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", ["Hello!"])]);
+//! assert!(ssml::validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices).is_empty());
+//! assert_eq!(ssml::validate::validate(&doc, Flavor::AmazonPolly)[0].lint, Lint::UnsupportedElement);
+//! ```
+//!
+//! Some lints only apply to certain flavors. For example, Google Cloud Text-to-Speech doesn't support the `duration` of
+//! [`Prosody`], voice `age` selection, or `say-as` values like `address`, and expects full voice names:
+//!
+//! ```
+//! use ssml::{Flavor, ProsodyControl, SpeechFormat, validate::Lint};
+//!
+//! let doc = ssml::speak(
+//! 	Some("en-US"),
+//! 	[
+//! 		ssml::Element::from(ssml::prosody(ProsodyControl::default().with_duration("2s"), ["Hello!"])),
+//! 		ssml::say_as(SpeechFormat::Address, "1600 Amphitheatre Pkwy").into(),
+//! 		ssml::voice("Wavenet-D", ["Hi!"]).into()
+//! 	]
+//! );
+//! let lints: Vec<_> = ssml::validate::validate(&doc, Flavor::GoogleCloudTextToSpeech)
+//! 	.into_iter()
+//! 	.map(|d| d.lint)
+//! 	.collect();
+//! assert_eq!(lints, [Lint::UnsupportedAttribute, Lint::UnsupportedSayAs, Lint::UnqualifiedVoiceName]);
+//! ```
+//!
+//! Speech synthesizers also limit how long a break can be. ACSS rejects breaks longer than 5 seconds, Amazon Polly
+//! longer than 10 seconds & ElevenLabs longer than 3 seconds; consecutive breaks which add up to more are reported too:
+//!
+//! ```
+//! use ssml::{
+//! 	Flavor, TimeDesignation,
+//! 	validate::{Fix, Lint}
+//! };
+//!
+//! let doc = ssml::speak(
+//! 	Some("en-US"),
+//! 	[ssml::voice(
+//! 		"en-US-JennyNeural",
+//! 		[
+//! 			ssml::Element::from(ssml::breaks("8s")),
+//! 			"Still there?".into(),
+//! 			ssml::breaks("3s").into(),
+//! 			ssml::breaks("3s").into()
+//! 		]
+//! 	)]
+//! );
+//! let diagnostics = ssml::validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices);
+//! let lints: Vec<_> = diagnostics.iter().map(|d| (d.lint, d.path.as_slice())).collect();
+//! assert_eq!(
+//! 	lints,
+//! 	[(Lint::ConsecutiveBreaks, &[0, 2][..]), (Lint::LongBreak, &[0, 0]), (Lint::UnsupportedBreakTime, &[0, 0])]
+//! );
+//! assert_eq!(diagnostics[2].fix, Some(Fix::SetBreakTime(TimeDesignation::from_millis(5_000.))));
+//! ```
+//!
+//! Some diagnostics carry a machine-applicable [`Fix`], which can be applied with
+//! [`apply_fixes`](crate::transform::apply_fixes).
+
+use alloc::{borrow::Cow, string::ToString, sync::Arc, vec::Vec};
+use core::fmt::{self, Display};
+
+use crate::{
+	Audio, Break, CustomElement, Element, ElementKind, Emphasis, Error, Flavor, FlavorSpec, Lang, Lexicon, Mark, Meta, Metadata, MetadataContent, Paragraph,
+	Phoneme, Prosody, ProsodyRate, SayAs, Sentence, Speak, Sub, Support, Text, TimeDesignation, Variant, Voice, Word, group::Group, transform::voice_locale
+};
+
+/// The severity of a [`Lint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+	/// The lint is not checked.
+	Allow,
+	/// Violations are reported as a [`Diagnostic`] by [`Validate::lint`], but don't fail validation.
+	Warn,
+	/// Violations fail validation with [`Error::InvalidElement`].
+	Error
+}
+
+macro_rules! lints {
+	($($variant:ident($name:literal, $level:ident, $description:literal)),*) => {
+		/// A check performed during validation; see the [module-level documentation](self).
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+		#[non_exhaustive]
+		pub enum Lint {
+			$(#[doc = concat!("`", $name, "`: ", $description, ".")]
+			$variant),*
+		}
+
+		impl Lint {
+			/// Returns the `snake_case` name of this lint, e.g. `long_break`.
+			pub fn name(self) -> &'static str {
+				match self {
+					$(Lint::$variant => $name),*
+				}
+			}
+
+			/// Returns the level of this lint when it isn't configured otherwise.
+			pub fn default_level(self) -> Level {
+				match self {
+					$(Lint::$variant => Level::$level),*
+				}
+			}
+
+			/// Returns a description of what this lint checks for, which is also the reason given by
+			/// [`Error::InvalidElement`] when the lint fails validation.
+			pub fn description(self) -> &'static str {
+				match self {
+					$(Lint::$variant => $description),*
+				}
+			}
+		}
+	};
+}
+
+lints! {
+	InvalidMetadataName("invalid_metadata_name", Error, "metadata property names must be valid XML names"),
+	EmptyAudioSource("empty_audio_source", Error, "audio `src` must not be empty"),
+	InvalidAudioSpeed("invalid_audio_speed", Error, "audio `speed` must be positive"),
+	UnspecifiedVoice("unspecified_voice", Error, "voice must specify at least one of `name`, `gender`, `age`, `variant` or `languages`"),
+	NegativeBreak("negative_break", Error, "break `time` must not be negative"),
+	LongBreak("long_break", Warn, "break `time` is longer than the configured maximum"),
+	UnsupportedBreakTime("unsupported_break_time", Error, "break `time` is longer than the target flavor supports"),
+	ConsecutiveBreaks("consecutive_breaks", Warn, "consecutive breaks add up to more than the longest break the target flavor supports"),
+	EmptyMark("empty_mark", Error, "mark `name` must not be empty"),
+	EmptyLexiconUri("empty_lexicon_uri", Error, "lexicon `uri` must not be empty"),
+	MisplacedLexicon("misplaced_lexicon", Error, "lexicon must be a direct child of `voice` for the target flavor"),
+	EmptySayAs("empty_say_as", Error, "say-as must contain text"),
+	EmptyPhoneme("empty_phoneme", Error, "phoneme `ph` must not be empty"),
+	EmptyPhonemeText("empty_phoneme_text", Error, "phoneme must contain text"),
+	EmptySubAlias("empty_sub_alias", Error, "sub `alias` must not be empty"),
+	EmptySubText("empty_sub_text", Error, "sub must contain text"),
+	NestedParagraph("nested_paragraph", Error, "paragraphs must not be nested"),
+	NestedSentence("nested_sentence", Error, "sentence must not contain paragraphs or other sentences"),
+	EmptyWord("empty_word", Error, "word must contain text"),
+	EmptyWordRole("empty_word_role", Error, "word `role` must not be empty"),
+	NestedWord("nested_word", Error, "word must not contain paragraphs, sentences or other words"),
+	EmptyVariant("empty_variant", Error, "variant must have at least one alternative"),
+	UnkeyedVariant("unkeyed_variant", Error, "variant alternatives must each have a key"),
+	EmptyLang("empty_lang", Error, "lang `xml:lang` must not be empty"),
+	InvalidProsodyRate("invalid_prosody_rate", Error, "prosody `rate` must be positive"),
+	NegativeProsodyDuration("negative_prosody_duration", Error, "prosody `duration` must not be negative"),
+	EmptyCustomTag("empty_custom_tag", Error, "custom element tag must not be empty"),
+	InvalidStyleDegree("invalid_style_degree", Error, "express-as `styledegree` must be between 0.01 and 2"),
+	InvalidSilence("invalid_silence", Error, "silence `value` must be between 0 and 20 seconds"),
+	InvalidAudioDuration("invalid_audio_duration", Error, "audioduration `value` must be positive"),
+	InvalidVisemeOptions(
+		"invalid_viseme_options",
+		Error,
+		"viseme `framerate` & 3D `dimension` require the `FacialExpression` type, and `framerate` must be between 1 and 60"
+	),
+	MalformedMath("malformed_math", Error, "math content must be well-formed MathML markup"),
+	MisplacedVoiceSetting(
+		"misplaced_voice_setting",
+		Error,
+		"silence, viseme & audioduration elements must be direct children of `voice` for the target flavor"
+	),
+	MisorderedVoiceSetting(
+		"misordered_voice_setting",
+		Error,
+		"silence, viseme & audioduration elements must come before any other content of their `voice` for the target flavor"
+	),
+	InvalidVoiceTransformation(
+		"invalid_voice_transformation",
+		Error,
+		"voice-transformation `strength` & `timbre_extent` must be between 0% and 100%, and other parameters between -100% and 100%"
+	),
+	EmptySpeakerProfile("empty_speaker_profile", Error, "ttsembedding `speakerProfileId` must not be empty"),
+	InvalidBackgroundAudio(
+		"invalid_background_audio",
+		Error,
+		"background audio `volume` must be between 0 and 100, and `fadein` & `fadeout` between 0 and 10 seconds"
+	),
+	MisplacedBackgroundAudio("misplaced_background_audio", Error, "background audio must be a direct child of `speak` for the target flavor"),
+	DuplicateBackgroundAudio("duplicate_background_audio", Error, "only one background audio element is allowed per document for the target flavor"),
+	UnsupportedElement("unsupported_element", Error, "element is not supported by the target flavor"),
+	PartiallySupportedElement("partially_supported_element", Allow, "element is only partially supported by the target flavor, and may have no effect"),
+	ContentOutsideVoice("content_outside_voice", Error, "content must be within a `voice` element for the target flavor"),
+	UnsupportedAttribute("unsupported_attribute", Error, "attribute is not supported by the target flavor"),
+	UnsupportedSayAs("unsupported_say_as", Error, "say-as `interpret-as` value is not supported by the target flavor"),
+	UnqualifiedVoiceName(
+		"unqualified_voice_name",
+		Warn,
+		"voice `name` should be a full voice name including its locale, like `en-US-Wavenet-D`, for the target flavor"
+	),
+	AmbiguousVoiceSelection(
+		"ambiguous_voice_selection",
+		Warn,
+		"voice selected by `gender` or `variant` without `languages` may speak an unexpected language with the target flavor"
+	),
+	RawMeta("raw_meta", Allow, "raw `Meta` markup can't be checked and may not be supported by the speech synthesizer")
+}
+
+impl Display for Lint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// Configures the [`Level`] of each [`Lint`], for use with [`Validate::lint`].
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+	levels: Vec<(Lint, Level)>,
+	/// The flavor whose support is checked by flavor-specific lints like [`Lint::UnsupportedElement`]. Defaults to
+	/// [`Flavor::Generic`], which supports everything.
+	pub flavor: Flavor,
+	/// A custom flavor of SSML, used instead of [`flavor`](LintConfig::flavor) where set; see
+	/// [`LintConfig::flavor_spec`].
+	pub flavor_spec: Option<Arc<dyn FlavorSpec>>,
+	/// The longest break permitted by [`Lint::LongBreak`]. Defaults to 5 seconds.
+	pub max_break: TimeDesignation
+}
+
+impl Default for LintConfig {
+	fn default() -> Self {
+		LintConfig {
+			levels: Vec::new(),
+			flavor: Flavor::Generic,
+			flavor_spec: None,
+			max_break: TimeDesignation::from_millis(5_000.)
+		}
+	}
+}
+
+impl LintConfig {
+	/// Sets the level of a lint, overriding its [default level](Lint::default_level).
+	pub fn level(mut self, lint: Lint, level: Level) -> Self {
+		self.set_level(lint, level);
+		self
+	}
+
+	/// Sets the level of a lint, overriding its [default level](Lint::default_level).
+	pub fn set_level(&mut self, lint: Lint, level: Level) {
+		match self.levels.iter_mut().find(|(l, _)| *l == lint) {
+			Some((_, l)) => *l = level,
+			None => self.levels.push((lint, level))
+		}
+	}
+
+	/// Returns the configured level of a lint.
+	pub fn level_of(&self, lint: Lint) -> Level {
+		self.levels
+			.iter()
+			.find(|(l, _)| *l == lint)
+			.map_or_else(|| lint.default_level(), |(_, level)| *level)
+	}
+
+	/// Sets the flavor whose support is checked by flavor-specific lints, clearing any custom
+	/// [`flavor_spec`](LintConfig::flavor_spec).
+	pub fn flavor(mut self, flavor: Flavor) -> Self {
+		self.flavor = flavor;
+		self.flavor_spec = None;
+		self
+	}
+
+	/// Checks support against a custom flavor of SSML described by a [`FlavorSpec`], such as an Amazon Polly
+	/// [`Engine`](crate::polly::Engine). [`flavor`](LintConfig::flavor) is set to the spec's [`base`](FlavorSpec::base)
+	/// flavor.
+	pub fn flavor_spec(mut self, spec: impl FlavorSpec + 'static) -> Self {
+		self.flavor = spec.base();
+		self.flavor_spec = Some(Arc::new(spec));
+		self
+	}
+
+	/// Returns the [`FlavorSpec`] in use: the custom spec if one is set, or the configured [`Flavor`].
+	pub fn spec(&self) -> &dyn FlavorSpec {
+		match &self.flavor_spec {
+			Some(spec) => &**spec,
+			None => &self.flavor
+		}
+	}
+
+	/// Sets the longest break permitted by [`Lint::LongBreak`].
+	pub fn max_break(mut self, max: impl Into<TimeDesignation>) -> Self {
+		self.max_break = max.into();
+		self
+	}
+}
+
+/// A machine-applicable change to the offending element of a [`Diagnostic`] which resolves it; see
+/// [`apply_fixes`](crate::transform::apply_fixes).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Fix {
+	/// Remove the element & its children.
+	Remove,
+	/// Replace the element with its children.
+	Unwrap,
+	/// Wrap the element in a [`Voice`] with the given name.
+	WrapInVoice(Cow<'static, str>),
+	/// Set the time of the [`Break`].
+	SetBreakTime(TimeDesignation),
+	/// Set the `styledegree` of the [`Express`](crate::mstts::Express) section.
+	SetStyleDegree(f32)
+}
+
+impl Display for Fix {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Fix::Remove => f.write_str("remove the element"),
+			Fix::Unwrap => f.write_str("replace the element with its children"),
+			Fix::WrapInVoice(name) => write!(f, "wrap the element in `<voice name=\"{name}\">`"),
+			Fix::SetBreakTime(time) => write!(f, "set the break `time` to {time}"),
+			Fix::SetStyleDegree(degree) => write!(f, "set `styledegree` to {degree}")
+		}
+	}
+}
+
+/// A violated [`Lint`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+	pub lint: Lint,
+	/// The configured level of the lint; never [`Level::Allow`].
+	pub level: Level,
+	/// The path of the offending element, relative to the element being validated; see [`Speak::element_at`].
+	pub path: Vec<usize>,
+	/// A suggested fix for the violation, if one can be applied automatically.
+	pub fix: Option<Fix>
+}
+
+impl Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let level = match self.level {
+			Level::Error => "error",
+			_ => "warning"
+		};
+		write!(f, "{level}: {} (`{}`, element at {:?})", self.lint.description(), self.lint.name(), self.path)?;
+		if let Some(fix) = &self.fix {
+			write!(f, "; help: {fix}")?;
+		}
+		Ok(())
+	}
+}
+
+/// The state of an in-progress validation, passed to [`Validate::check`].
+#[derive(Debug)]
+pub struct LintContext<'c> {
+	config: &'c LintConfig,
+	path: Vec<usize>,
+	/// The kinds of the elements enclosing the element being checked, outermost first.
+	ancestors: Vec<ElementKind>,
+	/// The kind of the element being checked, which encloses the elements checked by [`LintContext::check_all`].
+	current: Option<ElementKind>,
+	/// Whether any of the preceding siblings of the element being checked are content; see
+	/// [`LintContext::follows_content`].
+	follows_content: bool,
+	diagnostics: Vec<Diagnostic>,
+	fatal: bool
+}
+
+impl<'c> LintContext<'c> {
+	/// Creates a context in which violations of lints at [`Level::Error`] fail validation.
+	pub fn new(config: &'c LintConfig) -> Self {
+		LintContext {
+			config,
+			path: Vec::new(),
+			ancestors: Vec::new(),
+			current: None,
+			follows_content: false,
+			diagnostics: Vec::new(),
+			fatal: true
+		}
+	}
+
+	/// Creates a context in which violations of lints at [`Level::Error`] are reported as a [`Diagnostic`] instead of
+	/// failing validation.
+	pub fn non_fatal(config: &'c LintConfig) -> Self {
+		let mut cx = LintContext::new(config);
+		cx.fatal = false;
+		cx
+	}
+
+	pub fn config(&self) -> &LintConfig {
+		self.config
+	}
+
+	/// Returns the position of the element being checked, relative to the element validation started at; see
+	/// [`Diagnostic::path`].
+	pub fn path(&self) -> &[usize] {
+		&self.path
+	}
+
+	/// Returns the kinds of the elements enclosing the element being checked, outermost first, for lints which depend
+	/// on where an element is placed.
+	///
+	/// Elements without a kind (like [`Group`]s, [`Variant`]s & custom elements) aren't included; the children of
+	/// groups & variants are written inline, as children of the enclosing element.
+	///
+	/// ```
+	/// use ssml::{
+	/// 	Flavor, ProsodyRate,
+	/// 	mstts::{self, SilenceType},
+	/// 	validate::{self, Lint}
+	/// };
+	///
+	/// // ACSS only applies `mstts:silence` as a direct child of a voice
+	/// let silence = || mstts::silence(SilenceType::Leading, "200ms");
+	/// let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", [ssml::prosody(ProsodyRate::Slow, [silence()])])]);
+	/// let diagnostics = validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices);
+	/// assert_eq!(diagnostics[0].lint, Lint::MisplacedVoiceSetting);
+	/// assert_eq!(diagnostics[0].path, [0, 0, 0]);
+	///
+	/// let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", [ssml::Element::from(silence()), "Hello!".into()])]);
+	/// assert!(validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices).is_empty());
+	/// ```
+	pub fn ancestors(&self) -> &[ElementKind] {
+		&self.ancestors
+	}
+
+	/// Returns the kind of the element directly enclosing the element being checked, or `None` at the root.
+	pub fn parent(&self) -> Option<ElementKind> {
+		self.ancestors.last().copied()
+	}
+
+	/// Returns whether the element being checked comes after content (anything other than whitespace or voice
+	/// settings like [`mstts::silence`](crate::mstts::silence)) among its siblings, for lints which depend on the order
+	/// of elements.
+	///
+	/// Like [`LintContext::ancestors`], the children of groups & variants are treated as siblings of the elements
+	/// around them.
+	///
+	/// ```
+	/// use ssml::{
+	/// 	Flavor,
+	/// 	mstts::{self, SilenceType},
+	/// 	validate::{self, Lint}
+	/// };
+	///
+	/// // ACSS only applies voice settings which come before the voice's content
+	/// let silence = || ssml::Element::from(mstts::silence(SilenceType::SentenceBoundary, "200ms"));
+	/// let doc = ssml::speak(None, [ssml::voice("en-US-JennyNeural", [ssml::Element::from("Hello!"), silence()])]);
+	/// let diagnostics = validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices);
+	/// assert_eq!(diagnostics[0].lint, Lint::MisorderedVoiceSetting);
+	/// assert_eq!(diagnostics[0].path, [0, 1]);
+	///
+	/// let doc = ssml::speak(
+	/// 	None,
+	/// 	[ssml::voice("en-US-JennyNeural", [ssml::Element::from(ssml::group([silence()])), "Hello!".into()])]
+	/// );
+	/// assert!(validate::validate(&doc, Flavor::MicrosoftAzureCognitiveSpeechServices).is_empty());
+	/// ```
+	pub fn follows_content(&self) -> bool {
+		self.follows_content
+	}
+
+	/// Reports a violation of `lint` if `condition` is false, returning an error if the lint is at [`Level::Error`]
+	/// and this context is fatal.
+	pub fn check(&mut self, condition: bool, lint: Lint) -> crate::Result<()> {
+		self.check_with_fix(condition, lint, || None)
+	}
+
+	/// Like [`LintContext::check`], but attaches the [`Fix`] returned by `fix` to the reported [`Diagnostic`].
+	pub fn check_with_fix(&mut self, condition: bool, lint: Lint, fix: impl FnOnce() -> Option<Fix>) -> crate::Result<()> {
+		if condition {
+			return Ok(());
+		}
+		match self.config.level_of(lint) {
+			Level::Allow => Ok(()),
+			Level::Error if self.fatal => Err(Error::InvalidElement(lint.description())),
+			level => {
+				self.diagnostics.push(Diagnostic {
+					lint,
+					level,
+					path: self.path.clone(),
+					fix: fix()
+				});
+				Ok(())
+			}
+		}
+	}
+
+	/// Checks each of `elements`, tracking their position for [`Diagnostic::path`].
+	pub fn check_all(&mut self, elements: &[Element]) -> crate::Result<()> {
+		if let Some(max) = max_break_millis(self.config.flavor) {
+			self.check_consecutive_breaks(elements, max)?;
+		}
+		let enclosing = self.current.take();
+		// the children of elements without a kind continue the sequence of siblings they're in
+		let follows_content = match enclosing {
+			Some(kind) => {
+				self.ancestors.push(kind);
+				Some(core::mem::replace(&mut self.follows_content, false))
+			}
+			None => None
+		};
+		let mut result = Ok(());
+		for (i, element) in elements.iter().enumerate() {
+			self.path.push(i);
+			result = element.check(self);
+			self.path.pop();
+			if result.is_err() {
+				break;
+			}
+			self.follows_content |= is_content(element);
+		}
+		if let Some(follows_content) = follows_content {
+			self.ancestors.pop();
+			self.follows_content = follows_content;
+		}
+		self.current = enclosing;
+		result
+	}
+
+	/// Reports [`Lint::ConsecutiveBreaks`] at the first break in each run of `elements` whose combined time exceeds
+	/// `max` milliseconds. Breaks separated only by whitespace are consecutive.
+	fn check_consecutive_breaks(&mut self, elements: &[Element], max: f32) -> crate::Result<()> {
+		// the index of the first break in the current run, & the combined time of its breaks
+		let mut run: Option<(usize, f32)> = None;
+		let mut reported = false;
+		for (i, el) in elements.iter().enumerate() {
+			match el {
+				Element::Break(el) => {
+					let (start, total) = run.get_or_insert((i, 0.));
+					*total += el.time().map_or(0., TimeDesignation::to_millis);
+					if *start != i && *total > max && !reported {
+						reported = true;
+						self.check_child(*start, false, Lint::ConsecutiveBreaks, || None)?;
+					}
+				}
+				Element::Text(text) if text.as_str().trim().is_empty() => {}
+				_ => {
+					run = None;
+					reported = false;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Like [`LintContext::check_with_fix`], but reports the violation at the child of the current element at `index`.
+	fn check_child(&mut self, index: usize, condition: bool, lint: Lint, fix: impl FnOnce() -> Option<Fix>) -> crate::Result<()> {
+		self.path.push(index);
+		self.check_with_fix(condition, lint, fix)?;
+		self.path.pop();
+		Ok(())
+	}
+
+	/// Returns the diagnostics reported so far.
+	pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+		self.diagnostics
+	}
+}
+
+/// Trait for SSML elements which can check themselves (and their children) for errors.
+pub trait Validate {
+	/// Checks this element and all of its children, returning [`Error::InvalidElement`] if any element is invalid.
+	///
+	/// Lints are checked at their [default level](Lint::default_level); warnings are discarded.
+	fn validate(&self) -> crate::Result<()> {
+		self.lint(&LintConfig::default()).map(drop)
+	}
+
+	/// Checks this element and all of its children with the lint levels given by `config`, returning
+	/// [`Error::InvalidElement`] if a lint at [`Level::Error`] is violated, or the violated lints at [`Level::Warn`]
+	/// otherwise.
+	fn lint(&self, config: &LintConfig) -> crate::Result<Vec<Diagnostic>> {
+		let mut cx = LintContext::new(config);
+		self.check(&mut cx)?;
+		Ok(cx.into_diagnostics())
+	}
+
+	/// Checks this element and all of its children within an in-progress validation.
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()>;
+}
+
+/// A wrapper which validates its element when converting it into an [`Element`] with [`TryFrom`].
+#[derive(Debug, Clone)]
+pub struct Strict<T>(pub T);
+
+impl<'s, T: Validate + Into<Element<'s>>> TryFrom<Strict<T>> for Element<'s> {
+	type Error = Error;
+
+	fn try_from(value: Strict<T>) -> Result<Self, Self::Error> {
+		value.0.validate()?;
+		Ok(value.0.into())
+	}
+}
+
+/// Checks a whole document against `flavor`, returning a [`Diagnostic`] for every violated lint (at its default level)
+/// instead of stopping at the first error.
+///
+/// This checks nesting rules, required attributes & which elements the flavor supports, independently of
+/// serialization.
+pub fn validate(doc: &Speak, flavor: Flavor) -> Vec<Diagnostic> {
+	validate_with(doc, &LintConfig::default().flavor(flavor))
+}
+
+/// Like [`validate()`], but with the lint levels & flavor configured by `config`.
+pub fn validate_with(doc: &Speak, config: &LintConfig) -> Vec<Diagnostic> {
+	let mut cx = LintContext::non_fatal(config);
+	// a non-fatal context never fails
+	let _ = doc.check(&mut cx);
+	cx.into_diagnostics()
+}
+
+/// Returns the tag of `el` if it isn't supported by `flavor`.
+pub(crate) fn unsupported_tag(el: &Element, flavor: &dyn FlavorSpec) -> Option<&'static str> {
+	ElementKind::of(el)
+		.filter(|kind| flavor.supports(*kind) == Support::None)
+		.map(|kind| kind.tag())
+}
+
+/// Returns the tag of `el` if it's only partially supported by `flavor`.
+pub(crate) fn partially_supported_tag(el: &Element, flavor: &dyn FlavorSpec) -> Option<&'static str> {
+	ElementKind::of(el)
+		.filter(|kind| flavor.supports(*kind) == Support::Partial)
+		.map(|kind| kind.tag())
+}
+
+/// Returns the longest break supported by `flavor`, in milliseconds, if its speech synthesizer rejects or truncates
+/// longer breaks.
+fn max_break_millis(flavor: Flavor) -> Option<f32> {
+	match flavor {
+		Flavor::MicrosoftAzureCognitiveSpeechServices => Some(5_000.),
+		Flavor::AmazonPolly => Some(10_000.),
+		Flavor::ElevenLabs => Some(3_000.),
+		_ => None
+	}
+}
+
+/// The `interpret-as` values supported by Google Cloud Text-to-Speech.
+const GOOGLE_INTERPRET_AS: &[&str] = &[
+	"currency",
+	"telephone",
+	"verbatim",
+	"spell-out",
+	"date",
+	"characters",
+	"cardinal",
+	"ordinal",
+	"fraction",
+	"expletive",
+	"bleep",
+	"unit",
+	"time"
+];
+
+/// Returns whether validation targets ACSS & the element being checked, which configures its enclosing voice, isn't a
+/// direct child of a `voice`.
+fn outside_voice(cx: &LintContext<'_>) -> bool {
+	// the path is relative to the element being validated, so a lone element is also accepted
+	cx.config().flavor == Flavor::MicrosoftAzureCognitiveSpeechServices && !cx.path.is_empty() && cx.parent() != Some(ElementKind::Voice)
+}
+
+/// Returns whether validation targets ACSS & the element being checked, which configures its enclosing voice, comes
+/// after other content.
+fn after_content(cx: &LintContext<'_>) -> bool {
+	cx.config().flavor == Flavor::MicrosoftAzureCognitiveSpeechServices && cx.follows_content()
+}
+
+/// Returns whether `el` is content rather than whitespace or a setting of its enclosing voice; see
+/// [`LintContext::follows_content`].
+fn is_content(el: &Element) -> bool {
+	use crate::mstts::Element as Mstts;
+	match el {
+		Element::Lexicon(_) | Element::FlavorMSTTS(Mstts::Silence(_) | Mstts::Viseme(_) | Mstts::AudioDuration(_)) => false,
+		Element::Text(text) => !text.as_str().trim().is_empty(),
+		Element::Group(el) => el.children().iter().any(is_content),
+		Element::Variant(el) => el.children().iter().any(is_content),
+		_ => true
+	}
+}
+
+/// The `interpret-as` values supported by Microsoft Azure Cognitive Speech Services.
+const ACSS_INTERPRET_AS: &[&str] = &[
+	"address",
+	"cardinal",
+	"number",
+	"characters",
+	"spell-out",
+	"currency",
+	"date",
+	"digits",
+	"number_digit",
+	"duration",
+	"fraction",
+	"name",
+	"ordinal",
+	"telephone",
+	"time",
+	"unit"
+];
+
+/// The `interpret-as` values supported by eSpeak NG.
+const ESPEAK_INTERPRET_AS: &[&str] = &["characters", "tts:char", "tts:key", "tts:digits"];
+
+/// Returns whether validation targets Google Cloud Text-to-Speech.
+fn is_google(cx: &LintContext<'_>) -> bool {
+	cx.config().flavor == Flavor::GoogleCloudTextToSpeech
+}
+
+/// Returns whether `el` is content which must be within a `voice` element for ACSS.
+fn needs_voice(el: &Element) -> bool {
+	match el {
+		Element::Voice(_) | Element::Lexicon(_) | Element::FlavorMSTTS(crate::mstts::Element::BackgroundAudio(_)) => false,
+		Element::Text(text) => !text.as_str().trim().is_empty(),
+		Element::Group(el) => el.children().iter().any(needs_voice),
+		Element::Variant(el) => el.children().iter().any(needs_voice),
+		_ => true
+	}
+}
+
+/// Returns the name of the [`Voice`] closest to `elements[index]` among its siblings, preferring earlier siblings.
+fn nearest_voice(elements: &[Element], index: usize) -> Option<Cow<'static, str>> {
+	let name = |el: &Element| match el {
+		Element::Voice(voice) => voice.config().names.as_ref()?.first().map(|name| Cow::Owned(name.to_string())),
+		_ => None
+	};
+	elements[..index]
+		.iter()
+		.rev()
+		.find_map(name)
+		.or_else(|| elements[index + 1..].iter().find_map(name))
+}
+
+/// Returns whether `name` is a (namespace-less) XML name.
+fn is_name(name: &str) -> bool {
+	let mut chars = name.chars();
+	chars.next().is_some_and(|c| c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || matches!(c, '-' | '.' | '_'))
+}
+
+impl Validate for Element<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check_with_fix(unsupported_tag(self, cx.config().spec()).is_none(), Lint::UnsupportedElement, || Some(Fix::Unwrap))?;
+		cx.check(partially_supported_tag(self, cx.config().spec()).is_none(), Lint::PartiallySupportedElement)?;
+		let current = core::mem::replace(&mut cx.current, ElementKind::of(self));
+		let result = self.check_kind(cx);
+		cx.current = current;
+		result
+	}
+}
+
+impl Element<'_> {
+	fn check_kind(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		match self {
+			Element::Text(el) => el.check(cx),
+			Element::Audio(el) => el.check(cx),
+			Element::Voice(el) => el.check(cx),
+			Element::Meta(el) => el.check(cx),
+			Element::Break(el) => el.check(cx),
+			Element::Emphasis(el) => el.check(cx),
+			Element::Mark(el) => el.check(cx),
+			Element::Lexicon(el) => el.check(cx),
+			Element::SayAs(el) => el.check(cx),
+			Element::Phoneme(el) => el.check(cx),
+			Element::Sub(el) => el.check(cx),
+			Element::Paragraph(el) => el.check(cx),
+			Element::Sentence(el) => el.check(cx),
+			Element::Word(el) => el.check(cx),
+			Element::Group(el) => el.check(cx),
+			Element::Lang(el) => el.check(cx),
+			Element::Prosody(el) => el.check(cx),
+			Element::FlavorMSTTS(crate::mstts::Element::Express(el)) => {
+				let degree = el.expression().degree();
+				cx.check_with_fix((0.01..=2.).contains(&degree), Lint::InvalidStyleDegree, || Some(Fix::SetStyleDegree(degree.clamp(0.01, 2.))))?;
+				cx.check_all(el.children())
+			}
+			Element::FlavorMSTTS(crate::mstts::Element::Silence(el)) => {
+				cx.check(!outside_voice(cx), Lint::MisplacedVoiceSetting)?;
+				cx.check(!after_content(cx), Lint::MisorderedVoiceSetting)?;
+				cx.check((0. ..=20_000.).contains(&el.value().to_millis()), Lint::InvalidSilence)
+			}
+			Element::FlavorMSTTS(crate::mstts::Element::BackgroundAudio(el)) => el.check(cx),
+			Element::FlavorMSTTS(crate::mstts::Element::Viseme(el)) => {
+				cx.check(!outside_voice(cx), Lint::MisplacedVoiceSetting)?;
+				cx.check(!after_content(cx), Lint::MisorderedVoiceSetting)?;
+				cx.check(el.options_valid(), Lint::InvalidVisemeOptions)
+			}
+			Element::FlavorMSTTS(crate::mstts::Element::AudioDuration(el)) => {
+				cx.check(!outside_voice(cx), Lint::MisplacedVoiceSetting)?;
+				cx.check(!after_content(cx), Lint::MisorderedVoiceSetting)?;
+				cx.check(el.value().to_millis() > 0., Lint::InvalidAudioDuration)
+			}
+			Element::FlavorMSTTS(crate::mstts::Element::Math(el)) => {
+				cx.check(crate::literal::check(el.content(), Flavor::Generic).is_ok(), Lint::MalformedMath)
+			}
+			Element::FlavorMSTTS(crate::mstts::Element::TtsEmbedding(el)) => {
+				cx.check_with_fix(!el.speaker_profile_id().trim().is_empty(), Lint::EmptySpeakerProfile, || Some(Fix::Unwrap))?;
+				cx.check_all(el.children())
+			}
+			Element::FlavorPolly(crate::polly::Element::Domain(el)) => cx.check_all(el.children()),
+			Element::FlavorPolly(crate::polly::Element::Effect(el)) => cx.check_all(el.children()),
+			Element::FlavorWatson(crate::watson::Element::ExpressAs(el)) => cx.check_all(el.children()),
+			Element::FlavorWatson(crate::watson::Element::VoiceTransformation(el)) => {
+				cx.check(el.in_range(), Lint::InvalidVoiceTransformation)?;
+				cx.check_all(el.children())
+			}
+			Element::Custom(el) => el.check(cx),
+			Element::Variant(el) => el.check(cx)
+		}
+	}
+}
+
+impl Validate for Speak<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		self.metadata().iter().try_for_each(|metadata| metadata.check(cx))?;
+		if cx.config().flavor == Flavor::MicrosoftAzureCognitiveSpeechServices {
+			for (i, child) in self.children().iter().enumerate() {
+				let fix = || nearest_voice(self.children(), i).map(Fix::WrapInVoice);
+				cx.check_child(i, !needs_voice(child), Lint::ContentOutsideVoice, fix)?;
+			}
+			let is_background = |el: &&Element| matches!(el, Element::FlavorMSTTS(crate::mstts::Element::BackgroundAudio(_)));
+			for (i, _) in self.children().iter().enumerate().filter(|(_, el)| is_background(el)).skip(1) {
+				cx.check_child(i, false, Lint::DuplicateBackgroundAudio, || Some(Fix::Remove))?;
+			}
+		}
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for Metadata<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		match self.content() {
+			MetadataContent::Properties(properties) => cx.check(properties.iter().all(|(name, _)| is_name(name)), Lint::InvalidMetadataName),
+			MetadataContent::Raw(_) => Ok(())
+		}
+	}
+}
+
+impl Validate for Text<'_> {
+	fn check(&self, _: &mut LintContext<'_>) -> crate::Result<()> {
+		Ok(())
+	}
+}
+
+impl Validate for Meta<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check(false, Lint::RawMeta)
+	}
+}
+
+impl Validate for Audio<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check_with_fix(!self.src().trim().is_empty(), Lint::EmptyAudioSource, || Some(Fix::Unwrap))?;
+		cx.check(self.speed().is_none_or(|speed| speed > 0.), Lint::InvalidAudioSpeed)?;
+		cx.check_all(self.alternate())
+	}
+}
+
+impl Validate for crate::mstts::BackgroundAudio<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check_with_fix(!self.src().trim().is_empty(), Lint::EmptyAudioSource, || Some(Fix::Remove))?;
+		let fade = |fade: Option<&TimeDesignation>| fade.is_none_or(|fade| (0. ..=10_000.).contains(&fade.to_millis()));
+		cx.check(
+			self.volume().is_none_or(|volume| (0. ..=100.).contains(&volume)) && fade(self.fade_in()) && fade(self.fade_out()),
+			Lint::InvalidBackgroundAudio
+		)?;
+		// the path is relative to the element being validated, so a lone background audio element is also accepted
+		let misplaced = cx.config().flavor == Flavor::MicrosoftAzureCognitiveSpeechServices && cx.path.len() > 1;
+		cx.check_with_fix(!misplaced, Lint::MisplacedBackgroundAudio, || Some(Fix::Remove))
+	}
+}
+
+impl Validate for Voice<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		let config = self.config();
+		cx.check_with_fix(
+			config.names.as_ref().is_some_and(|names| names.iter().any(|name| !name.is_empty()))
+				|| config.gender.is_some()
+				|| config.age.is_some()
+				|| config.variant.is_some()
+				|| config.languages.as_ref().is_some_and(|languages| !languages.is_empty()),
+			Lint::UnspecifiedVoice,
+			|| Some(Fix::Unwrap)
+		)?;
+		if is_google(cx) {
+			let names = config.names.as_deref().unwrap_or_default();
+			cx.check(config.age.is_none(), Lint::UnsupportedAttribute)?;
+			cx.check(names.iter().all(|name| voice_locale(name).is_some()), Lint::UnqualifiedVoiceName)?;
+			let by_attributes = names.is_empty() && (config.gender.is_some() || config.variant.is_some());
+			cx.check(!by_attributes || config.languages.as_ref().is_some_and(|l| !l.is_empty()), Lint::AmbiguousVoiceSelection)?;
+		}
+		// also set here for voices validated on their own rather than as an `Element`
+		let current = cx.current.replace(ElementKind::Voice);
+		let result = cx.check_all(self.children());
+		cx.current = current;
+		result
+	}
+}
+
+impl Validate for Break {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		let Some(time) = self.time() else {
+			return Ok(());
+		};
+		cx.check_with_fix(time.to_millis() >= 0., Lint::NegativeBreak, || Some(Fix::Remove))?;
+		let max = &cx.config().max_break;
+		let fix = Fix::SetBreakTime(max.clone());
+		cx.check_with_fix(time.to_millis() <= max.to_millis(), Lint::LongBreak, || Some(fix))?;
+		match max_break_millis(cx.config().flavor) {
+			Some(max) => cx.check_with_fix(time.to_millis() <= max, Lint::UnsupportedBreakTime, || Some(Fix::SetBreakTime(TimeDesignation::from_millis(max)))),
+			None => Ok(())
+		}
+	}
+}
+
+impl Validate for Emphasis<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for Lexicon<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check_with_fix(!self.uri().trim().is_empty(), Lint::EmptyLexiconUri, || Some(Fix::Remove))?;
+		cx.check(!outside_voice(cx), Lint::MisplacedLexicon)
+	}
+}
+
+impl Validate for Mark<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check(!self.name().is_empty(), Lint::EmptyMark)
+	}
+}
+
+impl Validate for SayAs<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check(!self.text().trim().is_empty(), Lint::EmptySayAs)?;
+		let flavor = cx.config().flavor;
+		let supported = match flavor {
+			Flavor::GoogleCloudTextToSpeech => Some(GOOGLE_INTERPRET_AS),
+			Flavor::EspeakNg => Some(ESPEAK_INTERPRET_AS),
+			Flavor::MicrosoftAzureCognitiveSpeechServices => Some(ACSS_INTERPRET_AS),
+			_ => None
+		};
+		if let Some(supported) = supported {
+			cx.check(supported.contains(&self.format().interpret_as(flavor)), Lint::UnsupportedSayAs)?;
+		}
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for Phoneme<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check(!self.ph().trim().is_empty(), Lint::EmptyPhoneme)?;
+		cx.check(!self.text().trim().is_empty(), Lint::EmptyPhonemeText)
+	}
+}
+
+impl Validate for Sub<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check(!self.alias().trim().is_empty(), Lint::EmptySubAlias)?;
+		cx.check(!self.text().trim().is_empty(), Lint::EmptySubText)
+	}
+}
+
+impl Validate for Paragraph<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check(!self.children().iter().any(|el| matches!(el, Element::Paragraph(_))), Lint::NestedParagraph)?;
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for Sentence<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		let nested = |el: &Element| matches!(el, Element::Paragraph(_) | Element::Sentence(_));
+		cx.check(!self.children().iter().any(nested), Lint::NestedSentence)?;
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for Word<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check(!self.children().is_empty(), Lint::EmptyWord)?;
+		cx.check(self.role().is_none_or(|role| !role.is_empty()), Lint::EmptyWordRole)?;
+		let structural = |el: &Element| matches!(el, Element::Paragraph(_) | Element::Sentence(_) | Element::Word(_));
+		cx.check(!self.children().iter().any(structural), Lint::NestedWord)?;
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for Group<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for Variant<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check(self.alternatives().next().is_some(), Lint::EmptyVariant)?;
+		cx.check(self.alternatives().count() == self.children().len(), Lint::UnkeyedVariant)?;
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for Lang<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check_with_fix(!self.language().is_empty(), Lint::EmptyLang, || Some(Fix::Unwrap))?;
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for Prosody<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		let control = self.control();
+		cx.check(!matches!(control.rate, Some(ProsodyRate::Rate(rate)) if rate <= 0.), Lint::InvalidProsodyRate)?;
+		cx.check(control.duration.as_ref().is_none_or(|d| d.to_millis() >= 0.), Lint::NegativeProsodyDuration)?;
+		cx.check(!is_google(cx) || control.duration.is_none(), Lint::UnsupportedAttribute)?;
+		cx.check_all(self.children())
+	}
+}
+
+impl Validate for CustomElement<'_> {
+	fn check(&self, cx: &mut LintContext<'_>) -> crate::Result<()> {
+		cx.check(!self.tag().is_empty(), Lint::EmptyCustomTag)?;
+		cx.check_all(self.children())
+	}
+}
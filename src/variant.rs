@@ -0,0 +1,180 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::Write;
+
+use crate::{Element, Serialize, SerializeOptions, XmlWriter, annotations::Annotations, util};
+
+/// Determines which alternative of a [`Variant`] is serialized. See [`SerializeOptions::variants`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VariantSelection {
+	/// Always select the first alternative.
+	#[default]
+	First,
+	/// Select the alternative with the given key, or the first alternative if no alternative has the key.
+	Key(Cow<'static, str>),
+	/// Pseudo-randomly select an alternative according to the alternatives' weights. The same seed always selects the
+	/// same alternatives for a given document.
+	Seed(u64)
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Alternative<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	key: Cow<'s, str>,
+	weight: u32
+}
+
+/// A container of alternative subtrees, only one of which is serialized, for A/B testing prompts within a single
+/// document.
+///
+/// Each alternative has a key & a weight. Which alternative is serialized is determined by
+/// [`SerializeOptions::variants`].
+///
+/// ```
+/// # use ssml::Serialize;
+/// use ssml::VariantSelection;
+///
+/// # fn main() -> ssml::Result<()> {
+/// let greeting = ssml::variant()
+/// 	.with_alternative("formal", 1, "Good morning.")
+/// 	.with_alternative("casual", 1, "Hey!");
+/// let doc = ssml::speak(None, [greeting]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&ssml::SerializeOptions::default().variants(VariantSelection::Key("casual".into())))?,
+/// 	r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis">Hey!</speak>"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variant<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	alternatives: Vec<Alternative<'s>>,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) children: Vec<Element<'s>>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Variant<'s> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds an alternative to this variant. Use a [`Group`](crate::Group) to hold multiple elements in one alternative.
+	pub fn with_alternative(mut self, key: impl Into<Cow<'s, str>>, weight: u32, element: impl Into<Element<'s>>) -> Self {
+		self.push_alternative(key, weight, element);
+		self
+	}
+
+	/// Adds an alternative to this variant. Use a [`Group`](crate::Group) to hold multiple elements in one alternative.
+	pub fn push_alternative(&mut self, key: impl Into<Cow<'s, str>>, weight: u32, element: impl Into<Element<'s>>) {
+		self.alternatives.push(Alternative { key: key.into(), weight });
+		self.children.push(element.into());
+	}
+
+	/// Returns an iterator over the key, weight & element of each alternative.
+	pub fn alternatives(&self) -> impl Iterator<Item = (&str, u32, &Element<'s>)> {
+		self.alternatives.iter().zip(&self.children).map(|(a, el)| (&*a.key, a.weight, el))
+	}
+
+	/// Returns the alternative elements of this variant, in the order they were added.
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	/// Returns a mutable reference to the alternative elements of this variant.
+	///
+	/// Elements pushed to this list which don't have a corresponding key are never selected.
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
+	/// Returns the index of the alternative which would be selected by `selection`, or `None` if this variant is empty.
+	///
+	/// ```
+	/// use ssml::VariantSelection;
+	///
+	/// let variant = ssml::variant().with_alternative("a", 1, "A").with_alternative("b", 0, "B");
+	/// assert_eq!(variant.select(&VariantSelection::Key("b".into())), Some(1));
+	/// assert_eq!(variant.select(&VariantSelection::Key("c".into())), Some(0));
+	/// // `b` has no weight, so it is never selected randomly
+	/// assert!((0..32).all(|seed| variant.select(&VariantSelection::Seed(seed)) == Some(0)));
+	/// ```
+	pub fn select(&self, selection: &VariantSelection) -> Option<usize> {
+		let len = self.alternatives.len().min(self.children.len());
+		if len == 0 {
+			return None;
+		}
+		let alternatives = &self.alternatives[..len];
+		match selection {
+			VariantSelection::First => Some(0),
+			VariantSelection::Key(key) => Some(alternatives.iter().position(|a| a.key == *key).unwrap_or(0)),
+			VariantSelection::Seed(seed) => {
+				let total: u64 = alternatives.iter().map(|a| a.weight as u64).sum();
+				if total == 0 {
+					return Some(0);
+				}
+				// mix in the keys so that separate experiments in one document are selected independently
+				let hash = alternatives
+					.iter()
+					.flat_map(|a| a.key.bytes().chain([0]))
+					.fold(0xcbf29ce484222325u64, |hash, b| (hash ^ b as u64).wrapping_mul(0x100000001b3));
+				let mut point = splitmix64(seed ^ hash) % total;
+				for (i, alternative) in alternatives.iter().enumerate() {
+					if point < alternative.weight as u64 {
+						return Some(i);
+					}
+					point -= alternative.weight as u64;
+				}
+				Some(len - 1)
+			}
+		}
+	}
+
+	pub fn to_owned(&self) -> Variant<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Variant<'static> {
+		Variant {
+			alternatives: self
+				.alternatives
+				.into_iter()
+				.map(|a| Alternative {
+					key: match a.key {
+						Cow::Borrowed(b) => Cow::Owned(b.to_string()),
+						Cow::Owned(b) => Cow::Owned(b)
+					},
+					weight: a.weight
+				})
+				.collect(),
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			annotations: self.annotations.into_owned()
+		}
+	}
+}
+
+pub(crate) fn splitmix64(x: u64) -> u64 {
+	let mut z = x.wrapping_add(0x9e3779b97f4a7c15);
+	z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+	z ^ (z >> 31)
+}
+
+impl Serialize for Variant<'_> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		if let Some(i) = self.select(&options.variants) {
+			util::serialize_child(writer, &self.children[i], i, options)?;
+		}
+		Ok(())
+	}
+}
+
+/// Creates an empty [`Variant`]. Add alternatives with [`Variant::with_alternative`].
+pub fn variant<'s>() -> Variant<'s> {
+	Variant::new()
+}
@@ -32,7 +32,10 @@
 //! # }
 //! ```
 
-use crate::{Audio, Break, CustomElement, Element, Emphasis, Lang, Mark, Meta, Prosody, SayAs, Speak, Text, Voice, mstts};
+use crate::{
+	Audio, Break, CustomElement, Element, Emphasis, Lang, Lexicon, Mark, Meta, Paragraph, Phoneme, Prosody, SayAs, Sentence, Speak, Sub, Text, Variant, Voice,
+	Word, mstts, polly, watson
+};
 
 pub trait Visit<'s> {
 	fn visit_speak(&mut self, node: &'s Speak) {
@@ -67,10 +70,34 @@ pub trait Visit<'s> {
 		self::visit_mark(self, node)
 	}
 
+	fn visit_lexicon(&mut self, node: &'s Lexicon) {
+		self::visit_lexicon(self, node)
+	}
+
 	fn visit_say_as(&mut self, node: &'s SayAs) {
 		self::visit_say_as(self, node)
 	}
 
+	fn visit_phoneme(&mut self, node: &'s Phoneme) {
+		self::visit_phoneme(self, node)
+	}
+
+	fn visit_sub(&mut self, node: &'s Sub) {
+		self::visit_sub(self, node)
+	}
+
+	fn visit_paragraph(&mut self, node: &'s Paragraph) {
+		self::visit_paragraph(self, node)
+	}
+
+	fn visit_sentence(&mut self, node: &'s Sentence) {
+		self::visit_sentence(self, node)
+	}
+
+	fn visit_word(&mut self, node: &'s Word) {
+		self::visit_word(self, node)
+	}
+
 	fn visit_lang(&mut self, node: &'s Lang) {
 		self::visit_lang(self, node)
 	}
@@ -83,6 +110,10 @@ pub trait Visit<'s> {
 		self::visit_custom(self, node)
 	}
 
+	fn visit_variant(&mut self, node: &'s Variant) {
+		self::visit_variant(self, node)
+	}
+
 	fn visit_mstts_element(&mut self, node: &'s mstts::Element) {
 		self::visit_mstts_element(self, node)
 	}
@@ -91,6 +122,54 @@ pub trait Visit<'s> {
 		self::visit_mstts_express(self, node)
 	}
 
+	fn visit_mstts_silence(&mut self, node: &'s mstts::Silence) {
+		self::visit_mstts_silence(self, node)
+	}
+
+	fn visit_mstts_background_audio(&mut self, node: &'s mstts::BackgroundAudio) {
+		self::visit_mstts_background_audio(self, node)
+	}
+
+	fn visit_mstts_viseme(&mut self, node: &'s mstts::Viseme) {
+		self::visit_mstts_viseme(self, node)
+	}
+
+	fn visit_mstts_tts_embedding(&mut self, node: &'s mstts::TtsEmbedding) {
+		self::visit_mstts_tts_embedding(self, node)
+	}
+
+	fn visit_mstts_audio_duration(&mut self, node: &'s mstts::AudioDuration) {
+		self::visit_mstts_audio_duration(self, node)
+	}
+
+	fn visit_mstts_math(&mut self, node: &'s mstts::Math) {
+		self::visit_mstts_math(self, node)
+	}
+
+	fn visit_polly_element(&mut self, node: &'s polly::Element) {
+		self::visit_polly_element(self, node)
+	}
+
+	fn visit_polly_domain(&mut self, node: &'s polly::Domain) {
+		self::visit_polly_domain(self, node)
+	}
+
+	fn visit_polly_effect(&mut self, node: &'s polly::Effect) {
+		self::visit_polly_effect(self, node)
+	}
+
+	fn visit_watson_element(&mut self, node: &'s watson::Element) {
+		self::visit_watson_element(self, node)
+	}
+
+	fn visit_watson_express_as(&mut self, node: &'s watson::ExpressAs) {
+		self::visit_watson_express_as(self, node)
+	}
+
+	fn visit_watson_voice_transformation(&mut self, node: &'s watson::VoiceTransformation) {
+		self::visit_watson_voice_transformation(self, node)
+	}
+
 	fn visit_element(&mut self, node: &'s Element) {
 		self::visit_element(self, node)
 	}
@@ -122,7 +201,35 @@ pub fn visit_emphasis<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s Emphasis)
 
 pub fn visit_mark<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s Mark) {}
 
-pub fn visit_say_as<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s SayAs) {}
+pub fn visit_lexicon<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s Lexicon) {}
+
+pub fn visit_say_as<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s SayAs) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
+
+pub fn visit_phoneme<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s Phoneme) {}
+
+pub fn visit_sub<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s Sub) {}
+
+pub fn visit_paragraph<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s Paragraph) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
+
+pub fn visit_sentence<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s Sentence) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
+
+pub fn visit_word<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s Word) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
 
 pub fn visit_lang<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s Lang) {
 	for node in node.children() {
@@ -138,9 +245,22 @@ pub fn visit_prosody<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s Prosody) {
 
 pub fn visit_custom<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s CustomElement) {}
 
+/// Visits every alternative of a [`Variant`], not just the one that would be serialized.
+pub fn visit_variant<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s Variant) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
+
 pub fn visit_mstts_element<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s mstts::Element) {
 	match node {
-		mstts::Element::Express(node) => visit_mstts_express(v, node)
+		mstts::Element::Express(node) => v.visit_mstts_express(node),
+		mstts::Element::Silence(node) => v.visit_mstts_silence(node),
+		mstts::Element::BackgroundAudio(node) => v.visit_mstts_background_audio(node),
+		mstts::Element::Viseme(node) => v.visit_mstts_viseme(node),
+		mstts::Element::TtsEmbedding(node) => v.visit_mstts_tts_embedding(node),
+		mstts::Element::AudioDuration(node) => v.visit_mstts_audio_duration(node),
+		mstts::Element::Math(node) => v.visit_mstts_math(node)
 	}
 }
 
@@ -150,23 +270,86 @@ pub fn visit_mstts_express<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s mstts
 	}
 }
 
+pub fn visit_mstts_silence<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s mstts::Silence) {}
+
+pub fn visit_mstts_background_audio<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s mstts::BackgroundAudio) {}
+
+pub fn visit_mstts_viseme<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s mstts::Viseme) {}
+
+pub fn visit_mstts_tts_embedding<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s mstts::TtsEmbedding) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
+
+pub fn visit_mstts_audio_duration<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s mstts::AudioDuration) {}
+
+pub fn visit_mstts_math<'s, V: Visit<'s> + ?Sized>(_v: &mut V, _node: &'s mstts::Math) {}
+
+pub fn visit_polly_element<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s polly::Element) {
+	match node {
+		polly::Element::Domain(node) => v.visit_polly_domain(node),
+		polly::Element::Effect(node) => v.visit_polly_effect(node)
+	}
+}
+
+pub fn visit_polly_domain<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s polly::Domain) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
+
+pub fn visit_polly_effect<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s polly::Effect) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
+
+pub fn visit_watson_element<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s watson::Element) {
+	match node {
+		watson::Element::ExpressAs(node) => v.visit_watson_express_as(node),
+		watson::Element::VoiceTransformation(node) => v.visit_watson_voice_transformation(node)
+	}
+}
+
+pub fn visit_watson_express_as<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s watson::ExpressAs) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
+
+pub fn visit_watson_voice_transformation<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s watson::VoiceTransformation) {
+	for node in node.children() {
+		v.visit_element(node);
+	}
+}
+
 pub fn visit_element<'s, V: Visit<'s> + ?Sized>(v: &mut V, node: &'s Element) {
 	match node {
-		Element::Audio(node) => visit_audio(v, node),
-		Element::Meta(node) => visit_meta(v, node),
-		Element::Text(node) => visit_text(v, node),
-		Element::Voice(node) => visit_voice(v, node),
-		Element::Break(node) => visit_break(v, node),
-		Element::Emphasis(node) => visit_emphasis(v, node),
-		Element::Mark(node) => visit_mark(v, node),
-		Element::SayAs(node) => visit_say_as(v, node),
-		Element::Lang(node) => visit_lang(v, node),
-		Element::Prosody(node) => visit_prosody(v, node),
-		Element::FlavorMSTTS(node) => visit_mstts_element(v, node),
-		Element::Custom(node) => visit_custom(v, node),
+		Element::Audio(node) => v.visit_audio(node),
+		Element::Meta(node) => v.visit_meta(node),
+		Element::Text(node) => v.visit_text(node),
+		Element::Voice(node) => v.visit_voice(node),
+		Element::Break(node) => v.visit_break(node),
+		Element::Emphasis(node) => v.visit_emphasis(node),
+		Element::Mark(node) => v.visit_mark(node),
+		Element::Lexicon(node) => v.visit_lexicon(node),
+		Element::SayAs(node) => v.visit_say_as(node),
+		Element::Phoneme(node) => v.visit_phoneme(node),
+		Element::Sub(node) => v.visit_sub(node),
+		Element::Paragraph(node) => v.visit_paragraph(node),
+		Element::Sentence(node) => v.visit_sentence(node),
+		Element::Word(node) => v.visit_word(node),
+		Element::Lang(node) => v.visit_lang(node),
+		Element::Prosody(node) => v.visit_prosody(node),
+		Element::FlavorMSTTS(node) => v.visit_mstts_element(node),
+		Element::FlavorPolly(node) => v.visit_polly_element(node),
+		Element::FlavorWatson(node) => v.visit_watson_element(node),
+		Element::Custom(node) => v.visit_custom(node),
+		Element::Variant(node) => v.visit_variant(node),
 		Element::Group(node) => {
 			for child in node.children() {
-				visit_element(v, child);
+				v.visit_element(child);
 			}
 		}
 	}
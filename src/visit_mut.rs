@@ -1,4 +1,7 @@
-use crate::{Audio, Break, CustomElement, Element, Emphasis, Lang, Mark, Meta, Prosody, SayAs, Speak, Text, Voice, mstts};
+use crate::{
+	Audio, Break, CustomElement, Element, Emphasis, Lang, Lexicon, Mark, Meta, Paragraph, Phoneme, Prosody, SayAs, Sentence, Speak, Sub, Text, Variant, Voice,
+	Word, mstts, polly, watson
+};
 
 pub trait VisitMut<'s> {
 	fn visit_speak_mut(&mut self, node: &'s mut Speak) {
@@ -33,10 +36,34 @@ pub trait VisitMut<'s> {
 		self::visit_mark_mut(self, node)
 	}
 
+	fn visit_lexicon_mut(&mut self, node: &'s mut Lexicon) {
+		self::visit_lexicon_mut(self, node)
+	}
+
 	fn visit_say_as_mut(&mut self, node: &'s mut SayAs) {
 		self::visit_say_as_mut(self, node)
 	}
 
+	fn visit_phoneme_mut(&mut self, node: &'s mut Phoneme) {
+		self::visit_phoneme_mut(self, node)
+	}
+
+	fn visit_sub_mut(&mut self, node: &'s mut Sub) {
+		self::visit_sub_mut(self, node)
+	}
+
+	fn visit_paragraph_mut(&mut self, node: &'s mut Paragraph) {
+		self::visit_paragraph_mut(self, node)
+	}
+
+	fn visit_sentence_mut(&mut self, node: &'s mut Sentence) {
+		self::visit_sentence_mut(self, node)
+	}
+
+	fn visit_word_mut(&mut self, node: &'s mut Word) {
+		self::visit_word_mut(self, node)
+	}
+
 	fn visit_lang_mut(&mut self, node: &'s mut Lang) {
 		self::visit_lang_mut(self, node)
 	}
@@ -49,6 +76,10 @@ pub trait VisitMut<'s> {
 		self::visit_custom_mut(self, node)
 	}
 
+	fn visit_variant_mut(&mut self, node: &'s mut Variant) {
+		self::visit_variant_mut(self, node)
+	}
+
 	fn visit_mstts_element_mut(&mut self, node: &'s mut mstts::Element) {
 		self::visit_mstts_element_mut(self, node)
 	}
@@ -57,6 +88,54 @@ pub trait VisitMut<'s> {
 		self::visit_mstts_express_mut(self, node)
 	}
 
+	fn visit_mstts_silence_mut(&mut self, node: &'s mut mstts::Silence) {
+		self::visit_mstts_silence_mut(self, node)
+	}
+
+	fn visit_mstts_background_audio_mut(&mut self, node: &'s mut mstts::BackgroundAudio) {
+		self::visit_mstts_background_audio_mut(self, node)
+	}
+
+	fn visit_mstts_viseme_mut(&mut self, node: &'s mut mstts::Viseme) {
+		self::visit_mstts_viseme_mut(self, node)
+	}
+
+	fn visit_mstts_tts_embedding_mut(&mut self, node: &'s mut mstts::TtsEmbedding) {
+		self::visit_mstts_tts_embedding_mut(self, node)
+	}
+
+	fn visit_mstts_audio_duration_mut(&mut self, node: &'s mut mstts::AudioDuration) {
+		self::visit_mstts_audio_duration_mut(self, node)
+	}
+
+	fn visit_mstts_math_mut(&mut self, node: &'s mut mstts::Math) {
+		self::visit_mstts_math_mut(self, node)
+	}
+
+	fn visit_polly_element_mut(&mut self, node: &'s mut polly::Element) {
+		self::visit_polly_element_mut(self, node)
+	}
+
+	fn visit_polly_domain_mut(&mut self, node: &'s mut polly::Domain) {
+		self::visit_polly_domain_mut(self, node)
+	}
+
+	fn visit_polly_effect_mut(&mut self, node: &'s mut polly::Effect) {
+		self::visit_polly_effect_mut(self, node)
+	}
+
+	fn visit_watson_element_mut(&mut self, node: &'s mut watson::Element) {
+		self::visit_watson_element_mut(self, node)
+	}
+
+	fn visit_watson_express_as_mut(&mut self, node: &'s mut watson::ExpressAs) {
+		self::visit_watson_express_as_mut(self, node)
+	}
+
+	fn visit_watson_voice_transformation_mut(&mut self, node: &'s mut watson::VoiceTransformation) {
+		self::visit_watson_voice_transformation_mut(self, node)
+	}
+
 	fn visit_element_mut(&mut self, node: &'s mut Element) {
 		self::visit_element_mut(self, node)
 	}
@@ -88,7 +167,35 @@ pub fn visit_emphasis_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut
 
 pub fn visit_mark_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut Mark) {}
 
-pub fn visit_say_as_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut SayAs) {}
+pub fn visit_lexicon_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut Lexicon) {}
+
+pub fn visit_say_as_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut SayAs) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
+
+pub fn visit_phoneme_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut Phoneme) {}
+
+pub fn visit_sub_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut Sub) {}
+
+pub fn visit_paragraph_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut Paragraph) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
+
+pub fn visit_sentence_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut Sentence) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
+
+pub fn visit_word_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut Word) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
 
 pub fn visit_lang_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut Lang) {
 	for node in node.children_mut() {
@@ -104,9 +211,22 @@ pub fn visit_prosody_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut
 
 pub fn visit_custom_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut CustomElement) {}
 
+/// Visits every alternative of a [`Variant`], not just the one that would be serialized.
+pub fn visit_variant_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut Variant) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
+
 pub fn visit_mstts_element_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut mstts::Element) {
 	match node {
-		mstts::Element::Express(node) => visit_mstts_express_mut(v, node)
+		mstts::Element::Express(node) => v.visit_mstts_express_mut(node),
+		mstts::Element::Silence(node) => v.visit_mstts_silence_mut(node),
+		mstts::Element::BackgroundAudio(node) => v.visit_mstts_background_audio_mut(node),
+		mstts::Element::Viseme(node) => v.visit_mstts_viseme_mut(node),
+		mstts::Element::TtsEmbedding(node) => v.visit_mstts_tts_embedding_mut(node),
+		mstts::Element::AudioDuration(node) => v.visit_mstts_audio_duration_mut(node),
+		mstts::Element::Math(node) => v.visit_mstts_math_mut(node)
 	}
 }
 
@@ -116,23 +236,86 @@ pub fn visit_mstts_express_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'
 	}
 }
 
+pub fn visit_mstts_silence_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut mstts::Silence) {}
+
+pub fn visit_mstts_background_audio_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut mstts::BackgroundAudio) {}
+
+pub fn visit_mstts_viseme_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut mstts::Viseme) {}
+
+pub fn visit_mstts_tts_embedding_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut mstts::TtsEmbedding) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
+
+pub fn visit_mstts_audio_duration_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut mstts::AudioDuration) {}
+
+pub fn visit_mstts_math_mut<'s, V: VisitMut<'s> + ?Sized>(_v: &mut V, _node: &'s mut mstts::Math) {}
+
+pub fn visit_polly_element_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut polly::Element) {
+	match node {
+		polly::Element::Domain(node) => v.visit_polly_domain_mut(node),
+		polly::Element::Effect(node) => v.visit_polly_effect_mut(node)
+	}
+}
+
+pub fn visit_polly_domain_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut polly::Domain) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
+
+pub fn visit_polly_effect_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut polly::Effect) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
+
+pub fn visit_watson_element_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut watson::Element) {
+	match node {
+		watson::Element::ExpressAs(node) => v.visit_watson_express_as_mut(node),
+		watson::Element::VoiceTransformation(node) => v.visit_watson_voice_transformation_mut(node)
+	}
+}
+
+pub fn visit_watson_express_as_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut watson::ExpressAs) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
+
+pub fn visit_watson_voice_transformation_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut watson::VoiceTransformation) {
+	for node in node.children_mut() {
+		v.visit_element_mut(node);
+	}
+}
+
 pub fn visit_element_mut<'s, V: VisitMut<'s> + ?Sized>(v: &mut V, node: &'s mut Element) {
 	match node {
-		Element::Audio(node) => visit_audio_mut(v, node),
-		Element::Meta(node) => visit_meta_mut(v, node),
-		Element::Text(node) => visit_text_mut(v, node),
-		Element::Voice(node) => visit_voice_mut(v, node),
-		Element::Break(node) => visit_break_mut(v, node),
-		Element::Emphasis(node) => visit_emphasis_mut(v, node),
-		Element::Mark(node) => visit_mark_mut(v, node),
-		Element::SayAs(node) => visit_say_as_mut(v, node),
-		Element::Lang(node) => visit_lang_mut(v, node),
-		Element::Prosody(node) => visit_prosody_mut(v, node),
-		Element::FlavorMSTTS(node) => visit_mstts_element_mut(v, node),
-		Element::Custom(node) => visit_custom_mut(v, node),
+		Element::Audio(node) => v.visit_audio_mut(node),
+		Element::Meta(node) => v.visit_meta_mut(node),
+		Element::Text(node) => v.visit_text_mut(node),
+		Element::Voice(node) => v.visit_voice_mut(node),
+		Element::Break(node) => v.visit_break_mut(node),
+		Element::Emphasis(node) => v.visit_emphasis_mut(node),
+		Element::Mark(node) => v.visit_mark_mut(node),
+		Element::Lexicon(node) => v.visit_lexicon_mut(node),
+		Element::SayAs(node) => v.visit_say_as_mut(node),
+		Element::Phoneme(node) => v.visit_phoneme_mut(node),
+		Element::Sub(node) => v.visit_sub_mut(node),
+		Element::Paragraph(node) => v.visit_paragraph_mut(node),
+		Element::Sentence(node) => v.visit_sentence_mut(node),
+		Element::Word(node) => v.visit_word_mut(node),
+		Element::Lang(node) => v.visit_lang_mut(node),
+		Element::Prosody(node) => v.visit_prosody_mut(node),
+		Element::FlavorMSTTS(node) => v.visit_mstts_element_mut(node),
+		Element::FlavorPolly(node) => v.visit_polly_element_mut(node),
+		Element::FlavorWatson(node) => v.visit_watson_element_mut(node),
+		Element::Custom(node) => v.visit_custom_mut(node),
+		Element::Variant(node) => v.visit_variant_mut(node),
 		Element::Group(node) => {
 			for child in node.children_mut() {
-				visit_element_mut(v, child);
+				v.visit_element_mut(child);
 			}
 		}
 	}
@@ -1,10 +1,12 @@
 use alloc::{borrow::Cow, string::ToString, vec, vec::Vec};
 use core::{
 	fmt::{self, Display, Write},
-	ops::{Add, AddAssign}
+	num::{NonZeroU32, ParseIntError},
+	ops::{Add, AddAssign},
+	str::FromStr
 };
 
-use crate::{Element, Serialize, SerializeOptions, XmlWriter, util, xml::TrustedNoEscape};
+use crate::{Element, Serialize, SerializeOptions, XmlWriter, annotations::Annotations, util, xml::TrustedNoEscape};
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -28,14 +30,189 @@ impl Display for VoiceGender {
 }
 impl TrustedNoEscape for VoiceGender {}
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoiceConfigError {
+	AgeOutOfRange,
+	ZeroVariant,
+	ParseInt(ParseIntError)
+}
+
+impl Display for VoiceConfigError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			VoiceConfigError::AgeOutOfRange => write!(f, "voice age must be at most {} years", VoiceAge::MAX),
+			VoiceConfigError::ZeroVariant => f.write_str("voice variant must be a positive integer"),
+			VoiceConfigError::ParseInt(e) => f.write_fmt(format_args!("couldn't parse integer: {e}"))
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VoiceConfigError {}
+
+/// The preferred age of a voice in years, from `0` to [`VoiceAge::MAX`].
+///
+/// ```
+/// # use ssml::VoiceAge;
+/// # fn main() -> ssml::Result<()> {
+/// assert_eq!(VoiceAge::try_from(42u32)?.get(), 42);
+/// assert_eq!("7".parse::<VoiceAge>()?.to_string(), "7");
+///
+/// // Fails
+/// assert!(VoiceAge::try_from(255u8).is_err());
+/// assert!("-1".parse::<VoiceAge>().is_err());
+/// assert!("forty".parse::<VoiceAge>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u8", into = "u8"))]
+pub struct VoiceAge(u8);
+
+impl VoiceAge {
+	/// The oldest age accepted, in years.
+	pub const MAX: u8 = 150;
+
+	pub fn get(&self) -> u8 {
+		self.0
+	}
+}
+
+impl TryFrom<u8> for VoiceAge {
+	type Error = VoiceConfigError;
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		if value > VoiceAge::MAX {
+			return Err(VoiceConfigError::AgeOutOfRange);
+		}
+		Ok(Self(value))
+	}
+}
+
+impl TryFrom<u32> for VoiceAge {
+	type Error = VoiceConfigError;
+
+	fn try_from(value: u32) -> Result<Self, Self::Error> {
+		u8::try_from(value).map_err(|_| VoiceConfigError::AgeOutOfRange)?.try_into()
+	}
+}
+
+impl From<VoiceAge> for u8 {
+	fn from(value: VoiceAge) -> Self {
+		value.0
+	}
+}
+
+impl FromStr for VoiceAge {
+	type Err = VoiceConfigError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.parse::<u32>().map_err(VoiceConfigError::ParseInt)?.try_into()
+	}
+}
+
+impl Display for VoiceAge {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+impl TrustedNoEscape for VoiceAge {}
+
+/// Selects between multiple voices matching the other attributes of a [`VoiceConfig`], starting from `1`.
+///
+/// ```
+/// # use ssml::VoiceVariant;
+/// # fn main() -> ssml::Result<()> {
+/// assert_eq!(VoiceVariant::try_from(2)?.get(), 2);
+/// assert_eq!("1".parse::<VoiceVariant>()?.to_string(), "1");
+///
+/// // Fails
+/// assert!(VoiceVariant::try_from(0).is_err());
+/// assert!("second".parse::<VoiceVariant>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct VoiceVariant(NonZeroU32);
+
+impl VoiceVariant {
+	pub const fn new(variant: NonZeroU32) -> Self {
+		Self(variant)
+	}
+
+	pub fn get(&self) -> u32 {
+		self.0.get()
+	}
+}
+
+impl From<NonZeroU32> for VoiceVariant {
+	fn from(value: NonZeroU32) -> Self {
+		Self(value)
+	}
+}
+
+impl TryFrom<u32> for VoiceVariant {
+	type Error = VoiceConfigError;
+
+	fn try_from(value: u32) -> Result<Self, Self::Error> {
+		NonZeroU32::new(value).map(Self).ok_or(VoiceConfigError::ZeroVariant)
+	}
+}
+
+impl FromStr for VoiceVariant {
+	type Err = VoiceConfigError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.parse::<u32>().map_err(VoiceConfigError::ParseInt)?.try_into()
+	}
+}
+
+impl Display for VoiceVariant {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+impl TrustedNoEscape for VoiceVariant {}
+
 /// Configuration for the [`Voice`] element.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions, VoiceConfig, VoiceGender};
+/// # fn main() -> ssml::Result<()> {
+/// let config = VoiceConfig {
+/// 	gender: Some(VoiceGender::Female),
+/// 	age: Some(30u8.try_into()?),
+/// 	variant: Some(2.try_into()?),
+/// 	..Default::default()
+/// };
+/// let doc = ssml::speak(None, [ssml::voice(config, ["Hello!"])]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::GoogleCloudTextToSpeech))?,
+/// 	r#"<speak><voice gender="female" age="30" variant="2">Hello!</voice></speak>"#
+/// );
+///
+/// // parsed attributes are validated too
+/// let doc = ssml::parse::parse_str(r#"<speak><voice age="41" variant="3">Hi!</voice></speak>"#)?;
+/// let ssml::Element::Voice(voice) = &doc.children()[0] else {
+/// 	unreachable!()
+/// };
+/// assert_eq!(voice.config().age.map(|age| age.get()), Some(41));
+/// assert_eq!(voice.config().variant.map(|variant| variant.get()), Some(3));
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Default, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VoiceConfig<'s> {
 	pub gender: Option<VoiceGender>,
-	pub age: Option<u8>,
+	pub age: Option<VoiceAge>,
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option_vec"))]
 	pub names: Option<Vec<Cow<'s, str>>>,
-	pub variant: Option<Cow<'s, str>>,
+	pub variant: Option<VoiceVariant>,
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option_vec"))]
 	pub languages: Option<Vec<Cow<'s, str>>>
 }
 
@@ -59,7 +236,7 @@ impl<'s> VoiceConfig<'s> {
 	pub fn into_owned(self) -> VoiceConfig<'static> {
 		VoiceConfig {
 			gender: self.gender.clone(),
-			age: self.age.clone(),
+			age: self.age,
 			names: self.names.map(|n| {
 				n.into_iter()
 					.map(|s| match s {
@@ -68,11 +245,7 @@ impl<'s> VoiceConfig<'s> {
 					})
 					.collect()
 			}),
-			variant: match self.variant {
-				Some(Cow::Borrowed(b)) => Some(Cow::Owned(b.to_string())),
-				Some(Cow::Owned(b)) => Some(Cow::Owned(b)),
-				None => None
-			},
+			variant: self.variant,
 			languages: self.languages.map(|n| {
 				n.into_iter()
 					.map(|s| match s {
@@ -92,11 +265,14 @@ impl<'s, S: Into<Cow<'s, str>>> From<S> for VoiceConfig<'s> {
 }
 
 impl<'s> Serialize for VoiceConfig<'s> {
-	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, _: &SerializeOptions) -> crate::Result<()> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		if self.languages.is_some() {
+			util::require_v1_1(options, "language")?;
+		}
 		writer.attr_opt("gender", self.gender.as_ref())?;
 		writer.attr_opt("age", self.age.as_ref())?;
 		writer.attr_opt("name", self.names.as_ref().map(|c| c.join(" ")))?;
-		writer.attr_opt("variant", self.variant.as_deref())?;
+		writer.attr_opt("variant", self.variant.as_ref())?;
 		writer.attr_opt("language", self.languages.as_ref().map(|c| c.join(" ")))
 	}
 }
@@ -105,9 +281,14 @@ impl<'s> Serialize for VoiceConfig<'s> {
 #[derive(Clone, Default, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Voice<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow))]
 	pub(crate) children: Vec<Element<'s>>,
+	#[cfg_attr(feature = "serde", serde(borrow, deserialize_with = "crate::serde_borrow::pairs"))]
 	pub(crate) attrs: Vec<(Cow<'s, str>, Cow<'s, str>)>,
-	config: VoiceConfig<'s>
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	config: VoiceConfig<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
 }
 
 impl<'s> Voice<'s> {
@@ -135,15 +316,22 @@ impl<'s> Voice<'s> {
 		Self {
 			children: elements.into_iter().map(|f| f.into()).collect(),
 			attrs: vec![],
-			config: config.into()
+			config: config.into(),
+			annotations: Annotations::default()
 		}
 	}
 
 	/// Modifies the voice configuration of this `voice` section.
 	///
 	/// ```
+	/// # fn main() -> ssml::Result<()> {
 	/// let mut voice = ssml::Voice::default();
-	/// voice = voice.with_config(ssml::VoiceConfig { age: Some(42), ..Default::default() });
+	/// voice = voice.with_config(ssml::VoiceConfig {
+	/// 	age: Some(42u8.try_into()?),
+	/// 	..Default::default()
+	/// });
+	/// # Ok(())
+	/// # }
 	/// ```
 	pub fn with_config(mut self, config: impl Into<VoiceConfig<'s>>) -> Self {
 		self.config = config.into();
@@ -151,10 +339,14 @@ impl<'s> Voice<'s> {
 	}
 
 	/// Returns the voice configuration used by this element.
-	pub fn config(&self) -> &VoiceConfig {
+	pub fn config(&self) -> &VoiceConfig<'s> {
 		&self.config
 	}
 
+	pub fn config_mut(&mut self) -> &mut VoiceConfig<'s> {
+		&mut self.config
+	}
+
 	pub fn set_config(&mut self, config: impl Into<VoiceConfig<'s>>) {
 		self.config = config.into();
 	}
@@ -174,8 +366,7 @@ impl<'s> Voice<'s> {
 	/// 	)?,
 	/// 	r#"<speak>
 	/// 	<voice name="en-US-Neural2-F">
-	/// 		Hello, world!
-	/// 		This is an SSML document.
+	/// 		Hello, world! This is an SSML document.
 	/// 	</voice>
 	/// </speak>"#
 	/// );
@@ -201,8 +392,7 @@ impl<'s> Voice<'s> {
 	/// 	)?,
 	/// 	r#"<speak>
 	/// 	<voice name="en-US-Neural2-F">
-	/// 		Hello, world!
-	/// 		This is an SSML document.
+	/// 		Hello, world! This is an SSML document.
 	/// 	</voice>
 	/// </speak>"#
 	/// );
@@ -246,7 +436,8 @@ impl<'s> Voice<'s> {
 					)
 				})
 				.collect(),
-			config: self.config.into_owned()
+			config: self.config.into_owned(),
+			annotations: self.annotations.into_owned()
 		}
 	}
 }
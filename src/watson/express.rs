@@ -0,0 +1,167 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::{self, Display, Write};
+
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// A speaking style for an [`ExpressAs`] section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExpressionType {
+	/// Conveys positive news, in a cheerful & upbeat manner.
+	GoodNews,
+	/// Conveys an apology or regret.
+	Apology,
+	/// Conveys doubt or uncertainty.
+	Uncertainty,
+	/// An expression not (yet) covered by this enum, written as-is to the `type` attribute.
+	Custom(Cow<'static, str>)
+}
+
+impl ExpressionType {
+	pub fn as_str(&self) -> &str {
+		match self {
+			ExpressionType::GoodNews => "GoodNews",
+			ExpressionType::Apology => "Apology",
+			ExpressionType::Uncertainty => "Uncertainty",
+			ExpressionType::Custom(name) => name
+		}
+	}
+}
+
+impl From<&str> for ExpressionType {
+	fn from(value: &str) -> Self {
+		match value {
+			"GoodNews" => ExpressionType::GoodNews,
+			"Apology" => ExpressionType::Apology,
+			"Uncertainty" => ExpressionType::Uncertainty,
+			name => ExpressionType::Custom(Cow::Owned(name.to_string()))
+		}
+	}
+}
+
+impl Display for ExpressionType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// Reads a section of a document with an expression, e.g. apologetically, with IBM Watson Text to Speech.
+///
+/// Expressions are only supported by some voices; see [the IBM Watson docs][watson] for more information.
+///
+/// [watson]: https://cloud.ibm.com/docs/text-to-speech?topic=text-to-speech-elements#express-as-element
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::watson::{self, ExpressionType};
+///
+/// let doc = ssml::speak(None, [watson::express_as(ExpressionType::Apology, ["Sorry, that seat is taken."])]);
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::IbmWatson))?,
+/// 	r#"<speak><express-as type="Apology">Sorry, that seat is taken.</express-as></speak>"#
+/// );
+///
+/// // other flavors don't support `express-as`
+/// assert!(doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::AmazonPolly)).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExpressAs<'s> {
+	expression: ExpressionType,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> ExpressAs<'s> {
+	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(expression: impl Into<ExpressionType>, elements: I) -> Self {
+		Self {
+			expression: expression.into(),
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn expression(&self) -> &ExpressionType {
+		&self.expression
+	}
+
+	pub fn set_expression(&mut self, expression: impl Into<ExpressionType>) {
+		self.expression = expression.into();
+	}
+
+	/// Extend this expression section with an additional element.
+	pub fn push(&mut self, element: impl Into<Element<'s>>) {
+		self.children.push(element.into());
+	}
+
+	/// Extend this expression section with additional elements.
+	pub fn extend<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&mut self, elements: I) {
+		self.children.extend(elements.into_iter().map(|f| f.into()));
+	}
+
+	/// Returns a reference to the elements contained within this expression section.
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	/// Returns a mutable reference to the elements contained within this expression section.
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
+	pub fn to_owned(&self) -> ExpressAs<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> ExpressAs<'static> {
+		ExpressAs {
+			expression: self.expression,
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`].
+	pub fn into_el(self) -> Element<'s> {
+		Element::FlavorWatson(super::Element::ExpressAs(self))
+	}
+}
+
+impl<'s> From<ExpressAs<'s>> for crate::Element<'s> {
+	fn from(value: ExpressAs<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for ExpressAs<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.element("express-as", |writer| {
+			writer.attr("type", self.expression.as_str())?;
+			attrs::write(writer, &self.extra_attrs)?;
+			util::serialize_elements(writer, &self.children, options)
+		})
+	}
+}
+
+/// Creates a new [`ExpressAs`] section, read with the given expression.
+pub fn express_as<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(expression: impl Into<ExpressionType>, elements: I) -> ExpressAs<'s> {
+	ExpressAs::new(expression, elements)
+}
@@ -0,0 +1,66 @@
+//! Elements exclusive to [`Flavor::IbmWatson`](crate::Flavor::IbmWatson).
+//!
+//! IBM Watson Text to Speech writes its extensions without a namespace prefix, so parsing recognizes `<express-as>` &
+//! `<voice-transformation>` regardless of flavor.
+//!
+//! ```
+//! # use ssml::{Flavor, Serialize, SerializeOptions};
+//! # fn main() -> ssml::Result<()> {
+//! let doc = ssml::parse::parse_str(r#"<speak><express-as type="GoodNews">You won!</express-as></speak>"#)?;
+//! assert!(matches!(doc.children()[0], ssml::Element::FlavorWatson(ssml::watson::Element::ExpressAs(_))));
+//! assert_eq!(
+//! 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::IbmWatson))?,
+//! 	r#"<speak><express-as type="GoodNews">You won!</express-as></speak>"#
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+
+pub mod express;
+pub mod transformation;
+pub use self::{
+	express::{ExpressAs, ExpressionType, express_as},
+	transformation::{TransformationType, VoiceTransformation, voice_transformation}
+};
+
+crate::element::el! {
+	#[derive(Debug, Clone)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub enum Element<'s> {
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		ExpressAs(ExpressAs<'s>),
+		#[cfg_attr(feature = "serde", serde(borrow))]
+		VoiceTransformation(VoiceTransformation<'s>)
+	}
+}
+
+impl<'s> Element<'s> {
+	pub fn to_owned(&self) -> Element<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Element<'static> {
+		match self {
+			Self::ExpressAs(el) => Element::ExpressAs(el.into_owned()),
+			Self::VoiceTransformation(el) => Element::VoiceTransformation(el.into_owned())
+		}
+	}
+
+	/// Returns a reference to the child elements of this element, if it is a container.
+	pub fn children(&self) -> Option<&[crate::Element<'s>]> {
+		match self {
+			Self::ExpressAs(el) => Some(el.children()),
+			Self::VoiceTransformation(el) => Some(el.children())
+		}
+	}
+
+	/// Returns a mutable reference to the child elements of this element, if it is a container.
+	pub fn children_mut(&mut self) -> Option<&mut Vec<crate::Element<'s>>> {
+		match self {
+			Self::ExpressAs(el) => Some(el.children_mut()),
+			Self::VoiceTransformation(el) => Some(el.children_mut())
+		}
+	}
+}
@@ -0,0 +1,325 @@
+use alloc::{borrow::Cow, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+	Element, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// The kind of transformation applied by a [`VoiceTransformation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransformationType {
+	/// Makes the voice sound younger. (`type="Young"`)
+	Young,
+	/// Makes the voice sound softer. (`type="Soft"`)
+	Soft,
+	/// Applies the parameters set on the [`VoiceTransformation`], like [`pitch`](VoiceTransformation::pitch) &
+	/// [`breathiness`](VoiceTransformation::breathiness). (`type="Custom"`)
+	Custom
+}
+
+impl TransformationType {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			TransformationType::Young => "Young",
+			TransformationType::Soft => "Soft",
+			TransformationType::Custom => "Custom"
+		}
+	}
+}
+
+/// Changes the characteristics of the voice speaking a section of a document with IBM Watson Text to Speech.
+///
+/// [`Young`](TransformationType::Young) & [`Soft`](TransformationType::Soft) transformations are scaled by their
+/// [`strength`](VoiceTransformation::strength); [`Custom`](TransformationType::Custom) transformations apply each of
+/// the given parameters instead. All parameters are percentages: `strength` & `timbre_extent` range from `0` to `100`,
+/// and the others from `-100` to `100`.
+///
+/// Transformations are only supported by some voices; see [the IBM Watson docs][watson] for more information.
+///
+/// [watson]: https://cloud.ibm.com/docs/text-to-speech?topic=text-to-speech-transformation
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// use ssml::watson::{self, TransformationType, VoiceTransformation};
+///
+/// let doc = ssml::speak(
+/// 	None,
+/// 	[
+/// 		watson::voice_transformation(TransformationType::Young, ["Feeling younger already."]).with_strength(80.),
+/// 		VoiceTransformation::new(TransformationType::Custom, ["And now, a deeper voice."])
+/// 			.with_pitch(-40.)
+/// 			.with_timbre("Breeze")
+/// 	]
+/// );
+/// assert_eq!(
+/// 	doc.serialize_to_string(&SerializeOptions::default().flavor(Flavor::IbmWatson))?,
+/// 	concat!(
+/// 		r#"<speak><voice-transformation type="Young" strength="80%">Feeling younger already.</voice-transformation>"#,
+/// 		r#"<voice-transformation type="Custom" pitch="-40%" timbre="Breeze">And now, a deeper voice.</voice-transformation></speak>"#
+/// 	)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VoiceTransformation<'s> {
+	kind: TransformationType,
+	strength: Option<f32>,
+	breathiness: Option<f32>,
+	pitch: Option<f32>,
+	pitch_range: Option<f32>,
+	glottal_tension: Option<f32>,
+	rate: Option<f32>,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	timbre: Option<Cow<'s, str>>,
+	timbre_extent: Option<f32>,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> VoiceTransformation<'s> {
+	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(kind: TransformationType, elements: I) -> Self {
+		Self {
+			kind,
+			strength: None,
+			breathiness: None,
+			pitch: None,
+			pitch_range: None,
+			glottal_tension: None,
+			rate: None,
+			timbre: None,
+			timbre_extent: None,
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	pub fn kind(&self) -> TransformationType {
+		self.kind
+	}
+
+	pub fn set_kind(&mut self, kind: TransformationType) {
+		self.kind = kind;
+	}
+
+	/// The degree to which a [`Young`](TransformationType::Young) or [`Soft`](TransformationType::Soft) transformation
+	/// is applied, from `0` to `100` percent.
+	pub fn strength(&self) -> Option<f32> {
+		self.strength
+	}
+
+	pub fn with_strength(mut self, percent: f32) -> Self {
+		self.strength = Some(percent);
+		self
+	}
+
+	pub fn set_strength(&mut self, percent: impl Into<Option<f32>>) {
+		self.strength = percent.into();
+	}
+
+	/// How breathy the voice sounds, from `-100` to `100` percent.
+	pub fn breathiness(&self) -> Option<f32> {
+		self.breathiness
+	}
+
+	pub fn with_breathiness(mut self, percent: f32) -> Self {
+		self.breathiness = Some(percent);
+		self
+	}
+
+	pub fn set_breathiness(&mut self, percent: impl Into<Option<f32>>) {
+		self.breathiness = percent.into();
+	}
+
+	/// The pitch of the voice, from `-100` to `100` percent.
+	pub fn pitch(&self) -> Option<f32> {
+		self.pitch
+	}
+
+	pub fn with_pitch(mut self, percent: f32) -> Self {
+		self.pitch = Some(percent);
+		self
+	}
+
+	pub fn set_pitch(&mut self, percent: impl Into<Option<f32>>) {
+		self.pitch = percent.into();
+	}
+
+	/// How much the pitch of the voice varies, from `-100` (robotic) to `100` (highly expressive) percent.
+	pub fn pitch_range(&self) -> Option<f32> {
+		self.pitch_range
+	}
+
+	pub fn with_pitch_range(mut self, percent: f32) -> Self {
+		self.pitch_range = Some(percent);
+		self
+	}
+
+	pub fn set_pitch_range(&mut self, percent: impl Into<Option<f32>>) {
+		self.pitch_range = percent.into();
+	}
+
+	/// How tense the vocal cords sound, from `-100` to `100` percent.
+	pub fn glottal_tension(&self) -> Option<f32> {
+		self.glottal_tension
+	}
+
+	pub fn with_glottal_tension(mut self, percent: f32) -> Self {
+		self.glottal_tension = Some(percent);
+		self
+	}
+
+	pub fn set_glottal_tension(&mut self, percent: impl Into<Option<f32>>) {
+		self.glottal_tension = percent.into();
+	}
+
+	/// The speaking rate of the voice, from `-100` to `100` percent.
+	pub fn rate(&self) -> Option<f32> {
+		self.rate
+	}
+
+	pub fn with_rate(mut self, percent: f32) -> Self {
+		self.rate = Some(percent);
+		self
+	}
+
+	pub fn set_rate(&mut self, percent: impl Into<Option<f32>>) {
+		self.rate = percent.into();
+	}
+
+	/// The timbre of the voice, either `Sunrise`, `Breeze`, or a frequency in hertz (e.g. `150`).
+	pub fn timbre(&self) -> Option<&str> {
+		self.timbre.as_deref()
+	}
+
+	pub fn with_timbre(mut self, timbre: impl Into<Cow<'s, str>>) -> Self {
+		self.timbre = Some(timbre.into());
+		self
+	}
+
+	pub fn set_timbre(&mut self, timbre: Option<impl Into<Cow<'s, str>>>) {
+		self.timbre = timbre.map(|f| f.into());
+	}
+
+	/// The degree to which the [`timbre`](VoiceTransformation::timbre) is applied, from `0` to `100` percent.
+	pub fn timbre_extent(&self) -> Option<f32> {
+		self.timbre_extent
+	}
+
+	pub fn with_timbre_extent(mut self, percent: f32) -> Self {
+		self.timbre_extent = Some(percent);
+		self
+	}
+
+	pub fn set_timbre_extent(&mut self, percent: impl Into<Option<f32>>) {
+		self.timbre_extent = percent.into();
+	}
+
+	/// Returns whether all parameters of this transformation are within their allowed ranges.
+	pub(crate) fn in_range(&self) -> bool {
+		let unsigned = [self.strength, self.timbre_extent]
+			.into_iter()
+			.flatten()
+			.all(|p| (0. ..=100.).contains(&p));
+		let signed = [self.breathiness, self.pitch, self.pitch_range, self.glottal_tension, self.rate]
+			.into_iter()
+			.flatten()
+			.all(|p| (-100. ..=100.).contains(&p));
+		unsigned && signed
+	}
+
+	/// Extend this transformation section with an additional element.
+	pub fn push(&mut self, element: impl Into<Element<'s>>) {
+		self.children.push(element.into());
+	}
+
+	/// Extend this transformation section with additional elements.
+	pub fn extend<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&mut self, elements: I) {
+		self.children.extend(elements.into_iter().map(|f| f.into()));
+	}
+
+	/// Returns a reference to the elements contained within this transformation section.
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	/// Returns a mutable reference to the elements contained within this transformation section.
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
+	pub fn to_owned(&self) -> VoiceTransformation<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> VoiceTransformation<'static> {
+		VoiceTransformation {
+			kind: self.kind,
+			strength: self.strength,
+			breathiness: self.breathiness,
+			pitch: self.pitch,
+			pitch_range: self.pitch_range,
+			glottal_tension: self.glottal_tension,
+			rate: self.rate,
+			timbre: self.timbre.map(|t| Cow::Owned(t.into_owned())),
+			timbre_extent: self.timbre_extent,
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+
+	/// Converts this element into an [`Element`].
+	pub fn into_el(self) -> Element<'s> {
+		Element::FlavorWatson(super::Element::VoiceTransformation(self))
+	}
+}
+
+impl<'s> From<VoiceTransformation<'s>> for crate::Element<'s> {
+	fn from(value: VoiceTransformation<'s>) -> Self {
+		value.into_el()
+	}
+}
+
+impl<'s> Serialize for VoiceTransformation<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		writer.element("voice-transformation", |writer| {
+			writer.attr("type", self.kind.as_str())?;
+			let percentages = [
+				("strength", self.strength),
+				("breathiness", self.breathiness),
+				("pitch", self.pitch),
+				("pitch_range", self.pitch_range),
+				("glottal_tension", self.glottal_tension),
+				("rate", self.rate)
+			];
+			for (name, percent) in percentages {
+				writer.attr_opt(name, percent.map(|p| alloc::format!("{p}%")))?;
+			}
+			writer.attr_opt("timbre", self.timbre.as_deref())?;
+			writer.attr_opt("timbre_extent", self.timbre_extent.map(|p| alloc::format!("{p}%")))?;
+			attrs::write(writer, &self.extra_attrs)?;
+			util::serialize_elements(writer, &self.children, options)
+		})
+	}
+}
+
+/// Creates a new [`VoiceTransformation`] section, applying the given kind of transformation.
+pub fn voice_transformation<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(kind: TransformationType, elements: I) -> VoiceTransformation<'s> {
+	VoiceTransformation::new(kind, elements)
+}
@@ -0,0 +1,143 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::{
+	fmt::Write,
+	ops::{Add, AddAssign}
+};
+
+use crate::{
+	Element, Flavor, Serialize, SerializeOptions, XmlWriter,
+	annotations::Annotations,
+	attrs::{self, Attrs},
+	util
+};
+
+/// [`Word`] marks its children as a single token, optionally with a `role` to disambiguate its pronunciation, e.g.
+/// to pick the part of speech of a homograph.
+///
+/// SSML 1.1 defines `<token>` and `<w>` as synonyms. [`Flavor::AmazonPolly`] and
+/// [`Flavor::MicrosoftAzureCognitiveSpeechServices`] are written with `<w>`; other flavors use `<token>`.
+///
+/// ```
+/// # use ssml::{Flavor, Serialize, SerializeOptions};
+/// # fn main() -> ssml::Result<()> {
+/// let word = ssml::word(["read"]).with_role("amazon:VBD");
+///
+/// assert_eq!(
+/// 	word.serialize_to_string(&SerializeOptions::default().flavor(Flavor::AmazonPolly))?,
+/// 	r#"<w role="amazon:VBD">read</w>"#
+/// );
+/// assert_eq!(word.serialize_to_string(&SerializeOptions::default())?, r#"<token role="amazon:VBD">read</token>"#);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Word<'s> {
+	#[cfg_attr(feature = "serde", serde(borrow, default, deserialize_with = "crate::serde_borrow::option"))]
+	role: Option<Cow<'s, str>>,
+	#[cfg_attr(feature = "serde", serde(borrow))]
+	pub(crate) children: Vec<Element<'s>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(borrow, default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::serde_borrow::pairs")
+	)]
+	pub(crate) extra_attrs: Attrs<'s>,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Annotations::is_empty"))]
+	pub(crate) annotations: Annotations<'s>
+}
+
+impl<'s> Word<'s> {
+	pub fn new<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Self {
+		Self {
+			role: None,
+			children: elements.into_iter().map(|f| f.into()).collect(),
+			extra_attrs: Vec::new(),
+			annotations: Annotations::default()
+		}
+	}
+
+	/// Sets the role of this word, e.g. `amazon:VB` to read a homograph as a verb with Amazon Polly.
+	pub fn with_role(mut self, role: impl Into<Cow<'s, str>>) -> Self {
+		self.role = Some(role.into());
+		self
+	}
+
+	pub fn role(&self) -> Option<&str> {
+		self.role.as_deref()
+	}
+
+	pub fn set_role(&mut self, role: impl Into<Cow<'s, str>>) {
+		self.role = Some(role.into());
+	}
+
+	pub fn take_role(&mut self) -> Option<Cow<'s, str>> {
+		self.role.take()
+	}
+
+	pub fn children(&self) -> &[Element<'s>] {
+		&self.children
+	}
+
+	pub fn children_mut(&mut self) -> &mut Vec<Element<'s>> {
+		&mut self.children
+	}
+
+	pub fn push(&mut self, element: impl Into<Element<'s>>) {
+		self.children.push(element.into());
+	}
+
+	pub fn extend<S: Into<Element<'s>>, I: IntoIterator<Item = S>>(&mut self, elements: I) {
+		self.children.extend(elements.into_iter().map(|f| f.into()));
+	}
+
+	pub fn to_owned(&self) -> Word<'static> {
+		self.clone().into_owned()
+	}
+
+	pub fn into_owned(self) -> Word<'static> {
+		Word {
+			role: match self.role {
+				Some(Cow::Borrowed(b)) => Some(Cow::Owned(b.to_string())),
+				Some(Cow::Owned(b)) => Some(Cow::Owned(b)),
+				None => None
+			},
+			children: self.children.into_iter().map(Element::into_owned).collect(),
+			extra_attrs: attrs::into_owned(self.extra_attrs),
+			annotations: self.annotations.into_owned()
+		}
+	}
+}
+
+impl<'s> Serialize for Word<'s> {
+	fn serialize_xml<W: Write>(&self, writer: &mut XmlWriter<W>, options: &SerializeOptions) -> crate::Result<()> {
+		let tag = match options.flavor {
+			Flavor::AmazonPolly | Flavor::MicrosoftAzureCognitiveSpeechServices => "w",
+			_ => "token"
+		};
+		util::require_v1_1(options, tag)?;
+		writer.element(tag, |writer| {
+			writer.attr_opt("role", self.role.as_deref())?;
+			attrs::write(writer, &self.extra_attrs)?;
+			util::serialize_elements(writer, &self.children, options)
+		})
+	}
+}
+
+impl<'s, 's2: 's, T: Into<Element<'s2>>> Add<T> for Word<'s> {
+	type Output = Word<'s>;
+
+	fn add(mut self, rhs: T) -> Self::Output {
+		self.push(rhs.into());
+		self
+	}
+}
+
+impl<'s, 's2: 's, T: Into<Element<'s2>>> AddAssign<T> for Word<'s> {
+	fn add_assign(&mut self, rhs: T) {
+		self.push(rhs.into());
+	}
+}
+
+pub fn word<'s, S: Into<Element<'s>>, I: IntoIterator<Item = S>>(elements: I) -> Word<'s> {
+	Word::new(elements)
+}
@@ -1,3 +1,3 @@
 mod writer;
-pub(crate) use self::writer::TrustedNoEscape;
 pub use self::writer::{EscapedDisplay, XmlWriter};
+pub(crate) use self::writer::{TrustedNoEscape, WriterState};
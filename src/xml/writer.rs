@@ -1,7 +1,22 @@
-use alloc::string::String;
-use core::fmt::{self, Display, Write};
+use alloc::{
+	borrow::Cow,
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec
+};
+use core::{
+	fmt::{self, Display, Write},
+	mem
+};
 
-use crate::util;
+use crate::{
+	AttributeRewrite, FlavorSpec, SerializeOptions,
+	diagnostics::{Diagnostic, Recovery},
+	plain_text::PlainText,
+	serializer::{FanOutHook, Placeholder},
+	source_map::SourceSpan,
+	util::{self, AsciiEscaper, AsciiOnly, AssertNoEscape, Escaper, EscapingWrite}
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum XmlState {
@@ -10,14 +25,104 @@ pub(crate) enum XmlState {
 	ElementClosed
 }
 
+/// A [`Write`] stream which keeps track of the number of bytes written to it.
+pub(crate) struct CountingWrite<W> {
+	pub(crate) inner: W,
+	pub(crate) count: usize,
+	/// Whether writes are discarded; used when only part of an element's output is wanted, see [`FanOutHook`].
+	pub(crate) muted: bool
+}
+
+impl<W: Write> Write for CountingWrite<W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if self.muted {
+			return Ok(());
+		}
+		self.count += s.len();
+		self.inner.write_str(s)
+	}
+
+	fn write_char(&mut self, c: char) -> fmt::Result {
+		if self.muted {
+			return Ok(());
+		}
+		self.count += c.len_utf8();
+		self.inner.write_char(c)
+	}
+}
+
 /// A utility for writing optionally formatted XML to a [`Write`] stream.
 pub struct XmlWriter<W> {
-	pub(crate) write: W,
+	pub(crate) write: CountingWrite<W>,
 	indent_level: u8,
 	pub(crate) pretty: bool,
-	state: XmlState
+	/// Whether the next line break is skipped, because it would sit next to whitespace in text & be mistaken for layout
+	/// when parsed.
+	pub(crate) inline: bool,
+	state: XmlState,
+	/// The path of child indices leading to the element currently being written; only tracked when source spans, plain
+	/// text or diagnostics are being collected, since nothing else reads it.
+	pub(crate) path: Vec<usize>,
+	/// Spans of all elements written so far, if source mapping is enabled.
+	pub(crate) spans: Option<Vec<SourceSpan>>,
+	/// Indices into `spans` of the elements currently being written.
+	open_spans: Vec<usize>,
+	/// The plain text written so far, if plain text output is enabled.
+	pub(crate) plain: Option<PlainText>,
+	/// Errors recovered from so far, if best-effort serialization is enabled.
+	pub(crate) diagnostics: Option<Vec<Diagnostic>>,
+	/// Whether recovered elements are written as-is instead of being skipped or unwrapped; see
+	/// [`serialize_with_warnings`](crate::diagnostics::serialize_with_warnings).
+	pub(crate) emit_anyway: bool,
+	/// The escaping strategy for text & attribute values, or `None` for the default XML escaping.
+	pub(crate) escaper: Option<Arc<dyn Escaper>>,
+	/// Whether the next element should be written with an end tag even if it has no content; see
+	/// [`EmptyElementPolicy::Expanded`](crate::EmptyElementPolicy::Expanded).
+	pub(crate) expand_empty: bool,
+	/// The custom flavor whose attribute rewrites are applied, if any; see
+	/// [`SerializeOptions::with_flavor_spec`](crate::SerializeOptions::with_flavor_spec).
+	pub(crate) flavor_spec: Option<Arc<dyn FlavorSpec>>,
+	/// Whether values are checked for double-escaping & unescaped markup; see
+	/// [`SerializeOptions::check_escaping`](crate::SerializeOptions::check_escaping).
+	pub(crate) check_escaping: bool,
+	/// The tag names of the elements currently being written; only tracked when `flavor_spec` is set.
+	tags: Vec<String>,
+	/// Set while writing a single element's own markup for [`serialize_all`](crate::serialize_all), in which case its
+	/// children are recorded instead of written.
+	pub(crate) fan_out: Option<FanOutHook>
+}
+
+/// The parts of an [`XmlWriter`]'s state which depend on its position in the document.
+#[derive(Debug, Clone)]
+pub(crate) struct WriterState {
+	indent_level: u8,
+	inline: bool,
+	state: XmlState,
+	path: Vec<usize>,
+	tags: Vec<String>,
+	expand_empty: bool
 }
 
+/// A value which can be written as XML text or an attribute value, escaping it as necessary; see [`XmlWriter::attr`].
+///
+/// This is implemented for strings (including [`Cow<str>`]) & `char`, which are escaped, and for numbers, `bool` and
+/// this crate's units, which are written as-is without allocating.
+///
+/// ```
+/// # use std::borrow::Cow;
+/// # fn main() -> ssml::Result<()> {
+/// let mut out = String::new();
+/// let mut writer = ssml::XmlWriter::new(&mut out, false);
+/// writer.element("acme:chime", |writer| {
+/// 	writer.attr("count", 3u32)?;
+/// 	writer.attr("loud", true)?;
+/// 	writer.attr("gain", 0.5f64)?;
+/// 	writer.attr("label", Cow::Borrowed("Fish & chips"))
+/// })?;
+/// assert_eq!(out, r#"<acme:chime count="3" loud="true" gain="0.5" label="Fish &amp; chips"/>"#);
+/// # Ok(())
+/// # }
+/// ```
 pub trait EscapedDisplay: Display {
 	fn escaped_fmt<W: Write>(&self, w: &mut W) -> fmt::Result;
 }
@@ -36,30 +141,234 @@ impl EscapedDisplay for &String {
 		util::escape(w, self)
 	}
 }
+impl EscapedDisplay for Cow<'_, str> {
+	fn escaped_fmt<W: Write>(&self, w: &mut W) -> fmt::Result {
+		util::escape(w, self)
+	}
+}
+impl EscapedDisplay for &Cow<'_, str> {
+	fn escaped_fmt<W: Write>(&self, w: &mut W) -> fmt::Result {
+		util::escape(w, self)
+	}
+}
+impl EscapedDisplay for char {
+	fn escaped_fmt<W: Write>(&self, w: &mut W) -> fmt::Result {
+		match util::escape_char(*self) {
+			Some(entity) => w.write_str(entity),
+			None => w.write_char(*self)
+		}
+	}
+}
 
 pub(crate) trait TrustedNoEscape: Display {}
 impl<T: TrustedNoEscape> EscapedDisplay for T {
 	fn escaped_fmt<W: Write>(&self, w: &mut W) -> fmt::Result {
-		w.write_fmt(format_args!("{}", self))
+		AssertNoEscape(w).write_fmt(format_args!("{}", self))
 	}
 }
 impl<T: TrustedNoEscape> TrustedNoEscape for &T {}
 impl TrustedNoEscape for u8 {}
+impl TrustedNoEscape for u16 {}
+impl TrustedNoEscape for u32 {}
+impl TrustedNoEscape for u64 {}
+impl TrustedNoEscape for usize {}
+impl TrustedNoEscape for i32 {}
+impl TrustedNoEscape for i64 {}
 impl TrustedNoEscape for f32 {}
+impl TrustedNoEscape for f64 {}
+impl TrustedNoEscape for bool {}
 
 impl<W: Write> XmlWriter<W> {
 	/// Creates a new [`XmlWriter`] with the given backing [`Write`] stream.
 	pub fn new(writer: W, pretty: bool) -> Self {
 		Self {
-			write: writer,
+			write: CountingWrite {
+				inner: writer,
+				count: 0,
+				muted: false
+			},
 			indent_level: 0,
 			pretty,
-			state: XmlState::DocumentStart
+			inline: false,
+			state: XmlState::DocumentStart,
+			path: Vec::new(),
+			spans: None,
+			open_spans: Vec::new(),
+			plain: None,
+			diagnostics: None,
+			emit_anyway: false,
+			escaper: None,
+			expand_empty: false,
+			flavor_spec: None,
+			check_escaping: false,
+			tags: Vec::new(),
+			fan_out: None
+		}
+	}
+
+	/// Creates a new [`XmlWriter`] configured according to the formatting & escaping settings of `options`.
+	pub(crate) fn for_options(writer: W, options: &SerializeOptions) -> Self {
+		let mut writer = XmlWriter::new(writer, options.pretty);
+		writer.escaper = match (options.ascii_only, &options.escaper) {
+			(false, escaper) => escaper.clone(),
+			(true, None) => Some(Arc::new(AsciiEscaper)),
+			(true, Some(escaper)) => Some(Arc::new(AsciiOnly(escaper.clone())))
+		};
+		writer.flavor_spec = options.flavor_spec.clone();
+		writer.check_escaping = options.check_escaping;
+		writer
+	}
+
+	pub(crate) fn save_state(&self) -> WriterState {
+		WriterState {
+			indent_level: self.indent_level,
+			inline: self.inline,
+			state: self.state,
+			path: self.path.clone(),
+			tags: self.tags.clone(),
+			expand_empty: self.expand_empty
+		}
+	}
+
+	pub(crate) fn restore_state(&mut self, state: WriterState) {
+		self.indent_level = state.indent_level;
+		self.inline = state.inline;
+		self.state = state.state;
+		self.path = state.path;
+		self.tags = state.tags;
+		self.expand_empty = state.expand_empty;
+	}
+
+	/// Records children which would be written here while [fanning out](FanOutHook), returning `true` if they were
+	/// recorded rather than needing to be written.
+	pub(crate) fn placeholder(&mut self, placeholder: Placeholder) -> bool {
+		let Some(hook) = &mut self.fan_out else {
+			return false;
+		};
+		let index = hook.seen;
+		hook.seen += 1;
+		if hook.record {
+			hook.placeholders.push(placeholder);
+		}
+		// the segment of output wanted by this pass lies between the placeholders `target - 1` & `target`
+		let target = hook.target;
+		if index + 1 == target {
+			if let Some(state) = self.fan_out.as_mut().and_then(|hook| hook.resume.take()) {
+				self.restore_state(state);
+			}
+			self.write.muted = false;
+		} else if index == target {
+			let state = self.save_state();
+			if let Some(hook) = &mut self.fan_out {
+				hook.captured = Some(state);
+			}
+			self.write.muted = true;
+		}
+		true
+	}
+
+	/// Consumes this writer, returning the underlying stream.
+	pub(crate) fn into_inner(self) -> W {
+		self.write.inner
+	}
+
+	/// Returns the number of bytes written so far.
+	pub fn position(&self) -> usize {
+		self.write.count
+	}
+
+	fn tracks_path(&self) -> bool {
+		self.spans.is_some() || self.plain.is_some() || self.diagnostics.is_some()
+	}
+
+	/// Marks the start of the child element with the given index of the element currently being written.
+	pub(crate) fn enter_child(&mut self, index: usize) {
+		if !self.tracks_path() {
+			return;
+		}
+		self.path.push(index);
+		if let Some(spans) = &mut self.spans {
+			spans.push(SourceSpan {
+				path: self.path.clone(),
+				range: usize::MAX..usize::MAX
+			});
+			self.open_spans.push(spans.len() - 1);
+		}
+	}
+
+	/// Marks the end of the child element started by the last call to [`XmlWriter::enter_child`].
+	pub(crate) fn exit_child(&mut self) {
+		if !self.tracks_path() {
+			return;
+		}
+		self.path.pop();
+		if let (Some(spans), Some(index)) = (&mut self.spans, self.open_spans.pop()) {
+			let span = &mut spans[index];
+			if span.range.start == usize::MAX {
+				// the element didn't write anything
+				span.range.start = self.write.count;
+			}
+			span.range.end = self.write.count;
+		}
+	}
+
+	/// Reports an error caused by the element currently being written.
+	///
+	/// In best-effort mode, the error is recorded as a [`Diagnostic`] and `Ok` is returned; otherwise, the error is
+	/// returned. `Ok(true)` means the element should be written as-is regardless, and `Ok(false)` means it should
+	/// recover as described by `recovery`.
+	pub(crate) fn recover(&mut self, error: crate::Error, recovery: Recovery) -> crate::Result<bool> {
+		let recovery = if self.emit_anyway { Recovery::Emitted } else { recovery };
+		match &mut self.diagnostics {
+			Some(diagnostics) => {
+				diagnostics.push(Diagnostic {
+					path: self.path.clone(),
+					error,
+					recovery
+				});
+				Ok(recovery == Recovery::Emitted)
+			}
+			None => Err(error)
+		}
+	}
+
+	/// Reports a warning about the element currently being written, which is written as-is regardless.
+	///
+	/// The warning is recorded as a [`Diagnostic`] (with [`Recovery::Emitted`]) if diagnostics are being collected, and
+	/// ignored otherwise.
+	pub(crate) fn warn(&mut self, error: crate::Error) {
+		if let Some(diagnostics) = &mut self.diagnostics {
+			diagnostics.push(Diagnostic {
+				path: self.path.clone(),
+				error,
+				recovery: Recovery::Emitted
+			});
+		}
+	}
+
+	/// Runs `f` without recording written text to the plain text output, e.g. for text which isn't spoken.
+	pub(crate) fn without_plain_text<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+		let plain = self.plain.take();
+		let res = f(self);
+		self.plain = plain;
+		res
+	}
+
+	/// Sets the start of any entered spans that haven't written anything yet to the current position.
+	fn start_spans(&mut self) {
+		if let Some(spans) = &mut self.spans {
+			for index in self.open_spans.iter().rev() {
+				let span = &mut spans[*index];
+				if span.range.start != usize::MAX {
+					break;
+				}
+				span.range.start = self.write.count;
+			}
 		}
 	}
 
 	fn pretty_break(&mut self) -> crate::Result<()> {
-		if self.pretty {
+		if self.pretty && !mem::take(&mut self.inline) {
 			self.write.write_char('\n')?;
 			for _ in 0..self.indent_level {
 				self.write.write_char('\t')?;
@@ -73,6 +382,7 @@ impl<W: Write> XmlWriter<W> {
 	/// Note that child elements **must** be written *after* any attributes.
 	pub fn element(&mut self, tag_name: impl AsRef<str>, ctx: impl FnOnce(&mut Self) -> crate::Result<()>) -> crate::Result<()> {
 		let tag_name = tag_name.as_ref();
+		let expand = mem::take(&mut self.expand_empty);
 
 		if self.state == XmlState::ElementUnclosed {
 			self.write.write_char('>')?;
@@ -80,16 +390,28 @@ impl<W: Write> XmlWriter<W> {
 		if self.state != XmlState::DocumentStart {
 			self.pretty_break()?;
 		}
+		self.start_spans();
 
 		self.write.write_char('<')?;
 		self.write.write_str(tag_name)?;
 
 		self.state = XmlState::ElementUnclosed;
 		self.indent_level = self.indent_level.saturating_add(1);
+		if self.flavor_spec.is_some() {
+			self.tags.push(tag_name.to_string());
+		}
 		ctx(self)?;
+		if self.flavor_spec.is_some() {
+			self.tags.pop();
+		}
 
 		self.indent_level = self.indent_level.saturating_sub(1);
 		match self.state {
+			XmlState::ElementUnclosed if expand => {
+				self.write.write_str("></")?;
+				self.write.write_str(tag_name)?;
+				self.write.write_char('>')?;
+			}
 			XmlState::ElementUnclosed => {
 				if self.pretty {
 					self.write.write_char(' ')?;
@@ -117,10 +439,59 @@ impl<W: Write> XmlWriter<W> {
 			return Err(crate::Error::AttributesInChildContext);
 		}
 
+		if let (Some(spec), Some(tag)) = (&self.flavor_spec, self.tags.last()) {
+			let value = attr_value.to_string();
+			return match spec.rewrite_attribute(tag, attr_name.as_ref(), &value) {
+				AttributeRewrite::Keep => self.write_attr(attr_name.as_ref(), &*value),
+				AttributeRewrite::Remove => Ok(()),
+				AttributeRewrite::Rename(name) => self.write_attr(&name, &*value),
+				AttributeRewrite::Set(value) => self.write_attr(attr_name.as_ref(), &*value)
+			};
+		}
+		self.write_attr(attr_name.as_ref(), attr_value)
+	}
+
+	fn write_attr(&mut self, attr_name: &str, attr_value: impl EscapedDisplay) -> crate::Result<()> {
+		if self.check_escaping {
+			let value = attr_value.to_string();
+			if util::contains_entity(&value) {
+				return Err(crate::Error::EscapingError("value appears to already be escaped", value));
+			}
+			let mut escaped = String::new();
+			match &self.escaper {
+				Some(escaper) => write!(
+					EscapingWrite {
+						inner: &mut escaped,
+						escaper: &**escaper
+					},
+					"{attr_value}"
+				)?,
+				None => attr_value.escaped_fmt(&mut escaped)?
+			}
+			if !util::is_escaped(&escaped) {
+				return Err(crate::Error::EscapingError("escaped value contains unescaped markup", escaped));
+			}
+			self.write.write_char(' ')?;
+			self.write.write_str(attr_name)?;
+			self.write.write_str("=\"")?;
+			self.write.write_str(&escaped)?;
+			self.write.write_char('"')?;
+			return Ok(());
+		}
+
 		self.write.write_char(' ')?;
-		self.write.write_str(attr_name.as_ref())?;
+		self.write.write_str(attr_name)?;
 		self.write.write_str("=\"")?;
-		attr_value.escaped_fmt(&mut self.write)?;
+		match &self.escaper {
+			Some(escaper) => {
+				let mut out = EscapingWrite {
+					inner: &mut self.write,
+					escaper: &**escaper
+				};
+				write!(out, "{attr_value}")?
+			}
+			None => attr_value.escaped_fmt(&mut self.write)?
+		}
 		self.write.write_char('"')?;
 
 		Ok(())
@@ -138,12 +509,32 @@ impl<W: Write> XmlWriter<W> {
 		if self.state == XmlState::ElementUnclosed {
 			self.write.write_char('>')?;
 		}
+		let contents = contents.as_ref();
 		if self.state != XmlState::DocumentStart {
+			self.inline |= contents.starts_with(char::is_whitespace);
 			self.pretty_break()?;
 		}
+		self.start_spans();
 
-		util::escape(&mut self.write, contents)?;
+		if self.check_escaping && util::contains_entity(contents) {
+			return Err(crate::Error::EscapingError("text appears to already be escaped", contents.to_string()));
+		}
+		let start = self.write.count;
+		match &self.escaper {
+			Some(escaper) => {
+				let mut out = EscapingWrite {
+					inner: &mut self.write,
+					escaper: &**escaper
+				};
+				out.write_str(contents)?
+			}
+			None => util::escape(&mut self.write, contents)?
+		}
+		if let Some(plain) = &mut self.plain {
+			plain.push(contents, start..self.write.count, &self.path);
+		}
 
+		self.inline = self.pretty && contents.ends_with(char::is_whitespace);
 		self.state = XmlState::ElementClosed;
 
 		Ok(())
@@ -157,6 +548,7 @@ impl<W: Write> XmlWriter<W> {
 		if self.state != XmlState::DocumentStart {
 			self.pretty_break()?;
 		}
+		self.start_spans();
 
 		write!(self.write, "{}", contents)?;
 